@@ -0,0 +1,7 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // Any input may be rejected, but none may panic.
+    let _ = bluenrg::event::parse(data);
+});
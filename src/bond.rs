@@ -0,0 +1,138 @@
+//! Host-side record of the devices bonded with the controller.
+//!
+//! The BlueNRG keeps the bonding keys in its own flash and has no command to export or restore
+//! them. What the host can see is the list of bonded addresses, from [GAP Get Bonded
+//! Devices](crate::gap::Commands::get_bonded_devices). [`SecurityDatabase`] holds that list, so
+//! the host can keep a copy and compare it to the controller's list after a reboot, for example to
+//! notice that the controller's flash was erased and the peers need to pair again.
+//!
+//! With the `serde` feature, [`SecurityDatabase`] implements `Serialize` and `Deserialize`, so it
+//! can be stored with any serde format. It is written as a sequence of addresses, each one an enum
+//! with a `Public` or `Random` variant holding the 6 address bytes, least significant byte first.
+
+use crate::event::command::{GapBondedDevices, MAX_ADDRESSES};
+use core::fmt::{Debug, Formatter, Result as FmtResult};
+
+/// The addresses of the devices bonded with the controller.
+#[derive(Copy, Clone)]
+pub struct SecurityDatabase {
+    address_count: usize,
+    address_buffer: [hci::BdAddrType; MAX_ADDRESSES],
+}
+
+impl SecurityDatabase {
+    /// Returns the addresses of the bonded devices, in the order the controller reported them.
+    pub fn bonded_addresses(&self) -> &[hci::BdAddrType] {
+        &self.address_buffer[..self.address_count]
+    }
+
+    /// Returns true if the device with the given address is bonded.
+    pub fn contains(&self, addr: &hci::BdAddrType) -> bool {
+        self.bonded_addresses().contains(addr)
+    }
+
+    fn empty() -> SecurityDatabase {
+        SecurityDatabase {
+            address_count: 0,
+            address_buffer: [hci::BdAddrType::Public(hci::BdAddr([0; 6])); MAX_ADDRESSES],
+        }
+    }
+}
+
+impl<'a> From<&'a GapBondedDevices> for SecurityDatabase {
+    fn from(bonded_devices: &'a GapBondedDevices) -> SecurityDatabase {
+        let addresses = bonded_devices.bonded_addresses();
+        let mut database = SecurityDatabase::empty();
+        database.address_buffer[..addresses.len()].copy_from_slice(addresses);
+        database.address_count = addresses.len();
+
+        database
+    }
+}
+
+impl PartialEq for SecurityDatabase {
+    fn eq(&self, other: &Self) -> bool {
+        self.bonded_addresses() == other.bonded_addresses()
+    }
+}
+
+impl Debug for SecurityDatabase {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{{")?;
+        for addr in self.bonded_addresses().iter() {
+            write!(f, "{:?}, ", addr)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+// The serialized form of a bonded address. hci::BdAddrType does not implement the serde traits.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+enum BondedAddress {
+    Public([u8; 6]),
+    Random([u8; 6]),
+}
+
+#[cfg(feature = "serde")]
+impl<'a> From<&'a hci::BdAddrType> for BondedAddress {
+    fn from(addr: &'a hci::BdAddrType) -> BondedAddress {
+        match *addr {
+            hci::BdAddrType::Public(addr) => BondedAddress::Public(addr.0),
+            hci::BdAddrType::Random(addr) => BondedAddress::Random(addr.0),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<BondedAddress> for hci::BdAddrType {
+    fn from(addr: BondedAddress) -> hci::BdAddrType {
+        match addr {
+            BondedAddress::Public(addr) => hci::BdAddrType::Public(hci::BdAddr(addr)),
+            BondedAddress::Random(addr) => hci::BdAddrType::Random(hci::BdAddr(addr)),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SecurityDatabase {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.bonded_addresses().iter().map(BondedAddress::from))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SecurityDatabase {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(SecurityDatabaseVisitor)
+    }
+}
+
+// Fills the fixed address buffer, so deserializing does not need an allocator.
+#[cfg(feature = "serde")]
+struct SecurityDatabaseVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for SecurityDatabaseVisitor {
+    type Value = SecurityDatabase;
+
+    fn expecting(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "a list of at most {} bonded addresses", MAX_ADDRESSES)
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> Result<SecurityDatabase, A::Error> {
+        let mut database = SecurityDatabase::empty();
+        while let Some(addr) = seq.next_element::<BondedAddress>()? {
+            if database.address_count == MAX_ADDRESSES {
+                return Err(serde::de::Error::invalid_length(MAX_ADDRESSES + 1, &self));
+            }
+            database.address_buffer[database.address_count] = addr.into();
+            database.address_count += 1;
+        }
+
+        Ok(database)
+    }
+}
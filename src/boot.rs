@@ -0,0 +1,99 @@
+//! Helper for tracking the controller through its boot sequence.
+//!
+//! After the controller is reset, the application has to wait for the
+//! [HalInitialized](BlueNRGEvent::HalInitialized) event before sending commands. The reset reason
+//! in that event tells whether the firmware started normally, entered updater mode, or restarted
+//! after a crash, in which case a [CrashReport](BlueNRGEvent::CrashReport) with the fault data
+//! follows. [`BootMonitor`] follows these events so the application only needs to act on the
+//! outcome.
+
+#[cfg(feature = "ms")]
+use crate::event::FaultData;
+use crate::event::{BlueNRGEvent, HalInitialized, ResetReason};
+
+/// Progress of the boot sequence, as reported by [`BootMonitor`].
+#[derive(Clone, Debug)]
+pub enum BootProgress {
+    /// The firmware has started and is ready to accept commands.
+    Ready,
+
+    /// The controller started in updater mode instead of running the BLE stack. Includes the
+    /// reason the updater was entered.
+    EnteredUpdater(ResetReason),
+
+    /// The firmware restarted after a crash. Includes the fault data from the crash report. Like
+    /// [Ready](BootProgress::Ready), this completes the boot sequence: the firmware is running
+    /// again and accepts commands, but the application may want to log the fault and reset the
+    /// controller.
+    #[cfg(feature = "ms")]
+    Crashed(FaultData),
+
+    /// The boot sequence has not completed yet.
+    StillWaiting,
+
+    /// The timeout given to [`with_timeout`](BootMonitor::with_timeout) elapsed before the boot
+    /// sequence completed.
+    TimedOut,
+}
+
+/// Follows the events the controller sends after a reset, until the firmware is ready.
+///
+/// Feed every event read after resetting the controller to
+/// [`handle_event`](BootMonitor::handle_event). If the monitor was created with a timeout, also
+/// call [`check_timeout`](BootMonitor::check_timeout) periodically with the current tick count.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BootMonitor {
+    timeout: Option<(u32, u32)>,
+}
+
+impl BootMonitor {
+    /// Returns a monitor that waits indefinitely.
+    pub fn new() -> BootMonitor {
+        BootMonitor::default()
+    }
+
+    /// Returns a monitor that times out `timeout` ticks after `now`. Ticks are in whatever unit the
+    /// caller uses for [`check_timeout`](BootMonitor::check_timeout), and may wrap around.
+    pub fn with_timeout(now: u32, timeout: u32) -> BootMonitor {
+        BootMonitor {
+            timeout: Some((now, timeout)),
+        }
+    }
+
+    /// Updates the boot progress with an event read from the controller.
+    ///
+    /// The monitor does not remember earlier events, so the progress depends only on `event`. With
+    /// BlueNRG-MS firmware, a [HalInitialized](BlueNRGEvent::HalInitialized) event with the
+    /// [Crash](ResetReason::Crash) reason reports [StillWaiting](BootProgress::StillWaiting), since
+    /// the crash report that follows it completes the sequence.
+    pub fn handle_event(&self, event: &BlueNRGEvent) -> BootProgress {
+        match *event {
+            BlueNRGEvent::HalInitialized(HalInitialized { reason, .. }) => match reason {
+                ResetReason::Updater | ResetReason::UpdaterBadFlag | ResetReason::UpdaterPin => {
+                    BootProgress::EnteredUpdater(reason)
+                }
+
+                // BlueNRG-MS firmware follows this with a crash report, which carries the fault
+                // data.
+                #[cfg(feature = "ms")]
+                ResetReason::Crash => BootProgress::StillWaiting,
+
+                _ => BootProgress::Ready,
+            },
+            #[cfg(feature = "ms")]
+            BlueNRGEvent::CrashReport(ref fault_data) => BootProgress::Crashed(fault_data.clone()),
+            _ => BootProgress::StillWaiting,
+        }
+    }
+
+    /// Checks whether the boot sequence has timed out, given the current tick count.
+    ///
+    /// Returns [TimedOut](BootProgress::TimedOut) if the monitor was created with a timeout that
+    /// has elapsed, and [StillWaiting](BootProgress::StillWaiting) otherwise.
+    pub fn check_timeout(&self, now: u32) -> BootProgress {
+        match self.timeout {
+            Some((start, timeout)) if now.wrapping_sub(start) >= timeout => BootProgress::TimedOut,
+            _ => BootProgress::StillWaiting,
+        }
+    }
+}
@@ -45,11 +45,18 @@ pub trait Commands {
     ///   or
     ///   [ConnectableDirectedLowDutyCycle](bluetooth_hci::host::AdvertisingType::ConnectableDirectedLowDutyCycle).
     /// - [`BadAdvertisingInterval`](Error::BadAdvertisingInterval) if
-    ///   [`advertising_interval`](DiscoverableParameters::advertising_interval) is inverted.
-    ///   That is, if the min is greater than the max.
+    ///   [`advertising_interval`](DiscoverableParameters::advertising_interval) is out of range,
+    ///   inverted (the min is greater than the max), or too short for the advertising type. See
+    ///   [`AdvertisingIntervalRange::validate_for`].
     /// - [`BadConnectionInterval`](Error::BadConnectionInterval) if
     ///   [`conn_interval`](DiscoverableParameters::conn_interval) is inverted. That is, both the
     ///   min and max are provided, and the min is greater than the max.
+    /// - [`BadAdvertisingDataLength`](Error::BadAdvertisingDataLength) if the
+    ///   [`local_name`](DiscoverableParameters::local_name) and
+    ///   [`advertising_data`](DiscoverableParameters::advertising_data) do not fit in the 31-byte
+    ///   advertising packet, next to the AD structures the controller adds: Flags (3 bytes), and
+    ///   the Slave Connection Interval Range (6 bytes) if
+    ///   [`conn_interval`](DiscoverableParameters::conn_interval) is given.
     ///
     /// # Generated evenst
     ///
@@ -80,11 +87,18 @@ pub trait Commands {
     ///   or
     ///   [ConnectableDirectedLowDutyCycle](bluetooth_hci::host::AdvertisingType::ConnectableDirectedLowDutyCycle).
     /// - [`BadAdvertisingInterval`](Error::BadAdvertisingInterval) if
-    ///   [`advertising_interval`](DiscoverableParameters::advertising_interval) is inverted.
-    ///   That is, if the min is greater than the max.
+    ///   [`advertising_interval`](DiscoverableParameters::advertising_interval) is out of range,
+    ///   inverted (the min is greater than the max), or too short for the advertising type. See
+    ///   [`AdvertisingIntervalRange::validate_for`].
     /// - [`BadConnectionInterval`](Error::BadConnectionInterval) if
     ///   [`conn_interval`](DiscoverableParameters::conn_interval) is inverted. That is, both the
     ///   min and max are provided, and the min is greater than the max.
+    /// - [`BadAdvertisingDataLength`](Error::BadAdvertisingDataLength) if the
+    ///   [`local_name`](DiscoverableParameters::local_name) and
+    ///   [`advertising_data`](DiscoverableParameters::advertising_data) do not fit in the 31-byte
+    ///   advertising packet, next to the AD structures the controller adds: Flags (3 bytes), and
+    ///   the Slave Connection Interval Range (6 bytes) if
+    ///   [`conn_interval`](DiscoverableParameters::conn_interval) is given.
     ///
     /// # Generated evenst
     ///
@@ -340,6 +354,10 @@ pub trait Commands {
 
     /// Put the device into undirected connectable mode.
     ///
+    /// Unlike [`set_discoverable`](Commands::set_discoverable), this does not set the advertising
+    /// data. Use [`update_advertising_data`](Commands::update_advertising_data) to manage it
+    /// separately.
+    ///
     /// The privacy setting done in the [`init`](Commands::init) command plays a role
     /// in deciding the valid parameters for this command.
     ///
@@ -348,7 +366,8 @@ pub trait Commands {
     /// - [BadAdvertisingFilterPolicy](Error::BadAdvertisingFilterPolicy) if the filter is
     ///   not one of the supported modes. It must be
     ///   [AllowConnectionAndScan](AdvertisingFilterPolicy::AllowConnectionAndScan) or
-    ///   (WhiteListConnectionAllowScan)[AdvertisingFilterPolicy::WhiteListConnectionAllowScan).
+    ///   [WhiteListConnectionAndScan](AdvertisingFilterPolicy::WhiteListConnectionAndScan). The
+    ///   firmware does not support filtering only scan requests or only connection requests.
     /// - Underlying communication errors.
     ///
     /// # Generated events
@@ -670,6 +689,10 @@ pub trait Commands {
     /// the devices to which it wants to connect by issuing the command
     /// [`create_connection`](Commands::create_connection).
     ///
+    /// The scan interval and window are checked when the
+    /// [`scan_window`](SelectiveConnectionEstablishmentParameters::scan_window) is built, so they
+    /// are always valid here.
+    ///
     /// # Errors
     ///
     /// - If the [`white_list`](SelectiveConnectionEstablishmentParameters::white_list) is too
@@ -696,7 +719,8 @@ pub trait Commands {
     ///
     /// # Errors
     ///
-    /// Only underlying communication errors are reported.
+    /// Only underlying communication errors are reported. The scan window, connection interval,
+    /// and expected connection length are validated when they are built.
     ///
     /// # Generated events
     ///
@@ -707,6 +731,11 @@ pub trait Commands {
     /// command [`terminate_procedure`](Commands::terminate_procedure) with the procedure_code set
     /// to
     /// [DirectConnectionEstablishment](crate::event::GapProcedure::DirectConnectionEstablishment).
+    ///
+    /// The vendor documentation calls this command `aci_gap_create_connection`; some firmware
+    /// examples call the procedure `start_direct_connection_establishment`.
+    #[doc(alias = "start_direct_connection_establishment")]
+    #[doc(alias = "aci_gap_create_connection")]
     fn create_connection(&mut self, params: &ConnectionParameters) -> nb::Result<(), Self::Error>;
 
     /// The GAP procedure(s) specified is terminated.
@@ -784,6 +813,9 @@ pub trait Commands {
     /// This command gets the list of the devices which are bonded. It returns the number of
     /// addresses and the corresponding address types and values.
     ///
+    /// To persist the list on the host, convert the returned parameters into a
+    /// [`SecurityDatabase`](crate::bond::SecurityDatabase).
+    ///
     /// # Errors
     ///
     /// Only underlying communication errors are reported.
@@ -851,6 +883,102 @@ pub trait Commands {
     /// A [command complete](crate::event::command::ReturnParameters::GapIsDeviceBonded) event is
     /// generated.
     fn is_device_bonded(&mut self, addr: hci::host::PeerAddrType) -> nb::Result<(), Self::Error>;
+
+    /// This command should be sent by the host in response to the [GAP Numeric Comparison
+    /// Value](crate::event::BlueNRGEvent::GapNumericComparisonValue) event, to report whether the
+    /// user confirmed that the displayed values match.
+    ///
+    /// Only firmware that implements LE Secure Connections supports this command.
+    ///
+    /// # Errors
+    ///
+    /// Only underlying communication errors are reported.
+    ///
+    /// # Generated events
+    ///
+    /// - A [Command
+    ///   Complete](crate::event::command::ReturnParameters::GapNumericComparisonValueConfirm)
+    ///   event is generated.
+    /// - When the pairing process completes, it will generate a
+    ///   [PairingComplete](crate::event::BlueNRGEvent::GapPairingComplete) event.
+    fn numeric_comparison_value_confirm(
+        &mut self,
+        conn_handle: hci::ConnectionHandle,
+        confirm: bool,
+    ) -> nb::Result<(), Self::Error>;
+
+    #[cfg(feature = "bluenrg-lp")]
+    /// Loads the identities of bonded peers into the controller's resolving list, so the
+    /// controller can resolve their private addresses itself.
+    ///
+    /// The controller looks up each peer's IRK in the security database. With `clear_first`, the
+    /// resolving list is cleared before the identities are added; otherwise they are added to the
+    /// entries already there. Call this after bonding and after each reset, before advertising or
+    /// scanning with privacy enabled.
+    ///
+    /// Host-based privacy uses [`resolve_private_address`](Commands::resolve_private_address) to
+    /// resolve addresses one at a time instead. Peers that connect with a resolvable private
+    /// address that the resolving list cannot resolve are reported with the [GAP Address Not
+    /// Resolved](crate::event::BlueNRGEvent::GapAddressNotResolved) event.
+    ///
+    /// Only firmware with controller-based privacy supports this command, so it is only available
+    /// with the `bluenrg-lp` feature.
+    ///
+    /// # Errors
+    ///
+    /// - [ResolvingListTooLong](Error::ResolvingListTooLong) if there are more than 36 identities,
+    ///   which would not fit in the command packet.
+    /// - Underlying communication errors.
+    ///
+    /// # Generated events
+    ///
+    /// A [command complete](crate::event::command::ReturnParameters::GapAddDevicesToResolvingList)
+    /// event is generated.
+    fn add_devices_to_resolving_list(
+        &mut self,
+        clear_first: bool,
+        identities: &[hci::host::PeerAddrType],
+    ) -> nb::Result<(), Error<Self::Error>>;
+
+    #[cfg(feature = "periodic-advertising")]
+    /// Sets the parameters for periodic advertising on the given advertising set.
+    ///
+    /// Only firmware that supports periodic advertising (Bluetooth 5.0) accepts this command. It is
+    /// sent as the standard LE Set Periodic Advertising Parameters command.
+    ///
+    /// # Errors
+    ///
+    /// - [BadPeriodicAdvertisingInterval](Error::BadPeriodicAdvertisingInterval) if the interval
+    ///   is inverted, or if either end of the range is less than 7.5 ms or greater than 81.91875
+    ///   s.
+    /// - Underlying communication errors.
+    ///
+    /// # Generated events
+    ///
+    /// A command complete event is generated.
+    fn set_periodic_advertising(
+        &mut self,
+        params: &PeriodicAdvertisingParameters,
+    ) -> nb::Result<(), Error<Self::Error>>;
+
+    #[cfg(feature = "periodic-advertising")]
+    /// Starts or stops periodic advertising on the given advertising set.
+    ///
+    /// Only firmware that supports periodic advertising (Bluetooth 5.0) accepts this command. It is
+    /// sent as the standard LE Set Periodic Advertising Enable command.
+    ///
+    /// # Errors
+    ///
+    /// Only underlying communication errors are reported.
+    ///
+    /// # Generated events
+    ///
+    /// A command complete event is generated.
+    fn periodic_advertising_enable(
+        &mut self,
+        advertising_handle: u8,
+        enable: bool,
+    ) -> nb::Result<(), Self::Error>;
 }
 
 impl<'bnrg, 'spi, 'dbuf, SPI, OutputPin1, OutputPin2, InputPin, E> Commands
@@ -1195,6 +1323,66 @@ where
 
         self.write_command(crate::opcode::GAP_IS_DEVICE_BONDED, &bytes)
     }
+
+    fn numeric_comparison_value_confirm(
+        &mut self,
+        conn_handle: hci::ConnectionHandle,
+        confirm: bool,
+    ) -> nb::Result<(), Self::Error> {
+        let mut bytes = [0; 3];
+        LittleEndian::write_u16(&mut bytes[0..2], conn_handle.0);
+        bytes[2] = confirm as u8;
+
+        self.write_command(crate::opcode::GAP_NUMERIC_COMPARISON_VALUE_CONFIRM, &bytes)
+    }
+
+    #[cfg(feature = "bluenrg-lp")]
+    fn add_devices_to_resolving_list(
+        &mut self,
+        clear_first: bool,
+        identities: &[hci::host::PeerAddrType],
+    ) -> nb::Result<(), Error<Self::Error>> {
+        const IDENTITY_LEN: usize = 7;
+        const MAX_RESOLVING_LIST_LEN: usize = 36;
+        if identities.len() > MAX_RESOLVING_LIST_LEN {
+            return Err(nb::Error::Other(Error::ResolvingListTooLong(
+                identities.len(),
+            )));
+        }
+
+        let mut bytes = [0; 2 + IDENTITY_LEN * MAX_RESOLVING_LIST_LEN];
+        bytes[0] = identities.len() as u8;
+        for (identity, chunk) in identities.iter().zip(bytes[1..].chunks_mut(IDENTITY_LEN)) {
+            identity.copy_into_slice(chunk);
+        }
+        let clear_index = 1 + IDENTITY_LEN * identities.len();
+        bytes[clear_index] = clear_first as u8;
+
+        self.write_command(
+            crate::opcode::GAP_ADD_DEVICES_TO_RESOLVING_LIST,
+            &bytes[..=clear_index],
+        )
+        .map_err(rewrap_error)
+    }
+
+    #[cfg(feature = "periodic-advertising")]
+    impl_validate_params!(
+        set_periodic_advertising,
+        PeriodicAdvertisingParameters,
+        crate::opcode::LE_SET_PERIODIC_ADVERTISING_PARAMETERS
+    );
+
+    #[cfg(feature = "periodic-advertising")]
+    fn periodic_advertising_enable(
+        &mut self,
+        advertising_handle: u8,
+        enable: bool,
+    ) -> nb::Result<(), Self::Error> {
+        self.write_command(
+            crate::opcode::LE_SET_PERIODIC_ADVERTISING_ENABLE,
+            &[enable as u8, advertising_handle],
+        )
+    }
 }
 
 /// Potential errors from parameter validation.
@@ -1215,10 +1403,11 @@ pub enum Error<E> {
     /// type is disallowed.  Returns the invalid advertising type.
     BadAdvertisingType(crate::AdvertisingType),
 
-    /// For the [GAP Set Limited Discoverable](Commands::set_limited_discoverable)
-    /// command, the advertising interval is inverted (that is, the max is less than the
-    /// min). Includes the provided range.
-    BadAdvertisingInterval(Duration, Duration),
+    /// For the [GAP Set Limited Discoverable](Commands::set_limited_discoverable), [GAP Set
+    /// Discoverable](Commands::set_discoverable) and (`ms` feature only) [GAP Set Direct
+    /// Connectable](Commands::set_direct_connectable) commands, the advertising interval is out of
+    /// range, inverted, or too short for the advertising type. Includes the reason.
+    BadAdvertisingInterval(AdvertisingIntervalError),
 
     /// For the [GAP Set Authentication
     /// Requirement](Commands::set_authentication_requirement) command, the encryption
@@ -1241,6 +1430,11 @@ pub enum Error<E> {
     /// For the [GAP Update Advertising Data](Commands::update_advertising_data) and [GAP
     /// Set Broadcast Mode](Commands::set_broadcast_mode) commands, the advertising data
     /// is too long. It must be 31 bytes or less. The length of the provided data is returned.
+    ///
+    /// For the [GAP Set Limited Discoverable](Commands::set_limited_discoverable) and [GAP Set
+    /// Discoverable](Commands::set_discoverable) commands, the local name and the service UUID
+    /// list do not fit in the advertising packet. Returns the length of the whole packet: the
+    /// name's AD structure, the service UUID list, and the AD structures the controller adds.
     BadAdvertisingDataLength(usize),
 
     /// For the [GAP Terminate](Commands::terminate) command, the termination reason was
@@ -1263,10 +1457,69 @@ pub enum Error<E> {
     /// provided bitfield had no bits set.
     NoProcedure,
 
+    /// For the [Set Periodic Advertising](Commands::set_periodic_advertising) command, the
+    /// periodic advertising interval is inverted (the min is greater than the max), or one end of
+    /// the range is outside 7.5 ms to 81.91875 s. Includes the provided range.
+    BadPeriodicAdvertisingInterval(Duration, Duration),
+
+    /// For the [GAP Add Devices to Resolving List](Commands::add_devices_to_resolving_list)
+    /// command, there were more identities than fit in the command packet (36). Includes the
+    /// number of identities provided.
+    ResolvingListTooLong(usize),
+
     /// Underlying communication error.
     Comm(E),
 }
 
+impl<E: core::fmt::Debug> core::fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            Error::BadConnectionInterval(min, max) => {
+                write!(f, "connection interval {:?} to {:?} is inverted", min, max)
+            }
+            Error::BadAdvertisingType(ref advertising_type) => {
+                write!(f, "advertising type {:?} is not allowed", advertising_type)
+            }
+            Error::BadAdvertisingInterval(ref e) => write!(f, "{}", e),
+            Error::BadEncryptionKeySizeRange(min, max) => {
+                write!(
+                    f,
+                    "encryption key size range {} to {} is inverted",
+                    min, max
+                )
+            }
+            Error::BadFixedPin(pin) => write!(f, "fixed PIN {} is greater than 999999", pin),
+            Error::BadAdvertisingFilterPolicy(ref policy) => {
+                write!(f, "advertising filter policy {:?} is not allowed", policy)
+            }
+            Error::BadAdvertisingDataLength(len) => {
+                write!(f, "advertising data length {} is too long", len)
+            }
+            Error::BadTerminationReason(ref reason) => {
+                write!(f, "termination reason {:?} is not allowed", reason)
+            }
+            Error::WhiteListTooLong => f.write_str("white list is too long"),
+            Error::NoProcedure => f.write_str("no procedure was selected"),
+            Error::BadPeriodicAdvertisingInterval(min, max) => write!(
+                f,
+                "periodic advertising interval {:?} to {:?} is invalid",
+                min, max
+            ),
+            Error::ResolvingListTooLong(len) => {
+                write!(
+                    f,
+                    "{} identities do not fit in the resolving list command",
+                    len
+                )
+            }
+            Error::Comm(ref e) => write!(f, "communication error: {:?}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: core::fmt::Debug> std::error::Error for Error<E> {}
+
 fn rewrap_error<E>(e: nb::Error<E>) -> nb::Error<Error<E>> {
     match e {
         nb::Error::WouldBlock => nb::Error::WouldBlock,
@@ -1292,6 +1545,200 @@ fn to_connection_length_value(d: Duration) -> u16 {
     (1600 * d.as_secs() as u32 + (d.subsec_micros() / 625)) as u16
 }
 
+/// Advertising interval, in units of 0.625 ms.
+///
+/// The Bluetooth specification allows advertising intervals from 20 ms to 10.24 seconds.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AdvertisingInterval(u16);
+
+impl AdvertisingInterval {
+    /// Shortest advertising interval: 20 ms.
+    pub const MIN: AdvertisingInterval = AdvertisingInterval(0x0020);
+
+    /// Longest advertising interval: 10.24 seconds.
+    pub const MAX: AdvertisingInterval = AdvertisingInterval(0x4000);
+
+    /// Returns the interval with the given number of 0.625 ms units, or `None` if it is out of
+    /// range.
+    pub fn from_units(units: u16) -> Option<AdvertisingInterval> {
+        if units < Self::MIN.0 || units > Self::MAX.0 {
+            return None;
+        }
+
+        Some(AdvertisingInterval(units))
+    }
+
+    /// Returns the longest interval that is not longer than the given duration, or `None` if the
+    /// duration is out of range.
+    pub fn from_duration(d: Duration) -> Option<AdvertisingInterval> {
+        if d < Self::MIN.duration() || d > Self::MAX.duration() {
+            return None;
+        }
+
+        Some(AdvertisingInterval(to_connection_length_value(d)))
+    }
+
+    /// Returns the interval in 0.625 ms units, as sent to the controller.
+    pub fn units(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the interval as a duration.
+    pub fn duration(self) -> Duration {
+        Duration::from_micros(625 * u64::from(self.0))
+    }
+}
+
+/// Errors that may occur when building an [`AdvertisingIntervalRange`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AdvertisingIntervalError {
+    /// A duration is shorter than 20 ms or longer than 10.24 seconds. Includes the duration.
+    OutOfRange(Duration),
+
+    /// The min is greater than the max. Includes the min and max.
+    Inverted(AdvertisingInterval, AdvertisingInterval),
+
+    /// The min is too short for the advertising type. Includes the advertising type and the min.
+    TooShortForType(AdvertisingType, AdvertisingInterval),
+}
+
+impl core::fmt::Display for AdvertisingIntervalError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            AdvertisingIntervalError::OutOfRange(d) => write!(
+                f,
+                "advertising interval {:?} is outside 20 ms to 10.24 s",
+                d
+            ),
+            AdvertisingIntervalError::Inverted(min, max) => write!(
+                f,
+                "advertising interval {:?} to {:?} is inverted",
+                min.duration(),
+                max.duration()
+            ),
+            AdvertisingIntervalError::TooShortForType(advertising_type, min) => write!(
+                f,
+                "advertising interval {:?} is too short for {:?}",
+                min.duration(),
+                advertising_type
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AdvertisingIntervalError {}
+
+/// Minimum and maximum advertising interval.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AdvertisingIntervalRange {
+    min: AdvertisingInterval,
+    max: AdvertisingInterval,
+}
+
+impl AdvertisingIntervalRange {
+    /// Length of the serialized range.
+    pub const LENGTH: usize = 4;
+
+    /// Returns the range between `min` and `max`.
+    ///
+    /// # Errors
+    ///
+    /// - [Inverted](AdvertisingIntervalError::Inverted) if `min` is greater than `max`.
+    pub fn new(
+        min: AdvertisingInterval,
+        max: AdvertisingInterval,
+    ) -> Result<AdvertisingIntervalRange, AdvertisingIntervalError> {
+        if min > max {
+            return Err(AdvertisingIntervalError::Inverted(min, max));
+        }
+
+        Ok(AdvertisingIntervalRange { min, max })
+    }
+
+    /// Returns the range between the `min` and `max` durations, rounded down to whole 0.625 ms
+    /// units.
+    ///
+    /// # Errors
+    ///
+    /// - [OutOfRange](AdvertisingIntervalError::OutOfRange) if either duration is shorter than
+    ///   20 ms or longer than 10.24 seconds.
+    /// - [Inverted](AdvertisingIntervalError::Inverted) if `min` is greater than `max`.
+    pub fn from_durations(
+        min: Duration,
+        max: Duration,
+    ) -> Result<AdvertisingIntervalRange, AdvertisingIntervalError> {
+        let to_interval = |d| {
+            AdvertisingInterval::from_duration(d).ok_or(AdvertisingIntervalError::OutOfRange(d))
+        };
+
+        AdvertisingIntervalRange::new(to_interval(min)?, to_interval(max)?)
+    }
+
+    /// Returns the minimum advertising interval.
+    pub fn min(&self) -> AdvertisingInterval {
+        self.min
+    }
+
+    /// Returns the maximum advertising interval.
+    pub fn max(&self) -> AdvertisingInterval {
+        self.max
+    }
+
+    /// Checks that the range may be used for the advertising type.
+    ///
+    /// Before version 5.0 of the Bluetooth specification, scannable and non-connectable undirected
+    /// advertising could not use intervals shorter than 100 ms. BlueNRG-MS firmware enforces this;
+    /// BlueNRG-LP firmware (the `bluenrg-lp` feature) does not.
+    ///
+    /// # Errors
+    ///
+    /// - [TooShortForType](AdvertisingIntervalError::TooShortForType) if the minimum interval is
+    ///   too short for the advertising type.
+    pub fn validate_for(
+        &self,
+        advertising_type: AdvertisingType,
+    ) -> Result<(), AdvertisingIntervalError> {
+        if self.min < Self::min_for(advertising_type) {
+            return Err(AdvertisingIntervalError::TooShortForType(
+                advertising_type,
+                self.min,
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "bluenrg-lp"))]
+    fn min_for(advertising_type: AdvertisingType) -> AdvertisingInterval {
+        // 100 ms
+        const MIN_NONCONNECTABLE: AdvertisingInterval = AdvertisingInterval(0x00A0);
+
+        match advertising_type {
+            AdvertisingType::ScannableUndirected | AdvertisingType::NonConnectableUndirected => {
+                MIN_NONCONNECTABLE
+            }
+            _ => AdvertisingInterval::MIN,
+        }
+    }
+
+    #[cfg(feature = "bluenrg-lp")]
+    fn min_for(_advertising_type: AdvertisingType) -> AdvertisingInterval {
+        AdvertisingInterval::MIN
+    }
+
+    /// Serializes the range into the first [`LENGTH`](AdvertisingIntervalRange::LENGTH) bytes of
+    /// the buffer: the min, then the max, each in 0.625 ms units.
+    ///
+    /// # Panics
+    ///
+    /// The buffer must be at least [`LENGTH`](AdvertisingIntervalRange::LENGTH) bytes long.
+    pub fn copy_into_slice(&self, bytes: &mut [u8]) {
+        LittleEndian::write_u16(&mut bytes[0..], self.min.0);
+        LittleEndian::write_u16(&mut bytes[2..], self.max.0);
+    }
+}
+
 /// Parameters for the
 /// [`set_limited_discoverable`](Commands::set_limited_discoverable) and
 /// [`set_discoverable`](Commands::set_discoverable) commands.
@@ -1309,7 +1756,9 @@ pub struct DiscoverableParameters<'a, 'b> {
     /// If not provided, the GAP will use default values (1.28 seconds).
     ///
     /// Range for both limits: 20 ms to 10.24 seconds.  The second value must be greater than or
-    /// equal to the first.
+    /// equal to the first. Unless the `bluenrg-lp` feature is enabled, the first value must be at
+    /// least 100 ms for [ScannableUndirected](AdvertisingType::ScannableUndirected) and
+    /// [NonConnectableUndirected](AdvertisingType::NonConnectableUndirected) advertising.
     pub advertising_interval: Option<(Duration, Duration)>,
 
     /// Address type for this device.
@@ -1323,7 +1772,11 @@ pub struct DiscoverableParameters<'a, 'b> {
 
     /// Service UUID list as defined in the Bluetooth spec, v4.1, Vol 3, Part C, Section 11.
     ///
-    /// Must be 31 bytes or fewer.
+    /// The controller sends it in one 31-byte advertising packet with the [local
+    /// name](DiscoverableParameters::local_name), which takes 2 bytes more than the name itself,
+    /// the Flags AD structure (3 bytes), and, if a [connection
+    /// interval](DiscoverableParameters::conn_interval) is given, the Slave Connection Interval
+    /// Range AD structure (6 bytes).
     pub advertising_data: &'b [u8],
 
     /// Expected length of the connection to the peripheral.
@@ -1342,10 +1795,10 @@ impl<'a, 'b> DiscoverableParameters<'a, 'b> {
             _ => return Err(Error::BadAdvertisingType(self.advertising_type)),
         }
 
-        if let Some(interval) = self.advertising_interval {
-            if interval.0 > interval.1 {
-                return Err(Error::BadAdvertisingInterval(interval.0, interval.1));
-            }
+        if let Some((min, max)) = self.advertising_interval {
+            AdvertisingIntervalRange::from_durations(min, max)
+                .and_then(|range| range.validate_for(self.advertising_type))
+                .map_err(Error::BadAdvertisingInterval)?;
         }
 
         if let (Some(min), Some(max)) = self.conn_interval {
@@ -1354,6 +1807,29 @@ impl<'a, 'b> DiscoverableParameters<'a, 'b> {
             }
         }
 
+        // The name and the service UUID list are sent in the same advertising packet, along with
+        // the Flags AD structure and, if a connection interval is given, the Slave Connection
+        // Interval Range AD structure, which the controller adds. The name is serialized as an AD
+        // structure, with a length byte in front of the type byte.
+        const MAX_ADVERTISING_DATA_LENGTH: usize = 31;
+        const FLAGS_AD_LEN: usize = 3;
+        const CONN_INTERVAL_AD_LEN: usize = 6;
+        let name_ad_len = if self.local_name.is_some() {
+            1 + self.name_len()
+        } else {
+            0
+        };
+        let conn_interval_ad_len = if self.conn_interval == (None, None) {
+            0
+        } else {
+            CONN_INTERVAL_AD_LEN
+        };
+        let advertising_data_len =
+            FLAGS_AD_LEN + name_ad_len + self.advertising_data.len() + conn_interval_ad_len;
+        if advertising_data_len > MAX_ADVERTISING_DATA_LENGTH {
+            return Err(Error::BadAdvertisingDataLength(advertising_data_len));
+        }
+
         Ok(())
     }
 
@@ -1363,18 +1839,15 @@ impl<'a, 'b> DiscoverableParameters<'a, 'b> {
         let len = self.required_len();
         assert!(len <= bytes.len());
 
-        let no_duration = Duration::from_secs(0);
-        let no_interval = (no_duration, no_duration);
-
         bytes[0] = self.advertising_type as u8;
-        LittleEndian::write_u16(
-            &mut bytes[1..],
-            to_connection_length_value(self.advertising_interval.unwrap_or(no_interval).0),
-        );
-        LittleEndian::write_u16(
-            &mut bytes[3..],
-            to_connection_length_value(self.advertising_interval.unwrap_or(no_interval).1),
-        );
+        match self.advertising_interval {
+            // The range was checked by validate.
+            Some((min, max)) => AdvertisingIntervalRange::from_durations(min, max)
+                .unwrap()
+                .copy_into_slice(&mut bytes[1..5]),
+            // Zeros select the default interval.
+            None => bytes[1..5].copy_from_slice(&[0; 4]),
+        }
         bytes[5] = self.address_type as u8;
         bytes[6] = self.filter_policy as u8;
         let advertising_data_len_index = match self.local_name {
@@ -1483,24 +1956,15 @@ impl DirectConnectableParameters {
     fn validate<E>(&self) -> Result<(), Error<E>> {
         #[cfg(feature = "ms")]
         {
-            const MIN_DURATION: Duration = Duration::from_millis(20);
-            const MAX_DURATION: Duration = Duration::from_millis(10240);
-
             match self.advertising_type {
                 AdvertisingType::ConnectableDirectedHighDutyCycle
                 | AdvertisingType::ConnectableDirectedLowDutyCycle => (),
                 _ => return Err(Error::BadAdvertisingType(self.advertising_type)),
             }
 
-            if self.advertising_interval.0 < MIN_DURATION
-                || self.advertising_interval.1 > MAX_DURATION
-                || self.advertising_interval.0 > self.advertising_interval.1
-            {
-                return Err(Error::BadAdvertisingInterval(
-                    self.advertising_interval.0,
-                    self.advertising_interval.1,
-                ));
-            }
+            let (min, max) = self.advertising_interval;
+            AdvertisingIntervalRange::from_durations(min, max)
+                .map_err(Error::BadAdvertisingInterval)?;
         }
 
         Ok(())
@@ -1520,14 +1984,12 @@ impl DirectConnectableParameters {
         {
             bytes[1] = self.advertising_type as u8;
             self.initiator_address.copy_into_slice(&mut bytes[2..9]);
-            LittleEndian::write_u16(
-                &mut bytes[9..],
-                to_connection_length_value(self.advertising_interval.0),
-            );
-            LittleEndian::write_u16(
-                &mut bytes[11..],
-                to_connection_length_value(self.advertising_interval.1),
-            );
+
+            // The range was checked by validate.
+            let (min, max) = self.advertising_interval;
+            AdvertisingIntervalRange::from_durations(min, max)
+                .unwrap()
+                .copy_into_slice(&mut bytes[9..13]);
         }
     }
 }
@@ -1739,10 +2201,171 @@ pub enum AdvertisingDataType {
     SolicitUuidList32 = 0x15,
     /// Service data
     ServiceData = 0x16,
+    /// [Appearance] of the device
+    Appearance = 0x19,
     /// Manufacturer-specific data
     ManufacturerSpecificData = 0xFF,
 }
 
+macro_rules! appearances {
+    ($($(#[$attr:meta])* $variant:ident = $value:literal, $name:literal;)*) => {
+        /// External appearance of the device, from the appearance values assigned by the Bluetooth
+        /// SIG. The high 10 bits are the category, and the low 6 bits are the subcategory.
+        ///
+        /// This is the value of the appearance characteristic of the GAP service, and of the
+        /// [appearance](AdvertisingDataType::Appearance) advertising data.
+        #[derive(Copy, Clone, Debug, PartialEq)]
+        pub enum Appearance {
+            $(
+                $(#[$attr])*
+                $variant,
+            )*
+            /// A value that is not one of the variants above. Includes the value.
+            Other(u16),
+        }
+
+        impl From<Appearance> for u16 {
+            fn from(appearance: Appearance) -> u16 {
+                match appearance {
+                    $(Appearance::$variant => $value,)*
+                    Appearance::Other(value) => value,
+                }
+            }
+        }
+
+        // Every value maps to an appearance, so this also provides `TryFrom<u16>`, which never
+        // fails.
+        impl From<u16> for Appearance {
+            fn from(value: u16) -> Appearance {
+                match value {
+                    $($value => Appearance::$variant,)*
+                    _ => Appearance::Other(value),
+                }
+            }
+        }
+
+        impl core::fmt::Display for Appearance {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                match *self {
+                    $(Appearance::$variant => f.write_str($name),)*
+                    Appearance::Other(value) => write!(f, "Appearance 0x{:04X}", value),
+                }
+            }
+        }
+    };
+}
+
+appearances! {
+    /// Unknown
+    Unknown = 0, "Unknown";
+    /// Generic phone
+    GenericPhone = 64, "Generic Phone";
+    /// Generic computer
+    GenericComputer = 128, "Generic Computer";
+    /// Generic watch
+    GenericWatch = 192, "Generic Watch";
+    /// Watch: sports watch
+    SportsWatch = 193, "Sports Watch";
+    /// Generic clock
+    GenericClock = 256, "Generic Clock";
+    /// Generic display
+    GenericDisplay = 320, "Generic Display";
+    /// Generic remote control
+    GenericRemoteControl = 384, "Generic Remote Control";
+    /// Generic eye-glasses
+    GenericEyeGlasses = 448, "Generic Eye-glasses";
+    /// Generic tag
+    GenericTag = 512, "Generic Tag";
+    /// Generic keyring
+    GenericKeyring = 576, "Generic Keyring";
+    /// Generic media player
+    GenericMediaPlayer = 640, "Generic Media Player";
+    /// Generic barcode scanner
+    GenericBarcodeScanner = 704, "Generic Barcode Scanner";
+    /// Generic thermometer
+    GenericThermometer = 768, "Generic Thermometer";
+    /// Thermometer: ear
+    EarThermometer = 769, "Thermometer: Ear";
+    /// Generic heart rate sensor
+    GenericHeartRateSensor = 832, "Generic Heart Rate Sensor";
+    /// Heart rate sensor: heart rate belt
+    HeartRateBelt = 833, "Heart Rate Sensor: Heart Rate Belt";
+    /// Generic blood pressure
+    GenericBloodPressure = 896, "Generic Blood Pressure";
+    /// Blood pressure: arm
+    ArmBloodPressure = 897, "Blood Pressure: Arm";
+    /// Blood pressure: wrist
+    WristBloodPressure = 898, "Blood Pressure: Wrist";
+    /// Human interface device (HID)
+    HumanInterfaceDevice = 960, "Human Interface Device";
+    /// HID: keyboard
+    Keyboard = 961, "Keyboard";
+    /// HID: mouse
+    Mouse = 962, "Mouse";
+    /// HID: joystick
+    Joystick = 963, "Joystick";
+    /// HID: gamepad
+    Gamepad = 964, "Gamepad";
+    /// HID: digitizer tablet
+    DigitizerTablet = 965, "Digitizer Tablet";
+    /// HID: card reader
+    CardReader = 966, "Card Reader";
+    /// HID: digital pen
+    DigitalPen = 967, "Digital Pen";
+    /// HID: barcode scanner
+    HidBarcodeScanner = 968, "Barcode Scanner";
+    /// Generic glucose meter
+    GenericGlucoseMeter = 1024, "Generic Glucose Meter";
+    /// Generic running/walking sensor
+    GenericRunningWalkingSensor = 1088, "Generic Running Walking Sensor";
+    /// Running/walking sensor: in-shoe
+    InShoeRunningWalkingSensor = 1089, "Running Walking Sensor: In-Shoe";
+    /// Running/walking sensor: on-shoe
+    OnShoeRunningWalkingSensor = 1090, "Running Walking Sensor: On-Shoe";
+    /// Running/walking sensor: on-hip
+    OnHipRunningWalkingSensor = 1091, "Running Walking Sensor: On-Hip";
+    /// Generic cycling
+    GenericCycling = 1152, "Generic Cycling";
+    /// Cycling: cycling computer
+    CyclingComputer = 1153, "Cycling: Cycling Computer";
+    /// Cycling: speed sensor
+    CyclingSpeedSensor = 1154, "Cycling: Speed Sensor";
+    /// Cycling: cadence sensor
+    CyclingCadenceSensor = 1155, "Cycling: Cadence Sensor";
+    /// Cycling: power sensor
+    CyclingPowerSensor = 1156, "Cycling: Power Sensor";
+    /// Cycling: speed and cadence sensor
+    CyclingSpeedAndCadenceSensor = 1157, "Cycling: Speed and Cadence Sensor";
+    /// Generic pulse oximeter
+    GenericPulseOximeter = 3136, "Generic Pulse Oximeter";
+    /// Pulse oximeter: fingertip
+    FingertipPulseOximeter = 3137, "Pulse Oximeter: Fingertip";
+    /// Pulse oximeter: wrist worn
+    WristWornPulseOximeter = 3138, "Pulse Oximeter: Wrist Worn";
+    /// Generic weight scale
+    GenericWeightScale = 3200, "Generic Weight Scale";
+    /// Generic outdoor sports activity
+    GenericOutdoorSportsActivity = 5184, "Generic Outdoor Sports Activity";
+    /// Outdoor sports activity: location display device
+    LocationDisplay = 5185, "Location Display Device";
+    /// Outdoor sports activity: location and navigation display device
+    LocationAndNavigationDisplay = 5186, "Location and Navigation Display Device";
+    /// Outdoor sports activity: location pod
+    LocationPod = 5187, "Location Pod";
+    /// Outdoor sports activity: location and navigation pod
+    LocationAndNavigationPod = 5188, "Location and Navigation Pod";
+}
+
+impl Appearance {
+    /// Returns the value in the little-endian byte order of the appearance characteristic, for
+    /// [`update_characteristic_value`](crate::gatt::Commands::update_characteristic_value).
+    pub fn to_bytes(self) -> [u8; 2] {
+        let mut bytes = [0; 2];
+        LittleEndian::write_u16(&mut bytes, self.into());
+        bytes
+    }
+}
+
 bitflags! {
     /// Event types for [GAP Set Event Mask](Commands::set_event_mask).
     pub struct EventFlags: u16 {
@@ -2006,16 +2629,16 @@ bitflags! {
         const GENERAL_DISCOVERY = 0x02;
         /// [Name Discovery](Commands::start_name_discovery_procedure) procedure.
         const NAME_DISCOVERY = 0x04;
-        /// [Auto Connection Establishment](Commands::auto_connection_establishment).
+        /// [Auto Connection Establishment](Commands::start_auto_connection_establishment).
         const AUTO_CONNECTION_ESTABLISHMENT = 0x08;
         /// [General Connection
-        /// Establishment](Commands::general_connection_establishment).
+        /// Establishment](Commands::start_general_connection_establishment).
         const GENERAL_CONNECTION_ESTABLISHMENT = 0x10;
         /// [Selective Connection
-        /// Establishment](Commands::selective_connection_establishment).
+        /// Establishment](Commands::start_selective_connection_establishment).
         const SELECTIVE_CONNECTION_ESTABLISHMENT = 0x20;
         /// [Direct Connection
-        /// Establishment](Commands::direct_connection_establishment).
+        /// Establishment](Commands::create_connection).
         const DIRECT_CONNECTION_ESTABLISHMENT = 0x40;
         /// [Observation](Commands::start_observation_procedure) procedure.
         const OBSERVATION = 0x80;
@@ -2183,3 +2806,76 @@ impl ObservationProcedureParameters {
         bytes[6] = self.filter_duplicates as u8;
     }
 }
+
+#[cfg(feature = "periodic-advertising")]
+/// Parameters for the [Set Periodic Advertising](Commands::set_periodic_advertising) command.
+pub struct PeriodicAdvertisingParameters {
+    /// Advertising set to use for periodic advertising.
+    pub advertising_handle: u8,
+
+    /// Range of the periodic advertising interval, as (min, max). Both ends must be between 7.5 ms
+    /// and 81.91875 s, and the min must not be greater than the max. The interval is sent in units
+    /// of 1.25 ms, so values are rounded down.
+    pub interval: (Duration, Duration),
+
+    /// Fields to include in the periodic advertising PDUs.
+    pub properties: PeriodicAdvertisingProperties,
+}
+
+#[cfg(feature = "periodic-advertising")]
+impl PeriodicAdvertisingParameters {
+    const LENGTH: usize = 7;
+
+    fn validate<E>(&self) -> Result<(), Error<E>> {
+        const MIN_INTERVAL: u32 = 0x0006;
+        const MAX_INTERVAL: u32 = 0xFFFF;
+
+        let (min, max) = self.interval;
+        let in_range =
+            |d| (MIN_INTERVAL..=MAX_INTERVAL).contains(&to_periodic_advertising_interval_value(d));
+        if min > max || !in_range(min) || !in_range(max) {
+            return Err(Error::BadPeriodicAdvertisingInterval(min, max));
+        }
+
+        Ok(())
+    }
+
+    fn copy_into_slice(&self, bytes: &mut [u8]) {
+        assert_eq!(bytes.len(), Self::LENGTH);
+
+        bytes[0] = self.advertising_handle;
+        LittleEndian::write_u16(
+            &mut bytes[1..],
+            to_periodic_advertising_interval_value(self.interval.0) as u16,
+        );
+        LittleEndian::write_u16(
+            &mut bytes[3..],
+            to_periodic_advertising_interval_value(self.interval.1) as u16,
+        );
+        LittleEndian::write_u16(&mut bytes[5..], self.properties.bits());
+    }
+}
+
+#[cfg(feature = "periodic-advertising")]
+fn to_periodic_advertising_interval_value(d: Duration) -> u32 {
+    // Periodic advertising interval value: T = N * 1.25 ms
+    // We have T, we need to return N. The result may not fit in 16 bits, so that it can be
+    // validated.
+    // 800 = 1_000_000 / 1250
+    let n = 800 * d.as_secs() + u64::from(d.subsec_micros() / 1250);
+    if n > u64::from(u32::max_value()) {
+        u32::max_value()
+    } else {
+        n as u32
+    }
+}
+
+#[cfg(feature = "periodic-advertising")]
+bitflags! {
+    /// Fields to include in periodic advertising PDUs, for the [Set Periodic
+    /// Advertising](Commands::set_periodic_advertising) command.
+    pub struct PeriodicAdvertisingProperties: u16 {
+        /// Include the TX power in the advertising PDU.
+        const INCLUDE_TX_POWER = 0x0040;
+    }
+}
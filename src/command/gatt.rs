@@ -172,6 +172,10 @@ pub trait Commands {
     ///
     /// The default configuration is all the events masked.
     ///
+    /// The mask applies to all attributes and connections. Events caused by accessing a
+    /// characteristic are also filtered by the [event mask](CharacteristicEvent) given when the
+    /// characteristic was added, which can be used to silence individual characteristics.
+    ///
     /// # Errors
     ///
     /// Only underlying communication errors are reported.
@@ -868,6 +872,50 @@ pub trait Commands {
         &mut self,
         params: &UpdateLongCharacteristicValueParameters<'a>,
     ) -> nb::Result<(), Error<Self::Error>>;
+
+    /// Send the values of several characteristics to a client in a single ATT Multiple Handle
+    /// Value Notification.
+    ///
+    /// The notification is only delivered to clients that support it, and only firmware that
+    /// implements the Bluetooth 5.2 ATT Multiple Handle Value Notification accepts this command.
+    ///
+    /// # Errors
+    ///
+    /// - [NotificationsExceedMtu](Error::NotificationsExceedMtu) if the notifications would not
+    ///   fit in one ATT PDU of the given [MTU](MultipleNotificationsParameters::mtu).
+    /// - [ValueBufferTooLong](Error::ValueBufferTooLong) if the notifications would not fit in one
+    ///   command packet. The serialized notifications may be at most 252 bytes, including 4 bytes
+    ///   per notification for the handle and length.
+    /// - Underlying communication errors are reported.
+    ///
+    /// # Generated events
+    ///
+    /// When the command has completed, the controller will generate a [command
+    /// complete](crate::event::command::ReturnParameters::GattSendMultipleNotifications) event.
+    fn send_multiple_notifications<'a>(
+        &mut self,
+        params: &MultipleNotificationsParameters<'a>,
+    ) -> nb::Result<(), Error<Self::Error>>;
+
+    /// Store the GATT database (including the security database of bonded devices) in flash.
+    ///
+    /// BlueNRG-2 firmware keeps the database in RAM and only writes it to flash when this command
+    /// is issued, so bonds are lost on power cycle unless the application calls it. Call it after
+    /// a [GAP Pairing Complete](crate::event::BlueNRGEvent::GapPairingComplete) event reports
+    /// successful bonding, and after changing the GATT database. Other firmware persists the
+    /// database automatically and does not support this command.
+    ///
+    /// # Errors
+    ///
+    /// Only underlying communication errors are reported.
+    ///
+    /// # Generated events
+    ///
+    /// When the command has completed, the controller will generate a [command
+    /// complete](crate::event::command::ReturnParameters::GattStoreDb) event. If the database
+    /// could not be written, the status is
+    /// [FlashWriteFailed](crate::event::Status::FlashWriteFailed).
+    fn store_db(&mut self) -> nb::Result<(), Self::Error>;
 }
 
 impl<'bnrg, 'spi, 'dbuf, SPI, OutputPin1, OutputPin2, InputPin, E> Commands
@@ -1261,6 +1309,16 @@ where
         UpdateLongCharacteristicValueParameters<'a>,
         crate::opcode::GATT_UPDATE_LONG_CHARACTERISTIC_VALUE
     );
+
+    impl_validate_variable_length_params!(
+        send_multiple_notifications<'a>,
+        MultipleNotificationsParameters<'a>,
+        crate::opcode::GATT_SEND_MULTIPLE_NOTIFICATIONS
+    );
+
+    fn store_db(&mut self) -> nb::Result<(), Self::Error> {
+        self.write_command(crate::opcode::GATT_STORE_DB, &[])
+    }
 }
 
 /// Potential errors from parameter validation.
@@ -1290,10 +1348,43 @@ pub enum Error<E> {
     /// the serialized command to be more than 255 bytes. The maximum length is 126 handles.
     TooManyHandlesToRead,
 
+    /// For the [Send Multiple Notifications](Commands::send_multiple_notifications) command: the
+    /// serialized [notifications](MultipleNotificationsParameters::notifications) are longer than
+    /// fits in one ATT PDU of the given [MTU](MultipleNotificationsParameters::mtu). Includes the
+    /// length of the serialized notifications.
+    NotificationsExceedMtu(usize),
+
+    /// For [`serialize_into`](UpdateCharacteristicValueParameters::serialize_into): the buffer is
+    /// too small for the serialized parameters. Includes the required length.
+    BufferTooSmall(usize),
+
     /// Underlying communication error.
     Comm(E),
 }
 
+impl<E: core::fmt::Debug> core::fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            Error::DescriptorTooLong => {
+                f.write_str("descriptor value is longer than its maximum length")
+            }
+            Error::DescriptorBufferTooLong => {
+                f.write_str("descriptor value maximum length is greater than 227")
+            }
+            Error::ValueBufferTooLong => f.write_str("characteristic value is longer than 249"),
+            Error::TooManyHandlesToRead => f.write_str("more than 126 handles to read"),
+            Error::NotificationsExceedMtu(len) => {
+                write!(f, "notifications of length {} do not fit in the MTU", len)
+            }
+            Error::BufferTooSmall(len) => write!(f, "buffer is smaller than {} bytes", len),
+            Error::Comm(ref e) => write!(f, "communication error: {:?}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: core::fmt::Debug> std::error::Error for Error<E> {}
+
 fn rewrap_error<E>(e: nb::Error<E>) -> nb::Error<Error<E>> {
     match e {
         nb::Error::WouldBlock => nb::Error::WouldBlock,
@@ -1339,7 +1430,44 @@ pub enum Uuid {
     Uuid128([u8; 16]),
 }
 
+// Bluetooth Base UUID (00000000-0000-1000-8000-00805F9B34FB), in little-endian byte order. 16-bit
+// and 32-bit UUIDs replace bytes 12 through 15.
+const BASE_UUID: [u8; 16] = [
+    0xFB, 0x34, 0x9B, 0x5F, 0x80, 0x00, 0x00, 0x80, 0x00, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
 impl Uuid {
+    /// Returns the 128-bit UUID for a 32-bit UUID, expanded with the Bluetooth Base UUID. There is
+    /// no 32-bit variant, since the controller only accepts 16-bit and 128-bit UUIDs.
+    pub fn from_uuid32(uuid: u32) -> Uuid {
+        let mut bytes = BASE_UUID;
+        LittleEndian::write_u32(&mut bytes[12..16], uuid);
+        Uuid::Uuid128(bytes)
+    }
+
+    /// Returns true if both UUIDs identify the same attribute type, regardless of whether they are
+    /// given as 16-bit or 128-bit UUIDs.
+    ///
+    /// A 16-bit UUID is an alias for a 128-bit UUID based on the Bluetooth Base UUID
+    /// (`00000000-0000-1000-8000-00805F9B34FB`), so it matches its 128-bit expansion. Unlike `==`,
+    /// which compares the representations, this compares both UUIDs in 128-bit form.
+    pub fn matches(&self, other: &Uuid) -> bool {
+        self.to_uuid128() == other.to_uuid128()
+    }
+
+    /// Returns the 128-bit form of the UUID, in the same little-endian byte order as
+    /// [`Uuid::Uuid128`]. 16-bit UUIDs are expanded with the Bluetooth Base UUID.
+    pub fn to_uuid128(&self) -> [u8; 16] {
+        match *self {
+            Uuid::Uuid16(uuid) => {
+                let mut bytes = BASE_UUID;
+                LittleEndian::write_u16(&mut bytes[12..14], uuid);
+                bytes
+            }
+            Uuid::Uuid128(uuid) => uuid,
+        }
+    }
+
     fn copy_into_slice(&self, bytes: &mut [u8]) -> usize {
         match *self {
             Uuid::Uuid16(uuid) => {
@@ -1405,6 +1533,7 @@ pub struct ServiceHandle(pub u16);
 
 /// Two ordered points that represent a range. The points may be identical to represent a range with
 /// only one value.
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Range<T> {
     from: T,
     to: T,
@@ -1432,6 +1561,17 @@ pub enum RangeError {
     Inverted,
 }
 
+impl core::fmt::Display for RangeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            RangeError::Inverted => f.write_str("range begins after it ends"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RangeError {}
+
 /// Parameters for the [GATT Add Characteristic](Commands::add_characteristic) command.
 pub struct AddCharacteristicParameters {
     /// Handle of the service to which the characteristic has to be added
@@ -1568,6 +1708,18 @@ bitflags! {
 
 bitflags! {
     /// Which events may be generated when a characteristic is accessed.
+    ///
+    /// This mask is set per characteristic when it is added, and is applied before the global
+    /// [GATT event mask](Commands::set_event_mask): an event is only reported if both masks allow
+    /// it. For example, a [GATT Attribute
+    /// Modified](crate::event::BlueNRGEvent::GattAttributeModified) event requires
+    /// [ATTRIBUTE_WRITE](CharacteristicEvent::ATTRIBUTE_WRITE) for the characteristic and
+    /// [ATTRIBUTE_MODIFIED](Event::ATTRIBUTE_MODIFIED) in the global mask. To silence writes to a
+    /// frequently written characteristic while still seeing writes to the others, leave
+    /// `ATTRIBUTE_WRITE` out of its mask.
+    ///
+    /// The firmware has no command to change the mask of a characteristic after it is added; the
+    /// characteristic would have to be deleted and added again.
     pub struct CharacteristicEvent: u8 {
         /// The application will be notified when a client writes to this attribute.
         const ATTRIBUTE_WRITE = 0x01;
@@ -1623,6 +1775,18 @@ pub enum EncryptionKeySizeError {
     TooLong,
 }
 
+impl core::fmt::Display for EncryptionKeySizeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            EncryptionKeySizeError::TooShort => f.write_str("encryption key size is too short"),
+            EncryptionKeySizeError::TooLong => f.write_str("encryption key size is too long"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EncryptionKeySizeError {}
+
 /// Handle for GATT characteristics.
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
 pub struct CharacteristicHandle(pub u16);
@@ -1802,6 +1966,34 @@ impl<'a> UpdateCharacteristicValueParameters<'a> {
 
         6 + self.value.len()
     }
+
+    /// Number of bytes [`serialize_into`](UpdateCharacteristicValueParameters::serialize_into)
+    /// writes for these parameters.
+    pub fn serialized_len(&self) -> usize {
+        6 + self.value.len()
+    }
+
+    /// Serializes the command parameters directly into `bytes`, for example a transport's transmit
+    /// buffer, and returns the number of bytes written. This is the same data that
+    /// [`update_characteristic_value`](Commands::update_characteristic_value) sends, but without
+    /// the intermediate 255-byte copy it makes on the stack. The command header is not included.
+    ///
+    /// # Errors
+    ///
+    /// - [ValueBufferTooLong](Error::ValueBufferTooLong) if the
+    ///   [value](UpdateCharacteristicValueParameters::value) is so long that the command packet
+    ///   would exceed 255 bytes. The maximum allowed length is 249 bytes.
+    /// - [BufferTooSmall](Error::BufferTooSmall) if `bytes` is shorter than
+    ///   [`serialized_len`](UpdateCharacteristicValueParameters::serialized_len). Nothing is
+    ///   written in that case.
+    pub fn serialize_into<E>(&self, bytes: &mut [u8]) -> Result<usize, Error<E>> {
+        self.validate()?;
+        if bytes.len() < self.serialized_len() {
+            return Err(Error::BufferTooSmall(self.serialized_len()));
+        }
+
+        Ok(self.copy_into_slice(bytes))
+    }
 }
 
 /// Parameters for the [GATT Delete Included Service](Commands::delete_included_service) command.
@@ -2332,3 +2524,61 @@ bitflags! {
         const INDICATION = 0x02;
     }
 }
+
+/// Parameters for the [Send Multiple Notifications](Commands::send_multiple_notifications)
+/// command.
+pub struct MultipleNotificationsParameters<'a> {
+    /// Connection handle of the client to notify.
+    pub conn_handle: hci::ConnectionHandle,
+
+    /// ATT MTU of the connection. The ATT opcode and all notifications must fit in one PDU of this
+    /// size.
+    pub mtu: usize,
+
+    /// Handles and values of the characteristics to send.
+    pub notifications: &'a [(CharacteristicHandle, &'a [u8])],
+}
+
+impl<'a> MultipleNotificationsParameters<'a> {
+    const MAX_LENGTH: usize = 255;
+
+    fn validate<E>(&self) -> Result<(), Error<E>> {
+        let notifications_len = self.notifications_len();
+        if 1 + notifications_len > self.mtu {
+            return Err(Error::NotificationsExceedMtu(notifications_len));
+        }
+
+        if self.len() > Self::MAX_LENGTH {
+            return Err(Error::ValueBufferTooLong);
+        }
+
+        Ok(())
+    }
+
+    fn copy_into_slice(&self, bytes: &mut [u8]) -> usize {
+        assert!(bytes.len() >= self.len());
+
+        LittleEndian::write_u16(&mut bytes[0..2], self.conn_handle.0);
+        bytes[2] = self.notifications.len() as u8;
+        let mut next = 3;
+        for (handle, value) in self.notifications.iter() {
+            LittleEndian::write_u16(&mut bytes[next..next + 2], handle.0);
+            LittleEndian::write_u16(&mut bytes[next + 2..next + 4], value.len() as u16);
+            bytes[next + 4..next + 4 + value.len()].copy_from_slice(value);
+            next += 4 + value.len();
+        }
+
+        next
+    }
+
+    fn notifications_len(&self) -> usize {
+        self.notifications
+            .iter()
+            .map(|(_, value)| 4 + value.len())
+            .sum()
+    }
+
+    fn len(&self) -> usize {
+        3 + self.notifications_len()
+    }
+}
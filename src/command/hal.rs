@@ -5,6 +5,7 @@ extern crate byteorder;
 extern crate embedded_hal as hal;
 extern crate nb;
 
+use crate::keys::IdentityResolvingKey;
 use byteorder::{ByteOrder, LittleEndian};
 
 /// Vendor-specific HCI commands for the [`ActiveBlueNRG`](crate::ActiveBlueNRG).
@@ -108,6 +109,8 @@ pub trait Commands {
     /// the counter wraps back and starts from 0 again. The counter is not cleared until the next
     /// Direct TX test starts.
     ///
+    /// ST's documentation calls this command `aci_hal_le_tx_test_packet_number`.
+    ///
     /// # Errors
     ///
     /// Only underlying communication errors are reported.
@@ -116,6 +119,8 @@ pub trait Commands {
     ///
     /// The controller will generate a [command
     /// complete](crate::event::command::ReturnParameters::HalGetTxTestPacketCount) event.
+    #[doc(alias = "le_tx_test_packet_number")]
+    #[doc(alias = "aci_hal_le_tx_test_packet_number")]
     fn get_tx_test_packet_count(&mut self) -> nb::Result<(), Self::Error>;
 
     /// This command starts a carrier frequency, i.e. a tone, on a specific channel.
@@ -174,6 +179,161 @@ pub trait Commands {
     /// The controller will generate a [command
     /// complete](crate::event::command::ReturnParameters::HalGetAnchorPeriod) event.
     fn get_anchor_period(&mut self) -> nb::Result<(), Self::Error>;
+
+    /// Reads a register of the radio.
+    ///
+    /// This is an expert-level command for RF debugging and production trimming. The register map
+    /// is not documented publicly and differs between chips; only use addresses given by ST for
+    /// the chip in use.
+    ///
+    /// # Errors
+    ///
+    /// Only underlying communication errors are reported.
+    ///
+    /// # Generated events
+    ///
+    /// The controller will generate a [command
+    /// complete](crate::event::command::ReturnParameters::HalReadRadioRegister) event.
+    fn read_radio_register(&mut self, address: u8) -> nb::Result<(), Self::Error>;
+
+    /// Writes a register of the radio.
+    ///
+    /// This is an expert-level command for RF debugging and production trimming. Writing the wrong
+    /// value can detune the radio, break regulatory compliance, or stop the controller from
+    /// communicating until it is reset. The controller does not validate the address or the
+    /// value, and the change is lost when the controller is reset.
+    ///
+    /// # Errors
+    ///
+    /// Only underlying communication errors are reported.
+    ///
+    /// # Generated events
+    ///
+    /// The controller will generate a [command
+    /// complete](crate::event::command::ReturnParameters::HalWriteRadioRegister) event.
+    fn write_radio_register(&mut self, address: u8, value: u8) -> nb::Result<(), Self::Error>;
+
+    /// Suggests the maximum payload size (in octets) and transmission time (in microseconds) the
+    /// controller should use for packets sent on the connection (Data Length Extension).
+    ///
+    /// This is the standard HCI LE Set Data Length command. The controller and the peer may still
+    /// settle on smaller values.
+    ///
+    /// # Errors
+    ///
+    /// - [BadTxOctets](DataLengthError::BadTxOctets) if `tx_octets` is less than 27 or greater than
+    ///   251.
+    /// - [BadTxTime](DataLengthError::BadTxTime) if `tx_time` is less than 328 or greater than
+    ///   17040.
+    /// - Underlying communication errors
+    ///
+    /// # Generated events
+    ///
+    /// The controller will generate a command complete event with the status and connection
+    /// handle, and an LE Data Length Change event if the values used on the connection change.
+    fn le_set_data_length(
+        &mut self,
+        conn_handle: hci::ConnectionHandle,
+        tx_octets: u16,
+        tx_time: u16,
+    ) -> nb::Result<(), DataLengthError<Self::Error>>;
+
+    /// Reads the maximum payload sizes (in octets) and transmission times (in microseconds) the
+    /// controller supports for sent and received packets.
+    ///
+    /// This is the standard HCI LE Read Maximum Data Length command.
+    ///
+    /// # Errors
+    ///
+    /// Only underlying communication errors are reported.
+    ///
+    /// # Generated events
+    ///
+    /// The controller will generate a [command
+    /// complete](crate::event::command::ReturnParameters::LeReadMaximumDataLength) event.
+    fn le_read_maximum_data_length(&mut self) -> nb::Result<(), Self::Error>;
+
+    /// Sets the preferred PHYs for the connection, e.g. to use the 2M PHY for throughput or the
+    /// Coded PHY for range.
+    ///
+    /// This is the standard HCI LE Set PHY command, and is only accepted by controllers that
+    /// support Bluetooth 5.0 PHYs. The controller negotiates the PHYs with the peer, which may not
+    /// support the preferred ones.
+    ///
+    /// `all_phys` tells the controller that the host has no preference for the transmitter or
+    /// receiver PHYs, in which case `tx_phys` or `rx_phys` (respectively) is ignored. `coding` is
+    /// only used if the Coded PHY is selected for the transmitter.
+    ///
+    /// # Errors
+    ///
+    /// Only underlying communication errors are reported.
+    ///
+    /// # Generated events
+    ///
+    /// The controller will generate a command status event, and an LE PHY Update Complete event
+    /// when the procedure ends.
+    fn le_set_phy(
+        &mut self,
+        conn_handle: hci::ConnectionHandle,
+        all_phys: AllPhys,
+        tx_phys: PhyMask,
+        rx_phys: PhyMask,
+        coding: CodingPreference,
+    ) -> nb::Result<(), Self::Error>;
+
+    /// Adds a peer to the controller's resolving list, with the IRKs used to resolve the peer's
+    /// private addresses and to generate the local ones on connections with it.
+    ///
+    /// This is the standard HCI LE Add Device To Resolving List command, for hosts that manage the
+    /// resolving list themselves instead of loading it from the security database with [GAP Add
+    /// Devices to Resolving List](crate::gap::Commands::add_devices_to_resolving_list)
+    /// (`bluenrg-lp` feature only). The controller rejects it while address resolution is enabled
+    /// and advertising, scanning or connecting.
+    ///
+    /// # Errors
+    ///
+    /// Only underlying communication errors are reported.
+    ///
+    /// # Generated events
+    ///
+    /// The controller will generate a [command
+    /// complete](crate::event::command::ReturnParameters::LeAddDeviceToResolvingList) event.
+    fn le_add_device_to_resolving_list(
+        &mut self,
+        params: &ResolvingListEntry,
+    ) -> nb::Result<(), Self::Error>;
+
+    /// Removes a peer from the controller's resolving list.
+    ///
+    /// This is the standard HCI LE Remove Device From Resolving List command.
+    ///
+    /// # Errors
+    ///
+    /// Only underlying communication errors are reported.
+    ///
+    /// # Generated events
+    ///
+    /// The controller will generate a [command
+    /// complete](crate::event::command::ReturnParameters::LeRemoveDeviceFromResolvingList) event.
+    /// Its status is an error if the peer is not in the resolving list.
+    fn le_remove_device_from_resolving_list(
+        &mut self,
+        peer_identity_address: hci::host::PeerAddrType,
+    ) -> nb::Result<(), Self::Error>;
+
+    /// Removes all entries from the controller's resolving list.
+    ///
+    /// This is the standard HCI LE Clear Resolving List command.
+    ///
+    /// # Errors
+    ///
+    /// Only underlying communication errors are reported.
+    ///
+    /// # Generated events
+    ///
+    /// The controller will generate a [command
+    /// complete](crate::event::command::ReturnParameters::LeClearResolvingList) event.
+    fn le_clear_resolving_list(&mut self) -> nb::Result<(), Self::Error>;
 }
 
 impl<'bnrg, 'spi, 'dbuf, SPI, OutputPin1, OutputPin2, InputPin, E> Commands
@@ -236,8 +396,129 @@ where
     fn get_anchor_period(&mut self) -> nb::Result<(), Self::Error> {
         self.write_command(crate::opcode::HAL_GET_ANCHOR_PERIOD, &[])
     }
+
+    fn read_radio_register(&mut self, address: u8) -> nb::Result<(), Self::Error> {
+        self.write_command(crate::opcode::HAL_READ_RADIO_REG, &[address])
+    }
+
+    fn write_radio_register(&mut self, address: u8, value: u8) -> nb::Result<(), Self::Error> {
+        self.write_command(crate::opcode::HAL_WRITE_RADIO_REG, &[address, value])
+    }
+
+    fn le_set_data_length(
+        &mut self,
+        conn_handle: hci::ConnectionHandle,
+        tx_octets: u16,
+        tx_time: u16,
+    ) -> nb::Result<(), DataLengthError<Self::Error>> {
+        if tx_octets < MIN_TX_OCTETS || tx_octets > MAX_TX_OCTETS {
+            return Err(nb::Error::Other(DataLengthError::BadTxOctets(tx_octets)));
+        }
+        if tx_time < MIN_TX_TIME || tx_time > MAX_TX_TIME {
+            return Err(nb::Error::Other(DataLengthError::BadTxTime(tx_time)));
+        }
+
+        let mut bytes = [0; 6];
+        LittleEndian::write_u16(&mut bytes[0..], conn_handle.0);
+        LittleEndian::write_u16(&mut bytes[2..], tx_octets);
+        LittleEndian::write_u16(&mut bytes[4..], tx_time);
+
+        self.write_command(crate::opcode::LE_SET_DATA_LENGTH, &bytes)
+            .map_err(|e| match e {
+                nb::Error::WouldBlock => nb::Error::WouldBlock,
+                nb::Error::Other(c) => nb::Error::Other(DataLengthError::Comm(c)),
+            })
+    }
+
+    fn le_read_maximum_data_length(&mut self) -> nb::Result<(), Self::Error> {
+        self.write_command(crate::opcode::LE_READ_MAXIMUM_DATA_LENGTH, &[])
+    }
+
+    fn le_set_phy(
+        &mut self,
+        conn_handle: hci::ConnectionHandle,
+        all_phys: AllPhys,
+        tx_phys: PhyMask,
+        rx_phys: PhyMask,
+        coding: CodingPreference,
+    ) -> nb::Result<(), Self::Error> {
+        let mut bytes = [0; 7];
+        LittleEndian::write_u16(&mut bytes[0..], conn_handle.0);
+        bytes[2] = all_phys.bits();
+        bytes[3] = tx_phys.bits();
+        bytes[4] = rx_phys.bits();
+        LittleEndian::write_u16(&mut bytes[5..], coding as u16);
+
+        self.write_command(crate::opcode::LE_SET_PHY, &bytes)
+    }
+
+    impl_params!(
+        le_add_device_to_resolving_list,
+        ResolvingListEntry,
+        crate::opcode::LE_ADD_DEVICE_TO_RESOLVING_LIST
+    );
+
+    fn le_remove_device_from_resolving_list(
+        &mut self,
+        peer_identity_address: hci::host::PeerAddrType,
+    ) -> nb::Result<(), Self::Error> {
+        let mut bytes = [0; 7];
+        peer_identity_address.copy_into_slice(&mut bytes);
+
+        self.write_command(crate::opcode::LE_REMOVE_DEVICE_FROM_RESOLVING_LIST, &bytes)
+    }
+
+    fn le_clear_resolving_list(&mut self) -> nb::Result<(), Self::Error> {
+        self.write_command(crate::opcode::LE_CLEAR_RESOLVING_LIST, &[])
+    }
 }
 
+/// The standard HCI events a BlueNRG application needs, for the HCI crate's
+/// [`set_event_mask`](hci::host::Hci::set_event_mask). It includes the LE meta event, without which
+/// none of the events in [`DEFAULT_LE_EVENT_MASK`] are reported.
+pub const DEFAULT_EVENT_MASK: hci::host::EventFlags = hci::host::EventFlags::from_bits_truncate(
+    hci::host::EventFlags::DISCONNECTION_COMPLETE.bits()
+        | hci::host::EventFlags::ENCRYPTION_CHANGE.bits()
+        | hci::host::EventFlags::READ_REMOTE_VERSION_INFORMATION_COMPLETE.bits()
+        | hci::host::EventFlags::HARDWARE_ERROR.bits()
+        | hci::host::EventFlags::DATA_BUFFER_OVERFLOW.bits()
+        | hci::host::EventFlags::ENCRYPTION_KEY_REFRESH_COMPLETE.bits()
+        | hci::host::EventFlags::LE_META_EVENT.bits(),
+);
+
+/// The LE meta events for connections, scanning, security, and data length and PHY updates, for the
+/// HCI crate's [`le_set_event_mask`](hci::host::Hci::le_set_event_mask). The periodic advertising
+/// events are left out, since only receivers of periodic advertising need them.
+pub const DEFAULT_LE_EVENT_MASK: hci::host::LeEventFlags =
+    hci::host::LeEventFlags::from_bits_truncate(
+        hci::host::LeEventFlags::CONNECTION_COMPLETE.bits()
+            | hci::host::LeEventFlags::ADVERTISING_REPORT.bits()
+            | hci::host::LeEventFlags::CONNECTION_UPDATE_COMPLETE.bits()
+            | hci::host::LeEventFlags::READ_REMOTE_FEATURES_COMPLETE.bits()
+            | hci::host::LeEventFlags::LONG_TERM_KEY_REQUEST.bits()
+            | hci::host::LeEventFlags::DATA_LENGTH_CHANGE.bits()
+            | hci::host::LeEventFlags::PHY_UPDATE_COMPLETE.bits(),
+    );
+
+/// Minimum payload size (in octets) that may be suggested with
+/// [`le_set_data_length`](Commands::le_set_data_length). This is the payload size without Data
+/// Length Extension.
+pub const MIN_TX_OCTETS: u16 = 27;
+
+/// Maximum payload size (in octets) that may be suggested with
+/// [`le_set_data_length`](Commands::le_set_data_length).
+pub const MAX_TX_OCTETS: u16 = 251;
+
+/// Minimum transmission time (in microseconds) that may be suggested with
+/// [`le_set_data_length`](Commands::le_set_data_length). This is the time to send a 27-octet
+/// payload on the LE 1M PHY.
+pub const MIN_TX_TIME: u16 = 328;
+
+/// Maximum transmission time (in microseconds) that may be suggested with
+/// [`le_set_data_length`](Commands::le_set_data_length). This is the time to send a 251-octet
+/// payload on the LE Coded PHY with S=8 coding.
+pub const MAX_TX_TIME: u16 = 0x4290;
+
 /// Potential errors from parameter validation.
 ///
 /// Before some commands are sent to the controller, the parameters are validated. This type
@@ -253,6 +534,52 @@ pub enum Error<E> {
     Comm(E),
 }
 
+impl<E: core::fmt::Debug> core::fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            Error::InvalidChannel(channel) => {
+                write!(f, "channel {} is greater than 39", channel)
+            }
+            Error::Comm(ref e) => write!(f, "communication error: {:?}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: core::fmt::Debug> std::error::Error for Error<E> {}
+
+/// Potential errors from the [LE Set Data Length](Commands::le_set_data_length) command.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DataLengthError<E> {
+    /// The suggested payload size was outside the allowed range ([`MIN_TX_OCTETS`] to
+    /// [`MAX_TX_OCTETS`]). The invalid size is returned.
+    BadTxOctets(u16),
+
+    /// The suggested transmission time was outside the allowed range ([`MIN_TX_TIME`] to
+    /// [`MAX_TX_TIME`] microseconds). The invalid time is returned.
+    BadTxTime(u16),
+
+    /// Underlying communication error.
+    Comm(E),
+}
+
+impl<E: core::fmt::Debug> core::fmt::Display for DataLengthError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            DataLengthError::BadTxOctets(octets) => {
+                write!(f, "payload size {} is outside 27 to 251 octets", octets)
+            }
+            DataLengthError::BadTxTime(time) => {
+                write!(f, "transmission time {} is outside 328 to 17040 us", time)
+            }
+            DataLengthError::Comm(ref e) => write!(f, "communication error: {:?}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: core::fmt::Debug> std::error::Error for DataLengthError<E> {}
+
 fn rewrap_error<E>(e: nb::Error<E>) -> nb::Error<Error<E>> {
     match e {
         nb::Error::WouldBlock => nb::Error::WouldBlock,
@@ -514,6 +841,7 @@ impl ConfigDataCompleteBuilder {
 }
 
 /// Roles that the server can adopt.
+#[derive(Copy, Clone, Debug, PartialEq)]
 #[repr(u8)]
 pub enum Role {
     /// Peripheral and primary device.
@@ -540,6 +868,7 @@ pub enum Role {
 
 /// Configuration parameters that are readable by the
 /// [`read_config_data`](Commands::read_config_data) command.
+#[derive(Copy, Clone, Debug, PartialEq)]
 #[repr(u8)]
 pub enum ConfigParameter {
     /// Bluetooth public address.
@@ -568,6 +897,7 @@ pub enum ConfigParameter {
 /// The controller uses two parameters to determine the actual power level: enable high power, and
 /// PA level. This enum combines the two parameters. The high byte is the PA level; the low byte is
 /// the enable high power flag.
+#[derive(Copy, Clone, Debug, PartialEq)]
 #[repr(u16)]
 pub enum PowerLevel {
     /// PA level 0, low power.
@@ -603,3 +933,64 @@ pub enum PowerLevel {
     /// PA level 7, high power.
     Dbm8_0 = 0x701,
 }
+
+bitflags! {
+    /// PHYs for the [LE Set PHY](Commands::le_set_phy) command.
+    pub struct PhyMask: u8 {
+        /// LE 1M PHY, supported by all controllers.
+        const LE_1M = 0x01;
+        /// LE 2M PHY, for higher throughput.
+        const LE_2M = 0x02;
+        /// LE Coded PHY, for longer range.
+        const LE_CODED = 0x04;
+    }
+}
+
+bitflags! {
+    /// Directions for which the host has no PHY preference, for the [LE Set
+    /// PHY](Commands::le_set_phy) command.
+    pub struct AllPhys: u8 {
+        /// The host has no preference for the transmitter PHY; the `tx_phys` mask is ignored.
+        const NO_TX_PREFERENCE = 0x01;
+        /// The host has no preference for the receiver PHY; the `rx_phys` mask is ignored.
+        const NO_RX_PREFERENCE = 0x02;
+    }
+}
+
+/// Coding to use when the transmitter uses the Coded PHY, for the [LE Set
+/// PHY](Commands::le_set_phy) command.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u16)]
+pub enum CodingPreference {
+    /// The host has no preferred coding.
+    NoPreference = 0x0000,
+    /// S=2 coding: 500 kb/s.
+    S2 = 0x0001,
+    /// S=8 coding: 125 kb/s, for the longest range.
+    S8 = 0x0002,
+}
+
+/// Parameters for the [LE Add Device To Resolving
+/// List](Commands::le_add_device_to_resolving_list) command.
+pub struct ResolvingListEntry {
+    /// Identity address of the peer: its public address or its static random address.
+    pub peer_identity_address: hci::host::PeerAddrType,
+
+    /// IRK the peer distributed during bonding, used to resolve its private addresses.
+    pub peer_irk: IdentityResolvingKey,
+
+    /// Local IRK, used to generate the local private addresses on connections with the peer.
+    pub local_irk: IdentityResolvingKey,
+}
+
+impl ResolvingListEntry {
+    const LENGTH: usize = 39;
+
+    fn copy_into_slice(&self, bytes: &mut [u8]) {
+        assert!(bytes.len() >= Self::LENGTH);
+
+        self.peer_identity_address.copy_into_slice(&mut bytes[0..7]);
+        bytes[7..23].copy_from_slice(self.peer_irk.as_bytes());
+        bytes[23..39].copy_from_slice(self.local_irk.as_bytes());
+    }
+}
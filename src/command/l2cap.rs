@@ -6,6 +6,10 @@ extern crate embedded_hal as hal;
 extern crate nb;
 
 use byteorder::{ByteOrder, LittleEndian};
+#[cfg(feature = "l2cap-events")]
+use core::time::Duration;
+#[cfg(feature = "l2cap-events")]
+use hci::types::ConnectionIntervalBuilder;
 use hci::types::{ConnectionInterval, ExpectedConnectionLength};
 
 /// L2Cap-specific commands for the [`ActiveBlueNRG`](crate::ActiveBlueNRG).
@@ -138,3 +142,105 @@ impl ConnectionParameterUpdateResponse {
         bytes[15] = self.accepted as u8;
     }
 }
+
+#[cfg(feature = "l2cap-events")]
+/// How an [`L2CapParameterPolicy`] answered a connection parameter update request.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ParameterDecision {
+    /// The requested parameters are within the acceptable ranges, and are used as-is.
+    Accepted,
+
+    /// Some of the requested parameters were outside the acceptable ranges, and were moved to the
+    /// nearest acceptable value.
+    Clamped,
+
+    /// The requested parameters are not acceptable.
+    Rejected,
+}
+
+#[cfg(feature = "l2cap-events")]
+/// Ranges of connection parameters that a central accepts from an [L2CAP Connection Update
+/// Request](crate::event::BlueNRGEvent::L2CapConnectionUpdateRequest).
+///
+/// The peripheral gives up on the request if it is not answered in time, so the policy answers it
+/// immediately with [`respond`](L2CapParameterPolicy::respond).
+#[derive(Clone, Debug)]
+pub struct L2CapParameterPolicy {
+    /// Shortest and longest acceptable connection interval, inclusive.
+    pub interval: (Duration, Duration),
+
+    /// Lowest and highest acceptable peripheral latency, in connection events, inclusive.
+    pub conn_latency: (u16, u16),
+
+    /// Shortest and longest acceptable supervision timeout, inclusive.
+    pub supervision_timeout: (Duration, Duration),
+
+    /// Expected length of connection events, used for every accepted request.
+    pub expected_connection_length: ExpectedConnectionLength,
+
+    /// If true, requests outside the acceptable ranges are [clamped](ParameterDecision::Clamped)
+    /// into them. Otherwise, they are [rejected](ParameterDecision::Rejected).
+    pub clamp: bool,
+}
+
+#[cfg(feature = "l2cap-events")]
+impl L2CapParameterPolicy {
+    /// Decides how to answer the request, and returns the decision with the parameters for
+    /// [`connection_parameter_update_response`](Commands::connection_parameter_update_response).
+    ///
+    /// The response always echoes the request's connection handle and identifier. If the request
+    /// is clamped, the response carries the clamped parameters, which the controller uses when it
+    /// updates the connection. A clamped request whose parameters are not consistent (for example,
+    /// because the supervision timeout is too short for the clamped interval and latency) is
+    /// rejected.
+    pub fn respond(
+        &self,
+        request: &crate::event::L2CapConnectionUpdateRequest,
+    ) -> (ParameterDecision, ConnectionParameterUpdateResponse) {
+        let (min_interval, max_interval) = request.conn_interval.interval();
+        let conn_latency = request.conn_interval.conn_latency();
+        let supervision_timeout = request.conn_interval.supervision_timeout();
+
+        let clamped_min_interval = clamp(min_interval, self.interval);
+        let clamped_max_interval = clamp(max_interval, self.interval);
+        let clamped_latency = clamp(conn_latency, self.conn_latency);
+        let clamped_timeout = clamp(supervision_timeout, self.supervision_timeout);
+
+        let acceptable = clamped_min_interval == min_interval
+            && clamped_max_interval == max_interval
+            && clamped_latency == conn_latency
+            && clamped_timeout == supervision_timeout;
+
+        let (decision, conn_interval) = if acceptable {
+            (ParameterDecision::Accepted, request.conn_interval)
+        } else if !self.clamp {
+            (ParameterDecision::Rejected, request.conn_interval)
+        } else {
+            match ConnectionIntervalBuilder::new()
+                .with_range(clamped_min_interval, clamped_max_interval)
+                .with_latency(clamped_latency)
+                .with_supervision_timeout(clamped_timeout)
+                .build()
+            {
+                Ok(interval) => (ParameterDecision::Clamped, interval),
+                Err(_) => (ParameterDecision::Rejected, request.conn_interval),
+            }
+        };
+
+        (
+            decision,
+            ConnectionParameterUpdateResponse {
+                conn_handle: request.conn_handle,
+                conn_interval,
+                expected_connection_length_range: self.expected_connection_length.clone(),
+                identifier: request.identifier,
+                accepted: decision != ParameterDecision::Rejected,
+            },
+        )
+    }
+}
+
+#[cfg(feature = "l2cap-events")]
+fn clamp<T: Ord>(value: T, (min, max): (T, T)) -> T {
+    value.max(min).min(max)
+}
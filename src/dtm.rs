@@ -0,0 +1,227 @@
+//! Helper for running Direct Test Mode (DTM) sequences, e.g. for regulatory testing.
+//!
+//! Direct Test Mode combines the standard LE test commands (receiver test, transmitter test, and
+//! test end) with the vendor-specific carrier tone commands. All of these commands identify the
+//! channel by its RF channel number, which orders the 40 channels by frequency (RF channel `N` is
+//! at `2402 + 2 * N` MHz). The link layer (and most test plans) instead use the LE channel index,
+//! which numbers the 37 data channels first and the 3 advertising channels last. The
+//! [`DirectTestMode`] helper takes LE channel indices and converts them internally.
+
+use crate::event::BlueNRGEvent;
+use crate::hal::Commands as HalCommands;
+use crate::UartController;
+use core::marker::PhantomData;
+use hci::host::Hci;
+
+pub use hci::host::TestPacketPayload;
+
+/// Largest LE channel index (or RF channel).
+pub const MAX_CHANNEL: u8 = 39;
+
+/// Largest payload length for the transmitter test, in bytes.
+pub const MAX_PAYLOAD_LEN: usize = 37;
+
+/// Converts an LE channel index (0-36 for data channels, 37-39 for advertising channels) to the RF
+/// channel number used by the test commands.
+///
+/// Returns `None` if the channel index is greater than 39.
+pub fn rf_channel(channel_index: u8) -> Option<u8> {
+    match channel_index {
+        37 => Some(0),
+        0..=10 => Some(channel_index + 1),
+        38 => Some(12),
+        11..=36 => Some(channel_index + 2),
+        39 => Some(39),
+        _ => None,
+    }
+}
+
+/// Converts an RF channel number, as used by the test commands, to the LE channel index.
+///
+/// Returns `None` if the RF channel is greater than 39.
+pub fn channel_index(rf_channel: u8) -> Option<u8> {
+    match rf_channel {
+        0 => Some(37),
+        1..=11 => Some(rf_channel - 1),
+        12 => Some(38),
+        13..=38 => Some(rf_channel - 2),
+        39 => Some(39),
+        _ => None,
+    }
+}
+
+/// Returns the number of packets received during a receiver test, if the event is the command
+/// complete event for the [`stop`](DirectTestMode::stop) command.
+///
+/// The count is only meaningful after a receiver test; the controller reports 0 after a
+/// transmitter test.
+pub fn packet_count(event: &hci::event::Event<BlueNRGEvent>) -> Option<usize> {
+    match event {
+        hci::event::Event::CommandComplete(complete) => match complete.return_params {
+            hci::event::command::ReturnParameters::LeTestEnd(ref params) => {
+                Some(params.number_of_packets)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Drives Direct Test Mode on a controller.
+///
+/// Each function only sends the command; the controller responds with a command complete event,
+/// which the application reads as usual. [`packet_count`] decodes the response to
+/// [`stop`](DirectTestMode::stop).
+pub struct DirectTestMode<'c, C: ?Sized, E> {
+    controller: &'c mut C,
+    _error: PhantomData<E>,
+}
+
+impl<'c, C, E> DirectTestMode<'c, C, E>
+where
+    C: UartController<E, VS = crate::event::Status> + ?Sized,
+{
+    /// Wraps the controller to run test sequences on it.
+    pub fn new(controller: &'c mut C) -> DirectTestMode<'c, C, E> {
+        DirectTestMode {
+            controller,
+            _error: PhantomData,
+        }
+    }
+
+    /// Starts the transmitter test on the given LE channel index, sending packets with
+    /// `payload_len` bytes of the given payload pattern.
+    ///
+    /// # Errors
+    ///
+    /// - [InvalidChannel](Error::InvalidChannel) if the channel index is greater than 39.
+    /// - [InvalidPayloadLength](Error::InvalidPayloadLength) if the payload length is greater
+    ///   than 37.
+    /// - [Hci](Error::Hci) if the HCI layer rejects the command parameters.
+    /// - Underlying communication errors.
+    pub fn start_tx_test(
+        &mut self,
+        channel: u8,
+        payload_len: usize,
+        packet_payload: TestPacketPayload,
+    ) -> nb::Result<(), Error<E>> {
+        let rf_channel = to_rf_channel(channel)?;
+        if payload_len > MAX_PAYLOAD_LEN {
+            return Err(nb::Error::Other(Error::InvalidPayloadLength(payload_len)));
+        }
+
+        self.controller
+            .le_transmitter_test(rf_channel, payload_len, packet_payload)
+            .map_err(rewrap_hci_error)
+    }
+
+    /// Starts the receiver test on the given LE channel index.
+    ///
+    /// # Errors
+    ///
+    /// - [InvalidChannel](Error::InvalidChannel) if the channel index is greater than 39.
+    /// - [Hci](Error::Hci) if the HCI layer rejects the command parameters.
+    /// - Underlying communication errors.
+    pub fn start_rx_test(&mut self, channel: u8) -> nb::Result<(), Error<E>> {
+        let rf_channel = to_rf_channel(channel)?;
+
+        self.controller
+            .le_receiver_test(rf_channel)
+            .map_err(rewrap_hci_error)
+    }
+
+    /// Ends the running receiver or transmitter test.
+    ///
+    /// The controller returns the number of packets received in its command complete event; use
+    /// [`packet_count`] to decode it.
+    ///
+    /// # Errors
+    ///
+    /// Only underlying communication errors are reported.
+    pub fn stop(&mut self) -> nb::Result<(), Error<E>> {
+        self.controller.le_test_end().map_err(rewrap_error)
+    }
+
+    /// Starts a carrier tone on the given LE channel index.
+    ///
+    /// # Errors
+    ///
+    /// - [InvalidChannel](Error::InvalidChannel) if the channel index is greater than 39.
+    /// - Underlying communication errors.
+    pub fn carrier_on(&mut self, channel: u8) -> nb::Result<(), Error<E>> {
+        let rf_channel = to_rf_channel(channel)?;
+
+        self.controller.start_tone(rf_channel).map_err(|e| match e {
+            nb::Error::WouldBlock => nb::Error::WouldBlock,
+            nb::Error::Other(crate::hal::Error::InvalidChannel(ch)) => {
+                nb::Error::Other(Error::InvalidChannel(ch))
+            }
+            nb::Error::Other(crate::hal::Error::Comm(e)) => nb::Error::Other(Error::Comm(e)),
+        })
+    }
+
+    /// Stops the carrier tone started by [`carrier_on`](DirectTestMode::carrier_on).
+    ///
+    /// # Errors
+    ///
+    /// Only underlying communication errors are reported.
+    pub fn carrier_off(&mut self) -> nb::Result<(), Error<E>> {
+        self.controller.stop_tone().map_err(rewrap_error)
+    }
+}
+
+/// Potential errors from Direct Test Mode parameter validation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Error<E> {
+    /// The LE channel index was greater than 39. Includes the invalid channel.
+    InvalidChannel(u8),
+
+    /// For the [transmitter test](DirectTestMode::start_tx_test), the payload length was greater
+    /// than 37. Includes the invalid length.
+    InvalidPayloadLength(usize),
+
+    /// Underlying communication error.
+    Comm(E),
+
+    /// The HCI layer rejected the LE test command parameters. Includes the HCI error.
+    Hci(hci::host::Error<E, crate::event::Status>),
+}
+
+impl<E: core::fmt::Debug> core::fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            Error::InvalidChannel(channel) => {
+                write!(f, "channel index {} is greater than 39", channel)
+            }
+            Error::InvalidPayloadLength(len) => {
+                write!(f, "payload length {} is greater than 37", len)
+            }
+            Error::Comm(ref e) => write!(f, "communication error: {:?}", e),
+            Error::Hci(ref e) => write!(f, "HCI error: {:?}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: core::fmt::Debug> std::error::Error for Error<E> {}
+
+fn to_rf_channel<E>(channel: u8) -> Result<u8, nb::Error<Error<E>>> {
+    rf_channel(channel).ok_or(nb::Error::Other(Error::InvalidChannel(channel)))
+}
+
+fn rewrap_error<E>(e: nb::Error<E>) -> nb::Error<Error<E>> {
+    match e {
+        nb::Error::WouldBlock => nb::Error::WouldBlock,
+        nb::Error::Other(c) => nb::Error::Other(Error::Comm(c)),
+    }
+}
+
+fn rewrap_hci_error<E>(
+    e: nb::Error<hci::host::Error<E, crate::event::Status>>,
+) -> nb::Error<Error<E>> {
+    match e {
+        nb::Error::WouldBlock => nb::Error::WouldBlock,
+        nb::Error::Other(hci::host::Error::Comm(c)) => nb::Error::Other(Error::Comm(c)),
+        nb::Error::Other(e) => nb::Error::Other(Error::Hci(e)),
+    }
+}
@@ -53,6 +53,30 @@ pub enum ReturnParameters {
     /// command.
     HalGetAnchorPeriod(HalAnchorPeriod),
 
+    /// Parameters returned by the [HAL Read Radio
+    /// Register](crate::hal::Commands::read_radio_register) command.
+    HalReadRadioRegister(HalRadioRegister),
+
+    /// Status returned by the [HAL Write Radio
+    /// Register](crate::hal::Commands::write_radio_register) command.
+    HalWriteRadioRegister(hci::Status<crate::event::Status>),
+
+    /// Status returned by the [LE Add Device To Resolving
+    /// List](crate::hal::Commands::le_add_device_to_resolving_list) command.
+    LeAddDeviceToResolvingList(hci::Status<crate::event::Status>),
+
+    /// Status returned by the [LE Remove Device From Resolving
+    /// List](crate::hal::Commands::le_remove_device_from_resolving_list) command.
+    LeRemoveDeviceFromResolvingList(hci::Status<crate::event::Status>),
+
+    /// Status returned by the [LE Clear Resolving
+    /// List](crate::hal::Commands::le_clear_resolving_list) command.
+    LeClearResolvingList(hci::Status<crate::event::Status>),
+
+    /// Parameters returned by the [LE Read Maximum Data
+    /// Length](crate::hal::Commands::le_read_maximum_data_length) command.
+    LeReadMaximumDataLength(LeMaximumDataLength),
+
     /// Status returned by the [GAP Set Non-Discoverable](crate::gap::Commands::set_nondiscoverable)
     /// command.
     GapSetNonDiscoverable(hci::Status<crate::event::Status>),
@@ -155,6 +179,15 @@ pub enum ReturnParameters {
     /// command.
     GapIsDeviceBonded(hci::Status<crate::event::Status>),
 
+    /// Status returned by the [GAP Numeric Comparison Value
+    /// Confirm](crate::gap::Commands::numeric_comparison_value_confirm) command.
+    GapNumericComparisonValueConfirm(hci::Status<crate::event::Status>),
+
+    #[cfg(feature = "bluenrg-lp")]
+    /// Status returned by the [GAP Add Devices to Resolving
+    /// List](crate::gap::Commands::add_devices_to_resolving_list) command.
+    GapAddDevicesToResolvingList(hci::Status<crate::event::Status>),
+
     /// Parameters returned by the [GATT Init](crate::gatt::Commands::init) command.
     GattInit(hci::Status<crate::event::Status>),
 
@@ -234,17 +267,48 @@ pub enum ReturnParameters {
     #[cfg(feature = "ms")]
     GattUpdateLongCharacteristicValue(hci::Status<crate::event::Status>),
 
+    /// Status after processing a [GATT Send Multiple
+    /// Notifications](crate::gatt::Commands::send_multiple_notifications) command.
+    GattSendMultipleNotifications(hci::Status<crate::event::Status>),
+
+    /// Status after processing a [GATT Store DB](crate::gatt::Commands::store_db) command. The
+    /// status is [FlashWriteFailed](crate::event::Status::FlashWriteFailed) if the database could
+    /// not be written to flash.
+    GattStoreDb(hci::Status<crate::event::Status>),
+
     /// Status returned by the [L2CAP Connection Parameter Update
     /// Response](crate::l2cap::Commands::connection_parameter_update_response) command.
     L2CapConnectionParameterUpdateResponse(hci::Status<crate::event::Status>),
 }
 
 impl hci::event::VendorReturnParameters for ReturnParameters {
-    type Error = super::BlueNRGError;
+    type Error = super::ParseError;
 
     fn new(bytes: &[u8]) -> Result<Self, hci::event::Error<Self::Error>> {
+        ReturnParameters::parse(bytes).map_err(|error| {
+            hci::event::Error::Vendor(super::ParseError {
+                code: if bytes.len() < 3 {
+                    None
+                } else {
+                    Some(LittleEndian::read_u16(&bytes[1..]))
+                },
+                error,
+            })
+        })
+    }
+}
+
+impl ReturnParameters {
+    fn parse(bytes: &[u8]) -> Result<Self, hci::event::Error<super::BlueNRGError>> {
         check_len_at_least(bytes, 3)?;
 
+        #[cfg(feature = "log")]
+        log::trace!(
+            "command complete {:#06x}, status {:?}",
+            LittleEndian::read_u16(&bytes[1..]),
+            bytes.get(3)
+        );
+
         match hci::Opcode(LittleEndian::read_u16(&bytes[1..])) {
             crate::opcode::HAL_GET_FIRMWARE_REVISION => Ok(
                 ReturnParameters::HalGetFirmwareRevision(to_hal_firmware_revision(&bytes[3..])?),
@@ -278,6 +342,24 @@ impl hci::event::VendorReturnParameters for ReturnParameters {
             crate::opcode::HAL_GET_ANCHOR_PERIOD => Ok(ReturnParameters::HalGetAnchorPeriod(
                 to_hal_anchor_period(&bytes[3..])?,
             )),
+            crate::opcode::HAL_READ_RADIO_REG => Ok(ReturnParameters::HalReadRadioRegister(
+                to_hal_radio_register(&bytes[3..])?,
+            )),
+            crate::opcode::HAL_WRITE_RADIO_REG => Ok(ReturnParameters::HalWriteRadioRegister(
+                to_status(&bytes[3..])?,
+            )),
+            crate::opcode::LE_ADD_DEVICE_TO_RESOLVING_LIST => Ok(
+                ReturnParameters::LeAddDeviceToResolvingList(to_status(&bytes[3..])?),
+            ),
+            crate::opcode::LE_REMOVE_DEVICE_FROM_RESOLVING_LIST => Ok(
+                ReturnParameters::LeRemoveDeviceFromResolvingList(to_status(&bytes[3..])?),
+            ),
+            crate::opcode::LE_CLEAR_RESOLVING_LIST => Ok(ReturnParameters::LeClearResolvingList(
+                to_status(&bytes[3..])?,
+            )),
+            crate::opcode::LE_READ_MAXIMUM_DATA_LENGTH => Ok(
+                ReturnParameters::LeReadMaximumDataLength(to_le_maximum_data_length(&bytes[3..])?),
+            ),
             crate::opcode::GAP_SET_NONDISCOVERABLE => Ok(ReturnParameters::GapSetNonDiscoverable(
                 to_status(&bytes[3..])?,
             )),
@@ -384,6 +466,13 @@ impl hci::event::VendorReturnParameters for ReturnParameters {
             crate::opcode::GAP_IS_DEVICE_BONDED => {
                 Ok(ReturnParameters::GapIsDeviceBonded(to_status(&bytes[3..])?))
             }
+            crate::opcode::GAP_NUMERIC_COMPARISON_VALUE_CONFIRM => Ok(
+                ReturnParameters::GapNumericComparisonValueConfirm(to_status(&bytes[3..])?),
+            ),
+            #[cfg(feature = "bluenrg-lp")]
+            crate::opcode::GAP_ADD_DEVICES_TO_RESOLVING_LIST => Ok(
+                ReturnParameters::GapAddDevicesToResolvingList(to_status(&bytes[3..])?),
+            ),
             crate::opcode::GATT_INIT => Ok(ReturnParameters::GattInit(to_status(&bytes[3..])?)),
             crate::opcode::GATT_ADD_SERVICE => Ok(ReturnParameters::GattAddService(
                 to_gatt_service(&bytes[3..])?,
@@ -468,6 +557,12 @@ impl hci::event::VendorReturnParameters for ReturnParameters {
                     ))
                 }
             }
+            crate::opcode::GATT_SEND_MULTIPLE_NOTIFICATIONS => Ok(
+                ReturnParameters::GattSendMultipleNotifications(to_status(&bytes[3..])?),
+            ),
+            crate::opcode::GATT_STORE_DB => {
+                Ok(ReturnParameters::GattStoreDb(to_status(&bytes[3..])?))
+            }
             crate::opcode::L2CAP_CONN_PARAM_UPDATE_RESP => Ok(
                 ReturnParameters::L2CapConnectionParameterUpdateResponse(to_status(&bytes[3..])?),
             ),
@@ -557,6 +652,103 @@ pub enum HalConfigParameter {
     Byte(u8),
 }
 
+impl HalConfigData {
+    /// Decodes the returned value as the parameter that was requested.
+    ///
+    /// Since the [value](HalConfigData::value) is distinguished by length only, this is the only
+    /// way to tell the encryption keys or the single-byte values apart.
+    ///
+    /// # Errors
+    ///
+    /// - [BadConfigParameterLength](super::BlueNRGError::BadConfigParameterLength) if the returned
+    ///   value does not have the length of the requested parameter. Includes the returned length.
+    /// - [BadBooleanValue](super::BlueNRGError::BadBooleanValue) if the
+    ///   [LinkLayerOnly](crate::hal::ConfigParameter::LinkLayerOnly) flag is neither 0 nor 1.
+    /// - [BadConfigRole](super::BlueNRGError::BadConfigRole) if the
+    ///   [Role](crate::hal::ConfigParameter::Role) is not a known role.
+    pub fn decode(
+        &self,
+        param: crate::hal::ConfigParameter,
+    ) -> Result<ConfigValue, super::BlueNRGError> {
+        use crate::hal::ConfigParameter;
+
+        match (param, &self.value) {
+            (ConfigParameter::PublicAddress, &HalConfigParameter::PublicAddress(addr)) => {
+                Ok(ConfigValue::PublicAddress(addr))
+            }
+            (ConfigParameter::Diversifier, &HalConfigParameter::Diversifier(div)) => {
+                Ok(ConfigValue::Diversifier(div))
+            }
+            (ConfigParameter::EncryptionRoot, &HalConfigParameter::EncryptionKey(ref key)) => {
+                Ok(ConfigValue::EncryptionRoot(key.clone()))
+            }
+            (ConfigParameter::IdentityRoot, &HalConfigParameter::EncryptionKey(ref key)) => {
+                Ok(ConfigValue::IdentityRoot(key.clone()))
+            }
+            (ConfigParameter::LinkLayerOnly, &HalConfigParameter::Byte(value)) => {
+                Ok(ConfigValue::LinkLayerOnly(to_boolean(value)?))
+            }
+            (ConfigParameter::Role, &HalConfigParameter::Byte(value)) => {
+                Ok(ConfigValue::Role(value.try_into()?))
+            }
+            (_, value) => Err(super::BlueNRGError::BadConfigParameterLength(value.len())),
+        }
+    }
+}
+
+impl HalConfigParameter {
+    fn len(&self) -> usize {
+        match *self {
+            HalConfigParameter::PublicAddress(_) => 6,
+            HalConfigParameter::Diversifier(_) => 2,
+            HalConfigParameter::EncryptionKey(_) => 16,
+            HalConfigParameter::Byte(_) => 1,
+        }
+    }
+}
+
+/// A value fetched by [HAL Read Config Data](crate::hal::Commands::read_config_data), decoded for
+/// the parameter that was requested. See [`HalConfigData::decode`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfigValue {
+    /// Bluetooth public address. Corresponds to
+    /// [PublicAddress](crate::hal::ConfigParameter::PublicAddress).
+    PublicAddress(hci::BdAddr),
+
+    /// Diversifier used to derive CSRK (connection signature resolving key). Corresponds to
+    /// [Diversifier](crate::hal::ConfigParameter::Diversifier).
+    Diversifier(u16),
+
+    /// Encryption root key. Corresponds to
+    /// [EncryptionRoot](crate::hal::ConfigParameter::EncryptionRoot).
+    EncryptionRoot(hci::host::EncryptionKey),
+
+    /// Identity root key. Corresponds to
+    /// [IdentityRoot](crate::hal::ConfigParameter::IdentityRoot).
+    IdentityRoot(hci::host::EncryptionKey),
+
+    /// True if the controller is in Link Layer only mode. Corresponds to
+    /// [LinkLayerOnly](crate::hal::ConfigParameter::LinkLayerOnly).
+    LinkLayerOnly(bool),
+
+    /// Roles and mode configuration. Corresponds to [Role](crate::hal::ConfigParameter::Role).
+    Role(crate::hal::Role),
+}
+
+impl TryFrom<u8> for crate::hal::Role {
+    type Error = super::BlueNRGError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(crate::hal::Role::Peripheral6Kb),
+            2 => Ok(crate::hal::Role::Peripheral12Kb),
+            3 => Ok(crate::hal::Role::Primary12Kb),
+            4 => Ok(crate::hal::Role::SimultaneousAdvertisingScanning),
+            _ => Err(super::BlueNRGError::BadConfigRole(value)),
+        }
+    }
+}
+
 fn to_hal_config_data(
     bytes: &[u8],
 ) -> Result<HalConfigData, hci::event::Error<super::BlueNRGError>> {
@@ -728,6 +920,62 @@ fn to_hal_anchor_period(
     })
 }
 
+/// Parameters returned by the [HAL Read Radio Register](crate::hal::Commands::read_radio_register)
+/// command.
+#[derive(Copy, Clone, Debug)]
+pub struct HalRadioRegister {
+    /// Did the command fail, and if so, how?
+    pub status: hci::Status<crate::event::Status>,
+
+    /// Value of the register.
+    pub value: u8,
+}
+
+fn to_hal_radio_register(
+    bytes: &[u8],
+) -> Result<HalRadioRegister, hci::event::Error<super::BlueNRGError>> {
+    require_len!(bytes, 2);
+
+    Ok(HalRadioRegister {
+        status: to_status(bytes)?,
+        value: bytes[1],
+    })
+}
+
+/// Parameters returned by the [LE Read Maximum Data
+/// Length](crate::hal::Commands::le_read_maximum_data_length) command.
+#[derive(Copy, Clone, Debug)]
+pub struct LeMaximumDataLength {
+    /// Did the command fail, and if so, how?
+    pub status: hci::Status<crate::event::Status>,
+
+    /// Maximum payload size (in octets) the controller supports for sent packets.
+    pub max_tx_octets: u16,
+
+    /// Maximum time the controller supports for sending a packet.
+    pub max_tx_time: Duration,
+
+    /// Maximum payload size (in octets) the controller supports for received packets.
+    pub max_rx_octets: u16,
+
+    /// Maximum time the controller supports for receiving a packet.
+    pub max_rx_time: Duration,
+}
+
+fn to_le_maximum_data_length(
+    bytes: &[u8],
+) -> Result<LeMaximumDataLength, hci::event::Error<super::BlueNRGError>> {
+    require_len!(bytes, 9);
+
+    Ok(LeMaximumDataLength {
+        status: to_status(bytes)?,
+        max_tx_octets: LittleEndian::read_u16(&bytes[1..]),
+        max_tx_time: Duration::from_micros(u64::from(LittleEndian::read_u16(&bytes[3..]))),
+        max_rx_octets: LittleEndian::read_u16(&bytes[5..]),
+        max_rx_time: Duration::from_micros(u64::from(LittleEndian::read_u16(&bytes[7..]))),
+    })
+}
+
 /// Parameters returned by the [GAP Init](crate::gap::Commands::init) command.
 #[derive(Copy, Clone, Debug)]
 pub struct GapInit {
@@ -874,7 +1122,7 @@ pub struct GapBondedDevices {
 }
 
 // Max packet size (255 bytes) less non-address data (4 bytes) divided by peer address size (7):
-const MAX_ADDRESSES: usize = 35;
+pub(crate) const MAX_ADDRESSES: usize = 35;
 
 impl GapBondedDevices {
     /// Return an iterator over the bonded device addresses.
@@ -0,0 +1,64 @@
+//! Link quality and connection parameters.
+//!
+//! The received signal strength and the transmit power level of a connection are read with the
+//! standard [`read_rssi`](hci::host::Hci::read_rssi) and
+//! [`read_transmit_power_level`](hci::host::Hci::read_transmit_power_level) commands, and their
+//! return parameters are decoded as the standard
+//! [`ReadRssi`](hci::event::command::ReturnParameters::ReadRssi) and
+//! [`ReadTransmitPowerLevel`](hci::event::command::ReturnParameters::ReadTransmitPowerLevel)
+//! return parameters. The BlueNRG accepts both.
+//!
+//! The BlueNRG has no command to read the parameters of a connection. The controller reports them
+//! in the standard LE Connection Complete and LE Connection Update Complete events instead, and
+//! [`CurrentConnectionParameters`] takes them from there.
+
+extern crate bluetooth_hci as hci;
+
+use super::BlueNRGEvent;
+use core::time::Duration;
+
+/// Parameters in use on a connection, as last reported by the controller.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CurrentConnectionParameters {
+    /// Connection the parameters apply to.
+    pub conn_handle: hci::ConnectionHandle,
+
+    /// Time between connection events.
+    pub interval: Duration,
+
+    /// Number of connection events the peripheral may skip.
+    pub conn_latency: u16,
+
+    /// Time without a packet from the peer after which the connection is considered lost.
+    pub supervision_timeout: Duration,
+}
+
+impl CurrentConnectionParameters {
+    /// Returns the parameters reported by the event, if it is a successful [LE Connection
+    /// Complete](hci::event::Event::LeConnectionComplete) or [LE Connection Update
+    /// Complete](hci::event::Event::LeConnectionUpdateComplete) event. Returns `None` for any other
+    /// event.
+    pub fn from_event(
+        event: &hci::event::Event<BlueNRGEvent>,
+    ) -> Option<CurrentConnectionParameters> {
+        let (status, conn_handle, conn_interval) = match *event {
+            hci::event::Event::LeConnectionComplete(ref event) => {
+                (event.status, event.conn_handle, &event.conn_interval)
+            }
+            hci::event::Event::LeConnectionUpdateComplete(ref event) => {
+                (event.status, event.conn_handle, &event.conn_interval)
+            }
+            _ => return None,
+        };
+        if status != hci::Status::Success {
+            return None;
+        }
+
+        Some(CurrentConnectionParameters {
+            conn_handle,
+            interval: conn_interval.interval(),
+            conn_latency: conn_interval.conn_latency(),
+            supervision_timeout: conn_interval.supervision_timeout(),
+        })
+    }
+}
@@ -6,24 +6,35 @@
 extern crate bluetooth_hci as hci;
 
 pub mod command;
+pub mod link;
+#[cfg(feature = "std")]
+pub mod owned;
+#[cfg(feature = "periodic-advertising")]
+pub mod periodic;
+pub mod phy;
+pub mod stream;
+#[cfg(feature = "ufmt")]
+mod ufmt_impls;
 
 use byteorder::{ByteOrder, LittleEndian};
 use core::cmp::PartialEq;
 use core::convert::{TryFrom, TryInto};
-use core::fmt::{Debug, Formatter, Result as FmtResult};
-use core::mem;
+use core::fmt::{Debug, Display, Formatter, Result as FmtResult};
 use core::time::Duration;
 
 pub use hci::types::{ConnectionInterval, ConnectionIntervalError};
 pub use hci::{BdAddr, BdAddrType, ConnectionHandle};
 
 /// Vendor-specific events for the BlueNRG-MS controllers.
+///
+/// Events are `Clone`, but not `Copy`: with the `alloc` feature, the events that carry large
+/// buffers box them.
 #[allow(clippy::large_enum_variant)]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub enum BlueNRGEvent {
     /// When the BlueNRG-MS firmware is started normally, it gives this event to the user to
     /// indicate the system has started.
-    HalInitialized(ResetReason),
+    HalInitialized(HalInitialized),
 
     /// If the host fails to read events from the controller quickly enough, the controller will
     /// generate this event. This event is never lost; it is inserted as soon as space is available
@@ -38,7 +49,8 @@ pub enum BlueNRGEvent {
     CrashReport(FaultData),
 
     /// This event is generated by the controller when the limited discoverable mode ends due to
-    /// timeout (180 seconds).
+    /// timeout (180 seconds). The event has no parameters, so it does not report any remaining
+    /// time.
     GapLimitedDiscoverableTimeout,
 
     /// This event is generated when the pairing process has completed successfully or a pairing
@@ -50,13 +62,22 @@ pub enum BlueNRGEvent {
     /// This event is generated by the Security manager to the application when a pass key is
     /// required for pairing.  When this event is received, the application has to respond with the
     /// `gap_pass_key_response` command.
-    GapPassKeyRequest(ConnectionHandle),
+    ///
+    /// This event is also used for the passkey display flow: if the device has a display (and no
+    /// keyboard), the application picks the pass key, shows it to the user, and sends it to the
+    /// controller with `gap_pass_key_response`. There is no separate passkey display event.
+    GapPassKeyRequest(GapPassKeyRequest),
 
     /// This event is generated by the Security manager to the application when the application has
     /// set that authorization is required for reading/writing of attributes. This event will be
     /// generated as soon as the pairing is complete. When this event is received,
     /// `gap_authorization_response` command should be used by the application.
-    GapAuthorizationRequest(ConnectionHandle),
+    ///
+    /// The event only identifies the connection; it does not say which operation or attribute
+    /// needs authorization. Per-attribute read and write authorization is reported through the
+    /// [`AttReadPermitRequest`](BlueNRGEvent::AttReadPermitRequest) and
+    /// [`AttWritePermitRequest`](BlueNRGEvent::AttWritePermitRequest) events instead.
+    GapAuthorizationRequest(GapAuthorizationRequest),
 
     /// This event is generated when the peripheral security request is successfully sent to the
     /// central device.
@@ -70,21 +91,26 @@ pub enum BlueNRGEvent {
     /// is called to reestablish a bond with a peripheral but the peripheral has lost the bond. In
     /// order to create a new bond the central device has to launch `gap_send_pairing_request` with
     /// `force_rebond` set to `true`.
+    ///
+    /// The event has no parameters, so it does not say which connection lost the bond. The
+    /// [`RebondPolicy`](crate::rebond::RebondPolicy) tracks the connection it applies to.
     GapBondLost,
 
     /// The event is given by the GAP layer to the upper layers when a device is discovered during
     /// scanning as a consequence of one of the GAP procedures started by the upper layers.
+    #[cfg(feature = "gap-central-events")]
     GapDeviceFound(GapDeviceFound),
 
     /// This event is sent by the GAP to the upper layers when a procedure previously started has
     /// been terminated by the upper layer or has completed for any other reason
+    #[cfg(feature = "gap-central-events")]
     GapProcedureComplete(GapProcedureComplete),
 
     /// This event is sent only by a privacy enabled peripheral. The event is sent to the upper
     /// layers when the peripheral is unsuccessful in resolving the resolvable address of the peer
     /// device after connecting to it.
     #[cfg(feature = "ms")]
-    GapAddressNotResolved(ConnectionHandle),
+    GapAddressNotResolved(GapAddressNotResolved),
 
     /// This event is generated when the reconnection address is generated during the general
     /// connection establishment procedure. The same address is set to the peer device also as a
@@ -92,22 +118,25 @@ pub enum BlueNRGEvent {
     /// reconnection address the next time while connecting to the bonded peripheral, the
     /// application needs to set its own address as well as the peer address to which it wants to
     /// connect to this reconnection address.
-    #[cfg(not(feature = "ms"))]
+    #[cfg(all(not(feature = "ms"), feature = "gap-central-events"))]
     GapReconnectionAddress(BdAddr),
 
     /// This event is generated when the central device responds to the L2CAP connection update
     /// request packet. For more info see
     /// [ConnectionParameterUpdateResponse](crate::l2cap::ConnectionParameterUpdateResponse)
     /// and CommandReject in Bluetooth Core v4.0 spec.
+    #[cfg(feature = "l2cap-events")]
     L2CapConnectionUpdateResponse(L2CapConnectionUpdateResponse),
 
     /// This event is generated when the central device does not respond to the connection update
     /// request within 30 seconds.
-    L2CapProcedureTimeout(ConnectionHandle),
+    #[cfg(feature = "l2cap-events")]
+    L2CapProcedureTimeout(L2CapProcedureTimeout),
 
     /// The event is given by the L2CAP layer when a connection update request is received from the
     /// peripheral. The application has to respond by calling
     /// [`l2cap_connection_parameter_update_response`](crate::l2cap::Commands::connection_parameter_update_response).
+    #[cfg(feature = "l2cap-events")]
     L2CapConnectionUpdateRequest(L2CapConnectionUpdateRequest),
 
     /// This event is generated to the application by the ATT server when a client modifies any
@@ -121,60 +150,75 @@ pub enum BlueNRGEvent {
 
     /// This event is generated when a ATT client procedure completes either with error or
     /// successfully.
-    GattProcedureTimeout(ConnectionHandle),
+    #[cfg(feature = "att-client-events")]
+    GattProcedureTimeout(GattProcedureTimeout),
 
     /// This event is generated in response to an Exchange MTU request.
+    #[cfg(feature = "att-client-events")]
     AttExchangeMtuResponse(AttExchangeMtuResponse),
 
     /// This event is generated in response to a Find Information Request. See Find Information
     /// Response in Bluetooth Core v4.0 spec.
+    #[cfg(feature = "att-client-events")]
     AttFindInformationResponse(AttFindInformationResponse),
 
     /// This event is generated in response to a Find By Type Value Request.
+    #[cfg(feature = "att-client-events")]
     AttFindByTypeValueResponse(AttFindByTypeValueResponse),
 
     /// This event is generated in response to a Read by Type Request.
+    #[cfg(feature = "att-client-events")]
     AttReadByTypeResponse(AttReadByTypeResponse),
 
     /// This event is generated in response to a Read Request.
+    #[cfg(feature = "att-client-events")]
     AttReadResponse(AttReadResponse),
 
     /// This event is generated in response to a Read Blob Request. The value in the response is the
     /// partial value starting from the offset in the request. See the Bluetooth Core v4.1 spec, Vol
     /// 3, section 3.4.4.5 and 3.4.4.6.
+    #[cfg(feature = "att-client-events")]
     AttReadBlobResponse(AttReadResponse),
 
     /// This event is generated in response to a Read Multiple Request. The value in the response is
     /// the set of values requested from the request. See the Bluetooth Core v4.1 spec, Vol 3,
     /// section 3.4.4.7 and 3.4.4.8.
+    #[cfg(feature = "att-client-events")]
     AttReadMultipleResponse(AttReadResponse),
 
     /// This event is generated in response to a Read By Group Type Request. See the Bluetooth Core
     /// v4.1 spec, Vol 3, section 3.4.4.9 and 3.4.4.10.
+    #[cfg(feature = "att-client-events")]
     AttReadByGroupTypeResponse(AttReadByGroupTypeResponse),
 
     /// This event is generated in response to a Prepare Write Request. See the Bluetooth Core v4.1
     /// spec, Vol 3, Part F, section 3.4.6.1 and 3.4.6.2
+    #[cfg(feature = "att-client-events")]
     AttPrepareWriteResponse(AttPrepareWriteResponse),
 
     /// This event is generated in response to an Execute Write Request. See the Bluetooth Core v4.1
     /// spec, Vol 3, Part F, section 3.4.6.3 and 3.4.6.4
-    AttExecuteWriteResponse(ConnectionHandle),
+    #[cfg(feature = "att-client-events")]
+    AttExecuteWriteResponse(AttExecuteWriteResponse),
 
     /// This event is generated when an indication is received from the server.
+    #[cfg(feature = "att-client-events")]
     GattIndication(AttributeValue),
 
     /// This event is generated when an notification is received from the server.
+    #[cfg(feature = "att-client-events")]
     GattNotification(AttributeValue),
 
     /// This event is generated when a GATT client procedure completes either with error or
     /// successfully.
+    #[cfg(feature = "att-client-events")]
     GattProcedureComplete(GattProcedureComplete),
 
     /// This event is generated when an Error Response is received from the server. The error
     /// response can be given by the server at the end of one of the GATT discovery procedures. This
     /// does not mean that the procedure ended with an error, but this error event is part of the
     /// procedure itself.
+    #[cfg(feature = "att-client-events")]
     AttErrorResponse(AttErrorResponse),
 
     /// This event can be generated during a "Discover Characteristics by UUID" procedure or a "Read
@@ -185,6 +229,7 @@ pub enum BlueNRGEvent {
     ///
     /// See the Bluetooth Core v4.1 spec, Vol 3, Part G, section 4.6.2 (discover characteristics by
     /// UUID), and section 4.8.2 (read using characteristic using UUID).
+    #[cfg(feature = "att-client-events")]
     GattDiscoverOrReadCharacteristicByUuidResponse(AttributeValue),
 
     /// This event is given to the application when a write request, write command or signed write
@@ -224,10 +269,18 @@ pub enum BlueNRGEvent {
     /// 2).  The event will be given only if a previous ACI command returned with
     /// [InsufficientResources](AttError::InsufficientResources).  On receiving this event, the
     /// application can continue to send notifications by calling `gatt_update_char_value`.
+    ///
+    /// The event can be masked with [`TX_POOL_AVAILABLE`](crate::gatt::Event::TX_POOL_AVAILABLE).
+    /// If the controller drops it, [Events Lost](BlueNRGEvent::EventsLost) reports
+    /// [`GATT_TX_POOL_AVAILABLE`](EventFlags::GATT_TX_POOL_AVAILABLE).
     #[cfg(feature = "ms")]
     GattTxPoolAvailable(GattTxPoolAvailable),
 
     /// This event is raised on the server when the client confirms the reception of an indication.
+    ///
+    /// ATT allows only one outstanding indication per connection, so the application must wait for
+    /// this event before it sends the next indication on the connection.
+    /// [`IndicationSender`](crate::throttle::IndicationSender) keeps track of that.
     #[cfg(feature = "ms")]
     GattServerConfirmation(ConnectionHandle),
 
@@ -242,6 +295,204 @@ pub enum BlueNRGEvent {
     /// application.
     #[cfg(feature = "ms")]
     AttPrepareWritePermitRequest(AttPrepareWritePermitRequest),
+
+    /// This event is generated when a client receives an ATT Multiple Handle Value Notification,
+    /// which carries the values of several attributes at once. Only firmware that implements the
+    /// Bluetooth 5.2 ATT Multiple Handle Value Notification generates this event.
+    #[cfg(feature = "att-client-events")]
+    GattMultipleNotification(GattMultipleNotification),
+
+    /// BlueNRG-LP only: this event is generated when an indication is received from the server.
+    /// Unlike [GattIndication](BlueNRGEvent::GattIndication), the value length is 16 bits, and long
+    /// values may be split over several events.
+    #[cfg(all(feature = "bluenrg-lp", feature = "att-client-events"))]
+    GattIndicationExt(ExtendedAttributeValue),
+
+    /// This event is generated during LE Secure Connections pairing with numeric comparison, when
+    /// both devices have a display. The application shows the value to the user and responds with
+    /// the `gap_numeric_comparison_value_confirm` command, according to whether the user confirms
+    /// that it matches the value shown on the peer. Only firmware that implements LE Secure
+    /// Connections generates this event.
+    GapNumericComparisonValue(GapNumericComparisonValue),
+}
+
+impl BlueNRGEvent {
+    /// Returns the vendor event code that the controller used for this event.
+    pub fn code(&self) -> u16 {
+        match *self {
+            BlueNRGEvent::HalInitialized(_) => 0x0001,
+            #[cfg(feature = "ms")]
+            BlueNRGEvent::EventsLost(_) => 0x0002,
+            #[cfg(feature = "ms")]
+            BlueNRGEvent::CrashReport(_) => 0x0003,
+            BlueNRGEvent::GapLimitedDiscoverableTimeout => 0x0400,
+            BlueNRGEvent::GapPairingComplete(_) => 0x0401,
+            BlueNRGEvent::GapPassKeyRequest(_) => 0x0402,
+            BlueNRGEvent::GapAuthorizationRequest(_) => 0x0403,
+            BlueNRGEvent::GapPeripheralSecurityInitiated => 0x0404,
+            BlueNRGEvent::GapBondLost => 0x0405,
+            #[cfg(feature = "gap-central-events")]
+            BlueNRGEvent::GapDeviceFound(_) => 0x0406,
+            #[cfg(feature = "gap-central-events")]
+            BlueNRGEvent::GapProcedureComplete(_) => 0x0407,
+            #[cfg(feature = "ms")]
+            BlueNRGEvent::GapAddressNotResolved(_) => 0x0408,
+            #[cfg(all(not(feature = "ms"), feature = "gap-central-events"))]
+            BlueNRGEvent::GapReconnectionAddress(_) => 0x0408,
+            BlueNRGEvent::GapNumericComparisonValue(_) => 0x0409,
+            #[cfg(feature = "l2cap-events")]
+            BlueNRGEvent::L2CapConnectionUpdateResponse(_) => 0x0800,
+            #[cfg(feature = "l2cap-events")]
+            BlueNRGEvent::L2CapProcedureTimeout(_) => 0x0801,
+            #[cfg(feature = "l2cap-events")]
+            BlueNRGEvent::L2CapConnectionUpdateRequest(_) => 0x0802,
+            BlueNRGEvent::GattAttributeModified(_) => 0x0C01,
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::GattProcedureTimeout(_) => 0x0C02,
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttExchangeMtuResponse(_) => 0x0C03,
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttFindInformationResponse(_) => 0x0C04,
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttFindByTypeValueResponse(_) => 0x0C05,
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttReadByTypeResponse(_) => 0x0C06,
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttReadResponse(_) => 0x0C07,
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttReadBlobResponse(_) => 0x0C08,
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttReadMultipleResponse(_) => 0x0C09,
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttReadByGroupTypeResponse(_) => 0x0C0A,
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttPrepareWriteResponse(_) => 0x0C0C,
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttExecuteWriteResponse(_) => 0x0C0D,
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::GattIndication(_) => 0x0C0E,
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::GattNotification(_) => 0x0C0F,
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::GattProcedureComplete(_) => 0x0C10,
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttErrorResponse(_) => 0x0C11,
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::GattDiscoverOrReadCharacteristicByUuidResponse(_) => 0x0C12,
+            BlueNRGEvent::AttWritePermitRequest(_) => 0x0C13,
+            BlueNRGEvent::AttReadPermitRequest(_) => 0x0C14,
+            BlueNRGEvent::AttReadMultiplePermitRequest(_) => 0x0C15,
+            #[cfg(feature = "ms")]
+            BlueNRGEvent::GattTxPoolAvailable(_) => 0x0C16,
+            #[cfg(feature = "ms")]
+            BlueNRGEvent::GattServerConfirmation(_) => 0x0C17,
+            #[cfg(feature = "ms")]
+            BlueNRGEvent::AttPrepareWritePermitRequest(_) => 0x0C18,
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::GattMultipleNotification(_) => 0x0C1A,
+            #[cfg(all(feature = "bluenrg-lp", feature = "att-client-events"))]
+            BlueNRGEvent::GattIndicationExt(_) => 0x0C1E,
+        }
+    }
+
+    /// Returns the reset reason if this is a [HAL Initialized](BlueNRGEvent::HalInitialized)
+    /// event, so callers that only care about the reason do not need to destructure the event.
+    pub fn reset_reason(&self) -> Option<ResetReason> {
+        match *self {
+            BlueNRGEvent::HalInitialized(ref event) => Some(event.reason),
+            _ => None,
+        }
+    }
+
+    /// Returns the category of the event, e.g. to tell whether the application must reply to it.
+    pub fn category(&self) -> EventCategory {
+        match *self {
+            BlueNRGEvent::HalInitialized(_) => EventCategory::Status,
+            #[cfg(feature = "ms")]
+            BlueNRGEvent::EventsLost(_) => EventCategory::Status,
+            #[cfg(feature = "ms")]
+            BlueNRGEvent::CrashReport(_) => EventCategory::Status,
+            BlueNRGEvent::GapLimitedDiscoverableTimeout => EventCategory::ProcedureComplete,
+            BlueNRGEvent::GapPairingComplete(_) => EventCategory::ProcedureComplete,
+            BlueNRGEvent::GapPassKeyRequest(_) => EventCategory::Request,
+            BlueNRGEvent::GapAuthorizationRequest(_) => EventCategory::Request,
+            BlueNRGEvent::GapPeripheralSecurityInitiated => EventCategory::Status,
+            BlueNRGEvent::GapBondLost => EventCategory::Request,
+            #[cfg(feature = "gap-central-events")]
+            BlueNRGEvent::GapDeviceFound(_) => EventCategory::Notification,
+            #[cfg(feature = "gap-central-events")]
+            BlueNRGEvent::GapProcedureComplete(_) => EventCategory::ProcedureComplete,
+            #[cfg(feature = "ms")]
+            BlueNRGEvent::GapAddressNotResolved(_) => EventCategory::Status,
+            #[cfg(all(not(feature = "ms"), feature = "gap-central-events"))]
+            BlueNRGEvent::GapReconnectionAddress(_) => EventCategory::Notification,
+            BlueNRGEvent::GapNumericComparisonValue(_) => EventCategory::Request,
+            #[cfg(feature = "l2cap-events")]
+            BlueNRGEvent::L2CapConnectionUpdateResponse(_) => EventCategory::Response,
+            #[cfg(feature = "l2cap-events")]
+            BlueNRGEvent::L2CapProcedureTimeout(_) => EventCategory::ProcedureComplete,
+            #[cfg(feature = "l2cap-events")]
+            BlueNRGEvent::L2CapConnectionUpdateRequest(_) => EventCategory::Request,
+            BlueNRGEvent::GattAttributeModified(_) => EventCategory::Notification,
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::GattProcedureTimeout(_) => EventCategory::ProcedureComplete,
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttExchangeMtuResponse(_)
+            | BlueNRGEvent::AttFindInformationResponse(_)
+            | BlueNRGEvent::AttFindByTypeValueResponse(_)
+            | BlueNRGEvent::AttReadByTypeResponse(_)
+            | BlueNRGEvent::AttReadResponse(_)
+            | BlueNRGEvent::AttReadBlobResponse(_)
+            | BlueNRGEvent::AttReadMultipleResponse(_)
+            | BlueNRGEvent::AttReadByGroupTypeResponse(_)
+            | BlueNRGEvent::AttPrepareWriteResponse(_)
+            | BlueNRGEvent::AttExecuteWriteResponse(_)
+            | BlueNRGEvent::AttErrorResponse(_)
+            | BlueNRGEvent::GattDiscoverOrReadCharacteristicByUuidResponse(_) => {
+                EventCategory::Response
+            }
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::GattIndication(_)
+            | BlueNRGEvent::GattNotification(_)
+            | BlueNRGEvent::GattMultipleNotification(_) => EventCategory::Notification,
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::GattProcedureComplete(_) => EventCategory::ProcedureComplete,
+            BlueNRGEvent::AttWritePermitRequest(_) => EventCategory::Request,
+            BlueNRGEvent::AttReadPermitRequest(_) => EventCategory::Request,
+            BlueNRGEvent::AttReadMultiplePermitRequest(_) => EventCategory::Request,
+            #[cfg(feature = "ms")]
+            BlueNRGEvent::GattTxPoolAvailable(_) => EventCategory::Status,
+            #[cfg(feature = "ms")]
+            BlueNRGEvent::GattServerConfirmation(_) => EventCategory::Response,
+            #[cfg(feature = "ms")]
+            BlueNRGEvent::AttPrepareWritePermitRequest(_) => EventCategory::Request,
+            #[cfg(all(feature = "bluenrg-lp", feature = "att-client-events"))]
+            BlueNRGEvent::GattIndicationExt(_) => EventCategory::Notification,
+        }
+    }
+}
+
+/// Categories of [vendor events](BlueNRGEvent), for routing them in the application.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum EventCategory {
+    /// The peer's response to a request sent by this device, such as an ATT read response or the
+    /// confirmation of an indication.
+    Response,
+
+    /// A request that the application must reply to with a command, such as a pass key request or
+    /// a permit request. The controller waits for the reply, and the procedure times out without
+    /// it.
+    Request,
+
+    /// Data that the peer sent or changed without a request from this device, such as a
+    /// notification, an indication, or a write to a local attribute.
+    Notification,
+
+    /// The end of a procedure, either because it completed or because it timed out.
+    ProcedureComplete,
+
+    /// A change in the state of the controller, such as the end of a reset or available buffers.
+    Status,
 }
 
 /// Enumeration of vendor-specific status codes.
@@ -395,6 +646,10 @@ pub enum BlueNRGError {
     /// recognized. Includes the unrecognized byte.
     BadGapPairingStatus(u8),
 
+    /// For the [GAP Pairing Complete](BlueNRGEvent::GapPairingComplete) event: The failure reason
+    /// was not recognized. Includes the unrecognized byte.
+    BadPairingFailureReason(u8),
+
     /// For the [GAP Device Found](BlueNRGEvent::GapDeviceFound) event: the type of event was not
     /// recognized. Includes the unrecognized byte.
     BadGapDeviceFoundEvent(u8),
@@ -404,11 +659,13 @@ pub enum BlueNRGError {
     BadGapBdAddrType(u8),
 
     /// For the [GAP Procedure Complete](BlueNRGEvent::GapProcedureComplete) event: The procedure
-    /// code was not recognized. Includes the unrecognized byte.
+    /// code was not recognized: it is 0, or only the observation procedure, which has no
+    /// [`GapProcedure`] of its own. Includes the unrecognized byte.
     BadGapProcedure(u8),
 
     /// For the [GAP Procedure Complete](BlueNRGEvent::GapProcedureComplete) event: The procedure
-    /// status was not recognized. Includes the unrecognized byte.
+    /// status was not recognized by [`GapProcedureStatus::strict`]. Includes the unrecognized
+    /// byte.
     BadGapProcedureStatus(u8),
 
     /// For any L2CAP event: The event data length did not match the expected length. The first
@@ -488,12 +745,22 @@ pub enum BlueNRGError {
     /// ends with a partial attribute handle-value pair.
     AttReadByTypeResponsePartial,
 
+    /// For the [ATT Read by Type Response](BlueNRGEvent::AttReadByTypeResponse) event: The
+    /// handle-value pair length is too short to hold the 2-byte attribute handle. Includes the
+    /// invalid length.
+    BadAttReadByTypeResponsePairLength(u8),
+
     /// For the [ATT Read by Group Type Response](BlueNRGEvent::AttReadByGroupTypeResponse) event:
     /// The packet ends with a partial attribute data group.
     AttReadByGroupTypeResponsePartial,
 
+    /// For the [ATT Read by Group Type Response](BlueNRGEvent::AttReadByGroupTypeResponse) event:
+    /// The attribute data length is too short to hold the 2-byte attribute handle and the 2-byte
+    /// end group handle. Includes the invalid length.
+    BadAttReadByGroupTypeResponseGroupLength(u8),
+
     /// For the [GATT Procedure Complete](BlueNRGEvent::GattProcedureComplete) event: The status
-    /// code was not recognized. Includes the unrecognized byte.
+    /// code was not recognized by [`GattProcedureStatus::strict`]. Includes the unrecognized byte.
     BadGattProcedureStatus(u8),
 
     /// For the [ATT Error Response](BlueNRGEvent::AttErrorResponse) event: The request opcode was
@@ -508,12 +775,22 @@ pub enum BlueNRGError {
     /// event: The packet ends with a partial attribute handle.
     AttReadMultiplePermitRequestPartial,
 
+    /// For the [GATT Multiple Notification](BlueNRGEvent::GattMultipleNotification) event: The
+    /// packet ends with a partial handle-length-value tuple.
+    GattMultipleNotificationPartial,
+
     /// For the [HAL Read Config Data](crate::hal::Commands::read_config_data) command complete
     /// [event](command::ReturnParameters::HalReadConfigData): The returned value has a length that
     /// does not correspond to a requested parameter. Known lengths are 1, 2, 6, or 16. Includes the
     /// number of bytes returned.
     BadConfigParameterLength(usize),
 
+    /// For the [HAL Read Config Data](crate::hal::Commands::read_config_data) command complete
+    /// [event](command::ReturnParameters::HalReadConfigData), decoded as the
+    /// [Role](crate::hal::ConfigParameter::Role): the value is not a known role. Includes the
+    /// unknown byte.
+    BadConfigRole(u8),
+
     /// For the [HAL Get Link Status](crate::hal::Commands::get_link_status) command complete
     /// [event](command::ReturnParameters::HalGetLinkStatus): One of the bytes representing a link
     /// state does not represent a known link state. Returns the unknown value.
@@ -541,8 +818,193 @@ pub enum BlueNRGError {
     /// [event](command::ReturnParameters::GapGetBondedDevices): one of the address type bytes was
     /// invalid. Includes the invalid byte.
     BadBdAddrType(u8),
+
+    /// For the [LE PHY Update Complete](phy::LePhyUpdateComplete) and [periodic advertising sync
+    /// established](periodic::PeriodicAdvertisingEvent::SyncEstablished) events: the PHY was not
+    /// recognized. Includes the unrecognized byte.
+    BadPhy(u8),
+
+    /// For the [periodic advertising report](periodic::PeriodicAdvertisingEvent::Report) event: the
+    /// data status was not recognized. Includes the unrecognized byte.
+    #[cfg(feature = "periodic-advertising")]
+    BadPeriodicAdvertisingDataStatus(u8),
+}
+
+impl Display for BlueNRGError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            BlueNRGError::UnknownEvent(code) => write!(f, "unknown event code {:#06x}", code),
+            BlueNRGError::UnknownResetReason(value) => {
+                write!(f, "unknown reset reason {:#04x}", value)
+            }
+            #[cfg(feature = "ms")]
+            BlueNRGError::BadEventFlags(flags) => {
+                write!(f, "unknown lost event flags {:#x}", flags)
+            }
+            #[cfg(feature = "ms")]
+            BlueNRGError::UnknownCrashReason(value) => {
+                write!(f, "unknown crash reason {:#04x}", value)
+            }
+            BlueNRGError::BadGapPairingStatus(value) => {
+                write!(f, "unknown GAP pairing status {:#04x}", value)
+            }
+            BlueNRGError::BadPairingFailureReason(value) => {
+                write!(f, "unknown pairing failure reason {:#04x}", value)
+            }
+            BlueNRGError::BadGapDeviceFoundEvent(value) => {
+                write!(f, "unknown GAP device found event type {:#04x}", value)
+            }
+            BlueNRGError::BadGapBdAddrType(value) => {
+                write!(f, "unknown GAP device found address type {:#04x}", value)
+            }
+            BlueNRGError::BadGapProcedure(value) => {
+                write!(f, "unknown GAP procedure {:#04x}", value)
+            }
+            BlueNRGError::BadGapProcedureStatus(value) => {
+                write!(f, "unknown GAP procedure status {:#04x}", value)
+            }
+            BlueNRGError::BadL2CapDataLength(expected, actual) => write!(
+                f,
+                "L2CAP event data length is {}, expected {}",
+                actual, expected
+            ),
+            BlueNRGError::BadL2CapLength(expected, actual) => {
+                write!(f, "L2CAP length is {}, expected {}", actual, expected)
+            }
+            BlueNRGError::BadL2CapRejectionReason(value) => {
+                write!(f, "unknown L2CAP rejection reason {:#06x}", value)
+            }
+            BlueNRGError::BadL2CapConnectionResponseCode(value) => {
+                write!(f, "unknown L2CAP connection response code {:#04x}", value)
+            }
+            BlueNRGError::BadL2CapConnectionResponseResult(value) => {
+                write!(f, "unknown L2CAP connection response result {:#06x}", value)
+            }
+            BlueNRGError::BadConnectionInterval(ref e) => {
+                write!(f, "invalid connection interval: {:?}", e)
+            }
+            BlueNRGError::BadL2CapConnectionUpdateRequestInterval(min, max) => write!(
+                f,
+                "invalid L2CAP connection update interval {:?} to {:?}",
+                min, max
+            ),
+            BlueNRGError::BadL2CapConnectionUpdateRequestLatency(latency, max) => write!(
+                f,
+                "L2CAP connection update latency {} exceeds the maximum {}",
+                latency, max
+            ),
+            BlueNRGError::BadL2CapConnectionUpdateRequestTimeout(timeout) => {
+                write!(f, "invalid L2CAP connection update timeout {:?}", timeout)
+            }
+            BlueNRGError::BadAttFindInformationResponseFormat(value) => write!(
+                f,
+                "unknown ATT find information response format {:#04x}",
+                value
+            ),
+            BlueNRGError::AttFindInformationResponsePartialPair16 => {
+                f.write_str("ATT find information response ends with a partial 16-bit UUID pair")
+            }
+            BlueNRGError::AttFindInformationResponsePartialPair128 => {
+                f.write_str("ATT find information response ends with a partial 128-bit UUID pair")
+            }
+            BlueNRGError::AttFindByTypeValuePartial => {
+                f.write_str("ATT find by type value response ends with a partial pair")
+            }
+            BlueNRGError::AttReadByTypeResponsePartial => {
+                f.write_str("ATT read by type response ends with a partial pair")
+            }
+            BlueNRGError::BadAttReadByTypeResponsePairLength(len) => write!(
+                f,
+                "ATT read by type response pair length {} is too short",
+                len
+            ),
+            BlueNRGError::AttReadByGroupTypeResponsePartial => {
+                f.write_str("ATT read by group type response ends with a partial group")
+            }
+            BlueNRGError::BadAttReadByGroupTypeResponseGroupLength(len) => write!(
+                f,
+                "ATT read by group type response group length {} is too short",
+                len
+            ),
+            BlueNRGError::BadGattProcedureStatus(value) => {
+                write!(f, "unknown GATT procedure status {:#04x}", value)
+            }
+            BlueNRGError::BadAttRequestOpcode(value) => {
+                write!(f, "unknown ATT request opcode {:#04x}", value)
+            }
+            BlueNRGError::BadAttError(value) => write!(f, "unknown ATT error {:#04x}", value),
+            BlueNRGError::AttReadMultiplePermitRequestPartial => {
+                f.write_str("ATT read multiple permit request ends with a partial handle")
+            }
+            BlueNRGError::GattMultipleNotificationPartial => {
+                f.write_str("GATT multiple notification ends with a partial value")
+            }
+            BlueNRGError::BadConfigParameterLength(len) => {
+                write!(f, "config data has unexpected length {}", len)
+            }
+            BlueNRGError::BadConfigRole(value) => write!(f, "unknown role {:#04x}", value),
+            BlueNRGError::UnknownLinkState(value) => {
+                write!(f, "unknown link state {:#04x}", value)
+            }
+            BlueNRGError::BadBooleanValue(value) => {
+                write!(f, "boolean value is {:#04x}, expected 0 or 1", value)
+            }
+            BlueNRGError::BadPassKeyRequirement(value) => {
+                write!(f, "unknown pass key requirement {:#04x}", value)
+            }
+            BlueNRGError::PartialBondedDeviceAddress => {
+                f.write_str("bonded device list ends with a partial address")
+            }
+            BlueNRGError::BadBdAddrType(value) => {
+                write!(f, "unknown address type {:#04x}", value)
+            }
+            BlueNRGError::BadPhy(value) => write!(f, "unknown PHY {:#04x}", value),
+            #[cfg(feature = "periodic-advertising")]
+            BlueNRGError::BadPeriodicAdvertisingDataStatus(value) => {
+                write!(f, "unknown periodic advertising data status {:#04x}", value)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BlueNRGError {}
+
+/// An error deserializing a vendor event or vendor command return parameters, together with the
+/// code of the event or command that failed.
+///
+/// [`BlueNRGEvent::new`](hci::event::VendorEvent::new) reports every failure as a
+/// [Vendor](hci::event::Error::Vendor) error holding a `ParseError`, so that, for example, a
+/// [BadLength](hci::event::Error::BadLength) says which event had the wrong length.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParseError {
+    /// Code of the vendor event, or opcode of the command whose return parameters could not be
+    /// parsed. `None` if the buffer was too short to contain it.
+    pub code: Option<u16>,
+
+    /// Why the event could not be parsed.
+    pub error: hci::event::Error<BlueNRGError>,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self.code {
+            Some(code) => write!(f, "{:#06x}: ", code)?,
+            None => (),
+        }
+        match self.error {
+            hci::event::Error::BadLength(actual, expected) => {
+                write!(f, "bad length {} (expected {})", actual, expected)
+            }
+            hci::event::Error::Vendor(ref e) => Display::fmt(e, f),
+            ref e => write!(f, "{:?}", e),
+        }
+    }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
 macro_rules! require_len {
     ($left:expr, $right:expr) => {
         if $left.len() != $right {
@@ -559,6 +1021,95 @@ macro_rules! require_len_at_least {
     };
 }
 
+// Checks the length of an event that carries no variable-length data. Some transports pad events
+// with trailing bytes; with the `tolerant-decode` feature those bytes are ignored instead of being
+// reported as a bad length.
+macro_rules! require_fixed_len {
+    ($left:expr, $right:expr) => {
+        if cfg!(feature = "tolerant-decode") {
+            require_len_at_least!($left, $right);
+        } else {
+            require_len!($left, $right);
+        }
+    };
+}
+
+// The maximum length of a vendor event, including the 2-byte event code. Standard HCI event packets
+// carry at most 255 bytes of parameters. Firmware that supports extended packets can deliver a full
+// 512-byte attribute value, plus up to 9 bytes of event code, handles, and lengths.
+#[cfg(not(feature = "extended-packets"))]
+const MAX_EVENT_LEN: usize = 255;
+#[cfg(feature = "extended-packets")]
+const MAX_EVENT_LEN: usize = 521;
+
+// Storage for the payload buffers of the events that can carry a full packet of data. With the
+// `alloc` feature the buffer is boxed, so those events (and `BlueNRGEvent`) stay small and the
+// buffer is only allocated when such an event arrives. Without it, the buffer is stored inline. The
+// accessors of each event dereference the payload, so callers cannot tell the difference.
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+struct Payload<T>(alloc::boxed::Box<T>);
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Clone)]
+struct Payload<T>(T);
+
+impl<T> Payload<T> {
+    #[cfg(feature = "alloc")]
+    fn new(value: T) -> Payload<T> {
+        Payload(alloc::boxed::Box::new(value))
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    fn new(value: T) -> Payload<T> {
+        Payload(value)
+    }
+}
+
+impl<T> core::ops::Deref for Payload<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> core::ops::DerefMut for Payload<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: Debug> Debug for Payload<T> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        Debug::fmt(&**self, f)
+    }
+}
+
+// Returns the length of the event data that starts at `data_start`, as reported by the length byte
+// at `len_index`, after checking that the buffer contains exactly that much data.
+//
+// With extended packets, the data may be too long for the length byte to describe, so the byte only
+// holds the low 8 bits of the length and the packet length is authoritative.
+fn to_data_len(
+    buffer: &[u8],
+    len_index: usize,
+    data_start: usize,
+) -> Result<usize, hci::event::Error<BlueNRGError>> {
+    let data_len = buffer[len_index] as usize;
+
+    #[cfg(feature = "extended-packets")]
+    {
+        let actual_len = buffer.len() - data_start;
+        if actual_len & 0xFF == data_len {
+            return Ok(actual_len);
+        }
+    }
+
+    require_len!(buffer, data_start + data_len);
+    Ok(data_len)
+}
+
 fn first_16<T>(buffer: &[T]) -> &[T] {
     if buffer.len() < 16 {
         &buffer
@@ -567,185 +1118,282 @@ fn first_16<T>(buffer: &[T]) -> &[T] {
     }
 }
 
+/// Deserializes a vendor-specific event from the given buffer.
+///
+/// This is the same as [`BlueNRGEvent::new`](hci::event::VendorEvent::new), but does not require
+/// the [`VendorEvent`](hci::event::VendorEvent) trait to be in scope. The buffer must begin with the
+/// 2-byte event code, as the controller sends it in the vendor-specific event packet.
+///
+/// Being a plain function, it is also a convenient target for fuzzing the parser. A `cargo-fuzz`
+/// target only needs to hand it the fuzzer's input:
+///
+/// ```ignore
+/// #![no_main]
+/// use libfuzzer_sys::fuzz_target;
+///
+/// fuzz_target!(|data: &[u8]| {
+///     let _ = bluenrg::event::parse(data);
+/// });
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as [`BlueNRGEvent::new`](hci::event::VendorEvent::new).
+pub fn parse(buffer: &[u8]) -> Result<BlueNRGEvent, hci::event::Error<ParseError>> {
+    hci::event::VendorEvent::new(buffer)
+}
+
+fn event_code(buffer: &[u8]) -> Option<u16> {
+    if buffer.len() < 2 {
+        return None;
+    }
+
+    Some(LittleEndian::read_u16(&buffer[0..=1]))
+}
+
 impl hci::event::VendorEvent for BlueNRGEvent {
-    type Error = BlueNRGError;
+    type Error = ParseError;
     type ReturnParameters = command::ReturnParameters;
     type Status = Status;
 
-    fn new(buffer: &[u8]) -> Result<Self, hci::event::Error<BlueNRGError>> {
-        require_len_at_least!(buffer, 2);
-
-        let event_code = LittleEndian::read_u16(&buffer[0..=1]);
-        match event_code {
-            0x0001 => Ok(BlueNRGEvent::HalInitialized(to_hal_initialized(buffer)?)),
-            0x0002 => {
-                #[cfg(feature = "ms")]
-                {
-                    Ok(BlueNRGEvent::EventsLost(to_lost_event(buffer)?))
-                }
-
-                #[cfg(not(feature = "ms"))]
-                {
-                    Err(hci::event::Error::Vendor(BlueNRGError::UnknownEvent(
-                        event_code,
-                    )))
-                }
+    fn new(buffer: &[u8]) -> Result<Self, hci::event::Error<ParseError>> {
+        #[cfg(feature = "log")]
+        {
+            match event_code(buffer) {
+                Some(code) => log::trace!(
+                    "vendor event {:#06x}, {} bytes: {:02x?}",
+                    code,
+                    buffer.len(),
+                    first_16(buffer)
+                ),
+                None => log::trace!("vendor event, {} bytes", buffer.len()),
             }
-            0x0003 => {
-                #[cfg(feature = "ms")]
-                {
-                    Ok(BlueNRGEvent::CrashReport(to_crash_report(buffer)?))
-                }
+        }
 
-                #[cfg(not(feature = "ms"))]
-                {
-                    Err(hci::event::Error::Vendor(BlueNRGError::UnknownEvent(
-                        event_code,
-                    )))
-                }
-            }
-            0x0400 => Ok(BlueNRGEvent::GapLimitedDiscoverableTimeout),
-            0x0401 => Ok(BlueNRGEvent::GapPairingComplete(to_gap_pairing_complete(
-                buffer,
-            )?)),
-            0x0402 => Ok(BlueNRGEvent::GapPassKeyRequest(to_conn_handle(buffer)?)),
-            0x0403 => Ok(BlueNRGEvent::GapAuthorizationRequest(to_conn_handle(
-                buffer,
-            )?)),
-            0x0404 => Ok(BlueNRGEvent::GapPeripheralSecurityInitiated),
-            0x0405 => Ok(BlueNRGEvent::GapBondLost),
-            0x0406 => Ok(BlueNRGEvent::GapDeviceFound(to_gap_device_found(buffer)?)),
-            0x0407 => Ok(BlueNRGEvent::GapProcedureComplete(
-                to_gap_procedure_complete(buffer)?,
-            )),
-            0x0408 => {
-                #[cfg(feature = "ms")]
-                {
-                    Ok(BlueNRGEvent::GapAddressNotResolved(to_conn_handle(buffer)?))
-                }
+        let result = to_vendor_event(buffer).map_err(|error| ParseError {
+            code: event_code(buffer),
+            error,
+        });
 
-                #[cfg(not(feature = "ms"))]
-                {
-                    Ok(BlueNRGEvent::GapReconnectionAddress(
-                        to_gap_reconnection_address(buffer)?,
-                    ))
-                }
+        #[cfg(feature = "log")]
+        {
+            if let Err(ref error) = result {
+                log::warn!("failed to parse {}", error);
             }
-            0x0800 => Ok(BlueNRGEvent::L2CapConnectionUpdateResponse(
-                to_l2cap_connection_update_response(buffer)?,
-            )),
-            0x0801 => Ok(BlueNRGEvent::L2CapProcedureTimeout(
-                to_l2cap_procedure_timeout(buffer)?,
-            )),
-            0x0802 => Ok(BlueNRGEvent::L2CapConnectionUpdateRequest(
-                to_l2cap_connection_update_request(buffer)?,
-            )),
-            0x0C01 => Ok(BlueNRGEvent::GattAttributeModified(
-                to_gatt_attribute_modified(buffer)?,
-            )),
-            0x0C02 => Ok(BlueNRGEvent::GattProcedureTimeout(to_conn_handle(buffer)?)),
-            0x0C03 => Ok(BlueNRGEvent::AttExchangeMtuResponse(
-                to_att_exchange_mtu_resp(buffer)?,
-            )),
-            0x0C04 => Ok(BlueNRGEvent::AttFindInformationResponse(
-                to_att_find_information_response(buffer)?,
-            )),
-            0x0C05 => Ok(BlueNRGEvent::AttFindByTypeValueResponse(
-                to_att_find_by_value_type_response(buffer)?,
-            )),
-            0x0C06 => Ok(BlueNRGEvent::AttReadByTypeResponse(
-                to_att_read_by_type_response(buffer)?,
-            )),
-            0x0C07 => Ok(BlueNRGEvent::AttReadResponse(to_att_read_response(buffer)?)),
-            0x0C08 => Ok(BlueNRGEvent::AttReadBlobResponse(to_att_read_response(
-                buffer,
-            )?)),
-            0x0C09 => Ok(BlueNRGEvent::AttReadMultipleResponse(to_att_read_response(
-                buffer,
-            )?)),
-            0x0C0A => Ok(BlueNRGEvent::AttReadByGroupTypeResponse(
-                to_att_read_by_group_type_response(buffer)?,
-            )),
-            0x0C0C => Ok(BlueNRGEvent::AttPrepareWriteResponse(
-                to_att_prepare_write_response(buffer)?,
-            )),
-            0x0C0D => Ok(BlueNRGEvent::AttExecuteWriteResponse(to_conn_handle(
-                buffer,
-            )?)),
-            0x0C0E => Ok(BlueNRGEvent::GattIndication(to_attribute_value(buffer)?)),
-            0x0C0F => Ok(BlueNRGEvent::GattNotification(to_attribute_value(buffer)?)),
-            0x0C10 => Ok(BlueNRGEvent::GattProcedureComplete(
-                to_gatt_procedure_complete(buffer)?,
-            )),
-            0x0C11 => Ok(BlueNRGEvent::AttErrorResponse(to_att_error_response(
-                buffer,
-            )?)),
-            0x0C12 => Ok(
-                BlueNRGEvent::GattDiscoverOrReadCharacteristicByUuidResponse(to_attribute_value(
-                    buffer,
-                )?),
-            ),
-            0x0C13 => Ok(BlueNRGEvent::AttWritePermitRequest(
-                to_write_permit_request(buffer)?,
-            )),
-            0x0C14 => Ok(BlueNRGEvent::AttReadPermitRequest(
-                to_att_read_permit_request(buffer)?,
-            )),
-            0x0C15 => Ok(BlueNRGEvent::AttReadMultiplePermitRequest(
-                to_att_read_multiple_permit_request(buffer)?,
-            )),
-            0x0C16 => {
-                #[cfg(feature = "ms")]
-                {
-                    Ok(BlueNRGEvent::GattTxPoolAvailable(
-                        to_gatt_tx_pool_available(buffer)?,
-                    ))
-                }
+        }
 
-                #[cfg(not(feature = "ms"))]
-                {
-                    Err(hci::event::Error::Vendor(BlueNRGError::UnknownEvent(
-                        event_code,
-                    )))
-                }
-            }
-            0x0C17 => {
-                #[cfg(feature = "ms")]
-                {
-                    Ok(BlueNRGEvent::GattServerConfirmation(to_conn_handle(
-                        buffer,
-                    )?))
-                }
+        result.map_err(hci::event::Error::Vendor)
+    }
+}
 
-                #[cfg(not(feature = "ms"))]
-                {
-                    Err(hci::event::Error::Vendor(BlueNRGError::UnknownEvent(
-                        event_code,
-                    )))
-                }
-            }
-            0x0C18 => {
-                #[cfg(feature = "ms")]
-                {
-                    Ok(BlueNRGEvent::AttPrepareWritePermitRequest(
-                        to_att_prepare_write_permit_request(buffer)?,
-                    ))
-                }
+fn to_vendor_event(buffer: &[u8]) -> Result<BlueNRGEvent, hci::event::Error<BlueNRGError>> {
+    require_len_at_least!(buffer, 2);
+    if buffer.len() > MAX_EVENT_LEN {
+        return Err(hci::event::Error::BadLength(buffer.len(), MAX_EVENT_LEN));
+    }
 
-                #[cfg(not(feature = "ms"))]
-                {
-                    Err(hci::event::Error::Vendor(BlueNRGError::UnknownEvent(
-                        event_code,
-                    )))
-                }
-            }
-            _ => Err(hci::event::Error::Vendor(BlueNRGError::UnknownEvent(
-                event_code,
-            ))),
-        }
+    let event_code = LittleEndian::read_u16(&buffer[0..=1]);
+    match DECODERS.binary_search_by_key(&event_code, |&(code, _)| code) {
+        Ok(index) => (DECODERS[index].1)(buffer),
+        Err(_) => Err(hci::event::Error::Vendor(BlueNRGError::UnknownEvent(
+            event_code,
+        ))),
     }
 }
 
+/// Returns the vendor event codes that this build of the crate can decode, in ascending order.
+/// Which events are included depends on the crate features.
+pub fn event_codes() -> impl Iterator<Item = u16> {
+    DECODERS.iter().map(|&(code, _)| code)
+}
+
+type Decoder = fn(&[u8]) -> Result<BlueNRGEvent, hci::event::Error<BlueNRGError>>;
+
+// Decoders for each vendor event code, sorted by the code so they can be found with a binary
+// search. Codes that are not in the table (including those whose events are disabled by the crate
+// features) are reported as unknown events.
+static DECODERS: &[(u16, Decoder)] = &[
+    (0x0001, |buffer| {
+        to_hal_initialized(buffer).map(BlueNRGEvent::HalInitialized)
+    }),
+    #[cfg(feature = "ms")]
+    (0x0002, |buffer| {
+        to_lost_event(buffer).map(BlueNRGEvent::EventsLost)
+    }),
+    #[cfg(feature = "ms")]
+    (0x0003, |buffer| {
+        to_crash_report(buffer).map(BlueNRGEvent::CrashReport)
+    }),
+    (0x0400, |buffer| {
+        // The event has no parameters.
+        require_fixed_len!(buffer, 2);
+        Ok(BlueNRGEvent::GapLimitedDiscoverableTimeout)
+    }),
+    (0x0401, |buffer| {
+        to_gap_pairing_complete(buffer).map(BlueNRGEvent::GapPairingComplete)
+    }),
+    (0x0402, |buffer| {
+        Ok(BlueNRGEvent::GapPassKeyRequest(GapPassKeyRequest {
+            conn_handle: to_conn_handle(buffer)?,
+        }))
+    }),
+    (0x0403, |buffer| {
+        Ok(BlueNRGEvent::GapAuthorizationRequest(
+            GapAuthorizationRequest {
+                conn_handle: to_conn_handle(buffer)?,
+            },
+        ))
+    }),
+    (0x0404, |_| Ok(BlueNRGEvent::GapPeripheralSecurityInitiated)),
+    (0x0405, |buffer| {
+        // The event has no parameters.
+        require_fixed_len!(buffer, 2);
+        Ok(BlueNRGEvent::GapBondLost)
+    }),
+    // BlueNRG-LP reports discovered devices through the standard LE Advertising Report event.
+    #[cfg(all(feature = "gap-central-events", not(feature = "bluenrg-lp")))]
+    (0x0406, |buffer| {
+        to_gap_device_found(buffer).map(BlueNRGEvent::GapDeviceFound)
+    }),
+    #[cfg(feature = "gap-central-events")]
+    (0x0407, |buffer| {
+        to_gap_procedure_complete(buffer).map(BlueNRGEvent::GapProcedureComplete)
+    }),
+    #[cfg(feature = "ms")]
+    (0x0408, |buffer| {
+        Ok(BlueNRGEvent::GapAddressNotResolved(GapAddressNotResolved {
+            conn_handle: to_conn_handle(buffer)?,
+        }))
+    }),
+    #[cfg(all(not(feature = "ms"), feature = "gap-central-events"))]
+    (0x0408, |buffer| {
+        to_gap_reconnection_address(buffer).map(BlueNRGEvent::GapReconnectionAddress)
+    }),
+    (0x0409, |buffer| {
+        to_gap_numeric_comparison_value(buffer).map(BlueNRGEvent::GapNumericComparisonValue)
+    }),
+    #[cfg(feature = "l2cap-events")]
+    (0x0800, |buffer| {
+        to_l2cap_connection_update_response(buffer).map(BlueNRGEvent::L2CapConnectionUpdateResponse)
+    }),
+    #[cfg(feature = "l2cap-events")]
+    (0x0801, |buffer| {
+        to_l2cap_procedure_timeout(buffer).map(BlueNRGEvent::L2CapProcedureTimeout)
+    }),
+    #[cfg(feature = "l2cap-events")]
+    (0x0802, |buffer| {
+        to_l2cap_connection_update_request(buffer).map(BlueNRGEvent::L2CapConnectionUpdateRequest)
+    }),
+    #[cfg(not(feature = "bluenrg-lp"))]
+    (0x0C01, |buffer| {
+        to_gatt_attribute_modified(buffer).map(BlueNRGEvent::GattAttributeModified)
+    }),
+    #[cfg(feature = "bluenrg-lp")]
+    (0x0C01, |buffer| {
+        to_lp_gatt_attribute_modified(buffer).map(BlueNRGEvent::GattAttributeModified)
+    }),
+    #[cfg(feature = "att-client-events")]
+    (0x0C02, |buffer| {
+        Ok(BlueNRGEvent::GattProcedureTimeout(GattProcedureTimeout {
+            conn_handle: to_conn_handle(buffer)?,
+        }))
+    }),
+    #[cfg(feature = "att-client-events")]
+    (0x0C03, |buffer| {
+        to_att_exchange_mtu_resp(buffer).map(BlueNRGEvent::AttExchangeMtuResponse)
+    }),
+    #[cfg(feature = "att-client-events")]
+    (0x0C04, |buffer| {
+        to_att_find_information_response(buffer).map(BlueNRGEvent::AttFindInformationResponse)
+    }),
+    #[cfg(feature = "att-client-events")]
+    (0x0C05, |buffer| {
+        to_att_find_by_value_type_response(buffer).map(BlueNRGEvent::AttFindByTypeValueResponse)
+    }),
+    #[cfg(feature = "att-client-events")]
+    (0x0C06, |buffer| {
+        to_att_read_by_type_response(buffer).map(BlueNRGEvent::AttReadByTypeResponse)
+    }),
+    #[cfg(feature = "att-client-events")]
+    (0x0C07, |buffer| {
+        to_att_read_response(buffer).map(BlueNRGEvent::AttReadResponse)
+    }),
+    #[cfg(feature = "att-client-events")]
+    (0x0C08, |buffer| {
+        to_att_read_response(buffer).map(BlueNRGEvent::AttReadBlobResponse)
+    }),
+    #[cfg(feature = "att-client-events")]
+    (0x0C09, |buffer| {
+        to_att_read_response(buffer).map(BlueNRGEvent::AttReadMultipleResponse)
+    }),
+    #[cfg(feature = "att-client-events")]
+    (0x0C0A, |buffer| {
+        to_att_read_by_group_type_response(buffer).map(BlueNRGEvent::AttReadByGroupTypeResponse)
+    }),
+    #[cfg(feature = "att-client-events")]
+    (0x0C0C, |buffer| {
+        to_att_prepare_write_response(buffer).map(BlueNRGEvent::AttPrepareWriteResponse)
+    }),
+    #[cfg(feature = "att-client-events")]
+    (0x0C0D, |buffer| {
+        Ok(BlueNRGEvent::AttExecuteWriteResponse(
+            AttExecuteWriteResponse {
+                conn_handle: to_conn_handle(buffer)?,
+            },
+        ))
+    }),
+    #[cfg(feature = "att-client-events")]
+    (0x0C0E, |buffer| {
+        to_attribute_value(buffer).map(BlueNRGEvent::GattIndication)
+    }),
+    #[cfg(feature = "att-client-events")]
+    (0x0C0F, |buffer| {
+        to_attribute_value(buffer).map(BlueNRGEvent::GattNotification)
+    }),
+    #[cfg(feature = "att-client-events")]
+    (0x0C10, |buffer| {
+        to_gatt_procedure_complete(buffer).map(BlueNRGEvent::GattProcedureComplete)
+    }),
+    #[cfg(feature = "att-client-events")]
+    (0x0C11, |buffer| {
+        to_att_error_response(buffer).map(BlueNRGEvent::AttErrorResponse)
+    }),
+    #[cfg(feature = "att-client-events")]
+    (0x0C12, |buffer| {
+        to_attribute_value(buffer).map(BlueNRGEvent::GattDiscoverOrReadCharacteristicByUuidResponse)
+    }),
+    (0x0C13, |buffer| {
+        to_write_permit_request(buffer).map(BlueNRGEvent::AttWritePermitRequest)
+    }),
+    (0x0C14, |buffer| {
+        to_att_read_permit_request(buffer).map(BlueNRGEvent::AttReadPermitRequest)
+    }),
+    (0x0C15, |buffer| {
+        to_att_read_multiple_permit_request(buffer).map(BlueNRGEvent::AttReadMultiplePermitRequest)
+    }),
+    #[cfg(feature = "ms")]
+    (0x0C16, |buffer| {
+        to_gatt_tx_pool_available(buffer).map(BlueNRGEvent::GattTxPoolAvailable)
+    }),
+    #[cfg(feature = "ms")]
+    (0x0C17, |buffer| {
+        require_len!(buffer, 4);
+        to_conn_handle(buffer).map(BlueNRGEvent::GattServerConfirmation)
+    }),
+    #[cfg(feature = "ms")]
+    (0x0C18, |buffer| {
+        to_att_prepare_write_permit_request(buffer).map(BlueNRGEvent::AttPrepareWritePermitRequest)
+    }),
+    #[cfg(feature = "att-client-events")]
+    (0x0C1A, |buffer| {
+        to_gatt_multiple_notification(buffer).map(BlueNRGEvent::GattMultipleNotification)
+    }),
+    #[cfg(all(feature = "bluenrg-lp", feature = "att-client-events"))]
+    (0x0C1E, |buffer| {
+        to_extended_attribute_value(buffer).map(BlueNRGEvent::GattIndicationExt)
+    }),
+];
+
 /// Potential reasons the controller sent the [`HalInitialized`](BlueNRGEvent::HalInitialized)
 /// event.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -789,17 +1437,64 @@ impl TryFrom<u8> for ResetReason {
     }
 }
 
+/// The [HAL Initialized](BlueNRGEvent::HalInitialized) event.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct HalInitialized {
+    /// Why the controller was reset.
+    pub reason: ResetReason,
+
+    boot_info: Option<BootInfo>,
+}
+
+impl HalInitialized {
+    /// Returns the extra boot information that some firmware appends to the reset reason, or
+    /// `None` if the event only has the reset reason.
+    pub fn boot_info(&self) -> Option<&BootInfo> {
+        self.boot_info.as_ref()
+    }
+}
+
+/// Extra boot information in the [HAL Initialized](BlueNRGEvent::HalInitialized) event, after the
+/// reset reason.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BootInfo {
+    /// Number of times the controller has booted. The count wraps around after 255.
+    pub boot_count: u8,
+
+    /// Firmware revision byte, if the firmware reports it after the boot count.
+    pub firmware_revision: Option<u8>,
+}
+
 /// Convert a buffer to the `HalInitialized` `BlueNRGEvent`.
 ///
 /// # Errors
 ///
-/// - Returns a `BadLength` HCI error if the buffer is not exactly 3 bytes long
+/// - Returns a `BadLength` HCI error if the buffer is shorter than 3 bytes, or longer than 5 bytes
+///   (the reset reason, boot count and firmware revision). With the `tolerant-decode` feature,
+///   bytes after the firmware revision are ignored instead.
 ///
 /// - Returns a `UnknownResetReason` BlueNRG error if the reset reason is not recognized.
-fn to_hal_initialized(buffer: &[u8]) -> Result<ResetReason, hci::event::Error<BlueNRGError>> {
-    require_len!(buffer, 3);
+fn to_hal_initialized(buffer: &[u8]) -> Result<HalInitialized, hci::event::Error<BlueNRGError>> {
+    require_len_at_least!(buffer, 3);
+
+    let boot_info = match buffer.len() {
+        3 => None,
+        4 => Some(BootInfo {
+            boot_count: buffer[3],
+            firmware_revision: None,
+        }),
+        // Padding after the firmware revision is ignored.
+        len if len == 5 || cfg!(feature = "tolerant-decode") => Some(BootInfo {
+            boot_count: buffer[3],
+            firmware_revision: Some(buffer[4]),
+        }),
+        _ => return Err(hci::event::Error::BadLength(buffer.len(), 5)),
+    };
 
-    Ok(buffer[2].try_into().map_err(hci::event::Error::Vendor)?)
+    Ok(HalInitialized {
+        reason: buffer[2].try_into().map_err(hci::event::Error::Vendor)?,
+        boot_info,
+    })
 }
 
 #[cfg(feature = "ms")]
@@ -932,7 +1627,7 @@ bitflags! {
 ///   a lost event.
 #[cfg(feature = "ms")]
 fn to_lost_event(buffer: &[u8]) -> Result<EventFlags, hci::event::Error<BlueNRGError>> {
-    require_len!(buffer, 10);
+    require_fixed_len!(buffer, 10);
 
     let bits = LittleEndian::read_u64(&buffer[2..]);
     EventFlags::from_bits(bits)
@@ -978,7 +1673,7 @@ impl TryFrom<u8> for CrashReason {
 
 /// Fault data reported after a crash.
 #[cfg(feature = "ms")]
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct FaultData {
     /// Fault reason.
     pub reason: CrashReason,
@@ -1006,7 +1701,7 @@ pub struct FaultData {
     debug_data_len: usize,
 
     // Additional crash dump data
-    debug_data_buf: [u8; MAX_DEBUG_DATA_LEN],
+    debug_data_buf: Payload<[u8; MAX_DEBUG_DATA_LEN]>,
 }
 
 #[cfg(feature = "ms")]
@@ -1031,21 +1726,230 @@ impl Debug for FaultData {
 
 #[cfg(feature = "ms")]
 impl FaultData {
+    /// Returns fault data with the given reason and debug data, and all registers set to 0. The
+    /// registers can be set directly afterwards. This is mostly useful for testing crash handling
+    /// without a controller.
+    ///
+    /// Returns `None` if the debug data is longer than a crash report can hold.
+    pub fn new(reason: CrashReason, debug_data: &[u8]) -> Option<FaultData> {
+        if debug_data.len() > MAX_DEBUG_DATA_LEN {
+            return None;
+        }
+
+        let mut debug_data_buf = [0; MAX_DEBUG_DATA_LEN];
+        debug_data_buf[..debug_data.len()].copy_from_slice(debug_data);
+        Some(FaultData {
+            reason,
+            sp: 0,
+            r0: 0,
+            r1: 0,
+            r2: 0,
+            r3: 0,
+            r12: 0,
+            lr: 0,
+            pc: 0,
+            xpsr: 0,
+            debug_data_len: debug_data.len(),
+            debug_data_buf: Payload::new(debug_data_buf),
+        })
+    }
+
     /// Returns the valid debug data.
     pub fn debug_data(&self) -> &[u8] {
         &self.debug_data_buf[..self.debug_data_len]
     }
+
+    /// Number of bytes [`serialize`](FaultData::serialize) writes for this fault data.
+    pub fn serialized_len(&self) -> usize {
+        FAULT_DATA_HEADER_LEN + self.debug_data_len
+    }
+
+    /// Writes the fault data into `bytes` in a compact format suitable for persistent storage (for
+    /// example, a reserved flash page), so a crash can be reported after the next boot.
+    ///
+    /// The format is little-endian:
+    ///
+    /// | Offset | Size | Contents                                                              |
+    /// |--------|------|-----------------------------------------------------------------------|
+    /// | 0      | 1    | Format version ([`FAULT_DATA_FORMAT_VERSION`])                        |
+    /// | 1      | 1    | Crash reason: 0 = assertion, 1 = NMI fault, 2 = hard fault            |
+    /// | 2      | 36   | Registers SP, R0, R1, R2, R3, R12, LR, PC, xPSR (4 bytes each)        |
+    /// | 38     | 1    | Length of the debug data (N)                                          |
+    /// | 39     | N    | Debug data                                                            |
+    ///
+    /// Only the valid debug data is written. Returns the number of bytes written, which is
+    /// [`serialized_len`](FaultData::serialized_len).
+    ///
+    /// # Errors
+    ///
+    /// - [BufferTooSmall](FaultDataFormatError::BufferTooSmall) if `bytes` is shorter than
+    ///   [`serialized_len`](FaultData::serialized_len). Includes the required length. Nothing is
+    ///   written in this case.
+    pub fn serialize(&self, bytes: &mut [u8]) -> Result<usize, FaultDataFormatError> {
+        let len = self.serialized_len();
+        if bytes.len() < len {
+            return Err(FaultDataFormatError::BufferTooSmall(len));
+        }
+
+        bytes[0] = FAULT_DATA_FORMAT_VERSION;
+        bytes[1] = match self.reason {
+            CrashReason::Assertion => 0,
+            CrashReason::NmiFault => 1,
+            CrashReason::HardFault => 2,
+        };
+        LittleEndian::write_u32(&mut bytes[2..], self.sp);
+        LittleEndian::write_u32(&mut bytes[6..], self.r0);
+        LittleEndian::write_u32(&mut bytes[10..], self.r1);
+        LittleEndian::write_u32(&mut bytes[14..], self.r2);
+        LittleEndian::write_u32(&mut bytes[18..], self.r3);
+        LittleEndian::write_u32(&mut bytes[22..], self.r12);
+        LittleEndian::write_u32(&mut bytes[26..], self.lr);
+        LittleEndian::write_u32(&mut bytes[30..], self.pc);
+        LittleEndian::write_u32(&mut bytes[34..], self.xpsr);
+        bytes[38] = self.debug_data_len as u8;
+        bytes[FAULT_DATA_HEADER_LEN..len].copy_from_slice(self.debug_data());
+
+        Ok(len)
+    }
+
+    /// Reads fault data written by [`serialize`](FaultData::serialize). Bytes after the debug
+    /// data are ignored, so `bytes` may be the whole storage area.
+    ///
+    /// # Errors
+    ///
+    /// - [Truncated](FaultDataFormatError::Truncated) if `bytes` ends before the end of the fault
+    ///   data. Includes the length of `bytes` and the required length.
+    /// - [UnsupportedVersion](FaultDataFormatError::UnsupportedVersion) if the format version is
+    ///   not [`FAULT_DATA_FORMAT_VERSION`]. Erased or never-written storage usually reports this
+    ///   error.
+    /// - [BadCrashReason](FaultDataFormatError::BadCrashReason) if the crash reason is not
+    ///   recognized.
+    /// - [DebugDataTooLong](FaultDataFormatError::DebugDataTooLong) if the debug data is longer
+    ///   than the controller can report.
+    pub fn deserialize(bytes: &[u8]) -> Result<FaultData, FaultDataFormatError> {
+        if bytes.is_empty() {
+            return Err(FaultDataFormatError::Truncated(0, FAULT_DATA_HEADER_LEN));
+        }
+        if bytes[0] != FAULT_DATA_FORMAT_VERSION {
+            return Err(FaultDataFormatError::UnsupportedVersion(bytes[0]));
+        }
+        if bytes.len() < FAULT_DATA_HEADER_LEN {
+            return Err(FaultDataFormatError::Truncated(
+                bytes.len(),
+                FAULT_DATA_HEADER_LEN,
+            ));
+        }
+
+        let debug_data_len = bytes[38] as usize;
+        if debug_data_len > MAX_DEBUG_DATA_LEN {
+            return Err(FaultDataFormatError::DebugDataTooLong(debug_data_len));
+        }
+        let len = FAULT_DATA_HEADER_LEN + debug_data_len;
+        if bytes.len() < len {
+            return Err(FaultDataFormatError::Truncated(bytes.len(), len));
+        }
+
+        let mut fault_data = FaultData {
+            reason: match bytes[1] {
+                0 => CrashReason::Assertion,
+                1 => CrashReason::NmiFault,
+                2 => CrashReason::HardFault,
+                other => return Err(FaultDataFormatError::BadCrashReason(other)),
+            },
+            sp: LittleEndian::read_u32(&bytes[2..]),
+            r0: LittleEndian::read_u32(&bytes[6..]),
+            r1: LittleEndian::read_u32(&bytes[10..]),
+            r2: LittleEndian::read_u32(&bytes[14..]),
+            r3: LittleEndian::read_u32(&bytes[18..]),
+            r12: LittleEndian::read_u32(&bytes[22..]),
+            lr: LittleEndian::read_u32(&bytes[26..]),
+            pc: LittleEndian::read_u32(&bytes[30..]),
+            xpsr: LittleEndian::read_u32(&bytes[34..]),
+            debug_data_len,
+            debug_data_buf: Payload::new([0; MAX_DEBUG_DATA_LEN]),
+        };
+        fault_data.debug_data_buf[..debug_data_len]
+            .copy_from_slice(&bytes[FAULT_DATA_HEADER_LEN..len]);
+
+        Ok(fault_data)
+    }
 }
 
+/// Version of the format written by [`FaultData::serialize`]. Stored as the first byte of the
+/// serialized data.
 #[cfg(feature = "ms")]
-fn to_crash_report(buffer: &[u8]) -> Result<FaultData, hci::event::Error<BlueNRGError>> {
-    require_len_at_least!(buffer, 40);
+pub const FAULT_DATA_FORMAT_VERSION: u8 = 1;
 
-    let debug_data_len = buffer[39] as usize;
-    require_len!(buffer, 40 + debug_data_len);
+// Length of the serialized fault data before the debug data: version, reason, 9 registers, and the
+// debug data length.
+#[cfg(feature = "ms")]
+const FAULT_DATA_HEADER_LEN: usize = 39;
 
-    let mut fault_data = FaultData {
-        reason: buffer[2].try_into().map_err(hci::event::Error::Vendor)?,
+/// Errors that may occur when serializing or deserializing [`FaultData`].
+#[cfg(feature = "ms")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FaultDataFormatError {
+    /// The buffer given to [`serialize`](FaultData::serialize) is too small. Includes the
+    /// required length.
+    BufferTooSmall(usize),
+
+    /// The data given to [`deserialize`](FaultData::deserialize) ends early. Includes the length
+    /// of the data and the required length.
+    Truncated(usize, usize),
+
+    /// The format version is not supported. Includes the version byte.
+    UnsupportedVersion(u8),
+
+    /// The crash reason is not recognized. Includes the reason byte.
+    BadCrashReason(u8),
+
+    /// The debug data length is longer than any crash report can contain. Includes the length.
+    DebugDataTooLong(usize),
+}
+
+#[cfg(feature = "ms")]
+impl Display for FaultDataFormatError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            FaultDataFormatError::BufferTooSmall(required) => {
+                write!(f, "buffer is too small, {} bytes required", required)
+            }
+            FaultDataFormatError::Truncated(len, required) => {
+                write!(f, "data is {} bytes, {} required", len, required)
+            }
+            FaultDataFormatError::UnsupportedVersion(version) => {
+                write!(f, "unsupported fault data version {}", version)
+            }
+            FaultDataFormatError::BadCrashReason(value) => {
+                write!(f, "unknown crash reason {:#04x}", value)
+            }
+            FaultDataFormatError::DebugDataTooLong(len) => {
+                write!(f, "debug data length {} is too long", len)
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "ms", feature = "std"))]
+impl std::error::Error for FaultDataFormatError {}
+
+#[cfg(feature = "ms")]
+fn to_crash_report(buffer: &[u8]) -> Result<FaultData, hci::event::Error<BlueNRGError>> {
+    require_len_at_least!(buffer, 40);
+
+    // A standard event packet cannot hold more than MAX_DEBUG_DATA_LEN bytes of debug data, but the
+    // length byte can claim more. With extended packets, the buffer may even be long enough.
+    let debug_data_len = buffer[39] as usize;
+    if debug_data_len > MAX_DEBUG_DATA_LEN {
+        return Err(hci::event::Error::BadLength(
+            buffer.len(),
+            40 + MAX_DEBUG_DATA_LEN,
+        ));
+    }
+    require_len!(buffer, 40 + debug_data_len);
+
+    let mut fault_data = FaultData {
+        reason: buffer[2].try_into().map_err(hci::event::Error::Vendor)?,
         sp: LittleEndian::read_u32(&buffer[3..]),
         r0: LittleEndian::read_u32(&buffer[7..]),
         r1: LittleEndian::read_u32(&buffer[11..]),
@@ -1056,13 +1960,14 @@ fn to_crash_report(buffer: &[u8]) -> Result<FaultData, hci::event::Error<BlueNRG
         pc: LittleEndian::read_u32(&buffer[31..]),
         xpsr: LittleEndian::read_u32(&buffer[35..]),
         debug_data_len,
-        debug_data_buf: [0; MAX_DEBUG_DATA_LEN],
+        debug_data_buf: Payload::new([0; MAX_DEBUG_DATA_LEN]),
     };
     fault_data.debug_data_buf[..debug_data_len].copy_from_slice(&buffer[40..]);
 
     Ok(fault_data)
 }
 
+#[cfg(feature = "l2cap-events")]
 macro_rules! require_l2cap_event_data_len {
     ($left:expr, $right:expr) => {
         let actual = $left[4];
@@ -1074,6 +1979,7 @@ macro_rules! require_l2cap_event_data_len {
     };
 }
 
+#[cfg(feature = "l2cap-events")]
 macro_rules! require_l2cap_len {
     ($actual:expr, $expected:expr) => {
         if $actual != $expected {
@@ -1139,6 +2045,7 @@ pub enum L2CapConnectionUpdateResult {
     ParametersUpdated,
 }
 
+#[cfg(feature = "l2cap-events")]
 fn to_l2cap_connection_update_accepted_result(
     value: u16,
 ) -> Result<L2CapConnectionUpdateResult, BlueNRGError> {
@@ -1149,6 +2056,7 @@ fn to_l2cap_connection_update_accepted_result(
     }
 }
 
+#[cfg(feature = "l2cap-events")]
 fn extract_l2cap_connection_update_response_result(
     buffer: &[u8],
 ) -> Result<L2CapConnectionUpdateResult, BlueNRGError> {
@@ -1161,10 +2069,11 @@ fn extract_l2cap_connection_update_response_result(
     }
 }
 
+#[cfg(feature = "l2cap-events")]
 fn to_l2cap_connection_update_response(
     buffer: &[u8],
 ) -> Result<L2CapConnectionUpdateResponse, hci::event::Error<BlueNRGError>> {
-    require_len!(buffer, 11);
+    require_fixed_len!(buffer, 11);
     require_l2cap_event_data_len!(buffer, 6);
     require_l2cap_len!(LittleEndian::read_u16(&buffer[7..]), 2);
 
@@ -1177,19 +2086,23 @@ fn to_l2cap_connection_update_response(
 
 /// This event is generated when the central device does not respond to the connection update
 /// request within 30 seconds.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[non_exhaustive]
 pub struct L2CapProcedureTimeout {
     /// The connection handle related to the event.
     pub conn_handle: ConnectionHandle,
 }
 
+#[cfg(feature = "l2cap-events")]
 fn to_l2cap_procedure_timeout(
     buffer: &[u8],
-) -> Result<ConnectionHandle, hci::event::Error<BlueNRGError>> {
-    require_len!(buffer, 5);
+) -> Result<L2CapProcedureTimeout, hci::event::Error<BlueNRGError>> {
+    require_fixed_len!(buffer, 5);
     require_l2cap_event_data_len!(buffer, 0);
 
-    Ok(ConnectionHandle(LittleEndian::read_u16(&buffer[2..])))
+    Ok(L2CapProcedureTimeout {
+        conn_handle: ConnectionHandle(LittleEndian::read_u16(&buffer[2..])),
+    })
 }
 
 /// The event is given by the L2CAP layer when a connection update request is received from the
@@ -1217,10 +2130,54 @@ pub struct L2CapConnectionUpdateRequest {
     pub conn_interval: ConnectionInterval,
 }
 
+impl L2CapConnectionUpdateRequest {
+    /// Returns the parameters for
+    /// [`connection_parameter_update_response`](crate::l2cap::Commands::connection_parameter_update_response)
+    /// that accept the requested connection parameters. The response echoes the request's
+    /// connection handle, identifier and parameters, which were validated when the event was
+    /// decoded.
+    pub fn accept(
+        &self,
+        expected_connection_length: hci::types::ExpectedConnectionLength,
+    ) -> crate::l2cap::ConnectionParameterUpdateResponse {
+        self.response(expected_connection_length, true)
+    }
+
+    /// Returns the parameters for
+    /// [`connection_parameter_update_response`](crate::l2cap::Commands::connection_parameter_update_response)
+    /// that reject the requested connection parameters. The connection keeps its current
+    /// parameters.
+    pub fn reject(&self) -> crate::l2cap::ConnectionParameterUpdateResponse {
+        // The controller ignores the expected connection length of a rejection, and an empty range
+        // is always valid.
+        let expected_connection_length = hci::types::ExpectedConnectionLength::new(
+            Duration::from_secs(0),
+            Duration::from_secs(0),
+        )
+        .unwrap();
+        self.response(expected_connection_length, false)
+    }
+
+    fn response(
+        &self,
+        expected_connection_length: hci::types::ExpectedConnectionLength,
+        accepted: bool,
+    ) -> crate::l2cap::ConnectionParameterUpdateResponse {
+        crate::l2cap::ConnectionParameterUpdateResponse {
+            conn_handle: self.conn_handle,
+            conn_interval: self.conn_interval,
+            expected_connection_length_range: expected_connection_length,
+            identifier: self.identifier,
+            accepted,
+        }
+    }
+}
+
+#[cfg(feature = "l2cap-events")]
 fn to_l2cap_connection_update_request(
     buffer: &[u8],
 ) -> Result<L2CapConnectionUpdateRequest, hci::event::Error<BlueNRGError>> {
-    require_len!(buffer, 16);
+    require_fixed_len!(buffer, 16);
     require_l2cap_event_data_len!(buffer, 11);
     require_l2cap_len!(LittleEndian::read_u16(&buffer[6..]), 8);
 
@@ -1246,6 +2203,10 @@ pub struct GapPairingComplete {
 
     /// Reason the pairing is complete.
     pub status: GapPairingStatus,
+
+    /// Reason the pairing failed. Only newer firmware reports the reason, so this is `None` for
+    /// older firmware, and also when the firmware reports that there was no failure.
+    pub reason: Option<PairingFailureReason>,
 }
 
 /// Reasons the [GAP Pairing Complete](BlueNRGEvent::GapPairingComplete) event was generated.
@@ -1273,13 +2234,142 @@ impl TryFrom<u8> for GapPairingStatus {
     }
 }
 
+/// Reasons a pairing procedure failed, as reported by the [GAP Pairing
+/// Complete](BlueNRGEvent::GapPairingComplete) event on firmware that includes the reason.
+///
+/// See the Bluetooth specification, v5.0, Vol 3, Part H, Section 3.5.5.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PairingFailureReason {
+    /// The user input of the passkey failed, for example, the user cancelled the operation.
+    PasskeyEntryFailed,
+    /// The out-of-band data is not available.
+    OobNotAvailable,
+    /// The pairing procedure cannot be performed as authentication requirements cannot be met due
+    /// to IO capabilities of one or both devices.
+    AuthenticationRequirements,
+    /// The confirm value does not match the calculated compare value.
+    ConfirmValueFailed,
+    /// Pairing is not supported by the device.
+    PairingNotSupported,
+    /// The resultant encryption key size is insufficient for the security requirements of this
+    /// device.
+    EncryptionKeySize,
+    /// The SMP command received is not supported on this device.
+    CommandNotSupported,
+    /// Pairing failed due to an unspecified reason.
+    UnspecifiedReason,
+    /// Pairing or authentication procedure is disallowed because too little time has elapsed since
+    /// the last pairing request or security request.
+    RepeatedAttempts,
+    /// The command length is invalid or a parameter is outside of the specified range.
+    InvalidParameters,
+    /// The DHKey Check value received doesn't match the one calculated by the local device.
+    DhKeyCheckFailed,
+    /// The confirm values in the numeric comparison protocol do not match.
+    NumericComparisonFailed,
+    /// The pairing over the LE transport failed due to a pairing request sent over the BR/EDR
+    /// transport in process.
+    BrEdrPairingInProgress,
+    /// The BR/EDR Link Key generated on the BR/EDR transport cannot be used to derive and
+    /// distribute keys for the LE transport.
+    CrossTransportKeyDerivationNotAllowed,
+}
+
+impl TryFrom<u8> for PairingFailureReason {
+    type Error = BlueNRGError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0x01 => Ok(PairingFailureReason::PasskeyEntryFailed),
+            0x02 => Ok(PairingFailureReason::OobNotAvailable),
+            0x03 => Ok(PairingFailureReason::AuthenticationRequirements),
+            0x04 => Ok(PairingFailureReason::ConfirmValueFailed),
+            0x05 => Ok(PairingFailureReason::PairingNotSupported),
+            0x06 => Ok(PairingFailureReason::EncryptionKeySize),
+            0x07 => Ok(PairingFailureReason::CommandNotSupported),
+            0x08 => Ok(PairingFailureReason::UnspecifiedReason),
+            0x09 => Ok(PairingFailureReason::RepeatedAttempts),
+            0x0A => Ok(PairingFailureReason::InvalidParameters),
+            0x0B => Ok(PairingFailureReason::DhKeyCheckFailed),
+            0x0C => Ok(PairingFailureReason::NumericComparisonFailed),
+            0x0D => Ok(PairingFailureReason::BrEdrPairingInProgress),
+            0x0E => Ok(PairingFailureReason::CrossTransportKeyDerivationNotAllowed),
+            _ => Err(BlueNRGError::BadPairingFailureReason(value)),
+        }
+    }
+}
+
 fn to_gap_pairing_complete(
     buffer: &[u8],
 ) -> Result<GapPairingComplete, hci::event::Error<BlueNRGError>> {
-    require_len!(buffer, 5);
+    // Older firmware sends 5 bytes; newer firmware appends the failure reason. BlueNRG-LP firmware
+    // always includes the reason.
+    #[cfg(feature = "bluenrg-lp")]
+    require_fixed_len!(buffer, 6);
+
+    let reason = match buffer.len() {
+        5 => None,
+        6 if buffer[5] == 0 => None,
+        6 => Some(buffer[5].try_into().map_err(hci::event::Error::Vendor)?),
+        // Padding after the reason byte is ignored.
+        len if cfg!(feature = "tolerant-decode") && len > 6 => match buffer[5] {
+            0 => None,
+            reason => Some(reason.try_into().map_err(hci::event::Error::Vendor)?),
+        },
+        _ => return Err(hci::event::Error::BadLength(buffer.len(), 5)),
+    };
+
     Ok(GapPairingComplete {
         conn_handle: ConnectionHandle(LittleEndian::read_u16(&buffer[2..])),
         status: buffer[4].try_into().map_err(hci::event::Error::Vendor)?,
+        reason,
+    })
+}
+
+/// Parameters for the [GAP Pass Key Request](BlueNRGEvent::GapPassKeyRequest) event.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct GapPassKeyRequest {
+    /// Connection handle for which the pass key is required.
+    pub conn_handle: ConnectionHandle,
+}
+
+/// Parameters for the [GAP Authorization Request](BlueNRGEvent::GapAuthorizationRequest) event.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct GapAuthorizationRequest {
+    /// Connection handle for which authorization is required.
+    pub conn_handle: ConnectionHandle,
+}
+
+/// Parameters for the [GAP Address Not Resolved](BlueNRGEvent::GapAddressNotResolved) event.
+#[cfg(feature = "ms")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct GapAddressNotResolved {
+    /// Connection handle of the peer whose address could not be resolved.
+    pub conn_handle: ConnectionHandle,
+}
+
+/// Parameters for the [GAP Numeric Comparison Value](BlueNRGEvent::GapNumericComparisonValue)
+/// event.
+#[derive(Copy, Clone, Debug)]
+pub struct GapNumericComparisonValue {
+    /// Connection handle on which the pairing is taking place.
+    pub conn_handle: ConnectionHandle,
+
+    /// Value to display to the user (0 - 999999).
+    pub numeric_value: u32,
+}
+
+fn to_gap_numeric_comparison_value(
+    buffer: &[u8],
+) -> Result<GapNumericComparisonValue, hci::event::Error<BlueNRGError>> {
+    require_fixed_len!(buffer, 8);
+
+    Ok(GapNumericComparisonValue {
+        conn_handle: ConnectionHandle(LittleEndian::read_u16(&buffer[2..])),
+        numeric_value: LittleEndian::read_u32(&buffer[4..]),
     })
 }
 
@@ -1290,7 +2380,7 @@ fn to_conn_handle(buffer: &[u8]) -> Result<ConnectionHandle, hci::event::Error<B
 
 /// The event is given by the GAP layer to the upper layers when a device is discovered during
 /// scanning as a consequence of one of the GAP procedures started by the upper layers.
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone)]
 pub struct GapDeviceFound {
     /// Type of event
     pub event: GapDeviceFoundEvent,
@@ -1302,21 +2392,243 @@ pub struct GapDeviceFound {
     data_len: usize,
 
     // Advertising or scan response data.
-    data_buf: [u8; 31],
+    data_buf: Payload<[u8; MAX_ADVERTISING_DATA_LEN]>,
 
     /// Received signal strength indicator (range: -127 - 20).
     pub rssi: Option<i8>,
 }
 
+// Legacy advertising and scan response data is limited to 31 bytes. With extended advertising, the
+// device found event can carry up to 255 bytes.
+#[cfg(not(feature = "extended-advertising"))]
+const MAX_ADVERTISING_DATA_LEN: usize = 31;
+#[cfg(feature = "extended-advertising")]
+const MAX_ADVERTISING_DATA_LEN: usize = 255;
+
+impl Debug for GapDeviceFound {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(
+            f,
+            "{{.event = {:?}, .bdaddr = {:?}, .data = {:?}, .rssi = {:?}}}",
+            self.event,
+            self.bdaddr,
+            first_16(self.data()),
+            self.rssi
+        )
+    }
+}
+
 impl GapDeviceFound {
+    /// Returns a device found event with the given advertising or scan response data, for
+    /// example to test code that handles scan results without a controller.
+    ///
+    /// Returns `None` if the data is longer than the event can hold.
+    pub fn new(
+        event: GapDeviceFoundEvent,
+        bdaddr: BdAddrType,
+        data: &[u8],
+        rssi: Option<i8>,
+    ) -> Option<GapDeviceFound> {
+        if data.len() > MAX_ADVERTISING_DATA_LEN {
+            return None;
+        }
+
+        let mut data_buf = [0; MAX_ADVERTISING_DATA_LEN];
+        data_buf[..data.len()].copy_from_slice(data);
+        Some(GapDeviceFound {
+            event,
+            bdaddr,
+            data_len: data.len(),
+            data_buf: Payload::new(data_buf),
+            rssi,
+        })
+    }
+
     /// Returns the valid scan response data.
     pub fn data(&self) -> &[u8] {
         &self.data_buf[..self.data_len]
     }
+
+    /// Returns an iterator over the AD structures in the advertising or scan response data.
+    ///
+    /// Iteration stops at the first zero-length structure (the remaining data is padding) or at a
+    /// structure that is truncated by the end of the data.
+    pub fn ad_structures(&self) -> AdStructureIterator {
+        AdStructureIterator {
+            data: self.data(),
+            index: 0,
+        }
+    }
+
+    /// Returns the company identifier and payload of the manufacturer specific data (AD type 0xFF),
+    /// if the advertising or scan response data has any.
+    ///
+    /// A manufacturer specific data structure too short to hold the 2-byte company identifier is
+    /// skipped.
+    pub fn manufacturer_data(&self) -> Option<(u16, &[u8])> {
+        self.ad_structures()
+            .find(|ad| {
+                ad.ad_type == crate::gap::AdvertisingDataType::ManufacturerSpecificData as u8
+                    && ad.data.len() >= 2
+            })
+            .map(|ad| (LittleEndian::read_u16(ad.data), &ad.data[2..]))
+    }
+
+    /// Returns the appearance of the device (AD type 0x19), if the advertising or scan response
+    /// data includes it.
+    pub fn appearance(&self) -> Option<crate::gap::Appearance> {
+        self.ad_structures()
+            .find(|ad| {
+                ad.ad_type == crate::gap::AdvertisingDataType::Appearance as u8
+                    && ad.data.len() == 2
+            })
+            .map(|ad| LittleEndian::read_u16(ad.data).into())
+    }
+
+    /// Returns the local name of the device, if the advertising or scan response data includes
+    /// it. The complete local name (AD type 0x09) is preferred over the shortened one (0x08).
+    ///
+    /// Returns `None` if the name is not valid UTF-8.
+    pub fn local_name(&self) -> Option<&str> {
+        use crate::gap::AdvertisingDataType;
+
+        let mut shortened = None;
+        for ad in self.ad_structures() {
+            if ad.ad_type == AdvertisingDataType::CompleteLocalName as u8 {
+                return core::str::from_utf8(ad.data).ok();
+            }
+            if ad.ad_type == AdvertisingDataType::ShortenedLocalName as u8 && shortened.is_none() {
+                shortened = Some(ad.data);
+            }
+        }
+
+        shortened.and_then(|name| core::str::from_utf8(name).ok())
+    }
+
+    /// Returns an iterator over the service UUIDs listed in the advertising or scan response data,
+    /// from both the complete and incomplete lists of 16-bit, 32-bit, and 128-bit UUIDs.
+    ///
+    /// 32-bit UUIDs are returned in their [128-bit form](crate::gatt::Uuid::from_uuid32).
+    pub fn service_uuids(&self) -> ServiceUuidIterator {
+        ServiceUuidIterator {
+            ad_structures: self.ad_structures(),
+            uuid_len: 0,
+            data: &[],
+        }
+    }
+
+    /// Returns a key identifying the peer device: the address type (0 for public, 1 for random)
+    /// and the address bytes.
+    ///
+    /// The RSSI and data may change from one event to the next, but the key does not, so it can be
+    /// used to deduplicate devices found during scanning.
+    pub fn device_key(&self) -> (u8, [u8; 6]) {
+        match self.bdaddr {
+            BdAddrType::Public(addr) => (0, addr.0),
+            BdAddrType::Random(addr) => (1, addr.0),
+        }
+    }
 }
 
 pub use hci::event::AdvertisementEvent as GapDeviceFoundEvent;
 
+/// One AD structure from the advertising or scan response data of a [GAP Device
+/// Found](GapDeviceFound) event.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AdStructure<'a> {
+    /// The AD type, as assigned by the Bluetooth SIG.
+    pub ad_type: u8,
+
+    /// The AD data, not including the length or type.
+    pub data: &'a [u8],
+}
+
+/// Iterator over the [AD structures](AdStructure) in a [GAP Device Found](GapDeviceFound) event.
+pub struct AdStructureIterator<'a> {
+    data: &'a [u8],
+    index: usize,
+}
+
+impl<'a> Iterator for AdStructureIterator<'a> {
+    type Item = AdStructure<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.data.len() {
+            return None;
+        }
+
+        // The length byte covers the AD type and the AD data.
+        let len = self.data[self.index] as usize;
+        let type_index = self.index + 1;
+        let end = type_index + len;
+        if len == 0 || end > self.data.len() {
+            self.index = self.data.len();
+            return None;
+        }
+
+        self.index = end;
+        Some(AdStructure {
+            ad_type: self.data[type_index],
+            data: &self.data[type_index + 1..end],
+        })
+    }
+}
+
+/// Iterator over the service UUIDs in a [GAP Device Found](GapDeviceFound) event. Returned by
+/// [`GapDeviceFound::service_uuids`].
+pub struct ServiceUuidIterator<'a> {
+    ad_structures: AdStructureIterator<'a>,
+    // Length of each UUID in `data`.
+    uuid_len: usize,
+    // UUIDs not yet returned from the current AD structure.
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for ServiceUuidIterator<'a> {
+    type Item = crate::gatt::Uuid;
+    fn next(&mut self) -> Option<Self::Item> {
+        use crate::gap::AdvertisingDataType;
+        use crate::gatt::Uuid;
+
+        // Skip AD structures that are not UUID lists, and any incomplete UUID at the end of a
+        // list.
+        while self.uuid_len == 0 || self.data.len() < self.uuid_len {
+            let ad = self.ad_structures.next()?;
+            self.uuid_len = match ad.ad_type {
+                t if t == AdvertisingDataType::Uuid16 as u8
+                    || t == AdvertisingDataType::UuidCompleteList16 as u8 =>
+                {
+                    2
+                }
+                t if t == AdvertisingDataType::Uuid32 as u8
+                    || t == AdvertisingDataType::UuidCompleteList32 as u8 =>
+                {
+                    4
+                }
+                t if t == AdvertisingDataType::Uuid128 as u8
+                    || t == AdvertisingDataType::UuidCompleteList128 as u8 =>
+                {
+                    16
+                }
+                _ => 0,
+            };
+            self.data = ad.data;
+        }
+
+        let (uuid, rest) = self.data.split_at(self.uuid_len);
+        self.data = rest;
+        Some(match self.uuid_len {
+            2 => Uuid::Uuid16(LittleEndian::read_u16(uuid)),
+            4 => Uuid::from_uuid32(LittleEndian::read_u32(uuid)),
+            _ => {
+                let mut bytes = [0; 16];
+                bytes.copy_from_slice(uuid);
+                Uuid::Uuid128(bytes)
+            }
+        })
+    }
+}
+
+#[cfg(all(feature = "gap-central-events", not(feature = "bluenrg-lp")))]
 fn to_gap_device_found(buffer: &[u8]) -> Result<GapDeviceFound, hci::event::Error<BlueNRGError>> {
     const RSSI_UNAVAILABLE: i8 = 127;
 
@@ -1324,8 +2636,14 @@ fn to_gap_device_found(buffer: &[u8]) -> Result<GapDeviceFound, hci::event::Erro
 
     let data_len = buffer[10] as usize;
     require_len!(buffer, 12 + data_len);
+    if data_len > MAX_ADVERTISING_DATA_LEN {
+        return Err(hci::event::Error::BadLength(
+            buffer.len(),
+            12 + MAX_ADVERTISING_DATA_LEN,
+        ));
+    }
 
-    let rssi = unsafe { mem::transmute::<u8, i8>(buffer[buffer.len() - 1]) };
+    let rssi = buffer[buffer.len() - 1] as i8;
 
     let mut addr = BdAddr([0; 6]);
     addr.0.copy_from_slice(&buffer[4..10]);
@@ -1340,7 +2658,7 @@ fn to_gap_device_found(buffer: &[u8]) -> Result<GapDeviceFound, hci::event::Erro
         bdaddr: hci::to_bd_addr_type(buffer[3], addr)
             .map_err(|e| hci::event::Error::Vendor(BlueNRGError::BadGapBdAddrType(e.0)))?,
         data_len,
-        data_buf: [0; 31],
+        data_buf: Payload::new([0; MAX_ADVERTISING_DATA_LEN]),
         rssi: if rssi == RSSI_UNAVAILABLE {
             None
         } else {
@@ -1354,7 +2672,7 @@ fn to_gap_device_found(buffer: &[u8]) -> Result<GapDeviceFound, hci::event::Erro
 
 /// This event is sent by the GAP to the upper layers when a procedure previously started has been
 /// terminated by the upper layer or has completed for any other reason
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub struct GapProcedureComplete {
     /// Type of procedure that completed
     pub procedure: GapProcedure,
@@ -1362,41 +2680,65 @@ pub struct GapProcedureComplete {
     pub status: GapProcedureStatus,
 }
 
+impl GapProcedureComplete {
+    /// Returns the reconnection address if the procedure was the [General Connection
+    /// Establishment](GapProcedure::GeneralConnectionEstablishment) procedure.
+    pub fn reconnection_address(&self) -> Option<&BdAddr> {
+        match self.procedure {
+            GapProcedure::GeneralConnectionEstablishment(ref addr) => Some(addr),
+            _ => None,
+        }
+    }
+}
+
 /// Maximum length of the name returned in the [`NameDiscovery`](GapProcedure::NameDiscovery)
 /// procedure.
 pub const MAX_NAME_LEN: usize = 248;
 
-/// Newtype for the name buffer returned after successful
-/// [`NameDiscovery`](GapProcedure::NameDiscovery).
-#[derive(Copy, Clone)]
-pub struct NameBuffer(pub [u8; MAX_NAME_LEN]);
+/// Buffer for the name returned after successful [`NameDiscovery`](GapProcedure::NameDiscovery).
+///
+/// Dereferences to an array with enough space for the longest name. With the `alloc` feature, the
+/// array is boxed.
+#[derive(Clone)]
+pub struct NameBuffer(Payload<[u8; MAX_NAME_LEN]>);
+
+impl NameBuffer {
+    /// Returns a buffer holding the given bytes.
+    pub fn new(bytes: [u8; MAX_NAME_LEN]) -> NameBuffer {
+        NameBuffer(Payload::new(bytes))
+    }
+}
+
+impl core::ops::Deref for NameBuffer {
+    type Target = [u8; MAX_NAME_LEN];
+
+    fn deref(&self) -> &[u8; MAX_NAME_LEN] {
+        &self.0
+    }
+}
+
+impl core::ops::DerefMut for NameBuffer {
+    fn deref_mut(&mut self) -> &mut [u8; MAX_NAME_LEN] {
+        &mut self.0
+    }
+}
 
 impl Debug for NameBuffer {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        first_16(&self.0).fmt(f)
+        first_16(&self[..]).fmt(f)
     }
 }
 
 impl PartialEq<NameBuffer> for NameBuffer {
     fn eq(&self, other: &Self) -> bool {
-        if self.0.len() != other.0.len() {
-            return false;
-        }
-
-        for (a, b) in self.0.iter().zip(other.0.iter()) {
-            if a != b {
-                return false;
-            }
-        }
-
-        true
+        self[..] == other[..]
     }
 }
 
 /// Procedures whose completion may be reported by
 /// [`GapProcedureComplete`](BlueNRGEvent::GapProcedureComplete).
 #[allow(clippy::large_enum_variant)]
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum GapProcedure {
     /// See Vol 3, Part C, section 9.2.5.
     LimitedDiscovery,
@@ -1413,8 +2755,20 @@ pub enum GapProcedure {
     SelectiveConnectionEstablishment,
     /// See Vol 3, Part C, section 9.3.8.
     DirectConnectionEstablishment,
+    /// More than one procedure completed at once, which can happen when
+    /// [`terminate_procedure`](crate::gap::Commands::terminate_procedure) races with the natural end
+    /// of a procedure. Contains the procedures. The name and reconnection address that a single
+    /// [NameDiscovery](GapProcedure::NameDiscovery) or
+    /// [GeneralConnectionEstablishment](GapProcedure::GeneralConnectionEstablishment) completion
+    /// carries are not decoded.
+    Multiple(GapProcedureFlags),
 }
 
+/// Set of procedures reported by a [GAP Procedure Complete](BlueNRGEvent::GapProcedureComplete)
+/// event, as [multiple](GapProcedure::Multiple) procedures. The event uses the same bits as the
+/// [`terminate_procedure`](crate::gap::Commands::terminate_procedure) command.
+pub type GapProcedureFlags = crate::gap::Procedure;
+
 /// Possible results of a [GAP procedure](BlueNRGEvent::GapProcedureComplete).
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum GapProcedureStatus {
@@ -1424,21 +2778,55 @@ pub enum GapProcedureStatus {
     Failed,
     /// Procedure failed due to authentication requirements.
     AuthFailure,
+    /// Procedure failed with a status code that is not listed above. Includes the status code.
+    Other(u8),
+}
+
+impl GapProcedureStatus {
+    /// Returns the status for the status code reported by the controller. Unrecognized codes are
+    /// reported as [Other](GapProcedureStatus::Other).
+    pub fn from_raw(value: u8) -> GapProcedureStatus {
+        match value {
+            0x00 => GapProcedureStatus::Success,
+            0x41 => GapProcedureStatus::Failed,
+            0x05 => GapProcedureStatus::AuthFailure,
+            _ => GapProcedureStatus::Other(value),
+        }
+    }
+
+    /// Returns the status code reported by the controller.
+    pub fn raw(&self) -> u8 {
+        match *self {
+            GapProcedureStatus::Success => 0x00,
+            GapProcedureStatus::Failed => 0x41,
+            GapProcedureStatus::AuthFailure => 0x05,
+            GapProcedureStatus::Other(value) => value,
+        }
+    }
+
+    /// Returns the status if it is one of the listed status codes.
+    ///
+    /// # Errors
+    ///
+    /// - [BadGapProcedureStatus](BlueNRGError::BadGapProcedureStatus) if the status is
+    ///   [Other](GapProcedureStatus::Other). Includes the status code.
+    pub fn strict(self) -> Result<GapProcedureStatus, BlueNRGError> {
+        match self {
+            GapProcedureStatus::Other(value) => Err(BlueNRGError::BadGapProcedureStatus(value)),
+            status => Ok(status),
+        }
+    }
 }
 
 impl TryFrom<u8> for GapProcedureStatus {
     type Error = BlueNRGError;
 
     fn try_from(value: u8) -> Result<Self, Self::Error> {
-        match value {
-            0x00 => Ok(GapProcedureStatus::Success),
-            0x41 => Ok(GapProcedureStatus::Failed),
-            0x05 => Ok(GapProcedureStatus::AuthFailure),
-            _ => Err(BlueNRGError::BadGapProcedureStatus(value)),
-        }
+        GapProcedureStatus::from_raw(value).strict()
     }
 }
 
+#[cfg(feature = "gap-central-events")]
 fn to_gap_procedure_complete(
     buffer: &[u8],
 ) -> Result<GapProcedureComplete, hci::event::Error<BlueNRGError>> {
@@ -1450,36 +2838,42 @@ fn to_gap_procedure_complete(
         0x04 => {
             require_len_at_least!(buffer, 5);
             let name_len = buffer.len() - 4;
-            let mut name = NameBuffer([0; MAX_NAME_LEN]);
-            name.0[..name_len].copy_from_slice(&buffer[4..]);
+            if name_len > MAX_NAME_LEN {
+                return Err(hci::event::Error::BadLength(buffer.len(), 4 + MAX_NAME_LEN));
+            }
+            let mut name = [0; MAX_NAME_LEN];
+            name[..name_len].copy_from_slice(&buffer[4..]);
 
-            GapProcedure::NameDiscovery(name_len, name)
+            GapProcedure::NameDiscovery(name_len, NameBuffer::new(name))
         }
         0x08 => GapProcedure::AutoConnectionEstablishment,
         0x10 => {
-            require_len!(buffer, 10);
+            require_fixed_len!(buffer, 10);
             let mut addr = BdAddr([0; 6]);
             addr.0.copy_from_slice(&buffer[4..10]);
             GapProcedure::GeneralConnectionEstablishment(addr)
         }
         0x20 => GapProcedure::SelectiveConnectionEstablishment,
         0x40 => GapProcedure::DirectConnectionEstablishment,
-        _ => {
+        bits if bits.count_ones() > 1 => {
+            GapProcedure::Multiple(GapProcedureFlags::from_bits_truncate(bits))
+        }
+        bits => {
             return Err(hci::event::Error::Vendor(BlueNRGError::BadGapProcedure(
-                buffer[2],
+                bits,
             )));
         }
     };
 
     Ok(GapProcedureComplete {
         procedure,
-        status: buffer[3].try_into().map_err(hci::event::Error::Vendor)?,
+        status: GapProcedureStatus::from_raw(buffer[3]),
     })
 }
 
-#[cfg(not(feature = "ms"))]
+#[cfg(all(not(feature = "ms"), feature = "gap-central-events"))]
 fn to_gap_reconnection_address(buffer: &[u8]) -> Result<BdAddr, hci::event::Error<BlueNRGError>> {
-    require_len!(buffer, 8);
+    require_fixed_len!(buffer, 8);
     let mut addr = BdAddr([0; 6]);
     addr.0.copy_from_slice(&buffer[2..]);
     Ok(addr)
@@ -1492,28 +2886,26 @@ fn to_gap_reconnection_address(buffer: &[u8]) -> Result<BdAddr, hci::event::Erro
 /// - write characteristic value
 /// - write long characteristic value
 /// - reliable write
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 pub struct GattAttributeModified {
     /// The connection handle which modified the attribute
     pub conn_handle: ConnectionHandle,
     ///  Handle of the attribute that was modified
     pub attr_handle: AttributeHandle,
 
-    /// Offset of the reported value inside the attribute.
-    #[cfg(feature = "ms")]
-    pub offset: usize,
+    /// Offset of the reported value inside the attribute. Only BlueNRG-MS firmware reports the
+    /// offset; it is `None` for other firmware, which always reports the value from offset 0.
+    pub offset: Option<usize>,
 
     /// If the entire value of the attribute does not fit inside a single GattAttributeModified
     /// event, this is true to notify that other GattAttributeModified events will follow to report
-    /// the remaining value.
-    #[cfg(feature = "ms")]
-    pub continued: bool,
+    /// the remaining value. Only BlueNRG-MS firmware reports this; it is `None` for other firmware.
+    pub continued: Option<bool>,
 
     /// Number of valid bytes in |data|.
     data_len: usize,
-    /// The new attribute value, starting from the given offset. If compiling with "ms" support, the
-    /// offset is 0.
-    data_buf: [u8; MAX_ATTRIBUTE_LEN],
+    /// The new attribute value, starting from the given offset.
+    data_buf: Payload<[u8; MAX_ATTRIBUTE_LEN]>,
 }
 
 impl GattAttributeModified {
@@ -1522,23 +2914,84 @@ impl GattAttributeModified {
     pub fn data(&self) -> &[u8] {
         &self.data_buf[..self.data_len]
     }
+
+    /// Decodes the attribute data as a `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from [`GattValue::decode`](crate::value::GattValue::decode) if the
+    /// attribute data is not a valid `T`.
+    pub fn decode<'a, T: crate::value::GattValue<'a>>(
+        &'a self,
+    ) -> Result<T, crate::value::ValueError> {
+        T::decode(self.data())
+    }
+
+    /// Interprets the modification as a write to a Client Characteristic Configuration descriptor
+    /// (CCCD). Returns `None` if the data is not a complete CCCD value: 2 bytes, written from
+    /// offset 0.
+    ///
+    /// The event does not say whether the attribute is a CCCD, so the caller must still check that
+    /// [`attr_handle`](GattAttributeModified::attr_handle) is the handle of the CCCD it is
+    /// interested in.
+    pub fn as_cccd_write(&self) -> Option<CccdValue> {
+        if self.offset.unwrap_or(0) != 0 {
+            return None;
+        }
+
+        CccdValue::from_bytes(self.data()).ok()
+    }
+}
+
+/// Value of a Client Characteristic Configuration descriptor (CCCD), which a client writes to
+/// enable notifications or indications of a characteristic. See the Bluetooth specification, v4.1,
+/// Vol 3, Part G, Section 3.3.3.3.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CccdValue(pub u16);
+
+impl CccdValue {
+    /// Deserializes a CCCD value from the little-endian bytes written by the client.
+    ///
+    /// # Errors
+    ///
+    /// - [BadCccdLength] if the buffer is not 2 bytes long. Includes the length.
+    pub fn from_bytes(bytes: &[u8]) -> Result<CccdValue, BadCccdLength> {
+        if bytes.len() != 2 {
+            return Err(BadCccdLength(bytes.len()));
+        }
+
+        Ok(CccdValue(LittleEndian::read_u16(bytes)))
+    }
+
+    /// Returns true if the client enabled notifications.
+    pub fn notifications_enabled(&self) -> bool {
+        self.0 & 0x0001 != 0
+    }
+
+    /// Returns true if the client enabled indications.
+    pub fn indications_enabled(&self) -> bool {
+        self.0 & 0x0002 != 0
+    }
 }
 
+/// Error type for [`CccdValue::from_bytes`]. Includes the length of the buffer, which must be 2.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BadCccdLength(pub usize);
+
 /// Newtype for an attribute handle. These handles are IDs, not general integers, and should not be
 /// manipulated as such.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct AttributeHandle(pub u16);
 
 // Defines the maximum length of a ATT attribute value field. This is determined by the max packet
-// size (255) less the minimum number of bytes used by other fields in any packet.
-const MAX_ATTRIBUTE_LEN: usize = 248;
+// size less the minimum number of bytes used by other fields in any packet.
+const MAX_ATTRIBUTE_LEN: usize = MAX_EVENT_LEN - 7;
 
 impl Debug for GattAttributeModified {
-    #[cfg(feature = "ms")]
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
         write!(
             f,
-            "{{conn_handle: {:?}, attr_handle: {:?}, offset: {}, continued: {}, data: {:?}}}",
+            "{{conn_handle: {:?}, attr_handle: {:?}, offset: {:?}, continued: {:?}, data: {:?}}}",
             self.conn_handle,
             self.attr_handle,
             self.offset,
@@ -1546,62 +2999,74 @@ impl Debug for GattAttributeModified {
             first_16(self.data()),
         )
     }
-
-    #[cfg(not(feature = "ms"))]
-    fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        write!(
-            f,
-            "{{conn_handle: {:?}, attr_handle: {:?}, data: {:?}}}",
-            self.conn_handle,
-            self.attr_handle,
-            first_16(self.data()),
-        )
-    }
 }
 
-#[cfg(feature = "ms")]
+#[cfg(not(feature = "bluenrg-lp"))]
 fn to_gatt_attribute_modified(
     buffer: &[u8],
 ) -> Result<GattAttributeModified, hci::event::Error<BlueNRGError>> {
-    require_len_at_least!(buffer, 9);
+    // BlueNRG-MS firmware inserts a 2-byte offset field between the data length and the data.
+    let data_start = if cfg!(feature = "ms") { 9 } else { 7 };
+    require_len_at_least!(buffer, data_start);
 
-    let data_len = buffer[6] as usize;
-    require_len!(buffer, 9 + data_len);
+    let data_len = to_data_len(buffer, 6, data_start)?;
 
     let mut data = [0; MAX_ATTRIBUTE_LEN];
-    data[..data_len].copy_from_slice(&buffer[9..]);
+    data[..data_len].copy_from_slice(&buffer[data_start..]);
+
+    let (offset, continued) = if cfg!(feature = "ms") {
+        let offset_field = LittleEndian::read_u16(&buffer[7..]);
+        (
+            Some((offset_field & 0x7FFF) as usize),
+            Some((offset_field & 0x8000) > 0),
+        )
+    } else {
+        (None, None)
+    };
 
-    let offset_field = LittleEndian::read_u16(&buffer[7..]);
     Ok(GattAttributeModified {
         conn_handle: ConnectionHandle(LittleEndian::read_u16(&buffer[2..])),
         attr_handle: AttributeHandle(LittleEndian::read_u16(&buffer[4..])),
-        offset: (offset_field & 0x7FFF) as usize,
-        continued: (offset_field & 0x8000) > 0,
+        offset,
+        continued,
         data_len,
-        data_buf: data,
+        data_buf: Payload::new(data),
     })
 }
 
-#[cfg(not(feature = "ms"))]
-fn to_gatt_attribute_modified(
+// BlueNRG-LP firmware moves the offset field before the data length, and widens the data length to
+// 16 bits.
+#[cfg(feature = "bluenrg-lp")]
+fn to_lp_gatt_attribute_modified(
     buffer: &[u8],
 ) -> Result<GattAttributeModified, hci::event::Error<BlueNRGError>> {
-    require_len_at_least!(buffer, 7);
+    require_len_at_least!(buffer, 10);
 
-    let data_len = buffer[6] as usize;
-    require_len!(buffer, 7 + data_len);
+    let data_len = LittleEndian::read_u16(&buffer[8..]) as usize;
+    require_len!(buffer, 10 + data_len);
 
     let mut data = [0; MAX_ATTRIBUTE_LEN];
-    data[..data_len].copy_from_slice(&buffer[7..]);
+    data[..data_len].copy_from_slice(&buffer[10..]);
 
+    let offset_field = LittleEndian::read_u16(&buffer[6..]);
     Ok(GattAttributeModified {
         conn_handle: ConnectionHandle(LittleEndian::read_u16(&buffer[2..])),
         attr_handle: AttributeHandle(LittleEndian::read_u16(&buffer[4..])),
+        offset: Some((offset_field & 0x7FFF) as usize),
+        continued: Some((offset_field & 0x8000) > 0),
         data_len,
-        data_buf: data,
+        data_buf: Payload::new(data),
     })
 }
 
+/// Parameters for the [GATT Procedure Timeout](BlueNRGEvent::GattProcedureTimeout) event.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct GattProcedureTimeout {
+    /// The connection handle on which the procedure timed out.
+    pub conn_handle: ConnectionHandle,
+}
+
 /// This event is generated in response to an Exchange MTU request.
 #[derive(Copy, Clone, Debug)]
 pub struct AttExchangeMtuResponse {
@@ -1612,10 +3077,11 @@ pub struct AttExchangeMtuResponse {
     pub server_rx_mtu: usize,
 }
 
+#[cfg(feature = "att-client-events")]
 fn to_att_exchange_mtu_resp(
     buffer: &[u8],
 ) -> Result<AttExchangeMtuResponse, hci::event::Error<BlueNRGError>> {
-    require_len!(buffer, 7);
+    require_fixed_len!(buffer, 7);
     Ok(AttExchangeMtuResponse {
         conn_handle: ConnectionHandle(LittleEndian::read_u16(&buffer[2..])),
         server_rx_mtu: LittleEndian::read_u16(&buffer[5..]) as usize,
@@ -1624,7 +3090,7 @@ fn to_att_exchange_mtu_resp(
 
 /// This event is generated in response to a Find Information Request. See Find Information Response
 /// in Bluetooth Core v4.0 spec.
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub struct AttFindInformationResponse {
     /// The connection handle related to the response
     pub conn_handle: ConnectionHandle,
@@ -1632,7 +3098,7 @@ pub struct AttFindInformationResponse {
     /// split across response packets; this also implies that a handleUUID pair shall fit into a
     /// single response packet. The handle-UUID pairs shall be returned in ascending order of
     /// attribute handles.
-    handle_uuid_pairs: HandleUuidPairs,
+    handle_uuid_pairs: Payload<HandleUuidPairs>,
 }
 
 impl AttFindInformationResponse {
@@ -1641,7 +3107,7 @@ impl AttFindInformationResponse {
     /// single response packet. The handle-UUID pairs shall be returned in ascending order of
     /// attribute handles.
     pub fn handle_uuid_pair_iter(&self) -> HandleUuidPairIterator {
-        match self.handle_uuid_pairs {
+        match *self.handle_uuid_pairs {
             HandleUuidPairs::Format16(count, ref data) => {
                 HandleUuidPairIterator::Format16(HandleUuid16PairIterator {
                     data,
@@ -1658,16 +3124,66 @@ impl AttFindInformationResponse {
             }
         }
     }
+
+    /// Returns the number of handle-UUID pairs in the response.
+    pub fn len(&self) -> usize {
+        match *self.handle_uuid_pairs {
+            HandleUuidPairs::Format16(count, _) | HandleUuidPairs::Format128(count, _) => count,
+        }
+    }
+
+    /// Returns true if the response has no handle-UUID pairs.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the handle of the Client Characteristic Configuration descriptor (CCCD, UUID
+    /// 0x2902) in the response, if there is one.
+    ///
+    /// The CCCD always has a 16-bit UUID, so responses in the 128-bit format never contain one.
+    pub fn cccd_handle(&self) -> Option<AttributeHandle> {
+        match self.handle_uuid_pair_iter() {
+            HandleUuidPairIterator::Format16(mut iter) => iter
+                .find(|pair| {
+                    crate::gatt::Uuid::Uuid16(pair.uuid.0)
+                        == crate::uuids::descriptor::CLIENT_CHARACTERISTIC_CONFIGURATION
+                })
+                .map(|pair| pair.handle),
+            HandleUuidPairIterator::Format128(_) => None,
+        }
+    }
+
+    /// Appends the handle-UUID pairs to `out`, so they can be kept after the event is dropped.
+    /// Returns the number of pairs appended.
+    ///
+    /// # Errors
+    ///
+    /// - [Full](CollectError::Full) if `out` does not have room for all of the pairs. The pairs
+    ///   that fit are appended.
+    #[cfg(feature = "heapless")]
+    pub fn collect_into<const N: usize>(
+        &self,
+        out: &mut heapless::Vec<HandleUuidPair, N>,
+    ) -> Result<usize, CollectError> {
+        match self.handle_uuid_pair_iter() {
+            HandleUuidPairIterator::Format16(iter) => {
+                collect_owned(iter.map(|pair| Ok(HandleUuidPair::Format16(pair))), out)
+            }
+            HandleUuidPairIterator::Format128(iter) => {
+                collect_owned(iter.map(|pair| Ok(HandleUuidPair::Format128(pair))), out)
+            }
+        }
+    }
 }
 
-// Assuming a maximum HCI packet size of 255, these are the maximum number of handle-UUID pairs for
-// each format that can be in one packet.  Formats cannot be mixed in a single packet.
+// Given the maximum HCI packet size, these are the maximum number of handle-UUID pairs for each
+// format that can be in one packet.  Formats cannot be mixed in a single packet.
 //
 // Packets have 6 other bytes of data preceding the handle-UUID pairs.
 //
-// max = floor((255 - 6) / pair_length)
-const MAX_FORMAT16_PAIR_COUNT: usize = 62;
-const MAX_FORMAT128_PAIR_COUNT: usize = 13;
+// max = floor((MAX_EVENT_LEN - 6) / pair_length)
+const MAX_FORMAT16_PAIR_COUNT: usize = (MAX_EVENT_LEN - 6) / 4;
+const MAX_FORMAT128_PAIR_COUNT: usize = (MAX_EVENT_LEN - 6) / 18;
 
 /// One format of the handle-UUID pairs in the [`AttFindInformationResponse`] event. The UUIDs are
 /// 16 bits.
@@ -1679,6 +3195,13 @@ pub struct HandleUuid16Pair {
     pub uuid: Uuid16,
 }
 
+/// Shows the handle and the [UUID](Uuid16), as in "0x0003: Battery Level (0x2A19)".
+impl Display for HandleUuid16Pair {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "0x{:04X}: {}", self.handle.0, self.uuid)
+    }
+}
+
 /// One format of the handle-UUID pairs in the [`AttFindInformationResponse`] event. The UUIDs are
 /// 128 bits.
 #[derive(Copy, Clone, Debug)]
@@ -1689,10 +3212,43 @@ pub struct HandleUuid128Pair {
     pub uuid: Uuid128,
 }
 
+/// A handle-UUID pair in either format, as copied out of the [`AttFindInformationResponse`] event
+/// by [`collect_into`](AttFindInformationResponse::collect_into).
+#[derive(Copy, Clone, Debug)]
+pub enum HandleUuidPair {
+    /// The pair has a 16-bit UUID.
+    Format16(HandleUuid16Pair),
+    /// The pair has a 128-bit UUID.
+    Format128(HandleUuid128Pair),
+}
+
 /// Newtype for the 16-bit UUID buffer.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Uuid16(pub u16);
 
+impl Uuid16 {
+    /// Returns the name of the UUID, if it is one of the [assigned UUIDs](crate::uuids::name).
+    #[cfg(feature = "uuid-names")]
+    pub fn name(&self) -> Option<&'static str> {
+        crate::uuids::name(self.0)
+    }
+}
+
+/// Shows the UUID in hex. With the `uuid-names` feature, the name of an assigned UUID is shown
+/// first, as in "Battery Level (0x2A19)".
+impl Display for Uuid16 {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        #[cfg(feature = "uuid-names")]
+        {
+            if let Some(name) = self.name() {
+                return write!(f, "{} (0x{:04X})", name, self.0);
+            }
+        }
+
+        write!(f, "0x{:04X}", self.0)
+    }
+}
+
 /// Newtype for the 128-bit UUID buffer.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Uuid128(pub [u8; 16]);
@@ -1732,6 +3288,9 @@ impl Debug for HandleUuidPairs {
 
 /// Possible iterators over handle-UUID pairs that can be returnedby the [ATT find information
 /// response](AttFindInformationResponse). All pairs from the same event have the same format.
+///
+/// Match on the variant to get the pairs in their wire format, or iterate over it directly to get
+/// each handle with its [UUID](crate::gatt::Uuid) in either format.
 pub enum HandleUuidPairIterator<'a> {
     /// The event contains 16-bit UUIDs.
     Format16(HandleUuid16PairIterator<'a>),
@@ -1739,6 +3298,28 @@ pub enum HandleUuidPairIterator<'a> {
     Format128(HandleUuid128PairIterator<'a>),
 }
 
+impl<'a> Iterator for HandleUuidPairIterator<'a> {
+    type Item = (AttributeHandle, crate::gatt::Uuid);
+    fn next(&mut self) -> Option<Self::Item> {
+        match *self {
+            HandleUuidPairIterator::Format16(ref mut iter) => iter
+                .next()
+                .map(|pair| (pair.handle, crate::gatt::Uuid::Uuid16(pair.uuid.0))),
+            HandleUuidPairIterator::Format128(ref mut iter) => iter
+                .next()
+                .map(|pair| (pair.handle, crate::gatt::Uuid::Uuid128(pair.uuid.0))),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a AttFindInformationResponse {
+    type Item = (AttributeHandle, crate::gatt::Uuid);
+    type IntoIter = HandleUuidPairIterator<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.handle_uuid_pair_iter()
+    }
+}
+
 /// Iterator over handle-UUID pairs for 16-bit UUIDs.
 pub struct HandleUuid16PairIterator<'a> {
     data: &'a [HandleUuid16Pair; MAX_FORMAT16_PAIR_COUNT],
@@ -1779,17 +3360,17 @@ impl<'a> Iterator for HandleUuid128PairIterator<'a> {
     }
 }
 
+#[cfg(feature = "att-client-events")]
 fn to_att_find_information_response(
     buffer: &[u8],
 ) -> Result<AttFindInformationResponse, hci::event::Error<BlueNRGError>> {
     require_len_at_least!(buffer, 6);
 
-    let data_len = buffer[4] as usize;
-    require_len!(buffer, 5 + data_len);
+    let data_len = to_data_len(buffer, 4, 5)?;
 
     Ok(AttFindInformationResponse {
         conn_handle: to_conn_handle(buffer)?,
-        handle_uuid_pairs: match buffer[5] {
+        handle_uuid_pairs: Payload::new(match buffer[5] {
             1 => to_handle_uuid16_pairs(&buffer[6..]).map_err(hci::event::Error::Vendor)?,
             2 => to_handle_uuid128_pairs(&buffer[6..]).map_err(hci::event::Error::Vendor)?,
             _ => {
@@ -1797,10 +3378,11 @@ fn to_att_find_information_response(
                     BlueNRGError::BadAttFindInformationResponseFormat(buffer[5]),
                 ));
             }
-        },
+        }),
     })
 }
 
+#[cfg(feature = "att-client-events")]
 fn to_handle_uuid16_pairs(buffer: &[u8]) -> Result<HandleUuidPairs, BlueNRGError> {
     const PAIR_LEN: usize = 4;
     if buffer.len() % PAIR_LEN != 0 {
@@ -1821,6 +3403,7 @@ fn to_handle_uuid16_pairs(buffer: &[u8]) -> Result<HandleUuidPairs, BlueNRGError
     Ok(HandleUuidPairs::Format16(count, pairs))
 }
 
+#[cfg(feature = "att-client-events")]
 fn to_handle_uuid128_pairs(buffer: &[u8]) -> Result<HandleUuidPairs, BlueNRGError> {
     const PAIR_LEN: usize = 18;
     if buffer.len() % PAIR_LEN != 0 {
@@ -1843,7 +3426,7 @@ fn to_handle_uuid128_pairs(buffer: &[u8]) -> Result<HandleUuidPairs, BlueNRGErro
 }
 
 /// This event is generated in response to a Find By Type Value Request.
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 pub struct AttFindByTypeValueResponse {
     /// The connection handle related to the response.
     pub conn_handle: ConnectionHandle,
@@ -1852,7 +3435,7 @@ pub struct AttFindByTypeValueResponse {
     handle_pair_count: usize,
 
     /// Handles Information List as defined in Bluetooth Core v4.1 spec.
-    handles: [HandleInfoPair; MAX_HANDLE_INFO_PAIR_COUNT],
+    handles: Payload<[HandleInfoPair; MAX_HANDLE_INFO_PAIR_COUNT]>,
 }
 
 impl AttFindByTypeValueResponse {
@@ -1864,6 +3447,34 @@ impl AttFindByTypeValueResponse {
             next_index: 0,
         }
     }
+
+    /// Returns the number of handle pairs in the response.
+    ///
+    /// A response with no pairs is valid: the server found no attribute with the requested type
+    /// and value. It is not an error.
+    pub fn len(&self) -> usize {
+        self.handle_pair_count
+    }
+
+    /// Returns true if the response has no handle pairs.
+    pub fn is_empty(&self) -> bool {
+        self.handle_pair_count == 0
+    }
+
+    /// Appends the handle information pairs to `out`, so they can be kept after the event is
+    /// dropped. Returns the number of pairs appended.
+    ///
+    /// # Errors
+    ///
+    /// - [Full](CollectError::Full) if `out` does not have room for all of the pairs. The pairs
+    ///   that fit are appended.
+    #[cfg(feature = "heapless")]
+    pub fn collect_into<const N: usize>(
+        &self,
+        out: &mut heapless::Vec<HandleInfoPair, N>,
+    ) -> Result<usize, CollectError> {
+        collect_owned(self.handle_pairs_iter().map(Ok), out)
+    }
 }
 
 impl Debug for AttFindByTypeValueResponse {
@@ -1876,16 +3487,16 @@ impl Debug for AttFindByTypeValueResponse {
     }
 }
 
-// Assuming a maximum HCI packet size of 255, these are the maximum number of handle pairs that can
-// be in one packet.
+// Given the maximum HCI packet size, these are the maximum number of handle pairs that can be in
+// one packet.
 //
 // Packets have 5 other bytes of data preceding the handle-UUID pairs.
 //
-// max = floor((255 - 5) / 4)
-const MAX_HANDLE_INFO_PAIR_COUNT: usize = 62;
+// max = floor((MAX_EVENT_LEN - 5) / 4)
+const MAX_HANDLE_INFO_PAIR_COUNT: usize = (MAX_EVENT_LEN - 5) / 4;
 
 /// Simple container for the handle information returned in [`AttFindByTypeValueResponse`].
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct HandleInfoPair {
     /// Attribute handle
     pub attribute: AttributeHandle,
@@ -1918,6 +3529,15 @@ impl<'a> Iterator for HandleInfoPairIterator<'a> {
     }
 }
 
+impl<'a> IntoIterator for &'a AttFindByTypeValueResponse {
+    type Item = HandleInfoPair;
+    type IntoIter = HandleInfoPairIterator<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.handle_pairs_iter()
+    }
+}
+
+#[cfg(feature = "att-client-events")]
 fn to_att_find_by_value_type_response(
     buffer: &[u8],
 ) -> Result<AttFindByTypeValueResponse, hci::event::Error<BlueNRGError>> {
@@ -1925,8 +3545,7 @@ fn to_att_find_by_value_type_response(
 
     require_len_at_least!(buffer, 5);
 
-    let data_len = buffer[4] as usize;
-    require_len!(buffer, 5 + data_len);
+    let data_len = to_data_len(buffer, 4, 5)?;
 
     let pair_buffer = &buffer[5..];
     if pair_buffer.len() % PAIR_LEN != 0 {
@@ -1948,12 +3567,12 @@ fn to_att_find_by_value_type_response(
     Ok(AttFindByTypeValueResponse {
         conn_handle: to_conn_handle(buffer)?,
         handle_pair_count: count,
-        handles: pairs,
+        handles: Payload::new(pairs),
     })
 }
 
 /// This event is generated in response to a Read By Type Request.
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 pub struct AttReadByTypeResponse {
     /// The connection handle related to the response.
     pub conn_handle: ConnectionHandle,
@@ -1964,12 +3583,12 @@ pub struct AttReadByTypeResponse {
     value_len: usize,
     // Raw data of the response. Contains 2 octets for the attribute handle followed by `value_len`
     // octets of value data. These pairs repeat for `data_len` bytes.
-    handle_value_pair_buf: [u8; MAX_HANDLE_VALUE_PAIR_BUF_LEN],
+    handle_value_pair_buf: Payload<[u8; MAX_HANDLE_VALUE_PAIR_BUF_LEN]>,
 }
 
-// The maximum amount of data in the buffer is the max HCI packet size (255) less the other data in
-// the packet.
-const MAX_HANDLE_VALUE_PAIR_BUF_LEN: usize = 249;
+// The maximum amount of data in the buffer is the max HCI packet size less the other data in the
+// packet.
+const MAX_HANDLE_VALUE_PAIR_BUF_LEN: usize = MAX_EVENT_LEN - 6;
 
 impl Debug for AttReadByTypeResponse {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
@@ -1994,6 +3613,50 @@ impl AttReadByTypeResponse {
             index: 0,
         }
     }
+
+    /// Returns the number of handle-value pairs returned with the response. This is the number of
+    /// items that [`handle_value_pair_iter`](AttReadByTypeResponse::handle_value_pair_iter) yields.
+    pub fn pair_count(&self) -> usize {
+        self.data_len / (self.value_len + 2)
+    }
+
+    /// Returns the number of handle-value pairs in the response. Same as
+    /// [`pair_count`](AttReadByTypeResponse::pair_count).
+    pub fn len(&self) -> usize {
+        self.pair_count()
+    }
+
+    /// Returns true if the response has no handle-value pairs.
+    pub fn is_empty(&self) -> bool {
+        self.data_len == 0
+    }
+
+    /// Appends copies of the handle-value pairs to `out`, so they can be kept after the event is
+    /// dropped. Each copied value can hold up to `V` bytes. Returns the number of pairs appended.
+    ///
+    /// # Errors
+    ///
+    /// - [Full](CollectError::Full) if `out` does not have room for all of the pairs.
+    /// - [ValueTooLong](CollectError::ValueTooLong) if the values are longer than `V` bytes.
+    ///
+    /// In either case, the pairs before the failing one are appended.
+    #[cfg(feature = "heapless")]
+    pub fn collect_into<const N: usize, const V: usize>(
+        &self,
+        out: &mut heapless::Vec<OwnedHandleValuePair<V>, N>,
+    ) -> Result<usize, CollectError> {
+        collect_owned(
+            self.handle_value_pair_iter().map(|pair| {
+                heapless::Vec::from_slice(pair.value)
+                    .map(|value| OwnedHandleValuePair {
+                        handle: pair.handle,
+                        value,
+                    })
+                    .map_err(|_| pair.value.len())
+            }),
+            out,
+        )
+    }
 }
 
 /// Iterator over the valid handle-value pairs returned with the [ATT Read by Type
@@ -2023,6 +3686,14 @@ impl<'a> Iterator for HandleValuePairIterator<'a> {
     }
 }
 
+impl<'a> IntoIterator for &'a AttReadByTypeResponse {
+    type Item = HandleValuePair<'a>;
+    type IntoIter = HandleValuePairIterator<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.handle_value_pair_iter()
+    }
+}
+
 /// A single handle-value pair returned by the [ATT Read by Type response](AttReadByTypeResponse).
 pub struct HandleValuePair<'a> {
     /// Attribute handle
@@ -2032,15 +3703,32 @@ pub struct HandleValuePair<'a> {
     pub value: &'a [u8],
 }
 
+/// A handle-value pair copied out of the [ATT Read by Type response](AttReadByTypeResponse) by
+/// [`collect_into`](AttReadByTypeResponse::collect_into). The value can hold up to `V` bytes.
+#[cfg(feature = "heapless")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedHandleValuePair<const V: usize> {
+    /// Attribute handle
+    pub handle: AttributeHandle,
+    /// Attribute value
+    pub value: heapless::Vec<u8, V>,
+}
+
+#[cfg(feature = "att-client-events")]
 fn to_att_read_by_type_response(
     buffer: &[u8],
 ) -> Result<AttReadByTypeResponse, hci::event::Error<BlueNRGError>> {
     require_len_at_least!(buffer, 6);
 
-    let data_len = buffer[4] as usize;
-    require_len!(buffer, 5 + data_len);
+    to_data_len(buffer, 4, 5)?;
 
     let handle_value_pair_len = buffer[5] as usize;
+    if handle_value_pair_len < 2 {
+        return Err(hci::event::Error::Vendor(
+            BlueNRGError::BadAttReadByTypeResponsePairLength(buffer[5]),
+        ));
+    }
+
     let handle_value_pair_buf = &buffer[6..];
     if handle_value_pair_buf.len() % handle_value_pair_len != 0 {
         return Err(hci::event::Error::Vendor(
@@ -2048,6 +3736,13 @@ fn to_att_read_by_type_response(
         ));
     }
 
+    if handle_value_pair_buf.len() > MAX_HANDLE_VALUE_PAIR_BUF_LEN {
+        return Err(hci::event::Error::BadLength(
+            buffer.len(),
+            6 + MAX_HANDLE_VALUE_PAIR_BUF_LEN,
+        ));
+    }
+
     let mut full_handle_value_pair_buf = [0; MAX_HANDLE_VALUE_PAIR_BUF_LEN];
     full_handle_value_pair_buf[..handle_value_pair_buf.len()]
         .copy_from_slice(&handle_value_pair_buf);
@@ -2056,12 +3751,12 @@ fn to_att_read_by_type_response(
         conn_handle: ConnectionHandle(LittleEndian::read_u16(&buffer[2..])),
         data_len: handle_value_pair_buf.len(),
         value_len: handle_value_pair_len - 2,
-        handle_value_pair_buf: full_handle_value_pair_buf,
+        handle_value_pair_buf: Payload::new(full_handle_value_pair_buf),
     })
 }
 
 /// This event is generated in response to a Read Request.
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 pub struct AttReadResponse {
     /// The connection handle related to the response.
     pub conn_handle: ConnectionHandle,
@@ -2070,12 +3765,12 @@ pub struct AttReadResponse {
     value_len: usize,
 
     /// Buffer containing the value data.
-    value_buf: [u8; MAX_READ_RESPONSE_LEN],
+    value_buf: Payload<[u8; MAX_READ_RESPONSE_LEN]>,
 }
 
-// The maximum amount of data in the buffer is the max HCI packet size (255) less the other data in
-// the packet.
-const MAX_READ_RESPONSE_LEN: usize = 250;
+// The maximum amount of data in the buffer is the max HCI packet size less the other data in the
+// packet.
+const MAX_READ_RESPONSE_LEN: usize = MAX_EVENT_LEN - 5;
 
 impl Debug for AttReadResponse {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
@@ -2093,13 +3788,38 @@ impl AttReadResponse {
     pub fn value(&self) -> &[u8] {
         &self.value_buf[..self.value_len]
     }
+
+    /// Returns true if the value may have been truncated to fit the ATT MTU of the connection.
+    ///
+    /// A Read Response carries at most `mtu - 1` bytes of the value. If the value is exactly that
+    /// long, the attribute may be longer, and the rest has to be read with Read Blob requests:
+    /// start a [long read](crate::gatt::Commands::read_long_characteristic_value) with the
+    /// [offset](crate::gatt::LongCharacteristicReadParameters::offset) set to the length of this
+    /// value, and append the values of the [Read Blob
+    /// Responses](BlueNRGEvent::AttReadBlobResponse) until the procedure completes. If the full
+    /// attribute is exactly `mtu - 1` bytes long, the long read completes with no more data.
+    pub fn was_truncated(&self, mtu: u16) -> bool {
+        self.value_len + 1 == mtu as usize
+    }
+
+    /// Decodes the value as a `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from [`GattValue::decode`](crate::value::GattValue::decode) if the value
+    /// is not a valid `T`.
+    pub fn decode<'a, T: crate::value::GattValue<'a>>(
+        &'a self,
+    ) -> Result<T, crate::value::ValueError> {
+        T::decode(self.value())
+    }
 }
 
+#[cfg(feature = "att-client-events")]
 fn to_att_read_response(buffer: &[u8]) -> Result<AttReadResponse, hci::event::Error<BlueNRGError>> {
     require_len_at_least!(buffer, 5);
 
-    let data_len = buffer[4] as usize;
-    require_len!(buffer, 5 + data_len);
+    let data_len = to_data_len(buffer, 4, 5)?;
 
     let mut value_buf = [0; MAX_READ_RESPONSE_LEN];
     value_buf[..data_len].copy_from_slice(&buffer[5..]);
@@ -2107,13 +3827,13 @@ fn to_att_read_response(buffer: &[u8]) -> Result<AttReadResponse, hci::event::Er
     Ok(AttReadResponse {
         conn_handle: ConnectionHandle(LittleEndian::read_u16(&buffer[2..])),
         value_len: data_len,
-        value_buf,
+        value_buf: Payload::new(value_buf),
     })
 }
 
 /// This event is generated in response to a Read By Group Type Request. See the Bluetooth Core v4.1
 /// spec, Vol 3, section 3.4.4.9 and 3.4.4.10.
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 pub struct AttReadByGroupTypeResponse {
     ///  The connection handle related to the response.
     pub conn_handle: ConnectionHandle,
@@ -2129,12 +3849,12 @@ pub struct AttReadByGroupTypeResponse {
     // 1. 2 octets for attribute handle.
     // 2. 2 octets for end group handle.
     // 3. (attribute_group_len - 4) octets for attribute value.
-    attribute_data_buf: [u8; MAX_ATTRIBUTE_DATA_BUF_LEN],
+    attribute_data_buf: Payload<[u8; MAX_ATTRIBUTE_DATA_BUF_LEN]>,
 }
 
-// The maximum amount of data in the buffer is the max HCI packet size (255) less the other data in
-// the packet.
-const MAX_ATTRIBUTE_DATA_BUF_LEN: usize = 249;
+// The maximum amount of data in the buffer is the max HCI packet size less the other data in the
+// packet.
+const MAX_ATTRIBUTE_DATA_BUF_LEN: usize = MAX_EVENT_LEN - 6;
 
 impl AttReadByGroupTypeResponse {
     /// Create and return an iterator for the attribute data returned with the response.
@@ -2144,6 +3864,46 @@ impl AttReadByGroupTypeResponse {
             next_index: 0,
         }
     }
+
+    /// Returns the number of attribute data groups in the response. This is the number of items
+    /// that [`attribute_data_iter`](AttReadByGroupTypeResponse::attribute_data_iter) yields.
+    pub fn len(&self) -> usize {
+        self.data_len / self.attribute_group_len
+    }
+
+    /// Returns true if the response has no attribute data.
+    pub fn is_empty(&self) -> bool {
+        self.data_len == 0
+    }
+
+    /// Appends copies of the attribute data to `out`, so it can be kept after the event is
+    /// dropped. Each copied value can hold up to `V` bytes. Returns the number of attribute data
+    /// groups appended.
+    ///
+    /// # Errors
+    ///
+    /// - [Full](CollectError::Full) if `out` does not have room for all of the groups.
+    /// - [ValueTooLong](CollectError::ValueTooLong) if the values are longer than `V` bytes.
+    ///
+    /// In either case, the groups before the failing one are appended.
+    #[cfg(feature = "heapless")]
+    pub fn collect_into<const N: usize, const V: usize>(
+        &self,
+        out: &mut heapless::Vec<OwnedAttributeData<V>, N>,
+    ) -> Result<usize, CollectError> {
+        collect_owned(
+            self.attribute_data_iter().map(|data| {
+                heapless::Vec::from_slice(data.value)
+                    .map(|value| OwnedAttributeData {
+                        attribute_handle: data.attribute_handle,
+                        group_end_handle: data.group_end_handle,
+                        value,
+                    })
+                    .map_err(|_| data.value.len())
+            }),
+            out,
+        )
+    }
 }
 
 impl Debug for AttReadByGroupTypeResponse {
@@ -2191,6 +3951,14 @@ impl<'a> Iterator for AttributeDataIterator<'a> {
     }
 }
 
+impl<'a> IntoIterator for &'a AttReadByGroupTypeResponse {
+    type Item = AttributeData<'a>;
+    type IntoIter = AttributeDataIterator<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.attribute_data_iter()
+    }
+}
+
 /// Attribute data returned in the [`AttReadByGroupTypeResponse`] event.
 pub struct AttributeData<'a> {
     /// Attribute handle
@@ -2201,15 +3969,83 @@ pub struct AttributeData<'a> {
     pub value: &'a [u8],
 }
 
+/// Attribute data copied out of the [`AttReadByGroupTypeResponse`] event by
+/// [`collect_into`](AttReadByGroupTypeResponse::collect_into). The value can hold up to `V` bytes.
+#[cfg(feature = "heapless")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedAttributeData<const V: usize> {
+    /// Attribute handle
+    pub attribute_handle: AttributeHandle,
+    /// Group end handle
+    pub group_end_handle: GroupEndHandle,
+    /// Attribute value
+    pub value: heapless::Vec<u8, V>,
+}
+
+/// Errors that may occur when copying the items of a response event into a `heapless::Vec` with
+/// `collect_into`.
+#[cfg(feature = "heapless")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CollectError {
+    /// The vector filled up. Includes the number of items that were appended before it did.
+    Full(usize),
+
+    /// An attribute value did not fit in the value buffer of the owned item. Includes the number of
+    /// items that were appended before it, and the length of the value.
+    ValueTooLong(usize, usize),
+}
+
+#[cfg(feature = "heapless")]
+impl Display for CollectError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            CollectError::Full(count) => write!(f, "vector is full after {} items", count),
+            CollectError::ValueTooLong(count, len) => write!(
+                f,
+                "value of length {} does not fit after {} items",
+                len, count
+            ),
+        }
+    }
+}
+
+#[cfg(all(feature = "heapless", feature = "std"))]
+impl std::error::Error for CollectError {}
+
+// Appends the items to `out`. Each item is either the owned copy, or the length of a value that did
+// not fit in the copy.
+#[cfg(feature = "heapless")]
+fn collect_owned<T, I, const N: usize>(
+    items: I,
+    out: &mut heapless::Vec<T, N>,
+) -> Result<usize, CollectError>
+where
+    I: Iterator<Item = Result<T, usize>>,
+{
+    let mut written = 0;
+    for item in items {
+        let item = item.map_err(|len| CollectError::ValueTooLong(written, len))?;
+        out.push(item).map_err(|_| CollectError::Full(written))?;
+        written += 1;
+    }
+
+    Ok(written)
+}
+
+#[cfg(feature = "att-client-events")]
 fn to_att_read_by_group_type_response(
     buffer: &[u8],
 ) -> Result<AttReadByGroupTypeResponse, hci::event::Error<BlueNRGError>> {
     require_len_at_least!(buffer, 6);
 
-    let data_len = buffer[4] as usize;
-    require_len!(buffer, 5 + data_len);
+    let data_len = to_data_len(buffer, 4, 5)?;
 
     let attribute_group_len = buffer[5] as usize;
+    if attribute_group_len < 4 {
+        return Err(hci::event::Error::Vendor(
+            BlueNRGError::BadAttReadByGroupTypeResponseGroupLength(buffer[5]),
+        ));
+    }
 
     if buffer[6..].len() % attribute_group_len != 0 {
         return Err(hci::event::Error::Vendor(
@@ -2223,13 +4059,13 @@ fn to_att_read_by_group_type_response(
         conn_handle: ConnectionHandle(LittleEndian::read_u16(&buffer[2..])),
         data_len: data_len - 1, // lose 1 byte to attribute_group_len
         attribute_group_len,
-        attribute_data_buf,
+        attribute_data_buf: Payload::new(attribute_data_buf),
     })
 }
 
 /// This event is generated in response to a Prepare Write Request. See the Bluetooth Core v4.1
 /// spec, Vol 3, Part F, section 3.4.6.1 and 3.4.6.2
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 pub struct AttPrepareWriteResponse {
     /// The connection handle related to the response.
     pub conn_handle: ConnectionHandle,
@@ -2240,12 +4076,12 @@ pub struct AttPrepareWriteResponse {
 
     /// Number of valid bytes in |value_buf|
     value_len: usize,
-    value_buf: [u8; MAX_WRITE_RESPONSE_VALUE_LEN],
+    value_buf: Payload<[u8; MAX_WRITE_RESPONSE_VALUE_LEN]>,
 }
 
-// The maximum amount of data in the buffer is the max HCI packet size (255) less the other data in
-// the packet.
-const MAX_WRITE_RESPONSE_VALUE_LEN: usize = 246;
+// The maximum amount of data in the buffer is the max HCI packet size less the other data in the
+// packet.
+const MAX_WRITE_RESPONSE_VALUE_LEN: usize = MAX_EVENT_LEN - 9;
 
 impl Debug for AttPrepareWriteResponse {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
@@ -2267,13 +4103,13 @@ impl AttPrepareWriteResponse {
     }
 }
 
+#[cfg(feature = "att-client-events")]
 fn to_att_prepare_write_response(
     buffer: &[u8],
 ) -> Result<AttPrepareWriteResponse, hci::event::Error<BlueNRGError>> {
     require_len_at_least!(buffer, 9);
 
-    let data_len = buffer[4] as usize;
-    require_len!(buffer, 5 + data_len);
+    let data_len = to_data_len(buffer, 4, 5)?;
 
     let value_len = data_len - 4;
     let mut value_buf = [0; MAX_WRITE_RESPONSE_VALUE_LEN];
@@ -2283,13 +4119,21 @@ fn to_att_prepare_write_response(
         attribute_handle: AttributeHandle(LittleEndian::read_u16(&buffer[5..])),
         offset: LittleEndian::read_u16(&buffer[7..]) as usize,
         value_len,
-        value_buf,
+        value_buf: Payload::new(value_buf),
     })
 }
 
+/// Parameters for the [ATT Execute Write Response](BlueNRGEvent::AttExecuteWriteResponse) event.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct AttExecuteWriteResponse {
+    /// The connection handle related to the response.
+    pub conn_handle: ConnectionHandle,
+}
+
 /// Defines the attribute value returned by a [GATT Indication](BlueNRGEvent::GattIndication) or
 /// [GATT Notification](BlueNRGEvent::GattNotification) event.
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 pub struct AttributeValue {
     /// The connection handle related to the event.
     pub conn_handle: ConnectionHandle,
@@ -2299,12 +4143,12 @@ pub struct AttributeValue {
     // Number of valid bytes in value_buf
     value_len: usize,
     // Current value of the attribute. Only the first value_len bytes are valid.
-    value_buf: [u8; MAX_ATTRIBUTE_VALUE_LEN],
+    value_buf: Payload<[u8; MAX_ATTRIBUTE_VALUE_LEN]>,
 }
 
-// The maximum amount of data in the buffer is the max HCI packet size (255) less the other data in
-// the packet.
-const MAX_ATTRIBUTE_VALUE_LEN: usize = 248;
+// The maximum amount of data in the buffer is the max HCI packet size less the other data in the
+// packet.
+const MAX_ATTRIBUTE_VALUE_LEN: usize = MAX_EVENT_LEN - 7;
 
 impl Debug for AttributeValue {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
@@ -2323,13 +4167,83 @@ impl AttributeValue {
     pub fn value(&self) -> &[u8] {
         &self.value_buf[..self.value_len]
     }
+
+    /// Decodes the current value of the attribute as a `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from [`GattValue::decode`](crate::value::GattValue::decode) if the current
+    /// value of the attribute is not a valid `T`.
+    pub fn decode<'a, T: crate::value::GattValue<'a>>(
+        &'a self,
+    ) -> Result<T, crate::value::ValueError> {
+        T::decode(self.value())
+    }
+}
+
+/// Common view of the events that carry an attribute value on a connection: values the peer wrote
+/// to the local server ([`GattAttributeModified`]) and values the peer's server sent to the local
+/// client ([`AttributeValue`] and [`ExtendedAttributeValue`]). Code that handles both can be
+/// written once against this trait.
+pub trait AttributeEvent {
+    /// Returns the connection the value was received on.
+    fn conn_handle(&self) -> ConnectionHandle;
+
+    /// Returns the handle of the attribute.
+    fn attribute_handle(&self) -> AttributeHandle;
+
+    /// Returns the value carried by the event. For events that report part of a long value, this
+    /// is only the part in the event.
+    fn value(&self) -> &[u8];
+}
+
+impl AttributeEvent for AttributeValue {
+    fn conn_handle(&self) -> ConnectionHandle {
+        self.conn_handle
+    }
+
+    fn attribute_handle(&self) -> AttributeHandle {
+        self.attribute_handle
+    }
+
+    fn value(&self) -> &[u8] {
+        AttributeValue::value(self)
+    }
+}
+
+impl AttributeEvent for GattAttributeModified {
+    fn conn_handle(&self) -> ConnectionHandle {
+        self.conn_handle
+    }
+
+    fn attribute_handle(&self) -> AttributeHandle {
+        self.attr_handle
+    }
+
+    fn value(&self) -> &[u8] {
+        self.data()
+    }
+}
+
+impl AttributeEvent for ExtendedAttributeValue {
+    fn conn_handle(&self) -> ConnectionHandle {
+        self.conn_handle
+    }
+
+    fn attribute_handle(&self) -> AttributeHandle {
+        self.attribute_handle
+    }
+
+    fn value(&self) -> &[u8] {
+        ExtendedAttributeValue::value(self)
+    }
 }
 
+#[cfg(feature = "att-client-events")]
 fn to_attribute_value(buffer: &[u8]) -> Result<AttributeValue, hci::event::Error<BlueNRGError>> {
     require_len_at_least!(buffer, 7);
 
-    let data_len = buffer[4] as usize;
-    require_len!(buffer, 5 + data_len);
+    let data_len = to_data_len(buffer, 4, 5)?;
 
     let value_len = data_len - 2;
     let mut value_buf = [0; MAX_ATTRIBUTE_VALUE_LEN];
@@ -2338,7 +4252,7 @@ fn to_attribute_value(buffer: &[u8]) -> Result<AttributeValue, hci::event::Error
         conn_handle: ConnectionHandle(LittleEndian::read_u16(&buffer[2..])),
         attribute_handle: AttributeHandle(LittleEndian::read_u16(&buffer[5..])),
         value_len,
-        value_buf,
+        value_buf: Payload::new(value_buf),
     })
 }
 
@@ -2347,8 +4261,7 @@ fn to_write_permit_request(
 ) -> Result<AttributeValue, hci::event::Error<BlueNRGError>> {
     require_len_at_least!(buffer, 7);
 
-    let data_len = buffer[6] as usize;
-    require_len!(buffer, 7 + data_len);
+    let data_len = to_data_len(buffer, 6, 7)?;
 
     let value_len = data_len;
     let mut value_buf = [0; MAX_ATTRIBUTE_VALUE_LEN];
@@ -2357,7 +4270,7 @@ fn to_write_permit_request(
         conn_handle: ConnectionHandle(LittleEndian::read_u16(&buffer[2..])),
         attribute_handle: AttributeHandle(LittleEndian::read_u16(&buffer[4..])),
         value_len,
-        value_buf,
+        value_buf: Payload::new(value_buf),
     })
 }
 
@@ -2369,7 +4282,8 @@ pub struct GattProcedureComplete {
     pub conn_handle: ConnectionHandle,
 
     /// Indicates whether the procedure completed with [error](GattProcedureStatus::Failed) or was
-    /// [successful](GattProcedureStatus::Success).
+    /// [successful](GattProcedureStatus::Success). Status codes that are not listed are reported as
+    /// [Other](GattProcedureStatus::Other) rather than failing to parse the event.
     pub status: GattProcedureStatus,
 }
 
@@ -2381,34 +4295,67 @@ pub enum GattProcedureStatus {
     Success,
     /// BLE Status Failed
     Failed,
+    /// Procedure failed with a status code that is not listed above. Includes the status code.
+    Other(u8),
+}
+
+impl GattProcedureStatus {
+    /// Returns the status for the status code reported by the controller. Unrecognized codes are
+    /// reported as [Other](GattProcedureStatus::Other).
+    pub fn from_raw(value: u8) -> GattProcedureStatus {
+        match value {
+            0x00 => GattProcedureStatus::Success,
+            0x41 => GattProcedureStatus::Failed,
+            _ => GattProcedureStatus::Other(value),
+        }
+    }
+
+    /// Returns the status code reported by the controller.
+    pub fn raw(&self) -> u8 {
+        match *self {
+            GattProcedureStatus::Success => 0x00,
+            GattProcedureStatus::Failed => 0x41,
+            GattProcedureStatus::Other(value) => value,
+        }
+    }
+
+    /// Returns the status if it is one of the listed status codes.
+    ///
+    /// # Errors
+    ///
+    /// - [BadGattProcedureStatus](BlueNRGError::BadGattProcedureStatus) if the status is
+    ///   [Other](GattProcedureStatus::Other). Includes the status code.
+    pub fn strict(self) -> Result<GattProcedureStatus, BlueNRGError> {
+        match self {
+            GattProcedureStatus::Other(value) => Err(BlueNRGError::BadGattProcedureStatus(value)),
+            status => Ok(status),
+        }
+    }
 }
 
 impl TryFrom<u8> for GattProcedureStatus {
     type Error = BlueNRGError;
 
     fn try_from(value: u8) -> Result<Self, Self::Error> {
-        match value {
-            0x00 => Ok(GattProcedureStatus::Success),
-            0x41 => Ok(GattProcedureStatus::Failed),
-            _ => Err(BlueNRGError::BadGattProcedureStatus(value)),
-        }
+        GattProcedureStatus::from_raw(value).strict()
     }
 }
 
+#[cfg(feature = "att-client-events")]
 fn to_gatt_procedure_complete(
     buffer: &[u8],
 ) -> Result<GattProcedureComplete, hci::event::Error<BlueNRGError>> {
-    require_len!(buffer, 6);
+    require_fixed_len!(buffer, 6);
 
     Ok(GattProcedureComplete {
         conn_handle: ConnectionHandle(LittleEndian::read_u16(&buffer[2..])),
-        status: buffer[5].try_into().map_err(hci::event::Error::Vendor)?,
+        status: GattProcedureStatus::from_raw(buffer[5]),
     })
 }
 
 /// The Error Response is used to state that a given request cannot be performed, and to provide the
 /// reason. See the Bluetooth Core Specification, v4.1, Vol 3, Part F, Section 3.4.1.1.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct AttErrorResponse {
     /// The connection handle related to the event.
     pub conn_handle: ConnectionHandle,
@@ -2538,6 +4485,9 @@ pub enum AttError {
     OutOfRange = 0xFF,
 }
 
+// The error code is a single byte on the wire, and the enum must stay that way.
+const _: [(); 1] = [(); core::mem::size_of::<AttError>()];
+
 impl TryFrom<u8> for AttError {
     type Error = u8;
 
@@ -2702,10 +4652,11 @@ impl TryFrom<u8> for AttRequest {
     }
 }
 
+#[cfg(feature = "att-client-events")]
 fn to_att_error_response(
     buffer: &[u8],
 ) -> Result<AttErrorResponse, hci::event::Error<BlueNRGError>> {
-    require_len!(buffer, 9);
+    require_fixed_len!(buffer, 9);
     Ok(AttErrorResponse {
         conn_handle: ConnectionHandle(LittleEndian::read_u16(&buffer[2..])),
         request: buffer[5].try_into().map_err(hci::event::Error::Vendor)?,
@@ -2737,10 +4688,18 @@ pub struct AttReadPermitRequest {
     pub offset: usize,
 }
 
+#[cfg(not(feature = "bluenrg-lp"))]
 fn to_att_read_permit_request(
     buffer: &[u8],
 ) -> Result<AttReadPermitRequest, hci::event::Error<BlueNRGError>> {
-    require_len!(buffer, 9);
+    require_fixed_len!(buffer, 9);
+
+    // The data is always the 2-byte offset.
+    let data_len = buffer[6] as usize;
+    if data_len != 2 {
+        return Err(hci::event::Error::BadLength(7 + data_len, 9));
+    }
+
     Ok(AttReadPermitRequest {
         conn_handle: ConnectionHandle(LittleEndian::read_u16(&buffer[2..])),
         attribute_handle: AttributeHandle(LittleEndian::read_u16(&buffer[4..])),
@@ -2748,6 +4707,20 @@ fn to_att_read_permit_request(
     })
 }
 
+// BlueNRG-LP firmware drops the data length, and reports the offset right after the attribute
+// handle.
+#[cfg(feature = "bluenrg-lp")]
+fn to_att_read_permit_request(
+    buffer: &[u8],
+) -> Result<AttReadPermitRequest, hci::event::Error<BlueNRGError>> {
+    require_fixed_len!(buffer, 8);
+    Ok(AttReadPermitRequest {
+        conn_handle: ConnectionHandle(LittleEndian::read_u16(&buffer[2..])),
+        attribute_handle: AttributeHandle(LittleEndian::read_u16(&buffer[4..])),
+        offset: LittleEndian::read_u16(&buffer[6..]) as usize,
+    })
+}
+
 /// This event is given to the application when a read multiple request or read by type request is
 /// received by the server from the client. This event will be given to the application only if the
 /// event bit for this event generation is set when the characteristic was added.  On receiving this
@@ -2756,7 +4729,7 @@ fn to_att_read_permit_request(
 /// the client.
 ///
 /// See the Bluetooth Core v4.1 spec, Vol 3, Part F, section 3.4.4.
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 pub struct AttReadMultiplePermitRequest {
     /// Handle of the connection which requested to read the attribute.
     pub conn_handle: ConnectionHandle,
@@ -2765,12 +4738,12 @@ pub struct AttReadMultiplePermitRequest {
     handles_len: usize,
     /// Attribute handles returned by the ATT Read Multiple Permit Request. Only the first
     /// `handles_len` handles are valid.
-    handles_buf: [AttributeHandle; MAX_ATTRIBUTE_HANDLE_BUFFER_LEN],
+    handles_buf: Payload<[AttributeHandle; MAX_ATTRIBUTE_HANDLE_BUFFER_LEN]>,
 }
 
-// The maximum number of handles in the buffer is the max HCI packet size (255) less the other data in
-// the packet divided by the length of an attribute handle (2).
-const MAX_ATTRIBUTE_HANDLE_BUFFER_LEN: usize = 125;
+// The maximum number of handles in the buffer is the max HCI packet size less the other data in the
+// packet divided by the length of an attribute handle (2).
+const MAX_ATTRIBUTE_HANDLE_BUFFER_LEN: usize = (MAX_EVENT_LEN - 5) / 2;
 
 impl Debug for AttReadMultiplePermitRequest {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
@@ -2795,14 +4768,25 @@ fn to_att_read_multiple_permit_request(
 ) -> Result<AttReadMultiplePermitRequest, hci::event::Error<BlueNRGError>> {
     require_len_at_least!(buffer, 5);
 
-    let data_len = buffer[4] as usize;
-    if data_len % 2 != 0 {
-        return Err(hci::event::Error::Vendor(
-            BlueNRGError::AttReadMultiplePermitRequestPartial,
-        ));
-    }
+    // BlueNRG-LP firmware reports the number of handles instead of their length in bytes.
+    #[cfg(feature = "bluenrg-lp")]
+    let handle_len = {
+        let handle_len = buffer[4] as usize;
+        require_len!(buffer, 5 + 2 * handle_len);
+        handle_len
+    };
+
+    #[cfg(not(feature = "bluenrg-lp"))]
+    let handle_len = {
+        let data_len = to_data_len(buffer, 4, 5)?;
+        if data_len % 2 != 0 {
+            return Err(hci::event::Error::Vendor(
+                BlueNRGError::AttReadMultiplePermitRequestPartial,
+            ));
+        }
+        data_len / 2
+    };
 
-    let handle_len = data_len / 2;
     let mut handles = [AttributeHandle(0); MAX_ATTRIBUTE_HANDLE_BUFFER_LEN];
     for (i, handle) in handles.iter_mut().enumerate().take(handle_len) {
         let index = 5 + 2 * i;
@@ -2812,7 +4796,7 @@ fn to_att_read_multiple_permit_request(
     Ok(AttReadMultiplePermitRequest {
         conn_handle: ConnectionHandle(LittleEndian::read_u16(&buffer[2..])),
         handles_len: handle_len,
-        handles_buf: handles,
+        handles_buf: Payload::new(handles),
     })
 }
 
@@ -2832,7 +4816,7 @@ pub struct GattTxPoolAvailable {
 fn to_gatt_tx_pool_available(
     buffer: &[u8],
 ) -> Result<GattTxPoolAvailable, hci::event::Error<BlueNRGError>> {
-    require_len!(buffer, 6);
+    require_fixed_len!(buffer, 6);
     Ok(GattTxPoolAvailable {
         conn_handle: ConnectionHandle(LittleEndian::read_u16(&buffer[2..])),
         available_buffers: LittleEndian::read_u16(&buffer[4..]) as usize,
@@ -2850,7 +4834,7 @@ fn to_gatt_tx_pool_available(
 /// the attribute will not be modified and an error response will be sent to the client, with the
 /// error code as specified by the application.
 #[cfg(feature = "ms")]
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 pub struct AttPrepareWritePermitRequest {
     /// Connection handle on which the GATT procedure is running.
     pub conn_handle: ConnectionHandle,
@@ -2862,13 +4846,13 @@ pub struct AttPrepareWritePermitRequest {
     // Number of valid bytes in `value_buf`
     value_len: usize,
     // The data to be written. Only the first `value_len` bytes are valid.
-    value_buf: [u8; MAX_PREPARE_WRITE_PERMIT_REQ_VALUE_LEN],
+    value_buf: Payload<[u8; MAX_PREPARE_WRITE_PERMIT_REQ_VALUE_LEN]>,
 }
 
-// The maximum number of bytes in the buffer is the max HCI packet size (255) less the other data in
-// the packet.
+// The maximum number of bytes in the buffer is the max HCI packet size less the other data in the
+// packet.
 #[cfg(feature = "ms")]
-const MAX_PREPARE_WRITE_PERMIT_REQ_VALUE_LEN: usize = 246;
+const MAX_PREPARE_WRITE_PERMIT_REQ_VALUE_LEN: usize = MAX_EVENT_LEN - 9;
 
 #[cfg(feature = "ms")]
 impl Debug for AttPrepareWritePermitRequest {
@@ -2898,8 +4882,7 @@ fn to_att_prepare_write_permit_request(
 ) -> Result<AttPrepareWritePermitRequest, hci::event::Error<BlueNRGError>> {
     require_len_at_least!(buffer, 9);
 
-    let data_len = buffer[8] as usize;
-    require_len!(buffer, 9 + data_len);
+    let data_len = to_data_len(buffer, 8, 9)?;
 
     let mut value_buf = [0; MAX_PREPARE_WRITE_PERMIT_REQ_VALUE_LEN];
     value_buf[..data_len].copy_from_slice(&buffer[9..]);
@@ -2908,6 +4891,194 @@ fn to_att_prepare_write_permit_request(
         attribute_handle: AttributeHandle(LittleEndian::read_u16(&buffer[4..])),
         offset: LittleEndian::read_u16(&buffer[6..]) as usize,
         value_len: data_len,
-        value_buf,
+        value_buf: Payload::new(value_buf),
+    })
+}
+
+/// This event is generated when a client receives an ATT Multiple Handle Value Notification. See
+/// the Bluetooth Core v5.2 spec, Vol 3, Part F, section 3.4.7.4.
+#[derive(Clone)]
+pub struct GattMultipleNotification {
+    /// The connection handle related to the event.
+    pub conn_handle: ConnectionHandle,
+
+    // Number of valid bytes in `handle_value_buf`
+    data_len: usize,
+    // Raw data of the notification. Contains 2 octets for the attribute handle, 2 octets for the
+    // value length, and then the value. These tuples repeat for `data_len` bytes.
+    handle_value_buf: Payload<[u8; MAX_MULTIPLE_NOTIFICATION_BUF_LEN]>,
+}
+
+// The maximum amount of data in the buffer is the max HCI packet size less the other data in the
+// packet.
+const MAX_MULTIPLE_NOTIFICATION_BUF_LEN: usize = MAX_EVENT_LEN - 5;
+
+impl Debug for GattMultipleNotification {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{{.conn_handle = {:?}, ", self.conn_handle)?;
+        for handle_value_pair in self.handle_value_pair_iter() {
+            write!(
+                f,
+                "{{handle: {:?}, value: {:?}}}",
+                handle_value_pair.handle,
+                first_16(handle_value_pair.value)
+            )?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl GattMultipleNotification {
+    /// Return an iterator over all handle-value pairs in the notification.
+    pub fn handle_value_pair_iter(&self) -> MultipleNotificationIterator {
+        MultipleNotificationIterator {
+            event: &self,
+            index: 0,
+        }
+    }
+}
+
+/// Iterator over the handle-value pairs in a [GATT Multiple
+/// Notification](GattMultipleNotification).
+pub struct MultipleNotificationIterator<'a> {
+    event: &'a GattMultipleNotification,
+    index: usize,
+}
+
+impl<'a> Iterator for MultipleNotificationIterator<'a> {
+    type Item = HandleValuePair<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.event.data_len {
+            return None;
+        }
+
+        let handle_index = self.index;
+        let value_len =
+            LittleEndian::read_u16(&self.event.handle_value_buf[handle_index + 2..]) as usize;
+        let value_index = self.index + 4;
+        self.index = value_index + value_len;
+        Some(HandleValuePair {
+            handle: AttributeHandle(LittleEndian::read_u16(
+                &self.event.handle_value_buf[handle_index..],
+            )),
+            value: &self.event.handle_value_buf[value_index..self.index],
+        })
+    }
+}
+
+impl<'a> IntoIterator for &'a GattMultipleNotification {
+    type Item = HandleValuePair<'a>;
+    type IntoIter = MultipleNotificationIterator<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.handle_value_pair_iter()
+    }
+}
+
+#[cfg(feature = "att-client-events")]
+fn to_gatt_multiple_notification(
+    buffer: &[u8],
+) -> Result<GattMultipleNotification, hci::event::Error<BlueNRGError>> {
+    require_len_at_least!(buffer, 5);
+
+    let data_len = to_data_len(buffer, 4, 5)?;
+    let handle_value_buf = &buffer[5..];
+
+    // Make sure the tuples exactly fill the data, so the iterator never reads past the end.
+    let mut index = 0;
+    while index < handle_value_buf.len() {
+        if handle_value_buf.len() - index < 4 {
+            return Err(hci::event::Error::Vendor(
+                BlueNRGError::GattMultipleNotificationPartial,
+            ));
+        }
+
+        index += 4 + LittleEndian::read_u16(&handle_value_buf[index + 2..]) as usize;
+    }
+    if index != handle_value_buf.len() {
+        return Err(hci::event::Error::Vendor(
+            BlueNRGError::GattMultipleNotificationPartial,
+        ));
+    }
+
+    let mut full_handle_value_buf = [0; MAX_MULTIPLE_NOTIFICATION_BUF_LEN];
+    full_handle_value_buf[..data_len].copy_from_slice(handle_value_buf);
+
+    Ok(GattMultipleNotification {
+        conn_handle: ConnectionHandle(LittleEndian::read_u16(&buffer[2..])),
+        data_len,
+        handle_value_buf: Payload::new(full_handle_value_buf),
+    })
+}
+
+/// Parameters for the BlueNRG-LP [extended GATT Indication](BlueNRGEvent::GattIndicationExt)
+/// event.
+#[derive(Clone)]
+pub struct ExtendedAttributeValue {
+    /// The connection handle related to the event.
+    pub conn_handle: ConnectionHandle,
+
+    /// The handle of the attribute.
+    pub attribute_handle: AttributeHandle,
+
+    /// Offset of the reported value inside the attribute.
+    pub offset: usize,
+
+    /// If the entire value of the attribute does not fit inside a single event, this is true to
+    /// notify that other events will follow to report the remaining value.
+    pub continued: bool,
+
+    // Number of valid bytes in value_buf
+    value_len: usize,
+    // Value of the attribute, starting from the given offset. Only the first value_len bytes are
+    // valid.
+    value_buf: Payload<[u8; MAX_EXTENDED_ATTRIBUTE_VALUE_LEN]>,
+}
+
+// The maximum amount of data in the buffer is the max HCI packet size less the other data in the
+// packet.
+const MAX_EXTENDED_ATTRIBUTE_VALUE_LEN: usize = MAX_EVENT_LEN - 10;
+
+impl Debug for ExtendedAttributeValue {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(
+            f,
+            "{{.conn_handle = {:?}, .attribute_handle = {:?}, .offset = {}, .continued = {}, \
+             .value = {:?}}}",
+            self.conn_handle,
+            self.attribute_handle,
+            self.offset,
+            self.continued,
+            first_16(self.value())
+        )
+    }
+}
+
+impl ExtendedAttributeValue {
+    /// Returns the value of the attribute, starting from the given offset.
+    pub fn value(&self) -> &[u8] {
+        &self.value_buf[..self.value_len]
+    }
+}
+
+#[cfg(all(feature = "bluenrg-lp", feature = "att-client-events"))]
+fn to_extended_attribute_value(
+    buffer: &[u8],
+) -> Result<ExtendedAttributeValue, hci::event::Error<BlueNRGError>> {
+    require_len_at_least!(buffer, 10);
+
+    let value_len = LittleEndian::read_u16(&buffer[8..]) as usize;
+    require_len!(buffer, 10 + value_len);
+
+    let mut value_buf = [0; MAX_EXTENDED_ATTRIBUTE_VALUE_LEN];
+    value_buf[..value_len].copy_from_slice(&buffer[10..]);
+
+    let offset_field = LittleEndian::read_u16(&buffer[4..]);
+    Ok(ExtendedAttributeValue {
+        conn_handle: ConnectionHandle(LittleEndian::read_u16(&buffer[2..])),
+        attribute_handle: AttributeHandle(LittleEndian::read_u16(&buffer[6..])),
+        offset: (offset_field & 0x7FFF) as usize,
+        continued: (offset_field & 0x8000) > 0,
+        value_len,
+        value_buf: Payload::new(value_buf),
     })
 }
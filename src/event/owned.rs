@@ -0,0 +1,440 @@
+//! Owned, `Vec`-backed mirrors of the events that hold their payloads in fixed buffers.
+//!
+//! The events in this crate keep their payloads in buffers sized for the largest possible event, so
+//! they can be parsed without allocating. Host-side tools (log analysis, bridges, tests) usually
+//! keep many events around, and would rather hold only the bytes that were received. Each mirror
+//! here converts `From` a reference to its event, and holds the same fields with the payload
+//! copied into a `Vec`.
+
+use super::*;
+use std::vec::Vec;
+
+/// Owned copy of [`FaultData`].
+#[cfg(feature = "ms")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedFaultData {
+    /// Fault reason.
+    pub reason: CrashReason,
+    /// MCP SP register
+    pub sp: u32,
+    /// MCU R0 register
+    pub r0: u32,
+    /// MCU R1 register
+    pub r1: u32,
+    /// MCU R2 register
+    pub r2: u32,
+    /// MCU R3 register
+    pub r3: u32,
+    /// MCU R12 register
+    pub r12: u32,
+    /// MCU LR register
+    pub lr: u32,
+    /// MCU PC register
+    pub pc: u32,
+    /// MCU xPSR register
+    pub xpsr: u32,
+    /// Additional crash dump data.
+    pub debug_data: Vec<u8>,
+}
+
+#[cfg(feature = "ms")]
+impl From<&FaultData> for OwnedFaultData {
+    fn from(data: &FaultData) -> Self {
+        OwnedFaultData {
+            reason: data.reason,
+            sp: data.sp,
+            r0: data.r0,
+            r1: data.r1,
+            r2: data.r2,
+            r3: data.r3,
+            r12: data.r12,
+            lr: data.lr,
+            pc: data.pc,
+            xpsr: data.xpsr,
+            debug_data: data.debug_data().to_vec(),
+        }
+    }
+}
+
+/// Owned copy of [`GapDeviceFound`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedGapDeviceFound {
+    /// Type of event
+    pub event: GapDeviceFoundEvent,
+    /// Address of the peer device found during scanning
+    pub bdaddr: BdAddrType,
+    /// Advertising or scan response data.
+    pub data: Vec<u8>,
+    /// Received signal strength indicator (range: -127 - 20).
+    pub rssi: Option<i8>,
+}
+
+impl From<&GapDeviceFound> for OwnedGapDeviceFound {
+    fn from(event: &GapDeviceFound) -> Self {
+        OwnedGapDeviceFound {
+            event: event.event,
+            bdaddr: event.bdaddr,
+            data: event.data().to_vec(),
+            rssi: event.rssi,
+        }
+    }
+}
+
+/// Owned copy of [`GattAttributeModified`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedGattAttributeModified {
+    /// The connection handle which modified the attribute
+    pub conn_handle: ConnectionHandle,
+    /// Handle of the attribute that was modified
+    pub attr_handle: AttributeHandle,
+    /// Offset of the reported value inside the attribute, if the firmware reports it.
+    pub offset: Option<usize>,
+    /// True if other events will follow to report the remaining value, if the firmware reports it.
+    pub continued: Option<bool>,
+    /// The new attribute value, starting from the given offset.
+    pub data: Vec<u8>,
+}
+
+impl From<&GattAttributeModified> for OwnedGattAttributeModified {
+    fn from(event: &GattAttributeModified) -> Self {
+        OwnedGattAttributeModified {
+            conn_handle: event.conn_handle,
+            attr_handle: event.attr_handle,
+            offset: event.offset,
+            continued: event.continued,
+            data: event.data().to_vec(),
+        }
+    }
+}
+
+/// Owned copy of [`AttReadResponse`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedAttReadResponse {
+    /// The connection handle related to the response.
+    pub conn_handle: ConnectionHandle,
+    /// The value that was read.
+    pub value: Vec<u8>,
+}
+
+impl From<&AttReadResponse> for OwnedAttReadResponse {
+    fn from(event: &AttReadResponse) -> Self {
+        OwnedAttReadResponse {
+            conn_handle: event.conn_handle,
+            value: event.value().to_vec(),
+        }
+    }
+}
+
+/// Owned copy of [`AttPrepareWriteResponse`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedAttPrepareWriteResponse {
+    /// The connection handle related to the response.
+    pub conn_handle: ConnectionHandle,
+    /// The handle of the attribute to be written.
+    pub attribute_handle: AttributeHandle,
+    /// The offset of the first octet to be written.
+    pub offset: usize,
+    /// The partial value of the attribute to be written.
+    pub value: Vec<u8>,
+}
+
+impl From<&AttPrepareWriteResponse> for OwnedAttPrepareWriteResponse {
+    fn from(event: &AttPrepareWriteResponse) -> Self {
+        OwnedAttPrepareWriteResponse {
+            conn_handle: event.conn_handle,
+            attribute_handle: event.attribute_handle,
+            offset: event.offset,
+            value: event.value().to_vec(),
+        }
+    }
+}
+
+/// Owned copy of [`AttributeValue`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedAttributeValue {
+    /// The connection handle related to the event.
+    pub conn_handle: ConnectionHandle,
+    /// The handle of the attribute.
+    pub attribute_handle: AttributeHandle,
+    /// Current value of the attribute.
+    pub value: Vec<u8>,
+}
+
+impl From<&AttributeValue> for OwnedAttributeValue {
+    fn from(event: &AttributeValue) -> Self {
+        OwnedAttributeValue {
+            conn_handle: event.conn_handle,
+            attribute_handle: event.attribute_handle,
+            value: event.value().to_vec(),
+        }
+    }
+}
+
+/// Owned copy of [`AttReadMultiplePermitRequest`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedAttReadMultiplePermitRequest {
+    /// Handle of the connection which requested to read the attribute.
+    pub conn_handle: ConnectionHandle,
+    /// Attribute handles to be read.
+    pub handles: Vec<AttributeHandle>,
+}
+
+impl From<&AttReadMultiplePermitRequest> for OwnedAttReadMultiplePermitRequest {
+    fn from(event: &AttReadMultiplePermitRequest) -> Self {
+        OwnedAttReadMultiplePermitRequest {
+            conn_handle: event.conn_handle,
+            handles: event.handles().to_vec(),
+        }
+    }
+}
+
+/// Owned copy of [`AttPrepareWritePermitRequest`].
+#[cfg(feature = "ms")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedAttPrepareWritePermitRequest {
+    /// Connection handle on which the GATT procedure is running.
+    pub conn_handle: ConnectionHandle,
+    /// The handle of the attribute to be written.
+    pub attribute_handle: AttributeHandle,
+    /// The offset of the first octet to be written.
+    pub offset: usize,
+    /// The data to be written.
+    pub value: Vec<u8>,
+}
+
+#[cfg(feature = "ms")]
+impl From<&AttPrepareWritePermitRequest> for OwnedAttPrepareWritePermitRequest {
+    fn from(event: &AttPrepareWritePermitRequest) -> Self {
+        OwnedAttPrepareWritePermitRequest {
+            conn_handle: event.conn_handle,
+            attribute_handle: event.attribute_handle,
+            offset: event.offset,
+            value: event.value().to_vec(),
+        }
+    }
+}
+
+/// Owned copy of [`ExtendedAttributeValue`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedExtendedAttributeValue {
+    /// The connection handle related to the event.
+    pub conn_handle: ConnectionHandle,
+    /// The handle of the attribute.
+    pub attribute_handle: AttributeHandle,
+    /// Offset of the reported value inside the attribute.
+    pub offset: usize,
+    /// True if other events will follow to report the remaining value.
+    pub continued: bool,
+    /// Value of the attribute, starting from the given offset.
+    pub value: Vec<u8>,
+}
+
+impl From<&ExtendedAttributeValue> for OwnedExtendedAttributeValue {
+    fn from(event: &ExtendedAttributeValue) -> Self {
+        OwnedExtendedAttributeValue {
+            conn_handle: event.conn_handle,
+            attribute_handle: event.attribute_handle,
+            offset: event.offset,
+            continued: event.continued,
+            value: event.value().to_vec(),
+        }
+    }
+}
+
+/// Owned copy of [`GapProcedureComplete`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedGapProcedureComplete {
+    /// Type of procedure that completed
+    pub procedure: OwnedGapProcedure,
+    /// Status of the procedure
+    pub status: GapProcedureStatus,
+}
+
+impl From<&GapProcedureComplete> for OwnedGapProcedureComplete {
+    fn from(event: &GapProcedureComplete) -> Self {
+        OwnedGapProcedureComplete {
+            procedure: OwnedGapProcedure::from(&event.procedure),
+            status: event.status,
+        }
+    }
+}
+
+/// Owned copy of [`GapProcedure`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum OwnedGapProcedure {
+    /// See [`GapProcedure::LimitedDiscovery`].
+    LimitedDiscovery,
+    /// See [`GapProcedure::GeneralDiscovery`].
+    GeneralDiscovery,
+    /// See [`GapProcedure::NameDiscovery`]. Contains the name.
+    NameDiscovery(Vec<u8>),
+    /// See [`GapProcedure::AutoConnectionEstablishment`].
+    AutoConnectionEstablishment,
+    /// See [`GapProcedure::GeneralConnectionEstablishment`]. Contains the reconnection address.
+    GeneralConnectionEstablishment(BdAddr),
+    /// See [`GapProcedure::SelectiveConnectionEstablishment`].
+    SelectiveConnectionEstablishment,
+    /// See [`GapProcedure::DirectConnectionEstablishment`].
+    DirectConnectionEstablishment,
+    /// See [`GapProcedure::Multiple`]. Contains the procedures.
+    Multiple(GapProcedureFlags),
+}
+
+impl From<&GapProcedure> for OwnedGapProcedure {
+    fn from(procedure: &GapProcedure) -> Self {
+        match *procedure {
+            GapProcedure::LimitedDiscovery => OwnedGapProcedure::LimitedDiscovery,
+            GapProcedure::GeneralDiscovery => OwnedGapProcedure::GeneralDiscovery,
+            GapProcedure::NameDiscovery(len, ref name) => {
+                OwnedGapProcedure::NameDiscovery(name[..len].to_vec())
+            }
+            GapProcedure::AutoConnectionEstablishment => {
+                OwnedGapProcedure::AutoConnectionEstablishment
+            }
+            GapProcedure::GeneralConnectionEstablishment(addr) => {
+                OwnedGapProcedure::GeneralConnectionEstablishment(addr)
+            }
+            GapProcedure::SelectiveConnectionEstablishment => {
+                OwnedGapProcedure::SelectiveConnectionEstablishment
+            }
+            GapProcedure::DirectConnectionEstablishment => {
+                OwnedGapProcedure::DirectConnectionEstablishment
+            }
+            GapProcedure::Multiple(procedures) => OwnedGapProcedure::Multiple(procedures),
+        }
+    }
+}
+
+/// Owned copy of [`AttFindInformationResponse`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedAttFindInformationResponse {
+    /// The connection handle related to the response
+    pub conn_handle: ConnectionHandle,
+    /// The handle-UUID pairs, in ascending order of attribute handles.
+    pub handle_uuid_pairs: Vec<(AttributeHandle, crate::gatt::Uuid)>,
+}
+
+impl From<&AttFindInformationResponse> for OwnedAttFindInformationResponse {
+    fn from(event: &AttFindInformationResponse) -> Self {
+        OwnedAttFindInformationResponse {
+            conn_handle: event.conn_handle,
+            handle_uuid_pairs: event.handle_uuid_pair_iter().collect(),
+        }
+    }
+}
+
+/// Owned copy of [`AttFindByTypeValueResponse`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedAttFindByTypeValueResponse {
+    /// The connection handle related to the response.
+    pub conn_handle: ConnectionHandle,
+    /// Handles Information List as defined in Bluetooth Core v4.1 spec.
+    pub handles: Vec<HandleInfoPair>,
+}
+
+impl From<&AttFindByTypeValueResponse> for OwnedAttFindByTypeValueResponse {
+    fn from(event: &AttFindByTypeValueResponse) -> Self {
+        OwnedAttFindByTypeValueResponse {
+            conn_handle: event.conn_handle,
+            handles: event.handle_pairs_iter().collect(),
+        }
+    }
+}
+
+/// Owned copy of a [`HandleValuePair`], as returned by the [ATT Read by Type
+/// response](AttReadByTypeResponse) and the [GATT Multiple Notification](GattMultipleNotification).
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedValuePair {
+    /// Attribute handle
+    pub handle: AttributeHandle,
+    /// Attribute value
+    pub value: Vec<u8>,
+}
+
+impl From<HandleValuePair<'_>> for OwnedValuePair {
+    fn from(pair: HandleValuePair) -> Self {
+        OwnedValuePair {
+            handle: pair.handle,
+            value: pair.value.to_vec(),
+        }
+    }
+}
+
+/// Owned copy of [`AttReadByTypeResponse`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedAttReadByTypeResponse {
+    /// The connection handle related to the response.
+    pub conn_handle: ConnectionHandle,
+    /// The handle-value pairs returned with the response.
+    pub handle_value_pairs: Vec<OwnedValuePair>,
+}
+
+impl From<&AttReadByTypeResponse> for OwnedAttReadByTypeResponse {
+    fn from(event: &AttReadByTypeResponse) -> Self {
+        OwnedAttReadByTypeResponse {
+            conn_handle: event.conn_handle,
+            handle_value_pairs: event
+                .handle_value_pair_iter()
+                .map(OwnedValuePair::from)
+                .collect(),
+        }
+    }
+}
+
+/// Owned copy of an [`AttributeData`] returned by the [ATT Read by Group Type
+/// response](AttReadByGroupTypeResponse).
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedAttributeGroup {
+    /// Attribute handle
+    pub attribute_handle: AttributeHandle,
+    /// Group end handle
+    pub group_end_handle: GroupEndHandle,
+    /// Attribute value
+    pub value: Vec<u8>,
+}
+
+/// Owned copy of [`AttReadByGroupTypeResponse`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedAttReadByGroupTypeResponse {
+    ///  The connection handle related to the response.
+    pub conn_handle: ConnectionHandle,
+    /// The attribute data returned with the response.
+    pub attribute_data: Vec<OwnedAttributeGroup>,
+}
+
+impl From<&AttReadByGroupTypeResponse> for OwnedAttReadByGroupTypeResponse {
+    fn from(event: &AttReadByGroupTypeResponse) -> Self {
+        OwnedAttReadByGroupTypeResponse {
+            conn_handle: event.conn_handle,
+            attribute_data: event
+                .attribute_data_iter()
+                .map(|data| OwnedAttributeGroup {
+                    attribute_handle: data.attribute_handle,
+                    group_end_handle: data.group_end_handle,
+                    value: data.value.to_vec(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Owned copy of [`GattMultipleNotification`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedGattMultipleNotification {
+    /// The connection handle related to the event.
+    pub conn_handle: ConnectionHandle,
+    /// The handle-value pairs in the notification.
+    pub handle_value_pairs: Vec<OwnedValuePair>,
+}
+
+impl From<&GattMultipleNotification> for OwnedGattMultipleNotification {
+    fn from(event: &GattMultipleNotification) -> Self {
+        OwnedGattMultipleNotification {
+            conn_handle: event.conn_handle,
+            handle_value_pairs: event
+                .handle_value_pair_iter()
+                .map(OwnedValuePair::from)
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,242 @@
+//! Periodic advertising events.
+//!
+//! A receiver that synchronizes to periodic advertising (for example, a broadcast audio receiver)
+//! gets the standard LE Periodic Advertising Sync Established and LE Periodic Advertising Report
+//! events. These are LE meta events, but the Bluetooth HCI crate does not decode them, so this
+//! module provides the decoding. Pass the LE meta event parameters, starting with the subevent
+//! code, to [`PeriodicAdvertisingEvent::new`].
+
+extern crate bluetooth_hci as hci;
+
+use super::{BlueNRGError, Payload, Status};
+use byteorder::{ByteOrder, LittleEndian};
+use core::convert::{TryFrom, TryInto};
+use core::fmt::{Debug, Formatter, Result as FmtResult};
+use core::time::Duration;
+
+pub use super::phy::Phy;
+
+/// Periodic advertising events.
+#[derive(Clone, Debug)]
+pub enum PeriodicAdvertisingEvent {
+    /// The controller has synchronized to the periodic advertising train, or failed to do so.
+    SyncEstablished(PeriodicAdvertisingSyncEstablished),
+
+    /// The controller has received a periodic advertising packet.
+    Report(PeriodicAdvertisingReport),
+}
+
+impl PeriodicAdvertisingEvent {
+    /// Deserializes a periodic advertising event from the LE meta event parameters. The buffer
+    /// must start with the subevent code.
+    ///
+    /// # Errors
+    ///
+    /// - [UnknownEvent](BlueNRGError::UnknownEvent) if the subevent code is not one of the periodic
+    ///   advertising events. Includes the subevent code.
+    /// - [BadLength](hci::event::Error::BadLength) if the buffer is not the right size for the
+    ///   event.
+    /// - [BadBdAddrType](BlueNRGError::BadBdAddrType),
+    ///   [BadPhy](BlueNRGError::BadPhy), or
+    ///   [BadPeriodicAdvertisingDataStatus](BlueNRGError::BadPeriodicAdvertisingDataStatus) if a
+    ///   field contains an invalid value.
+    pub fn new(buffer: &[u8]) -> Result<PeriodicAdvertisingEvent, hci::event::Error<BlueNRGError>> {
+        require_len_at_least!(buffer, 1);
+
+        match buffer[0] {
+            0x0E => Ok(PeriodicAdvertisingEvent::SyncEstablished(
+                to_periodic_advertising_sync_established(buffer)?,
+            )),
+            0x0F => Ok(PeriodicAdvertisingEvent::Report(
+                to_periodic_advertising_report(buffer)?,
+            )),
+            subevent => Err(hci::event::Error::Vendor(BlueNRGError::UnknownEvent(
+                u16::from(subevent),
+            ))),
+        }
+    }
+}
+
+/// Newtype for the handle that identifies a periodic advertising train.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SyncHandle(pub u16);
+
+/// Parameters for the [Sync Established](PeriodicAdvertisingEvent::SyncEstablished) event.
+#[derive(Copy, Clone, Debug)]
+pub struct PeriodicAdvertisingSyncEstablished {
+    /// Did the controller synchronize to the periodic advertising, and if not, why?
+    pub status: hci::Status<Status>,
+
+    /// Handle that identifies the periodic advertising train in subsequent reports.
+    pub sync_handle: SyncHandle,
+
+    /// Advertising set identifier (SID) of the periodic advertising.
+    pub advertising_sid: u8,
+
+    /// Address of the periodic advertiser. If the controller resolved the advertiser's private
+    /// address, this is the advertiser's identity address.
+    pub advertiser_address: hci::BdAddrType,
+
+    /// True if the controller resolved the advertiser's private address with the resolving list,
+    /// and [`advertiser_address`](PeriodicAdvertisingSyncEstablished::advertiser_address) is the
+    /// identity address it resolved to.
+    pub advertiser_address_resolved: bool,
+
+    /// PHY used by the periodic advertiser.
+    pub advertiser_phy: Phy,
+
+    /// Interval between periodic advertising events.
+    pub periodic_advertising_interval: Duration,
+
+    /// Clock accuracy of the advertiser, encoded as in the Bluetooth specification (0 for 500 ppm
+    /// through 7 for 20 ppm).
+    pub advertiser_clock_accuracy: u8,
+}
+
+fn to_periodic_advertising_sync_established(
+    buffer: &[u8],
+) -> Result<PeriodicAdvertisingSyncEstablished, hci::event::Error<BlueNRGError>> {
+    require_len!(buffer, 16);
+
+    let mut addr = hci::BdAddr([0; 6]);
+    addr.0.copy_from_slice(&buffer[6..12]);
+
+    // Address types 0x02 and 0x03 are the public and random (static) identity addresses that the
+    // controller resolved from a resolvable private address.
+    let (addr_type, advertiser_address_resolved) = match buffer[5] {
+        0x02 => (0x00, true),
+        0x03 => (0x01, true),
+        other => (other, false),
+    };
+
+    // Periodic advertising interval value: T = N * 1.25 ms
+    let interval = LittleEndian::read_u16(&buffer[13..]);
+
+    Ok(PeriodicAdvertisingSyncEstablished {
+        status: buffer[1]
+            .try_into()
+            .map_err(hci::event::rewrap_bad_status)?,
+        sync_handle: SyncHandle(LittleEndian::read_u16(&buffer[2..])),
+        advertising_sid: buffer[4],
+        advertiser_address: hci::to_bd_addr_type(addr_type, addr)
+            .map_err(|_| hci::event::Error::Vendor(BlueNRGError::BadBdAddrType(buffer[5])))?,
+        advertiser_address_resolved,
+        advertiser_phy: buffer[12].try_into().map_err(hci::event::Error::Vendor)?,
+        periodic_advertising_interval: Duration::from_micros(1250 * u64::from(interval)),
+        advertiser_clock_accuracy: buffer[15],
+    })
+}
+
+/// Completeness of the data in a [periodic advertising report](PeriodicAdvertisingReport).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DataStatus {
+    /// The data is complete.
+    Complete,
+    /// The data is incomplete, and more data will follow in the next report.
+    Incomplete,
+    /// The data is incomplete, and no more data will follow.
+    Truncated,
+}
+
+impl TryFrom<u8> for DataStatus {
+    type Error = BlueNRGError;
+
+    fn try_from(value: u8) -> Result<DataStatus, Self::Error> {
+        match value {
+            0x00 => Ok(DataStatus::Complete),
+            0x01 => Ok(DataStatus::Incomplete),
+            0x02 => Ok(DataStatus::Truncated),
+            _ => Err(BlueNRGError::BadPeriodicAdvertisingDataStatus(value)),
+        }
+    }
+}
+
+/// Parameters for the [Report](PeriodicAdvertisingEvent::Report) event.
+#[derive(Clone)]
+pub struct PeriodicAdvertisingReport {
+    /// Handle that identifies the periodic advertising train.
+    pub sync_handle: SyncHandle,
+
+    /// Transmit power of the advertiser, in dBm, if it is available.
+    pub tx_power: Option<i8>,
+
+    /// Received signal strength, in dBm, if it is available.
+    pub rssi: Option<i8>,
+
+    /// Is the data complete?
+    pub data_status: DataStatus,
+
+    // Number of valid bytes in data_buf
+    data_len: usize,
+    // Periodic advertising data. Only the first data_len bytes are valid.
+    data_buf: Payload<[u8; MAX_PERIODIC_ADVERTISING_DATA_LEN]>,
+}
+
+// The maximum amount of data in the buffer is the max event length less the 8 bytes before the
+// data: the subevent code, sync handle, TX power, RSSI, unused byte, data status, and data length.
+const MAX_PERIODIC_ADVERTISING_DATA_LEN: usize = super::MAX_EVENT_LEN - 8;
+
+impl Debug for PeriodicAdvertisingReport {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(
+            f,
+            "{{.sync_handle = {:?}, .tx_power = {:?}, .rssi = {:?}, .data_status = {:?}, \
+             .data = {:?}}}",
+            self.sync_handle,
+            self.tx_power,
+            self.rssi,
+            self.data_status,
+            super::first_16(self.data())
+        )
+    }
+}
+
+impl PeriodicAdvertisingReport {
+    /// Returns the periodic advertising data.
+    pub fn data(&self) -> &[u8] {
+        &self.data_buf[..self.data_len]
+    }
+}
+
+fn to_periodic_advertising_report(
+    buffer: &[u8],
+) -> Result<PeriodicAdvertisingReport, hci::event::Error<BlueNRGError>> {
+    const TX_POWER_UNAVAILABLE: i8 = 127;
+    const RSSI_UNAVAILABLE: i8 = 127;
+
+    require_len_at_least!(buffer, 8);
+
+    let data_len = buffer[7] as usize;
+    require_len!(buffer, 8 + data_len);
+    if data_len > MAX_PERIODIC_ADVERTISING_DATA_LEN {
+        return Err(hci::event::Error::BadLength(
+            buffer.len(),
+            8 + MAX_PERIODIC_ADVERTISING_DATA_LEN,
+        ));
+    }
+
+    // Byte 5 is unused in Bluetooth 5.0 (later versions report the CTE type there).
+
+    let tx_power = buffer[3] as i8;
+    let rssi = buffer[4] as i8;
+
+    let mut data_buf = [0; MAX_PERIODIC_ADVERTISING_DATA_LEN];
+    data_buf[..data_len].copy_from_slice(&buffer[8..]);
+
+    Ok(PeriodicAdvertisingReport {
+        sync_handle: SyncHandle(LittleEndian::read_u16(&buffer[1..])),
+        tx_power: if tx_power == TX_POWER_UNAVAILABLE {
+            None
+        } else {
+            Some(tx_power)
+        },
+        rssi: if rssi == RSSI_UNAVAILABLE {
+            None
+        } else {
+            Some(rssi)
+        },
+        data_status: buffer[6].try_into().map_err(hci::event::Error::Vendor)?,
+        data_len,
+        data_buf: Payload::new(data_buf),
+    })
+}
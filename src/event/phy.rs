@@ -0,0 +1,113 @@
+//! PHY events.
+//!
+//! After the application requests PHYs with [`le_set_phy`](crate::hal::Commands::le_set_phy), the
+//! controller reports the PHYs it negotiated with the peer in the standard LE PHY Update Complete
+//! event. This is an LE meta event, but the Bluetooth HCI crate does not decode it, so this module
+//! provides the decoding. Pass the LE meta event parameters, starting with the subevent code, to
+//! [`LePhyUpdateComplete::new`].
+
+extern crate bluetooth_hci as hci;
+
+use super::{BlueNRGError, Status};
+use crate::hal::PhyMask;
+use byteorder::{ByteOrder, LittleEndian};
+use core::convert::{TryFrom, TryInto};
+
+/// A single PHY, as reported by the controller.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Phy {
+    /// LE 1M PHY.
+    Le1M,
+    /// LE 2M PHY.
+    Le2M,
+    /// LE Coded PHY.
+    LeCoded,
+}
+
+impl TryFrom<u8> for Phy {
+    type Error = BlueNRGError;
+
+    fn try_from(value: u8) -> Result<Phy, Self::Error> {
+        match value {
+            0x01 => Ok(Phy::Le1M),
+            0x02 => Ok(Phy::Le2M),
+            0x03 => Ok(Phy::LeCoded),
+            _ => Err(BlueNRGError::BadPhy(value)),
+        }
+    }
+}
+
+impl From<Phy> for PhyMask {
+    fn from(phy: Phy) -> PhyMask {
+        match phy {
+            Phy::Le1M => PhyMask::LE_1M,
+            Phy::Le2M => PhyMask::LE_2M,
+            Phy::LeCoded => PhyMask::LE_CODED,
+        }
+    }
+}
+
+// Subevent code of the LE PHY Update Complete event.
+const PHY_UPDATE_COMPLETE: u8 = 0x0C;
+
+/// Parameters for the LE PHY Update Complete event.
+#[derive(Copy, Clone, Debug)]
+pub struct LePhyUpdateComplete {
+    /// Did the PHY update procedure succeed, and if not, why?
+    pub status: hci::Status<Status>,
+
+    /// Connection on which the PHYs were updated.
+    pub conn_handle: hci::ConnectionHandle,
+
+    /// PHY the controller now uses to transmit on the connection. Only reported if the procedure
+    /// succeeded.
+    pub tx_phy: Option<Phy>,
+
+    /// PHY the controller now uses to receive on the connection. Only reported if the procedure
+    /// succeeded.
+    pub rx_phy: Option<Phy>,
+}
+
+impl LePhyUpdateComplete {
+    /// Deserializes the event from the LE meta event parameters. The buffer must start with the
+    /// subevent code.
+    ///
+    /// # Errors
+    ///
+    /// - [UnknownEvent](BlueNRGError::UnknownEvent) if the subevent code is not LE PHY Update
+    ///   Complete (0x0C). Includes the subevent code.
+    /// - [BadLength](hci::event::Error::BadLength) if the buffer is not the right size for the
+    ///   event.
+    /// - [BadPhy](BlueNRGError::BadPhy) if the procedure succeeded and either PHY is not
+    ///   recognized.
+    pub fn new(buffer: &[u8]) -> Result<LePhyUpdateComplete, hci::event::Error<BlueNRGError>> {
+        require_len_at_least!(buffer, 1);
+        if buffer[0] != PHY_UPDATE_COMPLETE {
+            return Err(hci::event::Error::Vendor(BlueNRGError::UnknownEvent(
+                u16::from(buffer[0]),
+            )));
+        }
+
+        require_len!(buffer, 6);
+        let status: hci::Status<Status> = buffer[1]
+            .try_into()
+            .map_err(hci::event::rewrap_bad_status)?;
+
+        // The PHY fields are not meaningful if the procedure failed.
+        let (tx_phy, rx_phy) = if status == hci::Status::Success {
+            (
+                Some(buffer[4].try_into().map_err(hci::event::Error::Vendor)?),
+                Some(buffer[5].try_into().map_err(hci::event::Error::Vendor)?),
+            )
+        } else {
+            (None, None)
+        };
+
+        Ok(LePhyUpdateComplete {
+            status,
+            conn_handle: hci::ConnectionHandle(LittleEndian::read_u16(&buffer[2..])),
+            tx_phy,
+            rx_phy,
+        })
+    }
+}
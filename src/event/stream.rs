@@ -0,0 +1,128 @@
+//! Parser for logs of concatenated vendor events.
+//!
+//! Applications that log vendor events (for example, to flash in the field) can replay the log on
+//! the host with [`EventStream`]. The log must hold the vendor event packets back to back, each
+//! with its HCI event header: the vendor event code (0xFF), the parameter length, and then the
+//! parameters (the BlueNRG event code followed by the event data). The header is what tells the
+//! stream where each event ends, so it must be logged along with the payload.
+
+extern crate bluetooth_hci as hci;
+
+use super::{BlueNRGEvent, ParseError};
+use hci::event::VendorEvent;
+
+// HCI event code for vendor-specific events.
+const VENDOR_EVENT_CODE: u8 = 0xFF;
+
+// Length of the HCI event header: event code and parameter length.
+const HEADER_LEN: usize = 2;
+
+/// Returns an iterator over the events in a buffer of back-to-back vendor event packets.
+pub fn parse_all(buffer: &[u8]) -> EventStream {
+    EventStream::new(buffer)
+}
+
+/// Iterator over the events in a buffer of back-to-back vendor event packets.
+///
+/// Each item is the next event, or an error that includes the offset of the packet that failed.
+/// If a packet is well-formed but its event cannot be parsed, the stream continues with the next
+/// packet. If the stream finds a byte that cannot start a vendor event packet, it reports the error
+/// and resynchronizes at the next byte that could. If the buffer ends in the middle of a packet,
+/// the stream reports the error and stops.
+pub struct EventStream<'a> {
+    buffer: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> EventStream<'a> {
+    /// Returns an iterator over the events in `buffer`.
+    pub fn new(buffer: &'a [u8]) -> EventStream<'a> {
+        EventStream { buffer, offset: 0 }
+    }
+
+    /// Returns the offset of the next packet in the buffer.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a> Iterator for EventStream<'a> {
+    type Item = Result<BlueNRGEvent, EventStreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.offset;
+        let remaining = &self.buffer[offset..];
+        if remaining.is_empty() {
+            return None;
+        }
+
+        if remaining[0] != VENDOR_EVENT_CODE {
+            // Skip to the next byte that could start a packet.
+            self.offset = match remaining[1..].iter().position(|&b| b == VENDOR_EVENT_CODE) {
+                Some(index) => offset + 1 + index,
+                None => self.buffer.len(),
+            };
+            return Some(Err(EventStreamError::BadEventCode(offset, remaining[0])));
+        }
+
+        if remaining.len() < HEADER_LEN || remaining.len() < HEADER_LEN + remaining[1] as usize {
+            self.offset = self.buffer.len();
+            return Some(Err(EventStreamError::Truncated(offset)));
+        }
+
+        let packet_len = HEADER_LEN + remaining[1] as usize;
+        self.offset += packet_len;
+
+        Some(
+            BlueNRGEvent::new(&remaining[HEADER_LEN..packet_len])
+                .map_err(|e| EventStreamError::BadEvent(offset, e)),
+        )
+    }
+}
+
+/// Errors reported by [`EventStream`].
+#[derive(Copy, Clone, Debug)]
+pub enum EventStreamError {
+    /// The byte at the offset is not the vendor event code, so it does not start a vendor event
+    /// packet. Includes the offset and the byte.
+    BadEventCode(usize, u8),
+
+    /// The buffer ends in the middle of the packet at the offset. Includes the offset.
+    Truncated(usize),
+
+    /// The event in the packet at the offset could not be parsed. Includes the offset and the
+    /// error.
+    BadEvent(usize, hci::event::Error<ParseError>),
+}
+
+impl core::fmt::Display for EventStreamError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            EventStreamError::BadEventCode(offset, code) => write!(
+                f,
+                "event code {:#04x} at offset {} is not a vendor event",
+                code, offset
+            ),
+            EventStreamError::Truncated(offset) => {
+                write!(f, "packet at offset {} is truncated", offset)
+            }
+            EventStreamError::BadEvent(offset, ref e) => {
+                write!(f, "bad event at offset {}: {:?}", offset, e)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EventStreamError {}
+
+impl EventStreamError {
+    /// Returns the offset in the buffer of the packet that failed.
+    pub fn offset(&self) -> usize {
+        match *self {
+            EventStreamError::BadEventCode(offset, _)
+            | EventStreamError::Truncated(offset)
+            | EventStreamError::BadEvent(offset, _) => offset,
+        }
+    }
+}
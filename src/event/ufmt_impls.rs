@@ -0,0 +1,378 @@
+//! Compact formatting of the most commonly logged events through [`ufmt`], for targets where the
+//! code size of `core::fmt` is too large.
+//!
+//! Each value renders as a single line: the variant name followed by its most useful fields.
+//! Handles are written as 4-digit hex, addresses as colon-separated hex starting with the most
+//! significant byte, and everything else as decimal. `uDebug` renders the same line as `uDisplay`.
+
+use super::*;
+use ufmt::{uDebug, uDisplay, uWrite, uwrite, Formatter};
+
+const HEX_DIGITS: &str = "0123456789abcdef";
+
+fn write_hex_u8<W>(f: &mut Formatter<'_, W>, value: u8) -> Result<(), W::Error>
+where
+    W: uWrite + ?Sized,
+{
+    let high = (value >> 4) as usize;
+    let low = (value & 0xF) as usize;
+    f.write_str(&HEX_DIGITS[high..=high])?;
+    f.write_str(&HEX_DIGITS[low..=low])
+}
+
+fn write_hex_u16<W>(f: &mut Formatter<'_, W>, value: u16) -> Result<(), W::Error>
+where
+    W: uWrite + ?Sized,
+{
+    f.write_str("0x")?;
+    write_hex_u8(f, (value >> 8) as u8)?;
+    write_hex_u8(f, value as u8)
+}
+
+fn write_conn_handle<W>(
+    f: &mut Formatter<'_, W>,
+    conn_handle: ConnectionHandle,
+) -> Result<(), W::Error>
+where
+    W: uWrite + ?Sized,
+{
+    f.write_str(" conn ")?;
+    write_hex_u16(f, conn_handle.0)
+}
+
+fn write_bd_addr<W>(f: &mut Formatter<'_, W>, addr: BdAddr) -> Result<(), W::Error>
+where
+    W: uWrite + ?Sized,
+{
+    for (i, byte) in addr.0.iter().rev().enumerate() {
+        if i > 0 {
+            f.write_str(":")?;
+        }
+        write_hex_u8(f, *byte)?;
+    }
+
+    Ok(())
+}
+
+macro_rules! impl_udebug_from_udisplay {
+    ($($type:ty),*) => {
+        $(
+            impl uDebug for $type {
+                fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+                where
+                    W: uWrite + ?Sized,
+                {
+                    uDisplay::fmt(self, f)
+                }
+            }
+        )*
+    };
+}
+
+impl_udebug_from_udisplay!(
+    BlueNRGEvent,
+    BlueNRGError,
+    GapDeviceFound,
+    GattAttributeModified,
+    AttErrorResponse
+);
+
+impl uDisplay for BlueNRGEvent {
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        match *self {
+            BlueNRGEvent::HalInitialized(_) => f.write_str("HalInitialized"),
+            #[cfg(feature = "ms")]
+            BlueNRGEvent::EventsLost(_) => f.write_str("EventsLost"),
+            #[cfg(feature = "ms")]
+            BlueNRGEvent::CrashReport(_) => f.write_str("CrashReport"),
+            BlueNRGEvent::GapLimitedDiscoverableTimeout => {
+                f.write_str("GapLimitedDiscoverableTimeout")
+            }
+            BlueNRGEvent::GapPairingComplete(_) => f.write_str("GapPairingComplete"),
+            BlueNRGEvent::GapPassKeyRequest(ref event) => {
+                f.write_str("GapPassKeyRequest")?;
+                write_conn_handle(f, event.conn_handle)
+            }
+            BlueNRGEvent::GapAuthorizationRequest(ref event) => {
+                f.write_str("GapAuthorizationRequest")?;
+                write_conn_handle(f, event.conn_handle)
+            }
+            BlueNRGEvent::GapPeripheralSecurityInitiated => {
+                f.write_str("GapPeripheralSecurityInitiated")
+            }
+            BlueNRGEvent::GapBondLost => f.write_str("GapBondLost"),
+            #[cfg(feature = "gap-central-events")]
+            BlueNRGEvent::GapDeviceFound(ref event) => uDisplay::fmt(event, f),
+            #[cfg(feature = "gap-central-events")]
+            BlueNRGEvent::GapProcedureComplete(_) => f.write_str("GapProcedureComplete"),
+            #[cfg(feature = "ms")]
+            BlueNRGEvent::GapAddressNotResolved(ref event) => {
+                f.write_str("GapAddressNotResolved")?;
+                write_conn_handle(f, event.conn_handle)
+            }
+            #[cfg(all(not(feature = "ms"), feature = "gap-central-events"))]
+            BlueNRGEvent::GapReconnectionAddress(_) => f.write_str("GapReconnectionAddress"),
+            #[cfg(feature = "l2cap-events")]
+            BlueNRGEvent::L2CapConnectionUpdateResponse(_) => {
+                f.write_str("L2CapConnectionUpdateResponse")
+            }
+            #[cfg(feature = "l2cap-events")]
+            BlueNRGEvent::L2CapProcedureTimeout(ref event) => {
+                f.write_str("L2CapProcedureTimeout")?;
+                write_conn_handle(f, event.conn_handle)
+            }
+            #[cfg(feature = "l2cap-events")]
+            BlueNRGEvent::L2CapConnectionUpdateRequest(_) => {
+                f.write_str("L2CapConnectionUpdateRequest")
+            }
+            BlueNRGEvent::GattAttributeModified(ref event) => uDisplay::fmt(event, f),
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::GattProcedureTimeout(ref event) => {
+                f.write_str("GattProcedureTimeout")?;
+                write_conn_handle(f, event.conn_handle)
+            }
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttExchangeMtuResponse(_) => f.write_str("AttExchangeMtuResponse"),
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttFindInformationResponse(_) => {
+                f.write_str("AttFindInformationResponse")
+            }
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttFindByTypeValueResponse(_) => {
+                f.write_str("AttFindByTypeValueResponse")
+            }
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttReadByTypeResponse(_) => f.write_str("AttReadByTypeResponse"),
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttReadResponse(_) => f.write_str("AttReadResponse"),
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttReadBlobResponse(_) => f.write_str("AttReadBlobResponse"),
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttReadMultipleResponse(_) => f.write_str("AttReadMultipleResponse"),
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttReadByGroupTypeResponse(_) => {
+                f.write_str("AttReadByGroupTypeResponse")
+            }
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttPrepareWriteResponse(_) => f.write_str("AttPrepareWriteResponse"),
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttExecuteWriteResponse(ref event) => {
+                f.write_str("AttExecuteWriteResponse")?;
+                write_conn_handle(f, event.conn_handle)
+            }
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::GattIndication(_) => f.write_str("GattIndication"),
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::GattNotification(_) => f.write_str("GattNotification"),
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::GattProcedureComplete(_) => f.write_str("GattProcedureComplete"),
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::AttErrorResponse(ref event) => uDisplay::fmt(event, f),
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::GattDiscoverOrReadCharacteristicByUuidResponse(_) => {
+                f.write_str("GattDiscoverOrReadCharacteristicByUuidResponse")
+            }
+            BlueNRGEvent::AttWritePermitRequest(_) => f.write_str("AttWritePermitRequest"),
+            BlueNRGEvent::AttReadPermitRequest(_) => f.write_str("AttReadPermitRequest"),
+            BlueNRGEvent::AttReadMultiplePermitRequest(_) => {
+                f.write_str("AttReadMultiplePermitRequest")
+            }
+            #[cfg(feature = "ms")]
+            BlueNRGEvent::GattTxPoolAvailable(_) => f.write_str("GattTxPoolAvailable"),
+            #[cfg(feature = "ms")]
+            BlueNRGEvent::GattServerConfirmation(conn_handle) => {
+                f.write_str("GattServerConfirmation")?;
+                write_conn_handle(f, conn_handle)
+            }
+            #[cfg(feature = "ms")]
+            BlueNRGEvent::AttPrepareWritePermitRequest(_) => {
+                f.write_str("AttPrepareWritePermitRequest")
+            }
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::GattMultipleNotification(_) => f.write_str("GattMultipleNotification"),
+            #[cfg(all(feature = "bluenrg-lp", feature = "att-client-events"))]
+            BlueNRGEvent::GattIndicationExt(ref event) => {
+                f.write_str("GattIndicationExt")?;
+                write_conn_handle(f, event.conn_handle)
+            }
+            BlueNRGEvent::GapNumericComparisonValue(ref event) => {
+                f.write_str("GapNumericComparisonValue")?;
+                write_conn_handle(f, event.conn_handle)
+            }
+        }
+    }
+}
+
+impl uDisplay for BlueNRGError {
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        match *self {
+            BlueNRGError::UnknownEvent(value) => uwrite!(f, "UnknownEvent {}", value),
+            BlueNRGError::UnknownResetReason(value) => uwrite!(f, "UnknownResetReason {}", value),
+            #[cfg(feature = "ms")]
+            BlueNRGError::BadEventFlags(value) => uwrite!(f, "BadEventFlags {}", value),
+            #[cfg(feature = "ms")]
+            BlueNRGError::UnknownCrashReason(value) => uwrite!(f, "UnknownCrashReason {}", value),
+            BlueNRGError::BadGapPairingStatus(value) => uwrite!(f, "BadGapPairingStatus {}", value),
+            BlueNRGError::BadPairingFailureReason(value) => {
+                uwrite!(f, "BadPairingFailureReason {}", value)
+            }
+            BlueNRGError::BadGapDeviceFoundEvent(value) => {
+                uwrite!(f, "BadGapDeviceFoundEvent {}", value)
+            }
+            BlueNRGError::BadGapBdAddrType(value) => uwrite!(f, "BadGapBdAddrType {}", value),
+            BlueNRGError::BadGapProcedure(value) => uwrite!(f, "BadGapProcedure {}", value),
+            BlueNRGError::BadGapProcedureStatus(value) => {
+                uwrite!(f, "BadGapProcedureStatus {}", value)
+            }
+            BlueNRGError::BadL2CapDataLength(first, second) => {
+                uwrite!(f, "BadL2CapDataLength {} {}", first, second)
+            }
+            BlueNRGError::BadL2CapLength(first, second) => {
+                uwrite!(f, "BadL2CapLength {} {}", first, second)
+            }
+            BlueNRGError::BadL2CapRejectionReason(value) => {
+                uwrite!(f, "BadL2CapRejectionReason {}", value)
+            }
+            BlueNRGError::BadL2CapConnectionResponseCode(value) => {
+                uwrite!(f, "BadL2CapConnectionResponseCode {}", value)
+            }
+            BlueNRGError::BadL2CapConnectionResponseResult(value) => {
+                uwrite!(f, "BadL2CapConnectionResponseResult {}", value)
+            }
+            BlueNRGError::BadConnectionInterval(_) => f.write_str("BadConnectionInterval"),
+            BlueNRGError::BadL2CapConnectionUpdateRequestInterval(min, max) => uwrite!(
+                f,
+                "BadL2CapConnectionUpdateRequestInterval {}ms {}ms",
+                min.as_millis() as u32,
+                max.as_millis() as u32
+            ),
+            BlueNRGError::BadL2CapConnectionUpdateRequestLatency(first, second) => uwrite!(
+                f,
+                "BadL2CapConnectionUpdateRequestLatency {} {}",
+                first,
+                second
+            ),
+            BlueNRGError::BadL2CapConnectionUpdateRequestTimeout(value) => {
+                uwrite!(
+                    f,
+                    "BadL2CapConnectionUpdateRequestTimeout {}ms",
+                    value.as_millis() as u32
+                )
+            }
+            BlueNRGError::BadAttFindInformationResponseFormat(value) => {
+                uwrite!(f, "BadAttFindInformationResponseFormat {}", value)
+            }
+            BlueNRGError::AttFindInformationResponsePartialPair16 => {
+                f.write_str("AttFindInformationResponsePartialPair16")
+            }
+            BlueNRGError::AttFindInformationResponsePartialPair128 => {
+                f.write_str("AttFindInformationResponsePartialPair128")
+            }
+            BlueNRGError::AttFindByTypeValuePartial => f.write_str("AttFindByTypeValuePartial"),
+            BlueNRGError::AttReadByTypeResponsePartial => {
+                f.write_str("AttReadByTypeResponsePartial")
+            }
+            BlueNRGError::BadAttReadByTypeResponsePairLength(value) => {
+                uwrite!(f, "BadAttReadByTypeResponsePairLength {}", value)
+            }
+            BlueNRGError::AttReadByGroupTypeResponsePartial => {
+                f.write_str("AttReadByGroupTypeResponsePartial")
+            }
+            BlueNRGError::BadAttReadByGroupTypeResponseGroupLength(value) => {
+                uwrite!(f, "BadAttReadByGroupTypeResponseGroupLength {}", value)
+            }
+            BlueNRGError::BadGattProcedureStatus(value) => {
+                uwrite!(f, "BadGattProcedureStatus {}", value)
+            }
+            BlueNRGError::BadAttRequestOpcode(value) => uwrite!(f, "BadAttRequestOpcode {}", value),
+            BlueNRGError::BadAttError(value) => uwrite!(f, "BadAttError {}", value),
+            BlueNRGError::AttReadMultiplePermitRequestPartial => {
+                f.write_str("AttReadMultiplePermitRequestPartial")
+            }
+            BlueNRGError::GattMultipleNotificationPartial => {
+                f.write_str("GattMultipleNotificationPartial")
+            }
+            BlueNRGError::BadConfigParameterLength(value) => {
+                uwrite!(f, "BadConfigParameterLength {}", value)
+            }
+            BlueNRGError::BadConfigRole(value) => uwrite!(f, "BadConfigRole {}", value),
+            BlueNRGError::UnknownLinkState(value) => uwrite!(f, "UnknownLinkState {}", value),
+            BlueNRGError::BadBooleanValue(value) => uwrite!(f, "BadBooleanValue {}", value),
+            BlueNRGError::BadPassKeyRequirement(value) => {
+                uwrite!(f, "BadPassKeyRequirement {}", value)
+            }
+            BlueNRGError::PartialBondedDeviceAddress => f.write_str("PartialBondedDeviceAddress"),
+            BlueNRGError::BadBdAddrType(value) => uwrite!(f, "BadBdAddrType {}", value),
+            BlueNRGError::BadPhy(value) => uwrite!(f, "BadPhy {}", value),
+            #[cfg(feature = "periodic-advertising")]
+            BlueNRGError::BadPeriodicAdvertisingDataStatus(value) => {
+                uwrite!(f, "BadPeriodicAdvertisingDataStatus {}", value)
+            }
+        }
+    }
+}
+
+impl uDisplay for GapDeviceFound {
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        f.write_str("GapDeviceFound ")?;
+        match self.bdaddr {
+            BdAddrType::Public(addr) => {
+                f.write_str("public ")?;
+                write_bd_addr(f, addr)?;
+            }
+            BdAddrType::Random(addr) => {
+                f.write_str("random ")?;
+                write_bd_addr(f, addr)?;
+            }
+        }
+        match self.rssi {
+            Some(rssi) => uwrite!(f, " rssi {}", rssi)?,
+            None => f.write_str(" rssi none")?,
+        }
+        uwrite!(f, " data {}", self.data_len)
+    }
+}
+
+impl uDisplay for GattAttributeModified {
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        f.write_str("GattAttributeModified")?;
+        write_conn_handle(f, self.conn_handle)?;
+        f.write_str(" attr ")?;
+        write_hex_u16(f, self.attr_handle.0)?;
+        if let Some(offset) = self.offset {
+            uwrite!(f, " offset {}", offset)?;
+        }
+        if let Some(true) = self.continued {
+            f.write_str(" continued")?;
+        }
+        uwrite!(f, " data {}", self.data_len)
+    }
+}
+
+impl uDisplay for AttErrorResponse {
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        f.write_str("AttErrorResponse")?;
+        write_conn_handle(f, self.conn_handle)?;
+        f.write_str(" attr ")?;
+        write_hex_u16(f, self.attribute_handle.0)?;
+        f.write_str(" request 0x")?;
+        write_hex_u8(f, self.request as u8)?;
+        f.write_str(" error 0x")?;
+        write_hex_u8(f, self.error as u8)
+    }
+}
@@ -0,0 +1,256 @@
+//! Firmware version detection.
+//!
+//! Event layouts and the available commands differ between the BlueNRG firmware stacks. The
+//! version is reported in two parts: the [HCI Read Local Version
+//! Information](hci::host::Hci::read_local_version_information) command returns the hardware and
+//! stack version (see [`LocalVersionInfoExt`](crate::LocalVersionInfoExt)), and the vendor [HAL Get
+//! Firmware Revision](crate::hal::Commands::get_firmware_revision) command returns the firmware
+//! revision. [`FirmwareDetection`] issues both commands and combines the results into a
+//! [`FirmwareInfo`], whose predicates answer what the firmware supports.
+
+use crate::event::command::ReturnParameters;
+use crate::event::BlueNRGEvent;
+use crate::{LocalVersionInfoExt, UartController, Version};
+
+/// BlueNRG firmware stacks, identified by the major version of the stack.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Stack {
+    /// BlueNRG (Bluetooth 4.0) stack, version 6.x.
+    BlueNrg,
+
+    /// BlueNRG-MS (Bluetooth 4.1) stack, version 7.x.
+    BlueNrgMs,
+
+    /// BlueNRG-1 and BlueNRG-2 (Bluetooth 4.2) stack, version 2.x.
+    V2,
+
+    /// BlueNRG-2 and BlueNRG-LP (Bluetooth 5) stack, version 3.x.
+    V3,
+
+    /// A stack version this crate does not know about. Includes the major version.
+    Unknown(u8),
+}
+
+/// The event decoding selected by the crate features.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Decoding {
+    /// Neither `ms` nor `bluenrg-lp`: the original BlueNRG event layouts.
+    BlueNrg,
+
+    /// The `ms` feature: the BlueNRG-MS event layouts.
+    Ms,
+
+    /// The `bluenrg-lp` feature: the event layouts of the version 2 and 3 stacks.
+    Lp,
+}
+
+impl Decoding {
+    /// The decoding this build of the crate uses.
+    pub const BUILT: Decoding = if cfg!(feature = "bluenrg-lp") {
+        Decoding::Lp
+    } else if cfg!(feature = "ms") {
+        Decoding::Ms
+    } else {
+        Decoding::BlueNrg
+    };
+}
+
+/// Version information of the controller firmware.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FirmwareInfo {
+    /// Version of the controller hardware.
+    pub hw_version: u8,
+
+    /// Major version of the firmware stack.
+    pub major: u8,
+
+    /// Minor version of the firmware stack.
+    pub minor: u8,
+
+    /// Patch version of the firmware stack.
+    pub patch: u8,
+
+    /// Firmware revision, as returned by [HAL Get Firmware
+    /// Revision](crate::hal::Commands::get_firmware_revision).
+    pub revision: u16,
+}
+
+impl FirmwareInfo {
+    /// Combines the version from the local version information with the firmware revision.
+    pub fn new(version: &Version, revision: u16) -> FirmwareInfo {
+        FirmwareInfo {
+            hw_version: version.hw_version,
+            major: version.major,
+            minor: version.minor,
+            patch: version.patch,
+            revision,
+        }
+    }
+
+    /// Returns the firmware stack.
+    pub fn stack(&self) -> Stack {
+        match self.major {
+            2 => Stack::V2,
+            3 => Stack::V3,
+            6 => Stack::BlueNrg,
+            7 => Stack::BlueNrgMs,
+            major => Stack::Unknown(major),
+        }
+    }
+
+    /// Returns the event decoding that matches the firmware, or `None` for an unknown stack.
+    /// Compare it to [`Decoding::BUILT`] to check that the crate was built with the right
+    /// features.
+    pub fn decoding(&self) -> Option<Decoding> {
+        match self.stack() {
+            Stack::BlueNrg => Some(Decoding::BlueNrg),
+            Stack::BlueNrgMs => Some(Decoding::Ms),
+            Stack::V2 | Stack::V3 => Some(Decoding::Lp),
+            Stack::Unknown(_) => None,
+        }
+    }
+
+    /// Returns true if the firmware has the version 3 stack's extended attribute events, such as
+    /// [GATT Indication Ext](crate::event::BlueNRGEvent::GattIndicationExt), which carry values
+    /// longer than one event packet.
+    pub fn supports_extended_attribute_events(&self) -> bool {
+        self.stack() == Stack::V3
+    }
+
+    /// Returns true if the firmware supports the Bluetooth 4.2 LE Data Length Extension. The
+    /// BlueNRG and BlueNRG-MS stacks implement earlier versions of the specification.
+    pub fn supports_data_length_extension(&self) -> bool {
+        matches!(self.stack(), Stack::V2 | Stack::V3)
+    }
+
+    /// Returns true if the firmware reports [GATT Tx Pool
+    /// Available](crate::event::BlueNRGEvent::GattTxPoolAvailable) events. The original BlueNRG
+    /// stack does not.
+    pub fn has_tx_pool_available_event(&self) -> bool {
+        matches!(self.stack(), Stack::BlueNrgMs | Stack::V2 | Stack::V3)
+    }
+}
+
+/// Errors that may occur while detecting the firmware version.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Error<E> {
+    /// The controller reported a failure for one of the commands. Includes the status.
+    CommandFailed(hci::Status<crate::event::Status>),
+
+    /// Underlying communication error.
+    Comm(E),
+}
+
+impl<E: core::fmt::Debug> core::fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            Error::CommandFailed(ref status) => write!(f, "version command failed: {:?}", status),
+            Error::Comm(ref e) => write!(f, "communication error: {:?}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: core::fmt::Debug> std::error::Error for Error<E> {}
+
+#[derive(Clone)]
+enum State {
+    ReadingVersion,
+    PendingRevision(Version),
+    ReadingRevision(Version),
+    Complete,
+}
+
+/// Reads the firmware version from the controller.
+///
+/// Start the detection with [`detect`](FirmwareDetection::detect), then pass every event to
+/// [`poll`](FirmwareDetection::poll) until it returns the [`FirmwareInfo`].
+pub struct FirmwareDetection {
+    state: State,
+}
+
+impl FirmwareDetection {
+    /// Sends the command to read the local version information, and returns the detection that
+    /// waits for its result.
+    ///
+    /// # Errors
+    ///
+    /// Only underlying communication errors are reported.
+    pub fn detect<C, E>(controller: &mut C) -> nb::Result<FirmwareDetection, Error<E>>
+    where
+        C: UartController<E, VS = crate::event::Status> + ?Sized,
+    {
+        controller
+            .read_local_version_information()
+            .map_err(rewrap_error)?;
+
+        Ok(FirmwareDetection {
+            state: State::ReadingVersion,
+        })
+    }
+
+    /// Updates the detection with an event read from the controller, and sends the command to
+    /// read the firmware revision once the version is known.
+    ///
+    /// Returns the firmware information once both commands have completed, and `None` until then.
+    /// Events that do not belong to the detection are ignored.
+    ///
+    /// # Errors
+    ///
+    /// - `WouldBlock` if the command to read the firmware revision could not be sent yet. Call
+    ///   `poll` again, with the same or a later event, to retry.
+    /// - [CommandFailed](Error::CommandFailed) if the controller reports that a command failed.
+    /// - Underlying communication errors.
+    pub fn poll<C, E>(
+        &mut self,
+        controller: &mut C,
+        event: &hci::event::Event<BlueNRGEvent>,
+    ) -> nb::Result<Option<FirmwareInfo>, Error<E>>
+    where
+        C: UartController<E, VS = crate::event::Status> + ?Sized,
+    {
+        let return_params = match *event {
+            hci::event::Event::CommandComplete(ref event) => Some(&event.return_params),
+            _ => None,
+        };
+
+        match (self.state.clone(), return_params) {
+            (
+                State::ReadingVersion,
+                Some(hci::event::command::ReturnParameters::ReadLocalVersionInformation(ref info)),
+            ) => {
+                if info.status != hci::Status::Success {
+                    return Err(nb::Error::Other(Error::CommandFailed(info.status)));
+                }
+                self.state = State::PendingRevision(info.bluenrg_version());
+            }
+            (
+                State::ReadingRevision(version),
+                Some(hci::event::command::ReturnParameters::Vendor(
+                    ReturnParameters::HalGetFirmwareRevision(ref params),
+                )),
+            ) => {
+                if params.status != hci::Status::Success {
+                    return Err(nb::Error::Other(Error::CommandFailed(params.status)));
+                }
+                self.state = State::Complete;
+                return Ok(Some(FirmwareInfo::new(&version, params.revision)));
+            }
+            _ => (),
+        }
+
+        if let State::PendingRevision(version) = self.state.clone() {
+            controller.get_firmware_revision().map_err(rewrap_error)?;
+            self.state = State::ReadingRevision(version);
+        }
+
+        Ok(None)
+    }
+}
+
+fn rewrap_error<E>(e: nb::Error<E>) -> nb::Error<Error<E>> {
+    match e {
+        nb::Error::WouldBlock => nb::Error::WouldBlock,
+        nb::Error::Other(c) => nb::Error::Other(Error::Comm(c)),
+    }
+}
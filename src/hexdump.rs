@@ -0,0 +1,57 @@
+//! Hex dumps of raw buffers, for logging events that could not be decoded.
+//!
+//! When [`BlueNRGEvent::new`](crate::event::BlueNRGEvent::new) fails with
+//! [`UnknownEvent`](crate::event::BlueNRGError::UnknownEvent) or a bad length, the raw event buffer
+//! is the most useful thing to include in a log or an issue report. [`hex_dump`] formats it without
+//! allocating, so it works with any `core::fmt::Write` sink, such as a UART or a
+//! `heapless::String`.
+
+use core::fmt::{Result, Write};
+
+const BYTES_PER_LINE: usize = 16;
+
+/// Writes the buffer in the canonical hex+ASCII format of `hexdump -C`.
+///
+/// Each line shows the offset of its first byte, up to 16 bytes in hex, and the same bytes as
+/// ASCII, with `.` for bytes that are not printable. A last line shows the length of the buffer.
+/// Unlike `hexdump`, repeated lines are not collapsed.
+///
+/// ```
+/// let mut dump = String::new();
+/// bluenrg::hexdump::hex_dump(&[0x0F, 0x0C, b'h', b'i'], &mut dump).unwrap();
+/// assert_eq!(
+///     dump,
+///     "00000000  0f 0c 68 69                                       |..hi|\n\
+///      00000004\n"
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Returns the error from the sink if it fails.
+pub fn hex_dump<W: Write + ?Sized>(buffer: &[u8], out: &mut W) -> Result {
+    for (line, bytes) in buffer.chunks(BYTES_PER_LINE).enumerate() {
+        write!(out, "{:08x} ", line * BYTES_PER_LINE)?;
+        for i in 0..BYTES_PER_LINE {
+            if i % 8 == 0 {
+                out.write_char(' ')?;
+            }
+            match bytes.get(i) {
+                Some(byte) => write!(out, "{:02x} ", byte)?,
+                None => out.write_str("   ")?,
+            }
+        }
+
+        out.write_str(" |")?;
+        for &byte in bytes {
+            out.write_char(if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            })?;
+        }
+        out.write_str("|\n")?;
+    }
+
+    writeln!(out, "{:08x}", buffer.len())
+}
@@ -0,0 +1,79 @@
+//! Types for the keys exchanged or distributed during pairing.
+//!
+//! Each key is a distinct type, so one kind of key cannot be passed where another is expected. The
+//! keys compare in constant time, and their `Debug` output shows only a short fingerprint instead
+//! of the key itself, so keys do not end up in logs.
+
+use core::fmt::{Debug, Formatter, Result as FmtResult};
+
+macro_rules! security_key {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone)]
+        pub struct $name([u8; 16]);
+
+        impl $name {
+            /// Wraps the key bytes, in the little-endian order used by the controller.
+            pub fn new(key: [u8; 16]) -> $name {
+                $name(key)
+            }
+
+            /// Returns the key bytes, in the little-endian order used by the controller.
+            pub fn as_bytes(&self) -> &[u8; 16] {
+                &self.0
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                constant_time_eq(&self.0, &other.0)
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl Debug for $name {
+            fn fmt(&self, f: &mut Formatter) -> FmtResult {
+                write!(
+                    f,
+                    concat!(stringify!($name), "(fingerprint {:04x})"),
+                    fingerprint(&self.0)
+                )
+            }
+        }
+    };
+}
+
+security_key!(
+    /// Long Term Key (LTK), used to encrypt the link when reconnecting to a bonded device.
+    LongTermKey
+);
+
+security_key!(
+    /// Identity Resolving Key (IRK), used to generate and resolve resolvable private addresses.
+    IdentityResolvingKey
+);
+
+security_key!(
+    /// Connection Signature Resolving Key (CSRK), used to sign data sent without encryption.
+    Csrk
+);
+
+// Compares the keys without returning early, so the time taken does not depend on where the keys
+// first differ.
+fn constant_time_eq(a: &[u8; 16], b: &[u8; 16]) -> bool {
+    a.iter()
+        .zip(b.iter())
+        .fold(0, |diff, (x, y)| diff | (x ^ y))
+        == 0
+}
+
+// 16-bit FNV-1a hash of the key (folded from the 32-bit hash). This is enough to tell keys apart in
+// logs without revealing them.
+fn fingerprint(key: &[u8; 16]) -> u16 {
+    let hash = key.iter().fold(0x811c_9dc5_u32, |hash, &byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+
+    ((hash >> 16) ^ (hash & 0xFFFF)) as u16
+}
@@ -40,14 +40,27 @@
 #![no_std]
 #![deny(missing_docs)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
 #[macro_use]
 extern crate bitflags;
 #[macro_use]
 extern crate bluetooth_hci as hci;
 extern crate byteorder;
 extern crate embedded_hal as emhal;
+#[cfg(feature = "heapless")]
+extern crate heapless;
+#[cfg(feature = "log")]
+extern crate log;
 #[macro_use(block)]
 extern crate nb;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+#[cfg(feature = "std")]
+extern crate std;
+#[cfg(feature = "ufmt")]
+extern crate ufmt;
 
 use byteorder::{ByteOrder, LittleEndian};
 use core::cmp::min;
@@ -56,10 +69,27 @@ use core::marker::PhantomData;
 use hci::host::HciHeader;
 use hci::Controller;
 
+pub mod bond;
+pub mod boot;
 mod cb;
 mod command;
+pub mod dtm;
 pub mod event;
+pub mod firmware;
+pub mod hexdump;
+pub mod keys;
+#[cfg(feature = "mock")]
+pub mod mock;
 mod opcode;
+#[cfg(feature = "att-client-events")]
+pub mod procedure;
+pub mod rebond;
+pub mod setup;
+pub mod stats;
+#[cfg(feature = "ms")]
+pub mod throttle;
+pub mod uuids;
+pub mod value;
 
 pub use command::gap;
 pub use command::gatt;
@@ -230,6 +260,9 @@ where
         let mut header = [0; HEADER_LEN];
         hci::host::uart::CommandHeader::new(opcode, params.len()).copy_into_slice(&mut header);
 
+        #[cfg(feature = "log")]
+        log::trace!("command {:#06x}, {} parameter bytes", opcode.0, params.len());
+
         self.write(&header, &params)
     }
 }
@@ -308,6 +341,12 @@ where
 }
 
 /// Specify vendor-specific extensions for the BlueNRG.
+///
+/// This is the [`hci::Vendor`] marker type that ties the BlueNRG [events](event::BlueNRGEvent)
+/// and [status codes](event::Status) together, so applications can name the HCI types for this
+/// controller without writing the glue themselves. The vendor-specific [return
+/// parameters](event::command::ReturnParameters) are reached through the event type, e.g.
+/// `hci::event::Event<<BlueNRGTypes as hci::Vendor>::Event>`.
 pub struct BlueNRGTypes;
 impl hci::Vendor for BlueNRGTypes {
     type Status = event::Status;
@@ -320,7 +359,7 @@ pub trait UartController<E>:
     + crate::gatt::Commands<Error = E>
     + crate::hal::Commands<Error = E>
     + crate::l2cap::Commands<Error = E>
-    + bluetooth_hci::host::uart::Hci<E, crate::event::BlueNRGEvent, crate::event::BlueNRGError>
+    + bluetooth_hci::host::uart::Hci<E, crate::event::BlueNRGEvent, crate::event::ParseError>
 {
 }
 impl<T, E> UartController<E> for T where
@@ -328,7 +367,7 @@ impl<T, E> UartController<E> for T where
         + crate::gatt::Commands<Error = E>
         + crate::hal::Commands<Error = E>
         + crate::l2cap::Commands<Error = E>
-        + bluetooth_hci::host::uart::Hci<E, crate::event::BlueNRGEvent, crate::event::BlueNRGError>
+        + bluetooth_hci::host::uart::Hci<E, crate::event::BlueNRGEvent, crate::event::ParseError>
 {
 }
 
@@ -426,7 +465,79 @@ impl<VS> LocalVersionInfoExt for hci::event::command::LocalVersionInfo<VS> {
     }
 }
 
+/// Sub-types of random device addresses, identified by the two most significant bits of the
+/// address.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum RandomAddressKind {
+    /// The most significant bits are `0b00`. The address changes periodically and cannot be
+    /// resolved to an identity.
+    NonResolvablePrivate,
+
+    /// The most significant bits are `0b01`. The address changes periodically, but peers that know
+    /// the device's identity resolving key can resolve it.
+    ResolvablePrivate,
+
+    /// The most significant bits are `0b11`. The address stays the same at least until the device
+    /// is power cycled.
+    Static,
+}
+
+impl RandomAddressKind {
+    /// Returns the sub-type of a random address, or `None` if the most significant bits hold the
+    /// reserved value `0b10`.
+    ///
+    /// [`hci::BdAddr`] is stored in little-endian order, so the most significant bits are in the
+    /// *last* byte of the array: `BdAddr([0x01, 0x02, 0x03, 0x04, 0x05, 0xC6])` is the static
+    /// address `C6:05:04:03:02:01`.
+    pub fn of(addr: hci::BdAddr) -> Option<RandomAddressKind> {
+        match addr.0[5] >> 6 {
+            0b00 => Some(RandomAddressKind::NonResolvablePrivate),
+            0b01 => Some(RandomAddressKind::ResolvablePrivate),
+            0b11 => Some(RandomAddressKind::Static),
+            _ => None,
+        }
+    }
+}
+
+/// Extension trait to classify [`hci::BdAddrType`] addresses by their [`RandomAddressKind`].
+pub trait BdAddrTypeExt {
+    /// Returns the sub-type of a random address. Returns `None` for public addresses, and for
+    /// random addresses with the reserved sub-type.
+    fn random_kind(&self) -> Option<RandomAddressKind>;
+
+    /// Returns true if the address is a random static address.
+    fn is_static(&self) -> bool {
+        self.random_kind() == Some(RandomAddressKind::Static)
+    }
+
+    /// Returns true if the address is a resolvable private address.
+    fn is_resolvable_private(&self) -> bool {
+        self.random_kind() == Some(RandomAddressKind::ResolvablePrivate)
+    }
+
+    /// Returns true if the address is a non-resolvable private address.
+    fn is_non_resolvable_private(&self) -> bool {
+        self.random_kind() == Some(RandomAddressKind::NonResolvablePrivate)
+    }
+}
+
+impl BdAddrTypeExt for hci::BdAddrType {
+    fn random_kind(&self) -> Option<RandomAddressKind> {
+        match *self {
+            hci::BdAddrType::Public(_) => None,
+            hci::BdAddrType::Random(addr) => RandomAddressKind::of(addr),
+        }
+    }
+}
+
 /// Hardware event codes returned by the `HardwareError` HCI event.
+///
+/// The BlueNRG reports hardware errors only with the standard HCI [Hardware
+/// Error](hci::event::Event::HardwareError) event (event code 0x10), which the HCI crate decodes;
+/// there is no vendor-specific event for them. Convert the code from that event with `TryFrom<u8>`.
+/// If the event was dropped because the host did not read it in time, the controller sets
+/// [HARDWARE_ERROR](crate::event::EventFlags::HARDWARE_ERROR) in the next [Events
+/// Lost](crate::event::BlueNRGEvent::EventsLost) event instead.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum HardwareError {
     /// Error on the SPI bus has been detected, most likely caused by incorrect SPI configuration on
@@ -448,6 +559,15 @@ pub enum HardwareError {
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct InvalidHardwareError(pub u8);
 
+impl core::fmt::Display for InvalidHardwareError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "unknown hardware error code {:#04x}", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidHardwareError {}
+
 impl TryFrom<u8> for HardwareError {
     type Error = InvalidHardwareError;
     fn try_from(value: u8) -> Result<Self, Self::Error> {
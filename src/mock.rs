@@ -0,0 +1,349 @@
+//! Test double for host-side application testing.
+//!
+//! [`MockController`] drives the same command and event code as a real BlueNRG, but instead of
+//! talking to a chip over SPI, it records the commands the application sends and feeds back
+//! scripted events. This lets application logic that talks to the controller be tested on the host,
+//! without hardware.
+//!
+//! Commands are described by issuing them against the mock, so tests use the same typed parameters
+//! as the application instead of raw bytes:
+//!
+//! ```
+//! # extern crate bluenrg;
+//! # extern crate bluetooth_hci as hci;
+//! # use bluenrg::gap::Commands as GapCommands;
+//! # use bluenrg::mock::MockController;
+//! # use hci::host::uart::Hci as HciUart;
+//! let mut rx_buffer = [0; 64];
+//! let mut mock = MockController::new(&mut rx_buffer);
+//!
+//! // Expect the application to stop advertising, and acknowledge the command.
+//! mock.expect(|controller| controller.set_nondiscoverable())
+//!     .then_command_complete(&[0x00]);
+//!
+//! // Run the application code under test.
+//! mock.act(|controller| controller.set_nondiscoverable()).unwrap();
+//! assert!(mock.act(|controller| controller.read()).is_ok());
+//!
+//! mock.assert_satisfied();
+//! ```
+//!
+//! The mock requires `std`, so it is only available with the `mock` feature.
+
+use crate::event::Status;
+use crate::{ActiveBlueNRG, BlueNRG, UartController};
+use byteorder::{ByteOrder, LittleEndian};
+use std::collections::VecDeque;
+use std::vec::Vec;
+
+/// A command issued to the [`MockController`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MockCommand {
+    /// Opcode of the command.
+    pub opcode: hci::Opcode,
+
+    /// Parameters of the command, as serialized for the controller.
+    pub params: Vec<u8>,
+}
+
+/// A command the [`MockController`] expects, and the events it delivers once the command is issued.
+///
+/// Returned by [`MockController::expect`].
+#[derive(Clone, Debug)]
+pub struct Expectation {
+    command: MockCommand,
+    responses: Vec<u8>,
+}
+
+impl Expectation {
+    /// Delivers a Command Complete event for the expected command once it is issued. The return
+    /// parameters start with the status byte.
+    pub fn then_command_complete(&mut self, return_params: &[u8]) -> &mut Expectation {
+        let opcode = self.command.opcode;
+        push_command_complete(&mut self.responses, opcode, return_params);
+        self
+    }
+
+    /// Delivers a Command Status event with the given status for the expected command once it is
+    /// issued.
+    pub fn then_command_status(&mut self, status: u8) -> &mut Expectation {
+        let opcode = self.command.opcode;
+        push_command_status(&mut self.responses, opcode, status);
+        self
+    }
+
+    /// Delivers a BlueNRG vendor event once the expected command is issued. `params` is everything
+    /// after the vendor event code.
+    pub fn then_vendor_event(&mut self, event_code: u16, params: &[u8]) -> &mut Expectation {
+        push_vendor_event(&mut self.responses, event_code, params);
+        self
+    }
+
+    /// Delivers an arbitrary HCI event once the expected command is issued. `event` starts with the
+    /// event code, followed by the parameter length and the parameters.
+    pub fn then_event(&mut self, event: &[u8]) -> &mut Expectation {
+        push_event(&mut self.responses, event);
+        self
+    }
+}
+
+/// Test double that records the commands the application sends and feeds back scripted events.
+///
+/// See the [module documentation](crate::mock) for an example.
+pub struct MockController<'buf> {
+    bnrg: BlueNRG<'buf, MockSpi, MockPin, MockPin, MockPin>,
+    spi: MockSpi,
+}
+
+impl<'buf> MockController<'buf> {
+    /// Returns a new mock controller that uses the given buffer to hold bytes read from the "chip"
+    /// until the application processes them, just like [`BlueNRG::new`].
+    pub fn new(rx_buffer: &'buf mut [u8]) -> MockController<'buf> {
+        MockController {
+            bnrg: BlueNRG::new(rx_buffer, MockPin, MockPin, MockPin),
+            spi: MockSpi::new(),
+        }
+    }
+
+    /// Invokes the given body with a controller that implements every command and can read the
+    /// scripted events, just like [`BlueNRG::with_spi`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the body issues a command that does not match the next
+    /// [expectation](MockController::expect).
+    pub fn act<T, F>(&mut self, body: F) -> T
+    where
+        F: FnOnce(&mut dyn UartController<(), VS = Status>) -> T,
+    {
+        let mut active = ActiveBlueNRG {
+            d: &mut self.bnrg,
+            spi: &mut self.spi,
+        };
+        body(&mut active)
+    }
+
+    /// Expects the application to issue the commands that the given body issues, in order, after
+    /// any earlier expectations. Returns the expectation for the last command, so the events it
+    /// triggers can be scripted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the body does not issue a command.
+    pub fn expect<T, F>(&mut self, body: F) -> &mut Expectation
+    where
+        F: FnOnce(&mut dyn UartController<(), VS = Status>) -> T,
+    {
+        let mut rx_buffer = [0; 8];
+        let mut bnrg = BlueNRG::new(&mut rx_buffer, MockPin, MockPin, MockPin);
+        let mut spi = MockSpi::new();
+        {
+            let mut active = ActiveBlueNRG {
+                d: &mut bnrg,
+                spi: &mut spi,
+            };
+            body(&mut active);
+        }
+
+        assert!(
+            !spi.commands.is_empty(),
+            "Expectation body did not issue a command"
+        );
+        for command in spi.commands {
+            self.spi.expectations.push_back(Expectation {
+                command,
+                responses: Vec::new(),
+            });
+        }
+
+        self.spi.expectations.back_mut().unwrap()
+    }
+
+    /// Delivers a Command Complete event for the given opcode. The return parameters start with the
+    /// status byte.
+    pub fn queue_command_complete(&mut self, opcode: hci::Opcode, return_params: &[u8]) {
+        push_command_complete(&mut self.spi.rx, opcode, return_params);
+    }
+
+    /// Delivers a BlueNRG vendor event. `params` is everything after the vendor event code.
+    pub fn queue_vendor_event(&mut self, event_code: u16, params: &[u8]) {
+        push_vendor_event(&mut self.spi.rx, event_code, params);
+    }
+
+    /// Delivers an arbitrary HCI event. `event` starts with the event code, followed by the
+    /// parameter length and the parameters.
+    pub fn queue_event(&mut self, event: &[u8]) {
+        push_event(&mut self.spi.rx, event);
+    }
+
+    /// Returns the commands issued so far, in order.
+    pub fn commands(&self) -> &[MockCommand] {
+        &self.spi.commands
+    }
+
+    /// Asserts that every expected command was issued.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an expected command was not issued, or if the application has not read all of the
+    /// scripted events.
+    pub fn assert_satisfied(&self) {
+        if let Some(expectation) = self.spi.expectations.front() {
+            panic!("Expected command was not issued: {:?}", expectation.command);
+        }
+        assert!(
+            self.spi.rx.is_empty(),
+            "{} bytes of scripted events were not read",
+            self.spi.rx.len()
+        );
+    }
+}
+
+// Simulates the SPI side of the BlueNRG.
+struct MockSpi {
+    // Bytes of the command currently being written.
+    partial_command: Vec<u8>,
+    // Commands written so far.
+    commands: Vec<MockCommand>,
+    // Commands the application is expected to write, in order.
+    expectations: VecDeque<Expectation>,
+    // Bytes the controller has ready to send to the host.
+    rx: VecDeque<u8>,
+}
+
+impl MockSpi {
+    fn new() -> MockSpi {
+        MockSpi {
+            partial_command: Vec::new(),
+            commands: Vec::new(),
+            expectations: VecDeque::new(),
+            rx: VecDeque::new(),
+        }
+    }
+
+    fn record_command(&mut self, command: MockCommand) {
+        if let Some(expectation) = self.expectations.pop_front() {
+            assert_eq!(
+                command, expectation.command,
+                "Command does not match expectation"
+            );
+            self.rx.extend(expectation.responses);
+        }
+        self.commands.push(command);
+    }
+}
+
+impl emhal::blocking::spi::Transfer<u8> for MockSpi {
+    type Error = ();
+
+    fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], Self::Error> {
+        const SPI_HEADER_LEN: usize = 5;
+        const BNRG_READY: u8 = 0x02;
+
+        // The host sends the SPI header to ask how many bytes it can write and read, and zeros to
+        // read data.
+        if words.len() == SPI_HEADER_LEN && words[0] != 0 {
+            words[0] = BNRG_READY;
+            LittleEndian::write_u16(&mut words[1..], 0xFFFF);
+            LittleEndian::write_u16(&mut words[3..], self.rx.len().min(0xFFFF) as u16);
+        } else {
+            for word in words.iter_mut() {
+                *word = self.rx.pop_front().unwrap_or(0);
+            }
+        }
+
+        Ok(words)
+    }
+}
+
+impl emhal::blocking::spi::Write<u8> for MockSpi {
+    type Error = ();
+
+    fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        // Command packet: packet type (1), opcode (2), parameter length (1), parameters.
+        const HEADER_LEN: usize = 4;
+
+        self.partial_command.extend_from_slice(words);
+        while self.partial_command.len() >= HEADER_LEN {
+            let len = HEADER_LEN + self.partial_command[3] as usize;
+            if self.partial_command.len() < len {
+                break;
+            }
+
+            let command = MockCommand {
+                opcode: hci::Opcode(LittleEndian::read_u16(&self.partial_command[1..])),
+                params: self.partial_command[HEADER_LEN..len].to_vec(),
+            };
+            self.partial_command.drain(..len);
+            self.record_command(command);
+        }
+
+        Ok(())
+    }
+}
+
+// Stands in for the chip select, reset, and data ready pins. Data ready always reads high; the
+// mock reports how much data is available in the SPI header.
+struct MockPin;
+
+impl emhal::digital::OutputPin for MockPin {
+    fn set_low(&mut self) {}
+
+    fn set_high(&mut self) {}
+}
+
+impl emhal::digital::InputPin for MockPin {
+    fn is_high(&self) -> bool {
+        true
+    }
+
+    fn is_low(&self) -> bool {
+        false
+    }
+}
+
+fn push_event<B: Extend<u8>>(buffer: &mut B, event: &[u8]) {
+    const PACKET_TYPE_HCI_EVENT: u8 = 0x04;
+
+    buffer.extend(Some(PACKET_TYPE_HCI_EVENT));
+    buffer.extend(event.iter().cloned());
+}
+
+fn push_command_complete<B: Extend<u8>>(buffer: &mut B, opcode: hci::Opcode, return_params: &[u8]) {
+    const COMMAND_COMPLETE: u8 = 0x0E;
+
+    let mut event = Vec::with_capacity(5 + return_params.len());
+    event.push(COMMAND_COMPLETE);
+    event.push((3 + return_params.len()) as u8);
+    event.push(1); // Number of HCI command packets
+    event.extend_from_slice(&[opcode.0 as u8, (opcode.0 >> 8) as u8]);
+    event.extend_from_slice(return_params);
+    push_event(buffer, &event);
+}
+
+fn push_command_status<B: Extend<u8>>(buffer: &mut B, opcode: hci::Opcode, status: u8) {
+    const COMMAND_STATUS: u8 = 0x0F;
+
+    push_event(
+        buffer,
+        &[
+            COMMAND_STATUS,
+            4,
+            status,
+            1, // Number of HCI command packets
+            opcode.0 as u8,
+            (opcode.0 >> 8) as u8,
+        ],
+    );
+}
+
+fn push_vendor_event<B: Extend<u8>>(buffer: &mut B, event_code: u16, params: &[u8]) {
+    const VENDOR_EVENT: u8 = 0xFF;
+
+    let mut event = Vec::with_capacity(4 + params.len());
+    event.push(VENDOR_EVENT);
+    event.push((2 + params.len()) as u8);
+    event.extend_from_slice(&[event_code as u8, (event_code >> 8) as u8]);
+    event.extend_from_slice(params);
+    push_event(buffer, &event);
+}
@@ -37,6 +37,8 @@ opcodes! {
         // The documentation says the OCF is 0xF8 (0b1111_1000), but that does not fit the OCF
         // length (7 bits). The C source code has 0x19, which is valid.
         pub const HAL_GET_ANCHOR_PERIOD = 0x19;
+        pub const HAL_WRITE_RADIO_REG = 0x30;
+        pub const HAL_READ_RADIO_REG = 0x31;
     }
     Gap = 0x1;
     {
@@ -76,6 +78,12 @@ opcodes! {
         pub const GAP_START_OBSERVATION_PROCEDURE = 0x22;
         pub const GAP_GET_BONDED_DEVICES = 0x23;
         pub const GAP_IS_DEVICE_BONDED = 0x24;
+
+        // Only supported by firmware that implements LE Secure Connections.
+        pub const GAP_NUMERIC_COMPARISON_VALUE_CONFIRM = 0x25;
+
+        // Only supported by firmware with controller-based privacy.
+        pub const GAP_ADD_DEVICES_TO_RESOLVING_LIST = 0x29;
     }
     Gatt = 0x2;
     {
@@ -123,6 +131,13 @@ opcodes! {
         pub const GATT_READ_HANDLE_VALUE = 0x2A;
         pub const GATT_READ_HANDLE_VALUE_OFFSET = 0x2B;
         pub const GATT_UPDATE_LONG_CHARACTERISTIC_VALUE = 0x2C;
+
+        // Only supported by BlueNRG-2 firmware, which keeps the GATT database in RAM.
+        pub const GATT_STORE_DB = 0x30;
+
+        // Only supported by firmware that implements the ATT Multiple Handle Value Notification
+        // (Bluetooth 5.2).
+        pub const GATT_SEND_MULTIPLE_NOTIFICATIONS = 0x31;
     }
     L2Cap = 0x3;
     {
@@ -130,3 +145,25 @@ opcodes! {
         pub const L2CAP_CONN_PARAM_UPDATE_RESP = 0x02;
     }
 }
+
+// Standard LE Controller commands that the BlueNRG accepts but the HCI crate does not wrap.
+const LE_OGF: u16 = 0x08;
+
+// Data Length Extension (Bluetooth 4.2).
+pub const LE_SET_DATA_LENGTH: Opcode = Opcode::new(LE_OGF, 0x0022);
+
+// Controller-based privacy (Bluetooth 4.2).
+pub const LE_ADD_DEVICE_TO_RESOLVING_LIST: Opcode = Opcode::new(LE_OGF, 0x0027);
+pub const LE_REMOVE_DEVICE_FROM_RESOLVING_LIST: Opcode = Opcode::new(LE_OGF, 0x0028);
+pub const LE_CLEAR_RESOLVING_LIST: Opcode = Opcode::new(LE_OGF, 0x0029);
+pub const LE_READ_MAXIMUM_DATA_LENGTH: Opcode = Opcode::new(LE_OGF, 0x002F);
+
+// 2M and Coded PHY (Bluetooth 5.0).
+pub const LE_SET_PHY: Opcode = Opcode::new(LE_OGF, 0x0032);
+
+// Periodic advertising (Bluetooth 5.0), which firmware that supports periodic advertising accepts
+// in place of vendor-specific commands.
+#[cfg(feature = "periodic-advertising")]
+pub const LE_SET_PERIODIC_ADVERTISING_PARAMETERS: Opcode = Opcode::new(LE_OGF, 0x003E);
+#[cfg(feature = "periodic-advertising")]
+pub const LE_SET_PERIODIC_ADVERTISING_ENABLE: Opcode = Opcode::new(LE_OGF, 0x0040);
@@ -0,0 +1,370 @@
+//! Helper for correlating GATT client procedures with their completion.
+//!
+//! The BlueNRG runs at most one GATT client procedure per connection at a time, and reports the end
+//! of the procedure with a [GATT Procedure Complete](BlueNRGEvent::GattProcedureComplete) (or [GATT
+//! Procedure Timeout](BlueNRGEvent::GattProcedureTimeout)) event that does not say which procedure
+//! finished. [`PendingProcedures`] remembers which procedure was started on each connection, so the
+//! completion can be matched to the request that started it.
+//!
+//! Record the procedure with [`start`](PendingProcedures::start) when the command to start it is
+//! accepted, and pass every event to [`handle_event`](PendingProcedures::handle_event).
+//!
+//! A procedure that fails at the ATT layer is reported with an [ATT Error
+//! Response](BlueNRGEvent::AttErrorResponse) before the completion. [`ProcedureTracker`] follows
+//! one procedure through both, and reports a single [`ProcedureOutcome`]. [`PendingProcedures`]
+//! and [`CccdDiscovery`] use it to report how their procedures ended.
+//!
+//! [`CccdDiscovery`] runs the most common descriptor discovery: finding the Client Characteristic
+//! Configuration descriptor of a characteristic, so the client can subscribe to it.
+
+use crate::event::{
+    AttErrorResponse, AttributeHandle, BlueNRGEvent, ConnectionHandle, GattProcedureStatus,
+};
+use crate::gatt::{CharacteristicHandle, Commands as GattCommands, Range, RangeError};
+
+/// GATT client procedures, named after the [GATT commands](crate::gatt::Commands) that start them.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GattProcedure {
+    /// [`exchange_configuration`](crate::gatt::Commands::exchange_configuration)
+    ExchangeConfiguration,
+    /// [`find_information_request`](crate::gatt::Commands::find_information_request)
+    FindInformation,
+    /// [`find_by_type_value_request`](crate::gatt::Commands::find_by_type_value_request)
+    FindByTypeValue,
+    /// [`read_by_type_request`](crate::gatt::Commands::read_by_type_request)
+    ReadByType,
+    /// [`read_by_group_type_request`](crate::gatt::Commands::read_by_group_type_request)
+    ReadByGroupType,
+    /// [`prepare_write_request`](crate::gatt::Commands::prepare_write_request)
+    PrepareWrite,
+    /// [`execute_write_request`](crate::gatt::Commands::execute_write_request) or
+    /// [`cancel_write_request`](crate::gatt::Commands::cancel_write_request)
+    ExecuteWrite,
+    /// [`discover_all_primary_services`](crate::gatt::Commands::discover_all_primary_services)
+    DiscoverAllPrimaryServices,
+    /// [`discover_primary_services_by_uuid`](crate::gatt::Commands::discover_primary_services_by_uuid)
+    DiscoverPrimaryServicesByUuid,
+    /// [`find_included_services`](crate::gatt::Commands::find_included_services)
+    FindIncludedServices,
+    /// [`discover_all_characteristics_of_service`](crate::gatt::Commands::discover_all_characteristics_of_service)
+    DiscoverAllCharacteristicsOfService,
+    /// [`discover_characteristics_by_uuid`](crate::gatt::Commands::discover_characteristics_by_uuid)
+    DiscoverCharacteristicsByUuid,
+    /// [`discover_all_characteristic_descriptors`](crate::gatt::Commands::discover_all_characteristic_descriptors)
+    DiscoverAllCharacteristicDescriptors,
+    /// [`read_characteristic_value`](crate::gatt::Commands::read_characteristic_value)
+    ReadCharacteristicValue,
+    /// [`read_characteristic_using_uuid`](crate::gatt::Commands::read_characteristic_using_uuid)
+    ReadCharacteristicUsingUuid,
+    /// [`read_long_characteristic_value`](crate::gatt::Commands::read_long_characteristic_value)
+    ReadLongCharacteristicValue,
+    /// [`read_multiple_characteristic_values`](crate::gatt::Commands::read_multiple_characteristic_values)
+    ReadMultipleCharacteristicValues,
+    /// [`write_characteristic_value`](crate::gatt::Commands::write_characteristic_value)
+    WriteCharacteristicValue,
+    /// [`write_long_characteristic_value`](crate::gatt::Commands::write_long_characteristic_value)
+    WriteLongCharacteristicValue,
+    /// [`write_characteristic_value_reliably`](crate::gatt::Commands::write_characteristic_value_reliably)
+    WriteCharacteristicValueReliably,
+    /// [`write_long_characteristic_descriptor`](crate::gatt::Commands::write_long_characteristic_descriptor)
+    WriteLongCharacteristicDescriptor,
+    /// [`read_long_characteristic_descriptor`](crate::gatt::Commands::read_long_characteristic_descriptor)
+    ReadLongCharacteristicDescriptor,
+    /// [`write_characteristic_descriptor`](crate::gatt::Commands::write_characteristic_descriptor)
+    WriteCharacteristicDescriptor,
+    /// [`read_characteristic_descriptor`](crate::gatt::Commands::read_characteristic_descriptor)
+    ReadCharacteristicDescriptor,
+}
+
+/// The result of a GATT client procedure, as reported by [`ProcedureTracker`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ProcedureOutcome {
+    /// The procedure completed successfully.
+    Success,
+
+    /// The server rejected a request of the procedure. Includes the [ATT Error
+    /// Response](BlueNRGEvent::AttErrorResponse) that the server sent. This takes precedence over
+    /// the status of the completion that follows it.
+    AttError(AttErrorResponse),
+
+    /// The procedure completed with a failure status, without an ATT error response. Includes the
+    /// status.
+    Failed(GattProcedureStatus),
+
+    /// The controller reported a [GATT Procedure Timeout](BlueNRGEvent::GattProcedureTimeout).
+    Timeout,
+}
+
+/// Follows one GATT client procedure on a connection until it ends.
+///
+/// Create the tracker when the command that starts the procedure is accepted, and pass every event
+/// to [`handle_event`](ProcedureTracker::handle_event). The response events themselves are left to
+/// the caller; the tracker only correlates the events that end the procedure.
+#[derive(Copy, Clone, Debug)]
+pub struct ProcedureTracker {
+    conn_handle: ConnectionHandle,
+    att_error: Option<AttErrorResponse>,
+}
+
+impl ProcedureTracker {
+    /// Returns a tracker for a procedure that was just started on the connection.
+    pub fn new(conn_handle: ConnectionHandle) -> ProcedureTracker {
+        ProcedureTracker {
+            conn_handle,
+            att_error: None,
+        }
+    }
+
+    /// Returns the connection on which the procedure runs.
+    pub fn conn_handle(&self) -> ConnectionHandle {
+        self.conn_handle
+    }
+
+    /// Updates the tracker with an event read from the controller.
+    ///
+    /// Returns the outcome if the event ends the procedure: a [GATT Procedure
+    /// Complete](BlueNRGEvent::GattProcedureComplete) or [GATT Procedure
+    /// Timeout](BlueNRGEvent::GattProcedureTimeout) event for the connection. An [ATT Error
+    /// Response](BlueNRGEvent::AttErrorResponse) for the connection is remembered and reported
+    /// with the completion that follows it. Returns `None` for any other event.
+    pub fn handle_event(&mut self, event: &BlueNRGEvent) -> Option<ProcedureOutcome> {
+        match *event {
+            BlueNRGEvent::AttErrorResponse(ref response)
+                if response.conn_handle == self.conn_handle =>
+            {
+                self.att_error = Some(*response);
+                None
+            }
+            BlueNRGEvent::GattProcedureComplete(ref event)
+                if event.conn_handle == self.conn_handle =>
+            {
+                Some(match (self.att_error.take(), event.status) {
+                    (Some(response), _) => ProcedureOutcome::AttError(response),
+                    (None, GattProcedureStatus::Success) => ProcedureOutcome::Success,
+                    (None, status) => ProcedureOutcome::Failed(status),
+                })
+            }
+            BlueNRGEvent::GattProcedureTimeout(ref event)
+                if event.conn_handle == self.conn_handle =>
+            {
+                self.att_error = None;
+                Some(ProcedureOutcome::Timeout)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A GATT client procedure that has ended, as reported by
+/// [`handle_event`](PendingProcedures::handle_event).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CompletedProcedure<T> {
+    /// Connection on which the procedure ran.
+    pub conn_handle: ConnectionHandle,
+
+    /// The procedure that was [started](PendingProcedures::start) on the connection.
+    pub procedure: T,
+
+    /// How the procedure ended.
+    pub outcome: ProcedureOutcome,
+}
+
+/// Errors that may occur when starting a procedure with [`PendingProcedures::start`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PendingProcedureError<T> {
+    /// A procedure is already pending on the connection. The controller would reject the new
+    /// procedure. Includes the pending procedure.
+    Busy(T),
+
+    /// Procedures are already pending on the maximum number of connections.
+    Full,
+}
+
+impl<T: core::fmt::Debug> core::fmt::Display for PendingProcedureError<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            PendingProcedureError::Busy(ref pending) => {
+                write!(f, "procedure already pending: {:?}", pending)
+            }
+            PendingProcedureError::Full => f.write_str("too many connections with procedures"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: core::fmt::Debug> std::error::Error for PendingProcedureError<T> {}
+
+/// Maximum number of connections the BlueNRG supports at once.
+const MAX_CONNECTIONS: usize = 8;
+
+/// Tracks the GATT client procedure pending on each connection.
+///
+/// `T` describes the procedure. It is [`GattProcedure`] by default, but applications can use their
+/// own type to attach more context (for example, which characteristic is being read).
+#[derive(Copy, Clone, Debug)]
+pub struct PendingProcedures<T = GattProcedure> {
+    pending: [Option<(ProcedureTracker, T)>; MAX_CONNECTIONS],
+}
+
+impl<T> PendingProcedures<T>
+where
+    T: Copy,
+{
+    /// Returns a tracker with no pending procedures.
+    pub fn new() -> PendingProcedures<T> {
+        PendingProcedures {
+            pending: [None; MAX_CONNECTIONS],
+        }
+    }
+
+    /// Records that `procedure` was started on the connection.
+    ///
+    /// # Errors
+    ///
+    /// - [Busy](PendingProcedureError::Busy) if a procedure is already pending on the connection.
+    /// - [Full](PendingProcedureError::Full) if procedures are already pending on the maximum
+    ///   number of connections.
+    pub fn start(
+        &mut self,
+        conn_handle: ConnectionHandle,
+        procedure: T,
+    ) -> Result<(), PendingProcedureError<T>> {
+        if let Some(pending) = self.pending(conn_handle) {
+            return Err(PendingProcedureError::Busy(pending));
+        }
+
+        match self.pending.iter_mut().find(|slot| slot.is_none()) {
+            Some(slot) => {
+                *slot = Some((ProcedureTracker::new(conn_handle), procedure));
+                Ok(())
+            }
+            None => Err(PendingProcedureError::Full),
+        }
+    }
+
+    /// Returns the procedure pending on the connection, if any.
+    pub fn pending(&self, conn_handle: ConnectionHandle) -> Option<T> {
+        self.pending
+            .iter()
+            .filter_map(|slot| *slot)
+            .find(|&(tracker, _)| tracker.conn_handle() == conn_handle)
+            .map(|(_, procedure)| procedure)
+    }
+
+    /// Forgets the procedure pending on the connection, if any, and returns it. Call this when the
+    /// connection is closed, since the controller does not report the end of the procedure then.
+    pub fn cancel(&mut self, conn_handle: ConnectionHandle) -> Option<T> {
+        for slot in self.pending.iter_mut() {
+            if let Some((tracker, procedure)) = *slot {
+                if tracker.conn_handle() == conn_handle {
+                    *slot = None;
+                    return Some(procedure);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Updates the tracker with an event read from the controller.
+    ///
+    /// Each pending procedure is followed by a [`ProcedureTracker`]. Returns the procedure that
+    /// ended if the event ends a pending procedure, as described in
+    /// [`ProcedureTracker::handle_event`]. Returns `None` for any other event.
+    pub fn handle_event(&mut self, event: &BlueNRGEvent) -> Option<CompletedProcedure<T>> {
+        for slot in self.pending.iter_mut() {
+            if let Some((ref mut tracker, procedure)) = *slot {
+                if let Some(outcome) = tracker.handle_event(event) {
+                    let conn_handle = tracker.conn_handle();
+                    *slot = None;
+                    return Some(CompletedProcedure {
+                        conn_handle,
+                        procedure,
+                        outcome,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<T> Default for PendingProcedures<T>
+where
+    T: Copy,
+{
+    fn default() -> PendingProcedures<T> {
+        PendingProcedures::new()
+    }
+}
+
+/// Discovers the descriptors of one characteristic to find its Client Characteristic Configuration
+/// descriptor (CCCD), which the client writes to subscribe to notifications or indications.
+///
+/// Call [`start`](CccdDiscovery::start) to send the [discover all characteristic
+/// descriptors](crate::gatt::Commands::discover_all_characteristic_descriptors) command, then pass
+/// every event to [`handle_event`](CccdDiscovery::handle_event) until it reports the end of the
+/// procedure.
+#[derive(Copy, Clone, Debug)]
+pub struct CccdDiscovery {
+    tracker: ProcedureTracker,
+    range: Range<CharacteristicHandle>,
+    cccd: Option<AttributeHandle>,
+}
+
+impl CccdDiscovery {
+    /// Returns a discovery for the characteristic with the given value handle. `end_handle` is the
+    /// last handle of the characteristic: the handle before the next characteristic declaration,
+    /// or the end of the service.
+    ///
+    /// # Errors
+    ///
+    /// - [Inverted](RangeError::Inverted) if `end_handle` is not after `value_handle`, so the
+    ///   characteristic has no room for descriptors.
+    pub fn new(
+        conn_handle: ConnectionHandle,
+        value_handle: CharacteristicHandle,
+        end_handle: CharacteristicHandle,
+    ) -> Result<CccdDiscovery, RangeError> {
+        let first = value_handle.0.checked_add(1).ok_or(RangeError::Inverted)?;
+
+        Ok(CccdDiscovery {
+            tracker: ProcedureTracker::new(conn_handle),
+            range: Range::new(CharacteristicHandle(first), end_handle)?,
+            cccd: None,
+        })
+    }
+
+    /// Sends the command to discover the descriptors of the characteristic.
+    ///
+    /// # Errors
+    ///
+    /// Only underlying communication errors are reported.
+    pub fn start<C>(&self, controller: &mut C) -> nb::Result<(), C::Error>
+    where
+        C: GattCommands + ?Sized,
+    {
+        controller.discover_all_characteristic_descriptors(self.tracker.conn_handle(), self.range)
+    }
+
+    /// Updates the discovery with an event read from the controller.
+    ///
+    /// Returns the outcome if the event ends the procedure, as described in
+    /// [`ProcedureTracker::handle_event`]. Returns `None` for any other event. Once the procedure
+    /// has ended, [`cccd_handle`](CccdDiscovery::cccd_handle) returns the result.
+    pub fn handle_event(&mut self, event: &BlueNRGEvent) -> Option<ProcedureOutcome> {
+        if let BlueNRGEvent::AttFindInformationResponse(ref response) = *event {
+            if response.conn_handle == self.tracker.conn_handle() && self.cccd.is_none() {
+                self.cccd = response.cccd_handle();
+            }
+        }
+
+        self.tracker.handle_event(event)
+    }
+
+    /// Returns the handle of the CCCD, if it has been found.
+    pub fn cccd_handle(&self) -> Option<AttributeHandle> {
+        self.cccd
+    }
+}
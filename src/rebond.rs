@@ -0,0 +1,171 @@
+//! Helper for responding to [GAP Bond Lost](BlueNRGEvent::GapBondLost) events.
+//!
+//! When a peer has lost the bond with this device, the BlueNRG stops the pairing procedure until
+//! the application either allows the rebond with
+//! [`allow_rebond`](crate::gap::Commands::allow_rebond) or gives up on the connection. An
+//! application that does neither leaves the pairing procedure waiting until it times out.
+//!
+//! [`RebondPolicy`] makes that decision. Pass every event to
+//! [`handle_event`](RebondPolicy::handle_event), and perform the [`RebondAction`] it returns for a
+//! bond lost event.
+//!
+//! The bond lost event does not say which connection it belongs to, so the policy follows the
+//! connections the controller reports and applies the event to the most recent one. With more than
+//! one connection open, start pairing on one connection at a time.
+
+use crate::event::BlueNRGEvent;
+
+/// A connection whose peer has lost the bond.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Peer {
+    /// Connection to the peer.
+    pub conn_handle: hci::ConnectionHandle,
+
+    /// Address the peer connected with.
+    pub address: hci::BdAddrType,
+}
+
+/// How the [`RebondPolicy`] decides whether to allow a rebond.
+pub enum RebondMode<F> {
+    /// Always allow the rebond.
+    Allow,
+
+    /// Allow the rebond if the function returns true. The function is called for each bond lost
+    /// event, so it can check e.g. whether the user is present to confirm the pairing.
+    AllowIf(F),
+
+    /// Never allow the rebond, and disconnect instead.
+    Reject,
+}
+
+/// What the application should do in response to a [GAP Bond
+/// Lost](BlueNRGEvent::GapBondLost) event.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum RebondAction {
+    /// Allow the rebond with [`allow_rebond`](crate::gap::Commands::allow_rebond). Includes the
+    /// connection.
+    AllowRebond(hci::ConnectionHandle),
+
+    /// Disconnect with [`terminate`](crate::gap::Commands::terminate). Includes the connection
+    /// and the reason to send to the peer.
+    Terminate(hci::ConnectionHandle, hci::Status<crate::event::Status>),
+}
+
+/// Decides how to respond to [GAP Bond Lost](BlueNRGEvent::GapBondLost) events.
+pub struct RebondPolicy<'a, F> {
+    mode: RebondMode<F>,
+    allow_list: Option<&'a [hci::BdAddrType]>,
+    peer: Option<Peer>,
+    rebonding: Option<hci::ConnectionHandle>,
+}
+
+impl<'a> RebondPolicy<'a, fn(&Peer) -> bool> {
+    /// Returns a policy that always allows the rebond.
+    pub fn allow() -> Self {
+        RebondPolicy::new(RebondMode::Allow)
+    }
+
+    /// Returns a policy that never allows the rebond.
+    pub fn reject() -> Self {
+        RebondPolicy::new(RebondMode::Reject)
+    }
+}
+
+impl<'a, F> RebondPolicy<'a, F>
+where
+    F: FnMut(&Peer) -> bool,
+{
+    /// Returns a policy that allows the rebond if `allow` returns true for the peer.
+    pub fn allow_if(allow: F) -> Self {
+        RebondPolicy::new(RebondMode::AllowIf(allow))
+    }
+
+    /// Returns a policy that decides with the given mode.
+    pub fn new(mode: RebondMode<F>) -> Self {
+        RebondPolicy {
+            mode,
+            allow_list: None,
+            peer: None,
+            rebonding: None,
+        }
+    }
+
+    /// Restricts the rebond to the given peers, for example the [bonded
+    /// addresses](crate::event::command::GapBondedDevices::bonded_addresses) the application
+    /// read before. A rebond from any other peer is rejected regardless of the mode.
+    ///
+    /// The list is compared with the address the peer connected with, as reported in the LE
+    /// Connection Complete event. A peer that connects with a resolvable private address does not
+    /// match its identity address in the list, and the host cannot resolve the address itself
+    /// because the IRKs stay in the controller, so its rebond is rejected. Applications whose
+    /// peers use privacy should decide with [`allow_if`](RebondPolicy::allow_if) instead. Like the
+    /// rest of the policy, the list is only checked against the most recent connection.
+    pub fn with_allow_list(mut self, addresses: &'a [hci::BdAddrType]) -> Self {
+        self.allow_list = Some(addresses);
+        self
+    }
+
+    /// Returns the connection that was allowed to rebond, if its pairing has not completed yet.
+    pub fn rebonding(&self) -> Option<hci::ConnectionHandle> {
+        self.rebonding
+    }
+
+    /// Updates the policy with an event read from the controller.
+    ///
+    /// Returns the action to perform for a [GAP Bond Lost](BlueNRGEvent::GapBondLost) event, and
+    /// `None` for all other events. A bond lost event without an open connection is ignored.
+    pub fn handle_event(
+        &mut self,
+        event: &hci::event::Event<BlueNRGEvent>,
+    ) -> Option<RebondAction> {
+        match *event {
+            hci::event::Event::LeConnectionComplete(ref event) => {
+                if event.status == hci::Status::Success {
+                    self.peer = Some(Peer {
+                        conn_handle: event.conn_handle,
+                        address: event.peer_bd_addr,
+                    });
+                }
+            }
+            hci::event::Event::DisconnectionComplete(ref event) => {
+                if self.peer.map(|peer| peer.conn_handle) == Some(event.conn_handle) {
+                    self.peer = None;
+                }
+                if self.rebonding == Some(event.conn_handle) {
+                    self.rebonding = None;
+                }
+            }
+            hci::event::Event::Vendor(BlueNRGEvent::GapPairingComplete(ref event)) => {
+                if self.rebonding == Some(event.conn_handle) {
+                    self.rebonding = None;
+                }
+            }
+            hci::event::Event::Vendor(BlueNRGEvent::GapBondLost) => {
+                let peer = self.peer?;
+                return Some(if self.allows(&peer) {
+                    self.rebonding = Some(peer.conn_handle);
+                    RebondAction::AllowRebond(peer.conn_handle)
+                } else {
+                    RebondAction::Terminate(peer.conn_handle, hci::Status::AuthFailure)
+                });
+            }
+            _ => (),
+        }
+
+        None
+    }
+
+    fn allows(&mut self, peer: &Peer) -> bool {
+        if let Some(addresses) = self.allow_list {
+            if !addresses.contains(&peer.address) {
+                return false;
+            }
+        }
+
+        match self.mode {
+            RebondMode::Allow => true,
+            RebondMode::AllowIf(ref mut allow) => allow(peer),
+            RebondMode::Reject => false,
+        }
+    }
+}
@@ -0,0 +1,363 @@
+//! Helper for the standard bring-up sequence.
+//!
+//! Nearly every application configures the controller the same way after it boots: set the public
+//! address, set the TX power, initialize the GATT and GAP, write the device name and appearance,
+//! set the authentication requirements, and start advertising. [`BlueNrgSetup`] runs that sequence
+//! from a declarative [`SetupParameters`], one command at a time, so it stays non-blocking.
+//!
+//! Pass every event read after resetting the controller to [`poll`](BlueNrgSetup::poll), until it
+//! reports [Complete](SetupProgress::Complete) with the handles of the GAP service. Events that do
+//! not belong to the sequence are ignored, so the application can keep handling them as usual.
+
+use crate::boot::{BootMonitor, BootProgress};
+use crate::event::command::{GapInit, ReturnParameters};
+use crate::event::{BlueNRGEvent, ResetReason, Status, MAX_NAME_LEN};
+use crate::gap::{Appearance, AuthenticationRequirements, DiscoverableParameters, Role};
+use crate::gatt::UpdateCharacteristicValueParameters;
+use crate::hal::{ConfigData, PowerLevel};
+
+/// Choices for the bring-up sequence run by [`BlueNrgSetup`].
+pub struct SetupParameters<'a, 'b> {
+    /// Public address of the device, written to the controller's configuration data.
+    pub address: hci::BdAddr,
+
+    /// Name written to the device name characteristic of the GAP service. Must be 248 bytes or
+    /// fewer.
+    pub name: &'a [u8],
+
+    /// Appearance written to the appearance characteristic of the GAP service.
+    pub appearance: Appearance,
+
+    /// GAP role(s) of the device.
+    pub role: Role,
+
+    /// Should the GAP enable privacy?
+    #[cfg(feature = "ms")]
+    pub privacy_enabled: bool,
+
+    /// Transmitter power level.
+    pub tx_power_level: PowerLevel,
+
+    /// Authentication requirements for pairing.
+    pub authentication: AuthenticationRequirements,
+
+    /// Parameters used to start advertising once the rest of the sequence is done.
+    pub discoverable: DiscoverableParameters<'a, 'b>,
+}
+
+/// Steps of the bring-up sequence, in the order [`BlueNrgSetup`] runs them. Each step is one
+/// command.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SetupStep {
+    /// [`write_config_data`](crate::hal::Commands::write_config_data) with the public address.
+    WriteConfigData,
+    /// [`set_tx_power_level`](crate::hal::Commands::set_tx_power_level)
+    SetTxPowerLevel,
+    /// [GATT `init`](crate::gatt::Commands::init)
+    GattInit,
+    /// [GAP `init`](crate::gap::Commands::init)
+    GapInit,
+    /// [`update_characteristic_value`](crate::gatt::Commands::update_characteristic_value) for the
+    /// device name characteristic.
+    UpdateDeviceName,
+    /// [`update_characteristic_value`](crate::gatt::Commands::update_characteristic_value) for the
+    /// appearance characteristic.
+    UpdateAppearance,
+    /// [`set_authentication_requirement`](crate::gap::Commands::set_authentication_requirement)
+    SetAuthenticationRequirement,
+    /// [`set_discoverable`](crate::gap::Commands::set_discoverable)
+    SetDiscoverable,
+}
+
+impl SetupStep {
+    fn next(self) -> Option<SetupStep> {
+        match self {
+            SetupStep::WriteConfigData => Some(SetupStep::SetTxPowerLevel),
+            SetupStep::SetTxPowerLevel => Some(SetupStep::GattInit),
+            SetupStep::GattInit => Some(SetupStep::GapInit),
+            SetupStep::GapInit => Some(SetupStep::UpdateDeviceName),
+            SetupStep::UpdateDeviceName => Some(SetupStep::UpdateAppearance),
+            SetupStep::UpdateAppearance => Some(SetupStep::SetAuthenticationRequirement),
+            SetupStep::SetAuthenticationRequirement => Some(SetupStep::SetDiscoverable),
+            SetupStep::SetDiscoverable => None,
+        }
+    }
+
+    // Returns the status of the command for this step, if the return parameters belong to it.
+    fn status(self, params: &ReturnParameters) -> Option<hci::Status<Status>> {
+        match (self, params) {
+            (SetupStep::WriteConfigData, &ReturnParameters::HalWriteConfigData(status))
+            | (SetupStep::SetTxPowerLevel, &ReturnParameters::HalSetTxPowerLevel(status))
+            | (SetupStep::GattInit, &ReturnParameters::GattInit(status))
+            | (
+                SetupStep::UpdateDeviceName,
+                &ReturnParameters::GattUpdateCharacteristicValue(status),
+            )
+            | (
+                SetupStep::UpdateAppearance,
+                &ReturnParameters::GattUpdateCharacteristicValue(status),
+            )
+            | (
+                SetupStep::SetAuthenticationRequirement,
+                &ReturnParameters::GapSetAuthenticationRequirement(status),
+            )
+            | (SetupStep::SetDiscoverable, &ReturnParameters::GapSetDiscoverable(status)) => {
+                Some(status)
+            }
+            (SetupStep::GapInit, &ReturnParameters::GapInit(ref params)) => Some(params.status),
+            _ => None,
+        }
+    }
+}
+
+/// Progress of the bring-up sequence, as reported by [`BlueNrgSetup::poll`].
+#[derive(Copy, Clone, Debug)]
+pub enum SetupProgress {
+    /// The controller has not reported that the firmware is ready yet.
+    WaitingForBoot,
+
+    /// The command for the step was sent, and the setup is waiting for it to complete.
+    Started(SetupStep),
+
+    /// Every step completed. Includes the GAP service and characteristic handles returned by
+    /// [GAP `init`](crate::gap::Commands::init).
+    Complete(GapInit),
+}
+
+/// Errors that may occur during the bring-up sequence.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SetupError<E> {
+    /// The controller started in updater mode instead of running the BLE stack. Includes the
+    /// reason the updater was entered.
+    EnteredUpdater(ResetReason),
+
+    /// The controller reported a failure for a step. Includes the step and the status.
+    CommandFailed(SetupStep, hci::Status<Status>),
+
+    /// A GAP command failed, either because its parameters are invalid or because of a
+    /// communication error.
+    Gap(crate::gap::Error<E>),
+
+    /// A GATT command failed, either because its parameters are invalid or because of a
+    /// communication error.
+    Gatt(crate::gatt::Error<E>),
+
+    /// The [name](SetupParameters::name) was longer than 248 bytes. Includes the length of the
+    /// name.
+    NameTooLong(usize),
+
+    /// Underlying communication error.
+    Comm(E),
+}
+
+impl<E: core::fmt::Debug> core::fmt::Display for SetupError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            SetupError::EnteredUpdater(reason) => {
+                write!(f, "controller entered updater mode: {:?}", reason)
+            }
+            SetupError::CommandFailed(step, ref status) => {
+                write!(f, "{:?} failed: {:?}", step, status)
+            }
+            SetupError::Gap(ref e) => write!(f, "GAP command failed: {}", e),
+            SetupError::Gatt(ref e) => write!(f, "GATT command failed: {}", e),
+            SetupError::NameTooLong(len) => {
+                write!(f, "device name is {} bytes, longer than 248 bytes", len)
+            }
+            SetupError::Comm(ref e) => write!(f, "communication error: {:?}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: core::fmt::Debug> std::error::Error for SetupError<E> {}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum State {
+    WaitingForBoot,
+    Pending(SetupStep),
+    Started(SetupStep),
+    Complete,
+}
+
+/// Runs the standard bring-up sequence described by [`SetupParameters`].
+pub struct BlueNrgSetup<'a, 'b> {
+    params: SetupParameters<'a, 'b>,
+    state: State,
+    gap_init: Option<GapInit>,
+}
+
+impl<'a, 'b> BlueNrgSetup<'a, 'b> {
+    /// Returns a setup that waits for the controller to boot before sending the first command.
+    pub fn new(params: SetupParameters<'a, 'b>) -> BlueNrgSetup<'a, 'b> {
+        BlueNrgSetup {
+            params,
+            state: State::WaitingForBoot,
+            gap_init: None,
+        }
+    }
+
+    /// Returns the GAP service and characteristic handles, once [GAP
+    /// `init`](SetupStep::GapInit) has completed.
+    pub fn gap_handles(&self) -> Option<GapInit> {
+        self.gap_init
+    }
+
+    /// Updates the setup with an event read from the controller, and sends the command for the
+    /// next step if the event completed the current one.
+    ///
+    /// Events that do not belong to the sequence are ignored.
+    ///
+    /// # Errors
+    ///
+    /// - `WouldBlock` if the command for the next step could not be sent yet. Call `poll` again,
+    ///   with the same or a later event, to retry.
+    /// - [EnteredUpdater](SetupError::EnteredUpdater) if the controller started in updater mode.
+    /// - [CommandFailed](SetupError::CommandFailed) if the controller reports that a step failed.
+    /// - [Gap](SetupError::Gap) or [Gatt](SetupError::Gatt) if a command's parameters are
+    ///   invalid.
+    /// - [NameTooLong](SetupError::NameTooLong) if the name is longer than 248 bytes. This is
+    ///   checked before any command is sent.
+    /// - Underlying communication errors.
+    pub fn poll<C, E>(
+        &mut self,
+        controller: &mut C,
+        event: &hci::event::Event<BlueNRGEvent>,
+    ) -> nb::Result<SetupProgress, SetupError<E>>
+    where
+        C: crate::gap::Commands<Error = E>
+            + crate::gatt::Commands<Error = E>
+            + crate::hal::Commands<Error = E>
+            + ?Sized,
+    {
+        if self.params.name.len() > MAX_NAME_LEN {
+            return Err(nb::Error::Other(SetupError::NameTooLong(
+                self.params.name.len(),
+            )));
+        }
+
+        self.handle_event(event)?;
+        if let State::Pending(step) = self.state {
+            self.send(controller, step)?;
+            self.state = State::Started(step);
+        }
+
+        Ok(match self.state {
+            State::WaitingForBoot => SetupProgress::WaitingForBoot,
+            State::Pending(step) | State::Started(step) => SetupProgress::Started(step),
+            State::Complete => SetupProgress::Complete(self.gap_init.unwrap()),
+        })
+    }
+
+    fn handle_event<E>(
+        &mut self,
+        event: &hci::event::Event<BlueNRGEvent>,
+    ) -> Result<(), SetupError<E>> {
+        match (self.state, event) {
+            (State::WaitingForBoot, &hci::event::Event::Vendor(ref event)) => {
+                match BootMonitor::new().handle_event(event) {
+                    BootProgress::Ready => (),
+                    #[cfg(feature = "ms")]
+                    BootProgress::Crashed(_) => (),
+                    BootProgress::EnteredUpdater(reason) => {
+                        return Err(SetupError::EnteredUpdater(reason))
+                    }
+                    _ => return Ok(()),
+                }
+                self.state = State::Pending(SetupStep::WriteConfigData);
+            }
+            (State::Started(step), &hci::event::Event::CommandComplete(ref event)) => {
+                let params = match event.return_params {
+                    hci::event::command::ReturnParameters::Vendor(ref params) => params,
+                    _ => return Ok(()),
+                };
+                let status = match step.status(params) {
+                    Some(status) => status,
+                    None => return Ok(()),
+                };
+                if status != hci::Status::Success {
+                    return Err(SetupError::CommandFailed(step, status));
+                }
+
+                if let ReturnParameters::GapInit(gap_init) = *params {
+                    self.gap_init = Some(gap_init);
+                }
+                self.state = match step.next() {
+                    Some(next) => State::Pending(next),
+                    None => State::Complete,
+                };
+            }
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    fn send<C, E>(&self, controller: &mut C, step: SetupStep) -> nb::Result<(), SetupError<E>>
+    where
+        C: crate::gap::Commands<Error = E>
+            + crate::gatt::Commands<Error = E>
+            + crate::hal::Commands<Error = E>
+            + ?Sized,
+    {
+        match step {
+            SetupStep::WriteConfigData => controller
+                .write_config_data(&ConfigData::public_address(self.params.address).build())
+                .map_err(rewrap(SetupError::Comm)),
+            SetupStep::SetTxPowerLevel => controller
+                .set_tx_power_level(self.params.tx_power_level)
+                .map_err(rewrap(SetupError::Comm)),
+            SetupStep::GattInit => controller.init_gatt().map_err(rewrap(SetupError::Comm)),
+            #[cfg(feature = "ms")]
+            SetupStep::GapInit => controller
+                .init_gap(
+                    self.params.role,
+                    self.params.privacy_enabled,
+                    self.params.name.len() as u8,
+                )
+                .map_err(rewrap(SetupError::Comm)),
+            #[cfg(not(feature = "ms"))]
+            SetupStep::GapInit => controller
+                .init_gap(self.params.role)
+                .map_err(rewrap(SetupError::Comm)),
+            SetupStep::UpdateDeviceName => {
+                // The step only starts once GAP init has completed.
+                let gap_init = self.gap_init.unwrap();
+                controller
+                    .update_characteristic_value(&UpdateCharacteristicValueParameters {
+                        service_handle: gap_init.service_handle,
+                        characteristic_handle: gap_init.dev_name_handle,
+                        offset: 0,
+                        value: self.params.name,
+                    })
+                    .map_err(rewrap(SetupError::Gatt))
+            }
+            SetupStep::UpdateAppearance => {
+                let gap_init = self.gap_init.unwrap();
+                controller
+                    .update_characteristic_value(&UpdateCharacteristicValueParameters {
+                        service_handle: gap_init.service_handle,
+                        characteristic_handle: gap_init.appearance_handle,
+                        offset: 0,
+                        value: &self.params.appearance.to_bytes(),
+                    })
+                    .map_err(rewrap(SetupError::Gatt))
+            }
+            SetupStep::SetAuthenticationRequirement => controller
+                .set_authentication_requirement(&self.params.authentication)
+                .map_err(rewrap(SetupError::Gap)),
+            SetupStep::SetDiscoverable => controller
+                .set_discoverable(&self.params.discoverable)
+                .map_err(rewrap(SetupError::Gap)),
+        }
+    }
+}
+
+fn rewrap<T, E>(
+    wrap: impl Fn(T) -> SetupError<E>,
+) -> impl Fn(nb::Error<T>) -> nb::Error<SetupError<E>> {
+    move |e| match e {
+        nb::Error::WouldBlock => nb::Error::WouldBlock,
+        nb::Error::Other(e) => nb::Error::Other(wrap(e)),
+    }
+}
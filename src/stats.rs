@@ -0,0 +1,311 @@
+//! Event statistics for field diagnostics.
+//!
+//! Devices in the field rarely have a debugger attached, so it helps to keep a running tally of
+//! what the controller has been sending and report it periodically. [`EventStats`] counts the
+//! events read from the controller by vendor event code, the parse errors by kind and by
+//! [`BlueNRGError`] variant, the [EventsLost](BlueNRGEvent::EventsLost) reports, and the most
+//! recent unknown event codes. It uses fixed-size storage, so it can live in a static on targets
+//! without an allocator.
+//!
+//! ```
+//! use bluenrg::stats::EventStats;
+//!
+//! let mut stats = EventStats::new();
+//! stats.record(&bluenrg::event::parse(&[0x05, 0x04]));
+//! stats.record(&bluenrg::event::parse(&[0x99, 0x0C]));
+//! assert_eq!(stats.count(0x0405), 1);
+//! assert_eq!(stats.errors(), 1);
+//! ```
+
+extern crate bluetooth_hci as hci;
+
+#[cfg(feature = "ms")]
+use crate::event::EventFlags;
+use crate::event::{BlueNRGError, BlueNRGEvent, ParseError};
+use core::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Maximum number of distinct event codes that are counted separately. This is enough for every
+/// event the crate can parse; events beyond it are only counted in the total.
+pub const MAX_EVENT_CODES: usize = 48;
+
+/// Number of unknown event codes that are remembered.
+pub const RECENT_UNKNOWN_EVENTS: usize = 4;
+
+/// Maximum number of distinct [`BlueNRGError`] variants that are counted separately. Errors of
+/// other variants are only counted as [ErrorKind::Vendor].
+pub const MAX_VENDOR_ERROR_VARIANTS: usize = 8;
+
+/// Kinds of parse errors counted by [`EventStats`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ErrorKind {
+    /// The event was not the right length.
+    BadLength,
+    /// The vendor event code was not recognized.
+    UnknownEvent,
+    /// The event was recognized, but one of its fields was not valid.
+    Vendor,
+    /// Any other error reported by the HCI layer.
+    Other,
+}
+
+const ERROR_KINDS: usize = 4;
+
+impl ErrorKind {
+    fn of(error: &hci::event::Error<ParseError>) -> ErrorKind {
+        let error = match *error {
+            hci::event::Error::Vendor(ParseError { ref error, .. }) => error,
+            _ => return ErrorKind::Other,
+        };
+        match *error {
+            hci::event::Error::BadLength(..) => ErrorKind::BadLength,
+            hci::event::Error::Vendor(BlueNRGError::UnknownEvent(_)) => ErrorKind::UnknownEvent,
+            hci::event::Error::Vendor(_) => ErrorKind::Vendor,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+/// Running counters of the events read from the controller.
+///
+/// Counters saturate instead of wrapping around. Call [`reset`](EventStats::reset) after each
+/// report to count per reporting period instead of since boot.
+#[derive(Clone, Debug)]
+pub struct EventStats {
+    counts: [(u16, u32); MAX_EVENT_CODES],
+    codes: usize,
+    events: u32,
+    errors: [u32; ERROR_KINDS],
+    vendor_errors: [(BlueNRGError, u32); MAX_VENDOR_ERROR_VARIANTS],
+    vendor_error_variants: usize,
+    #[cfg(feature = "ms")]
+    lost_events: u32,
+    #[cfg(feature = "ms")]
+    lost_event_flags: EventFlags,
+    recent_unknown: [u16; RECENT_UNKNOWN_EVENTS],
+    recent_unknown_len: usize,
+    next_unknown: usize,
+}
+
+impl Default for EventStats {
+    fn default() -> EventStats {
+        EventStats::new()
+    }
+}
+
+impl EventStats {
+    /// Returns an empty set of counters.
+    pub fn new() -> EventStats {
+        EventStats {
+            counts: [(0, 0); MAX_EVENT_CODES],
+            codes: 0,
+            events: 0,
+            errors: [0; ERROR_KINDS],
+            vendor_errors: [(BlueNRGError::UnknownEvent(0), 0); MAX_VENDOR_ERROR_VARIANTS],
+            vendor_error_variants: 0,
+            #[cfg(feature = "ms")]
+            lost_events: 0,
+            #[cfg(feature = "ms")]
+            lost_event_flags: EventFlags::empty(),
+            recent_unknown: [0; RECENT_UNKNOWN_EVENTS],
+            recent_unknown_len: 0,
+            next_unknown: 0,
+        }
+    }
+
+    /// Clears all counters.
+    pub fn reset(&mut self) {
+        *self = EventStats::new();
+    }
+
+    /// Updates the counters with the result of parsing an event.
+    pub fn record(&mut self, result: &Result<BlueNRGEvent, hci::event::Error<ParseError>>) {
+        match *result {
+            Ok(ref event) => self.record_event(event),
+            Err(ref error) => self.record_error(error),
+        }
+    }
+
+    fn record_event(&mut self, event: &BlueNRGEvent) {
+        self.events = self.events.saturating_add(1);
+
+        let code = event.code();
+        let known = self.counts[..self.codes]
+            .iter_mut()
+            .find(|entry| entry.0 == code);
+        match known {
+            Some(entry) => entry.1 = entry.1.saturating_add(1),
+            None if self.codes < MAX_EVENT_CODES => {
+                self.counts[self.codes] = (code, 1);
+                self.codes += 1;
+            }
+            None => (),
+        }
+
+        #[cfg(feature = "ms")]
+        {
+            if let BlueNRGEvent::EventsLost(flags) = *event {
+                self.lost_events = self.lost_events.saturating_add(1);
+                self.lost_event_flags |= flags;
+            }
+        }
+    }
+
+    fn record_error(&mut self, error: &hci::event::Error<ParseError>) {
+        let kind = ErrorKind::of(error);
+        self.errors[kind as usize] = self.errors[kind as usize].saturating_add(1);
+
+        match *error {
+            hci::event::Error::Vendor(ParseError {
+                error: hci::event::Error::Vendor(BlueNRGError::UnknownEvent(code)),
+                ..
+            }) => {
+                self.recent_unknown[self.next_unknown] = code;
+                self.next_unknown = (self.next_unknown + 1) % RECENT_UNKNOWN_EVENTS;
+                if self.recent_unknown_len < RECENT_UNKNOWN_EVENTS {
+                    self.recent_unknown_len += 1;
+                }
+            }
+            hci::event::Error::Vendor(ParseError {
+                error: hci::event::Error::Vendor(vendor_error),
+                ..
+            }) => self.record_vendor_error(vendor_error),
+            _ => (),
+        }
+    }
+
+    fn record_vendor_error(&mut self, error: BlueNRGError) {
+        let variant = core::mem::discriminant(&error);
+        let known = self.vendor_errors[..self.vendor_error_variants]
+            .iter_mut()
+            .find(|entry| core::mem::discriminant(&entry.0) == variant);
+        match known {
+            Some(entry) => *entry = (error, entry.1.saturating_add(1)),
+            None if self.vendor_error_variants < MAX_VENDOR_ERROR_VARIANTS => {
+                self.vendor_errors[self.vendor_error_variants] = (error, 1);
+                self.vendor_error_variants += 1;
+            }
+            None => (),
+        }
+    }
+
+    /// Returns the number of events that were parsed successfully.
+    pub fn events(&self) -> u32 {
+        self.events
+    }
+
+    /// Returns the number of events that were parsed successfully with the given vendor event
+    /// code.
+    pub fn count(&self, code: u16) -> u32 {
+        self.counts[..self.codes]
+            .iter()
+            .find(|&&(c, _)| c == code)
+            .map_or(0, |&(_, count)| count)
+    }
+
+    /// Returns the number of events per vendor event code, in the order the codes were first seen.
+    pub fn counts(&self) -> &[(u16, u32)] {
+        &self.counts[..self.codes]
+    }
+
+    /// Returns the total number of parse errors.
+    pub fn errors(&self) -> u32 {
+        self.errors
+            .iter()
+            .fold(0, |total: u32, &count| total.saturating_add(count))
+    }
+
+    /// Returns the number of parse errors of the given kind.
+    pub fn error_count(&self, kind: ErrorKind) -> u32 {
+        self.errors[kind as usize]
+    }
+
+    /// Returns the number of [Vendor](ErrorKind::Vendor) errors that are the same [`BlueNRGError`]
+    /// variant as `error`. The values in the variants are not compared.
+    pub fn vendor_error_count(&self, error: &BlueNRGError) -> u32 {
+        let variant = core::mem::discriminant(error);
+        self.vendor_errors[..self.vendor_error_variants]
+            .iter()
+            .find(|entry| core::mem::discriminant(&entry.0) == variant)
+            .map_or(0, |&(_, count)| count)
+    }
+
+    /// Returns the number of [Vendor](ErrorKind::Vendor) errors per [`BlueNRGError`] variant, in
+    /// the order the variants were first seen. Each variant is represented by its most recent
+    /// error.
+    pub fn vendor_errors(&self) -> &[(BlueNRGError, u32)] {
+        &self.vendor_errors[..self.vendor_error_variants]
+    }
+
+    /// Returns the number of [EventsLost](BlueNRGEvent::EventsLost) events.
+    #[cfg(feature = "ms")]
+    pub fn lost_events(&self) -> u32 {
+        self.lost_events
+    }
+
+    /// Returns every type of event that the controller reported as lost.
+    #[cfg(feature = "ms")]
+    pub fn lost_event_flags(&self) -> EventFlags {
+        self.lost_event_flags
+    }
+
+    /// Returns the most recent unknown vendor event codes, oldest first.
+    pub fn recent_unknown_events(&self) -> impl Iterator<Item = u16> + '_ {
+        let start = (self.next_unknown + RECENT_UNKNOWN_EVENTS - self.recent_unknown_len)
+            % RECENT_UNKNOWN_EVENTS;
+        (0..self.recent_unknown_len)
+            .map(move |i| self.recent_unknown[(start + i) % RECENT_UNKNOWN_EVENTS])
+    }
+}
+
+/// Renders the counters on one line, for periodic telemetry. For example:
+///
+/// ```text
+/// events 1 [0x0402:1] errors 2 (length 0 unknown 1 vendor 1 [BadPhy(4):1] other 0) recent [0x0c99]
+/// ```
+///
+/// The vendor errors are listed by variant, with the most recent error of each. With the `ms`
+/// feature, the number of lost-event reports and their combined flags follow the
+/// errors.
+impl Display for EventStats {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "events {} [", self.events)?;
+        for (i, &(code, count)) in self.counts().iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{:#06x}:{}", code, count)?;
+        }
+        write!(
+            f,
+            "] errors {} (length {} unknown {} vendor {} [",
+            self.errors(),
+            self.error_count(ErrorKind::BadLength),
+            self.error_count(ErrorKind::UnknownEvent),
+            self.error_count(ErrorKind::Vendor)
+        )?;
+        for (i, &(error, count)) in self.vendor_errors().iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{:?}:{}", error, count)?;
+        }
+        write!(f, "] other {})", self.error_count(ErrorKind::Other))?;
+        #[cfg(feature = "ms")]
+        {
+            write!(
+                f,
+                " lost {} ({:#x})",
+                self.lost_events,
+                self.lost_event_flags.bits()
+            )?;
+        }
+        f.write_str(" recent [")?;
+        for (i, code) in self.recent_unknown_events().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{:#06x}", code)?;
+        }
+        f.write_str("]")
+    }
+}
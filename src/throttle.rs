@@ -0,0 +1,413 @@
+//! Flow control for notifications and indications sent by the GATT server.
+//!
+//! The BlueNRG-MS keeps outgoing notifications in a small pool of TX buffers. When the pool is
+//! exhausted, [`update_characteristic_value`](crate::gatt::Commands::update_characteristic_value)
+//! completes with [InsufficientResources](Status::InsufficientResources) and the value is not sent.
+//! Once buffers free up, the controller reports [GATT TX Pool
+//! Available](BlueNRGEvent::GattTxPoolAvailable) for the connection, and the application may try
+//! again. [`NotificationThrottle`] keeps the notifications that could not be sent yet in a
+//! caller-provided buffer, and sends them again, in order, when the pool has room. A stream of
+//! notifications, such as the pieces of a long value, can be fed to it from an iterator with
+//! [`notify_from`](NotificationThrottle::notify_from).
+//!
+//! Indications are limited further: ATT allows only one outstanding indication per connection,
+//! until the client confirms it with
+//! [GATT Server Confirmation](BlueNRGEvent::GattServerConfirmation). If the client does not confirm
+//! within [`INDICATION_TIMEOUT`], the transaction times out and no more ATT traffic is allowed on
+//! the link. [`IndicationSender`] tracks the outstanding indication on each connection.
+
+use crate::event::command::ReturnParameters;
+use crate::event::{BlueNRGEvent, ConnectionHandle, Status};
+use core::time::Duration;
+
+/// What [`NotificationThrottle::notify`] did with a notification.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum NotificationOutcome {
+    /// The notification was sent to the controller.
+    Sent,
+
+    /// The notification was queued, because the TX pool is exhausted or earlier notifications are
+    /// still waiting. It will be sent by [`send_next`](NotificationThrottle::send_next).
+    Queued,
+
+    /// The pending buffer is full, so the notification was dropped.
+    Dropped,
+}
+
+/// Reported by [`NotificationThrottle::handle_event`] when the TX pool has room again.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Resume {
+    /// Number of queued notifications that the pool has room for: the smaller of the number of
+    /// free TX buffers and the number of queued notifications.
+    pub retries: usize,
+}
+
+/// Sends notifications on one connection, queueing them while the controller's TX pool is
+/// exhausted.
+///
+/// `T` is whatever the application needs to build the notification when it is sent, such as a
+/// sample or an index into its own buffers. Only one notification is sent to the controller at a
+/// time, so the throttle can tell which one the controller rejected; it stays at the front of the
+/// queue and is sent again first.
+///
+/// Every [`update_characteristic_value`](crate::gatt::Commands::update_characteristic_value) on the
+/// connection must go through the throttle, since the Command Complete event does not say which
+/// characteristic it is for. Pass every event to
+/// [`handle_event`](NotificationThrottle::handle_event) and then call
+/// [`send_next`](NotificationThrottle::send_next).
+pub struct NotificationThrottle<'buf, T> {
+    conn_handle: ConnectionHandle,
+    pending: &'buf mut [T],
+    front: usize,
+    len: usize,
+    in_flight: bool,
+    blocked: bool,
+}
+
+impl<'buf, T> NotificationThrottle<'buf, T>
+where
+    T: Copy,
+{
+    /// Returns a throttle for the connection that queues up to `pending.len()` notifications.
+    pub fn new(
+        conn_handle: ConnectionHandle,
+        pending: &'buf mut [T],
+    ) -> NotificationThrottle<'buf, T> {
+        NotificationThrottle {
+            conn_handle,
+            pending,
+            front: 0,
+            len: 0,
+            in_flight: false,
+            blocked: false,
+        }
+    }
+
+    /// Returns true if the TX pool was exhausted, and the throttle is waiting for the [GATT TX Pool
+    /// Available](BlueNRGEvent::GattTxPoolAvailable) event.
+    pub fn is_blocked(&self) -> bool {
+        self.blocked
+    }
+
+    /// Returns the number of notifications that have not been accepted by the controller yet,
+    /// including one that was sent but not completed.
+    pub fn pending(&self) -> usize {
+        self.len
+    }
+
+    /// Sends the notification with `send`, or queues it if it cannot be sent now.
+    ///
+    /// `send` is called with the notification to write it to the controller, normally with
+    /// [`update_characteristic_value`](crate::gatt::Commands::update_characteristic_value).
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors from `send`. If `send` fails, the notification is not queued.
+    pub fn notify<E, F>(&mut self, notification: T, send: F) -> nb::Result<NotificationOutcome, E>
+    where
+        F: FnOnce(&T) -> nb::Result<(), E>,
+    {
+        if self.len == self.pending.len() {
+            return Ok(NotificationOutcome::Dropped);
+        }
+
+        let idle = self.len == 0 && !self.blocked;
+        if idle {
+            send(&notification)?;
+            self.in_flight = true;
+        }
+        self.push(notification);
+
+        Ok(if idle {
+            NotificationOutcome::Sent
+        } else {
+            NotificationOutcome::Queued
+        })
+    }
+
+    /// Queues notifications from the iterator until the pending buffer is full, and sends the
+    /// oldest one with `send` if the controller can take it now. Returns the number of
+    /// notifications taken from the iterator.
+    ///
+    /// The notifications that do not fit are left in the iterator, so a long value can be sent in
+    /// pieces by passing the same iterator, such as `value.chunks(20)`, again after each event.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors from `send`. If `send` fails, the notifications stay queued, and the
+    /// oldest one is sent by the next call to [`send_next`](NotificationThrottle::send_next).
+    pub fn notify_from<I, E, F>(&mut self, notifications: &mut I, send: F) -> nb::Result<usize, E>
+    where
+        I: Iterator<Item = T>,
+        F: FnOnce(&T) -> nb::Result<(), E>,
+    {
+        let mut queued = 0;
+        while self.len < self.pending.len() {
+            match notifications.next() {
+                Some(notification) => self.push(notification),
+                None => break,
+            }
+            queued += 1;
+        }
+        self.send_next(send)?;
+
+        Ok(queued)
+    }
+
+    /// Sends the oldest queued notification with `send`, if the controller can take it now.
+    /// Returns true if a notification was sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors from `send`. If `send` fails, the notification stays queued.
+    pub fn send_next<E, F>(&mut self, send: F) -> nb::Result<bool, E>
+    where
+        F: FnOnce(&T) -> nb::Result<(), E>,
+    {
+        if self.blocked || self.in_flight || self.len == 0 {
+            return Ok(false);
+        }
+
+        send(&self.pending[self.front])?;
+        self.in_flight = true;
+
+        Ok(true)
+    }
+
+    /// Updates the throttle with an event read from the controller.
+    ///
+    /// Returns [`Resume`] if the event is a [GATT TX Pool
+    /// Available](BlueNRGEvent::GattTxPoolAvailable) event for the connection while notifications
+    /// are queued. Returns `None` for any other event.
+    pub fn handle_event(&mut self, event: &hci::event::Event<BlueNRGEvent>) -> Option<Resume> {
+        match *event {
+            hci::event::Event::CommandComplete(ref event) => {
+                if let hci::event::command::ReturnParameters::Vendor(
+                    ReturnParameters::GattUpdateCharacteristicValue(status),
+                ) = event.return_params
+                {
+                    self.complete(status);
+                }
+                None
+            }
+            hci::event::Event::Vendor(BlueNRGEvent::GattTxPoolAvailable(ref event))
+                if event.conn_handle == self.conn_handle =>
+            {
+                self.blocked = false;
+                if self.len == 0 {
+                    return None;
+                }
+
+                Some(Resume {
+                    retries: event.available_buffers.min(self.len),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn push(&mut self, notification: T) {
+        let index = (self.front + self.len) % self.pending.len();
+        self.pending[index] = notification;
+        self.len += 1;
+    }
+
+    fn complete(&mut self, status: hci::Status<Status>) {
+        if !self.in_flight {
+            return;
+        }
+        self.in_flight = false;
+
+        if status == hci::Status::Vendor(Status::InsufficientResources) {
+            // Keep the notification at the front, so it is sent again first.
+            self.blocked = true;
+            return;
+        }
+
+        self.front = (self.front + 1) % self.pending.len();
+        self.len -= 1;
+    }
+}
+
+/// Time the client has to confirm an indication before the ATT transaction times out (Bluetooth
+/// Core v4.1, Vol 3, Part F, section 3.3.3).
+pub const INDICATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Maximum number of connections the BlueNRG supports at once.
+const MAX_CONNECTIONS: usize = 8;
+
+/// Errors that may occur when sending an indication with [`IndicationSender::try_send`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum IndicationError<E> {
+    /// An indication is already outstanding on the connection. The client must confirm it before
+    /// the next one is sent.
+    Busy,
+
+    /// Indications are already outstanding on the maximum number of connections.
+    Full,
+
+    /// The indication could not be sent to the controller.
+    Comm(E),
+}
+
+/// How an outstanding indication ended.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum IndicationEnd {
+    /// The client confirmed the indication.
+    Confirmed,
+
+    /// The client did not confirm the indication in time. The ATT bearer cannot be used any more,
+    /// so the application should [terminate](crate::gap::Commands::terminate) the connection.
+    TimedOut,
+}
+
+/// An indication that has ended, as reported by [`IndicationSender::handle_event`] and
+/// [`IndicationSender::tick`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CompletedIndication {
+    /// Connection on which the indication was sent.
+    pub conn_handle: ConnectionHandle,
+
+    /// How the indication ended.
+    pub end: IndicationEnd,
+}
+
+/// Tracks the outstanding indication on each connection, and how long it has waited for the
+/// client's confirmation.
+///
+/// Send indications with [`try_send`](IndicationSender::try_send), pass every event to
+/// [`handle_event`](IndicationSender::handle_event), and call [`tick`](IndicationSender::tick)
+/// regularly with the time that has passed, so timeouts are noticed even if the controller does not
+/// report them.
+#[derive(Copy, Clone, Debug)]
+pub struct IndicationSender {
+    // Each outstanding indication, with the time it has waited for confirmation.
+    pending: [Option<(ConnectionHandle, Duration)>; MAX_CONNECTIONS],
+}
+
+impl IndicationSender {
+    /// Returns a sender with no outstanding indications.
+    pub fn new() -> IndicationSender {
+        IndicationSender {
+            pending: [None; MAX_CONNECTIONS],
+        }
+    }
+
+    /// Returns true if an indication is waiting for confirmation on the connection.
+    pub fn is_pending(&self, conn_handle: ConnectionHandle) -> bool {
+        self.pending
+            .iter()
+            .any(|slot| matches!(*slot, Some((handle, _)) if handle == conn_handle))
+    }
+
+    /// Sends an indication on the connection with `send`, unless one is already outstanding.
+    ///
+    /// `send` is called to write the indication to the controller, normally with
+    /// [`update_characteristic_value`](crate::gatt::Commands::update_characteristic_value) for a
+    /// characteristic that the client subscribed to indications for.
+    ///
+    /// # Errors
+    ///
+    /// - [Busy](IndicationError::Busy) if an indication is already outstanding on the connection.
+    ///   `send` is not called.
+    /// - [Full](IndicationError::Full) if indications are outstanding on the maximum number of
+    ///   connections. `send` is not called.
+    /// - [Comm](IndicationError::Comm) with the error from `send`. The indication is not recorded.
+    pub fn try_send<E, F>(
+        &mut self,
+        conn_handle: ConnectionHandle,
+        send: F,
+    ) -> nb::Result<(), IndicationError<E>>
+    where
+        F: FnOnce() -> nb::Result<(), E>,
+    {
+        if self.is_pending(conn_handle) {
+            return Err(nb::Error::Other(IndicationError::Busy));
+        }
+
+        let slot = self
+            .pending
+            .iter_mut()
+            .find(|slot| slot.is_none())
+            .ok_or(nb::Error::Other(IndicationError::Full))?;
+        send().map_err(|e| match e {
+            nb::Error::WouldBlock => nb::Error::WouldBlock,
+            nb::Error::Other(e) => nb::Error::Other(IndicationError::Comm(e)),
+        })?;
+        *slot = Some((conn_handle, Duration::from_secs(0)));
+
+        Ok(())
+    }
+
+    /// Forgets the indication outstanding on the connection, if any. Returns true if there was
+    /// one. Call this when the connection is closed, since the client will not confirm it then.
+    pub fn cancel(&mut self, conn_handle: ConnectionHandle) -> bool {
+        for slot in self.pending.iter_mut() {
+            if let Some((handle, _)) = *slot {
+                if handle == conn_handle {
+                    *slot = None;
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Updates the sender with an event read from the controller.
+    ///
+    /// Returns the indication that ended if the event is a [GATT Server
+    /// Confirmation](BlueNRGEvent::GattServerConfirmation) or [GATT Procedure
+    /// Timeout](BlueNRGEvent::GattProcedureTimeout) event for a connection with an outstanding
+    /// indication. Returns `None` for any other event.
+    pub fn handle_event(&mut self, event: &BlueNRGEvent) -> Option<CompletedIndication> {
+        let (conn_handle, end) = match *event {
+            BlueNRGEvent::GattServerConfirmation(conn_handle) => {
+                (conn_handle, IndicationEnd::Confirmed)
+            }
+            #[cfg(feature = "att-client-events")]
+            BlueNRGEvent::GattProcedureTimeout(ref event) => {
+                (event.conn_handle, IndicationEnd::TimedOut)
+            }
+            _ => return None,
+        };
+
+        if self.cancel(conn_handle) {
+            Some(CompletedIndication { conn_handle, end })
+        } else {
+            None
+        }
+    }
+
+    /// Adds `elapsed` to the time each outstanding indication has waited for confirmation.
+    ///
+    /// Returns an indication that has waited longer than [`INDICATION_TIMEOUT`], and forgets it. If
+    /// several time out at once, the others are returned by the following calls, which may pass a
+    /// zero `elapsed`.
+    pub fn tick(&mut self, elapsed: Duration) -> Option<CompletedIndication> {
+        let mut timed_out = None;
+        for slot in self.pending.iter_mut() {
+            let expired = match *slot {
+                Some((_, ref mut waited)) => {
+                    *waited += elapsed;
+                    *waited >= INDICATION_TIMEOUT
+                }
+                None => false,
+            };
+            if expired && timed_out.is_none() {
+                timed_out = slot.take().map(|(conn_handle, _)| conn_handle);
+            }
+        }
+
+        timed_out.map(|conn_handle| CompletedIndication {
+            conn_handle,
+            end: IndicationEnd::TimedOut,
+        })
+    }
+}
+
+impl Default for IndicationSender {
+    fn default() -> IndicationSender {
+        IndicationSender::new()
+    }
+}
@@ -0,0 +1,268 @@
+//! Well-known 16-bit UUIDs assigned by the Bluetooth SIG.
+//!
+//! The values come from the Bluetooth Assigned Numbers document. They are [`Uuid`]s, so they can be
+//! passed directly to the [GATT commands](crate::gatt::Commands), and compared with UUIDs found
+//! during discovery using [`Uuid::matches`]. Use [`Uuid::to_uuid128`] for the 128-bit form.
+//!
+//! With the `uuid-names` feature, [`name`] looks up the names of the assigned services,
+//! characteristics, and descriptors, for logging.
+
+use crate::gatt::Uuid;
+
+/// UUIDs of the GATT attribute types that declare services and characteristics.
+pub mod declaration {
+    use super::Uuid;
+
+    /// Primary Service declaration.
+    pub const PRIMARY_SERVICE: Uuid = Uuid::Uuid16(0x2800);
+    /// Secondary Service declaration.
+    pub const SECONDARY_SERVICE: Uuid = Uuid::Uuid16(0x2801);
+    /// Include declaration.
+    pub const INCLUDE: Uuid = Uuid::Uuid16(0x2802);
+    /// Characteristic declaration.
+    pub const CHARACTERISTIC: Uuid = Uuid::Uuid16(0x2803);
+}
+
+/// UUIDs of the characteristic descriptors. These are the same values as
+/// [`KnownDescriptor`](crate::gatt::KnownDescriptor).
+pub mod descriptor {
+    use super::Uuid;
+
+    /// Characteristic Extended Properties descriptor.
+    pub const CHARACTERISTIC_EXTENDED_PROPERTIES: Uuid = Uuid::Uuid16(0x2900);
+    /// Characteristic User Description descriptor.
+    pub const CHARACTERISTIC_USER_DESCRIPTION: Uuid = Uuid::Uuid16(0x2901);
+    /// Client Characteristic Configuration descriptor (CCCD).
+    pub const CLIENT_CHARACTERISTIC_CONFIGURATION: Uuid = Uuid::Uuid16(0x2902);
+    /// Server Characteristic Configuration descriptor.
+    pub const SERVER_CHARACTERISTIC_CONFIGURATION: Uuid = Uuid::Uuid16(0x2903);
+    /// Characteristic Presentation Format descriptor.
+    pub const CHARACTERISTIC_PRESENTATION_FORMAT: Uuid = Uuid::Uuid16(0x2904);
+    /// Characteristic Aggregate Format descriptor.
+    pub const CHARACTERISTIC_AGGREGATE_FORMAT: Uuid = Uuid::Uuid16(0x2905);
+}
+
+/// UUIDs of commonly used services.
+pub mod service {
+    use super::Uuid;
+
+    /// Generic Access service.
+    pub const GENERIC_ACCESS: Uuid = Uuid::Uuid16(0x1800);
+    /// Generic Attribute service.
+    pub const GENERIC_ATTRIBUTE: Uuid = Uuid::Uuid16(0x1801);
+    /// Immediate Alert service.
+    pub const IMMEDIATE_ALERT: Uuid = Uuid::Uuid16(0x1802);
+    /// Link Loss service.
+    pub const LINK_LOSS: Uuid = Uuid::Uuid16(0x1803);
+    /// Tx Power service.
+    pub const TX_POWER: Uuid = Uuid::Uuid16(0x1804);
+    /// Device Information service.
+    pub const DEVICE_INFORMATION: Uuid = Uuid::Uuid16(0x180A);
+    /// Heart Rate service.
+    pub const HEART_RATE: Uuid = Uuid::Uuid16(0x180D);
+    /// Battery service.
+    pub const BATTERY: Uuid = Uuid::Uuid16(0x180F);
+    /// Human Interface Device service.
+    pub const HUMAN_INTERFACE_DEVICE: Uuid = Uuid::Uuid16(0x1812);
+    /// Environmental Sensing service.
+    pub const ENVIRONMENTAL_SENSING: Uuid = Uuid::Uuid16(0x181A);
+}
+
+/// UUIDs of commonly used characteristics.
+pub mod characteristic {
+    use super::Uuid;
+
+    /// Device Name characteristic, in the Generic Access service.
+    pub const DEVICE_NAME: Uuid = Uuid::Uuid16(0x2A00);
+    /// Appearance characteristic, in the Generic Access service.
+    pub const APPEARANCE: Uuid = Uuid::Uuid16(0x2A01);
+    /// Peripheral Preferred Connection Parameters characteristic, in the Generic Access service.
+    pub const PERIPHERAL_PREFERRED_CONNECTION_PARAMETERS: Uuid = Uuid::Uuid16(0x2A04);
+    /// Service Changed characteristic, in the Generic Attribute service.
+    pub const SERVICE_CHANGED: Uuid = Uuid::Uuid16(0x2A05);
+    /// Alert Level characteristic.
+    pub const ALERT_LEVEL: Uuid = Uuid::Uuid16(0x2A06);
+    /// Tx Power Level characteristic.
+    pub const TX_POWER_LEVEL: Uuid = Uuid::Uuid16(0x2A07);
+    /// Battery Level characteristic.
+    pub const BATTERY_LEVEL: Uuid = Uuid::Uuid16(0x2A19);
+    /// System ID characteristic, in the Device Information service.
+    pub const SYSTEM_ID: Uuid = Uuid::Uuid16(0x2A23);
+    /// Model Number String characteristic, in the Device Information service.
+    pub const MODEL_NUMBER_STRING: Uuid = Uuid::Uuid16(0x2A24);
+    /// Serial Number String characteristic, in the Device Information service.
+    pub const SERIAL_NUMBER_STRING: Uuid = Uuid::Uuid16(0x2A25);
+    /// Firmware Revision String characteristic, in the Device Information service.
+    pub const FIRMWARE_REVISION_STRING: Uuid = Uuid::Uuid16(0x2A26);
+    /// Hardware Revision String characteristic, in the Device Information service.
+    pub const HARDWARE_REVISION_STRING: Uuid = Uuid::Uuid16(0x2A27);
+    /// Software Revision String characteristic, in the Device Information service.
+    pub const SOFTWARE_REVISION_STRING: Uuid = Uuid::Uuid16(0x2A28);
+    /// Manufacturer Name String characteristic, in the Device Information service.
+    pub const MANUFACTURER_NAME_STRING: Uuid = Uuid::Uuid16(0x2A29);
+    /// Heart Rate Measurement characteristic, in the Heart Rate service.
+    pub const HEART_RATE_MEASUREMENT: Uuid = Uuid::Uuid16(0x2A37);
+    /// PnP ID characteristic, in the Device Information service.
+    pub const PNP_ID: Uuid = Uuid::Uuid16(0x2A50);
+    /// Temperature characteristic, in the Environmental Sensing service.
+    pub const TEMPERATURE: Uuid = Uuid::Uuid16(0x2A6E);
+}
+
+/// Returns the name of an assigned 16-bit UUID, such as "Battery Level" for 0x2A19, or `None` if
+/// the UUID is not in the table. The table covers the declarations, descriptors, and the commonly
+/// used services and characteristics.
+#[cfg(feature = "uuid-names")]
+pub fn name(uuid: u16) -> Option<&'static str> {
+    NAMES
+        .binary_search_by_key(&uuid, |&(value, _)| value)
+        .ok()
+        .map(|index| NAMES[index].1)
+}
+
+/// Returns every assigned 16-bit UUID that [`name`] knows, with its name, sorted by UUID.
+#[cfg(feature = "uuid-names")]
+pub fn assigned_names() -> &'static [(u16, &'static str)] {
+    &NAMES
+}
+
+// Sorted by UUID, for the binary search in `name`.
+#[cfg(feature = "uuid-names")]
+static NAMES: [(u16, &str); 137] = [
+    (0x1800, "Generic Access"),
+    (0x1801, "Generic Attribute"),
+    (0x1802, "Immediate Alert"),
+    (0x1803, "Link Loss"),
+    (0x1804, "Tx Power"),
+    (0x1805, "Current Time"),
+    (0x1806, "Reference Time Update"),
+    (0x1807, "Next DST Change"),
+    (0x1808, "Glucose"),
+    (0x1809, "Health Thermometer"),
+    (0x180A, "Device Information"),
+    (0x180D, "Heart Rate"),
+    (0x180E, "Phone Alert Status"),
+    (0x180F, "Battery"),
+    (0x1810, "Blood Pressure"),
+    (0x1811, "Alert Notification"),
+    (0x1812, "Human Interface Device"),
+    (0x1813, "Scan Parameters"),
+    (0x1814, "Running Speed and Cadence"),
+    (0x1815, "Automation IO"),
+    (0x1816, "Cycling Speed and Cadence"),
+    (0x1818, "Cycling Power"),
+    (0x1819, "Location and Navigation"),
+    (0x181A, "Environmental Sensing"),
+    (0x181B, "Body Composition"),
+    (0x181C, "User Data"),
+    (0x181D, "Weight Scale"),
+    (0x181E, "Bond Management"),
+    (0x181F, "Continuous Glucose Monitoring"),
+    (0x1820, "Internet Protocol Support"),
+    (0x1821, "Indoor Positioning"),
+    (0x1822, "Pulse Oximeter"),
+    (0x1826, "Fitness Machine"),
+    (0x2800, "Primary Service"),
+    (0x2801, "Secondary Service"),
+    (0x2802, "Include"),
+    (0x2803, "Characteristic"),
+    (0x2900, "Characteristic Extended Properties"),
+    (0x2901, "Characteristic User Description"),
+    (0x2902, "Client Characteristic Configuration"),
+    (0x2903, "Server Characteristic Configuration"),
+    (0x2904, "Characteristic Presentation Format"),
+    (0x2905, "Characteristic Aggregate Format"),
+    (0x2906, "Valid Range"),
+    (0x2907, "External Report Reference"),
+    (0x2908, "Report Reference"),
+    (0x290B, "Environmental Sensing Configuration"),
+    (0x290C, "Environmental Sensing Measurement"),
+    (0x290D, "Environmental Sensing Trigger Setting"),
+    (0x2A00, "Device Name"),
+    (0x2A01, "Appearance"),
+    (0x2A02, "Peripheral Privacy Flag"),
+    (0x2A03, "Reconnection Address"),
+    (0x2A04, "Peripheral Preferred Connection Parameters"),
+    (0x2A05, "Service Changed"),
+    (0x2A06, "Alert Level"),
+    (0x2A07, "Tx Power Level"),
+    (0x2A08, "Date Time"),
+    (0x2A09, "Day of Week"),
+    (0x2A0A, "Day Date Time"),
+    (0x2A0C, "Exact Time 256"),
+    (0x2A0D, "DST Offset"),
+    (0x2A0E, "Time Zone"),
+    (0x2A0F, "Local Time Information"),
+    (0x2A11, "Time with DST"),
+    (0x2A12, "Time Accuracy"),
+    (0x2A13, "Time Source"),
+    (0x2A14, "Reference Time Information"),
+    (0x2A16, "Time Update Control Point"),
+    (0x2A17, "Time Update State"),
+    (0x2A18, "Glucose Measurement"),
+    (0x2A19, "Battery Level"),
+    (0x2A1C, "Temperature Measurement"),
+    (0x2A1D, "Temperature Type"),
+    (0x2A1E, "Intermediate Temperature"),
+    (0x2A21, "Measurement Interval"),
+    (0x2A22, "Boot Keyboard Input Report"),
+    (0x2A23, "System ID"),
+    (0x2A24, "Model Number String"),
+    (0x2A25, "Serial Number String"),
+    (0x2A26, "Firmware Revision String"),
+    (0x2A27, "Hardware Revision String"),
+    (0x2A28, "Software Revision String"),
+    (0x2A29, "Manufacturer Name String"),
+    (0x2A2B, "Current Time"),
+    (0x2A31, "Scan Refresh"),
+    (0x2A32, "Boot Keyboard Output Report"),
+    (0x2A33, "Boot Mouse Input Report"),
+    (0x2A34, "Glucose Measurement Context"),
+    (0x2A35, "Blood Pressure Measurement"),
+    (0x2A36, "Intermediate Cuff Pressure"),
+    (0x2A37, "Heart Rate Measurement"),
+    (0x2A38, "Body Sensor Location"),
+    (0x2A39, "Heart Rate Control Point"),
+    (0x2A3F, "Alert Status"),
+    (0x2A40, "Ringer Control Point"),
+    (0x2A41, "Ringer Setting"),
+    (0x2A42, "Alert Category ID Bit Mask"),
+    (0x2A43, "Alert Category ID"),
+    (0x2A44, "Alert Notification Control Point"),
+    (0x2A45, "Unread Alert Status"),
+    (0x2A46, "New Alert"),
+    (0x2A47, "Supported New Alert Category"),
+    (0x2A48, "Supported Unread Alert Category"),
+    (0x2A49, "Blood Pressure Feature"),
+    (0x2A4A, "HID Information"),
+    (0x2A4B, "Report Map"),
+    (0x2A4C, "HID Control Point"),
+    (0x2A4D, "Report"),
+    (0x2A4E, "Protocol Mode"),
+    (0x2A4F, "Scan Interval Window"),
+    (0x2A50, "PnP ID"),
+    (0x2A51, "Glucose Feature"),
+    (0x2A52, "Record Access Control Point"),
+    (0x2A53, "RSC Measurement"),
+    (0x2A54, "RSC Feature"),
+    (0x2A55, "SC Control Point"),
+    (0x2A5B, "CSC Measurement"),
+    (0x2A5C, "CSC Feature"),
+    (0x2A5D, "Sensor Location"),
+    (0x2A63, "Cycling Power Measurement"),
+    (0x2A65, "Cycling Power Feature"),
+    (0x2A66, "Cycling Power Control Point"),
+    (0x2A67, "Location and Speed"),
+    (0x2A68, "Navigation"),
+    (0x2A6C, "Elevation"),
+    (0x2A6D, "Pressure"),
+    (0x2A6E, "Temperature"),
+    (0x2A6F, "Humidity"),
+    (0x2A76, "UV Index"),
+    (0x2A9D, "Weight Measurement"),
+    (0x2A9E, "Weight Scale Feature"),
+    (0x2AA6, "Central Address Resolution"),
+    (0x2AC9, "Resolvable Private Address Only"),
+    (0x2B29, "Client Supported Features"),
+    (0x2B2A, "Database Hash"),
+    (0x2B3A, "Server Supported Features"),
+];
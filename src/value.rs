@@ -0,0 +1,639 @@
+//! Typed encoding and decoding of characteristic values.
+//!
+//! Attribute values arrive from the controller as byte slices (for example,
+//! [`AttributeValue::value`](crate::event::AttributeValue::value) or
+//! [`GattAttributeModified::data`](crate::event::GattAttributeModified::data)), and are sent to it
+//! the same way. [`GattValue`] converts between those bytes and the types that characteristics
+//! usually hold: little-endian integers, UTF-8 strings, and the IEEE 11073-20601 [`SFloat`] and
+//! [`Float`] types used by the health profiles. [`PresentationFormat`] reads the descriptor that
+//! describes how a characteristic value is formatted.
+//!
+//! ```
+//! use bluenrg::value::{GattValue, SFloat};
+//!
+//! let mut buffer = [0; 2];
+//! let temperature = SFloat::new(368, -1).unwrap();
+//! let len = temperature.encode(&mut buffer).unwrap();
+//! assert_eq!(&buffer[..len], [0x70, 0xF1]);
+//! assert_eq!(SFloat::decode(&buffer), Ok(temperature));
+//! ```
+
+use byteorder::{ByteOrder, LittleEndian};
+
+/// Errors that can occur when encoding or decoding a characteristic value.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ValueError {
+    /// The buffer is too small for the encoded value. Includes the length of the buffer and the
+    /// length of the encoded value, in that order.
+    BufferTooSmall(usize, usize),
+
+    /// The bytes are not the right length for the type. Includes the number of bytes and the
+    /// length expected for the type, in that order.
+    BadLength(usize, usize),
+
+    /// The bytes are not valid UTF-8.
+    BadUtf8,
+
+    /// The value cannot be represented in the type, or its encoding. For [`SFloat`] and [`Float`],
+    /// the mantissa or exponent does not fit in its field; for bounded strings, the string is
+    /// longer than the bound.
+    OutOfRange,
+}
+
+/// A type that can be stored in a characteristic value.
+///
+/// The lifetime is that of the bytes being decoded, so borrowed types such as `&str` can refer to
+/// them instead of copying.
+pub trait GattValue<'a>: Sized {
+    /// Encodes the value into the beginning of `buffer`, and returns the number of bytes written.
+    ///
+    /// # Errors
+    ///
+    /// - [`BufferTooSmall`](ValueError::BufferTooSmall) if the encoded value does not fit in
+    ///   `buffer`.
+    /// - [`OutOfRange`](ValueError::OutOfRange) if the value has no encoding.
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, ValueError>;
+
+    /// Decodes a value from `bytes`, which must hold exactly one encoded value.
+    ///
+    /// # Errors
+    ///
+    /// - [`BadLength`](ValueError::BadLength) if `bytes` is not the right length for the type.
+    /// - [`BadUtf8`](ValueError::BadUtf8) if a string value is not valid UTF-8.
+    /// - [`OutOfRange`](ValueError::OutOfRange) if the decoded value does not fit in the type.
+    fn decode(bytes: &'a [u8]) -> Result<Self, ValueError>;
+}
+
+fn check_buffer(buffer: &[u8], len: usize) -> Result<(), ValueError> {
+    if buffer.len() < len {
+        return Err(ValueError::BufferTooSmall(buffer.len(), len));
+    }
+
+    Ok(())
+}
+
+fn check_len(bytes: &[u8], len: usize) -> Result<(), ValueError> {
+    if bytes.len() != len {
+        return Err(ValueError::BadLength(bytes.len(), len));
+    }
+
+    Ok(())
+}
+
+impl<'a> GattValue<'a> for u8 {
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, ValueError> {
+        check_buffer(buffer, 1)?;
+        buffer[0] = *self;
+        Ok(1)
+    }
+
+    fn decode(bytes: &'a [u8]) -> Result<Self, ValueError> {
+        check_len(bytes, 1)?;
+        Ok(bytes[0])
+    }
+}
+
+impl<'a> GattValue<'a> for i8 {
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, ValueError> {
+        (*self as u8).encode(buffer)
+    }
+
+    fn decode(bytes: &'a [u8]) -> Result<Self, ValueError> {
+        u8::decode(bytes).map(|value| value as i8)
+    }
+}
+
+macro_rules! impl_gatt_value_le {
+    ($type:ty, $len:expr, $read:ident, $write:ident) => {
+        impl<'a> GattValue<'a> for $type {
+            fn encode(&self, buffer: &mut [u8]) -> Result<usize, ValueError> {
+                check_buffer(buffer, $len)?;
+                LittleEndian::$write(buffer, *self);
+                Ok($len)
+            }
+
+            fn decode(bytes: &'a [u8]) -> Result<Self, ValueError> {
+                check_len(bytes, $len)?;
+                Ok(LittleEndian::$read(bytes))
+            }
+        }
+    };
+}
+
+impl_gatt_value_le!(u16, 2, read_u16, write_u16);
+impl_gatt_value_le!(i16, 2, read_i16, write_i16);
+impl_gatt_value_le!(u32, 4, read_u32, write_u32);
+impl_gatt_value_le!(i32, 4, read_i32, write_i32);
+impl_gatt_value_le!(u64, 8, read_u64, write_u64);
+impl_gatt_value_le!(i64, 8, read_i64, write_i64);
+
+impl<'a> GattValue<'a> for bool {
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, ValueError> {
+        (*self as u8).encode(buffer)
+    }
+
+    fn decode(bytes: &'a [u8]) -> Result<Self, ValueError> {
+        match u8::decode(bytes)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(ValueError::OutOfRange),
+        }
+    }
+}
+
+/// Strings are encoded as UTF-8, without a terminator; the string takes the whole value.
+impl<'a> GattValue<'a> for &'a str {
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, ValueError> {
+        check_buffer(buffer, self.len())?;
+        buffer[..self.len()].copy_from_slice(self.as_bytes());
+        Ok(self.len())
+    }
+
+    fn decode(bytes: &'a [u8]) -> Result<Self, ValueError> {
+        core::str::from_utf8(bytes).map_err(|_| ValueError::BadUtf8)
+    }
+}
+
+/// Bounded strings are encoded like `&str`. Decoding fails with
+/// [`OutOfRange`](ValueError::OutOfRange) if the value is longer than `N` bytes.
+#[cfg(feature = "heapless")]
+impl<'a, const N: usize> GattValue<'a> for heapless::String<N> {
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, ValueError> {
+        self.as_str().encode(buffer)
+    }
+
+    fn decode(bytes: &'a [u8]) -> Result<Self, ValueError> {
+        let mut string = heapless::String::new();
+        string
+            .push_str(<&str>::decode(bytes)?)
+            .map_err(|_| ValueError::OutOfRange)?;
+        Ok(string)
+    }
+}
+
+/// The 16-bit IEEE 11073-20601 floating point type (SFLOAT), used by the health profiles.
+///
+/// The value is `mantissa * 10^exponent`. The mantissa is a 12-bit and the exponent a 4-bit signed
+/// integer. The special values ([`NAN`](SFloat::NAN), [`NRES`](SFloat::NRES),
+/// [`POSITIVE_INFINITY`](SFloat::POSITIVE_INFINITY), and
+/// [`NEGATIVE_INFINITY`](SFloat::NEGATIVE_INFINITY)) are reserved mantissas with a zero exponent.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SFloat {
+    mantissa: i16,
+    exponent: i8,
+}
+
+impl SFloat {
+    /// Not a number.
+    pub const NAN: SFloat = SFloat {
+        mantissa: 0x07FF,
+        exponent: 0,
+    };
+
+    /// Not at this resolution: the value cannot be represented with the available precision.
+    pub const NRES: SFloat = SFloat {
+        mantissa: -0x0800,
+        exponent: 0,
+    };
+
+    /// Positive infinity.
+    pub const POSITIVE_INFINITY: SFloat = SFloat {
+        mantissa: 0x07FE,
+        exponent: 0,
+    };
+
+    /// Negative infinity.
+    pub const NEGATIVE_INFINITY: SFloat = SFloat {
+        mantissa: -0x07FE,
+        exponent: 0,
+    };
+
+    /// Returns the value `mantissa * 10^exponent`.
+    ///
+    /// # Errors
+    ///
+    /// - [`OutOfRange`](ValueError::OutOfRange) if the mantissa does not fit in 12 bits or the
+    ///   exponent in 4 bits.
+    pub fn new(mantissa: i16, exponent: i8) -> Result<SFloat, ValueError> {
+        if !(-0x0800..=0x07FF).contains(&mantissa) || !(-8..=7).contains(&exponent) {
+            return Err(ValueError::OutOfRange);
+        }
+
+        Ok(SFloat { mantissa, exponent })
+    }
+
+    /// Returns the mantissa.
+    pub fn mantissa(&self) -> i16 {
+        self.mantissa
+    }
+
+    /// Returns the base-10 exponent.
+    pub fn exponent(&self) -> i8 {
+        self.exponent
+    }
+
+    fn is_special(&self) -> bool {
+        self.exponent == 0 && (self.mantissa >= 0x07FE || self.mantissa <= -0x07FE)
+    }
+
+    /// Returns true if the value is [`NAN`](SFloat::NAN), [`NRES`](SFloat::NRES), or the reserved
+    /// value between the infinities.
+    pub fn is_nan(&self) -> bool {
+        self.is_special() && !self.is_infinite()
+    }
+
+    /// Returns true if the value is one of the infinities.
+    pub fn is_infinite(&self) -> bool {
+        *self == SFloat::POSITIVE_INFINITY || *self == SFloat::NEGATIVE_INFINITY
+    }
+
+    /// Returns the value as an `f32`. The special values become the corresponding `f32` special
+    /// values; [`NRES`](SFloat::NRES) becomes NaN.
+    pub fn to_f32(&self) -> f32 {
+        if self.is_special() {
+            return special_to_f32(self.mantissa.signum() as i32, self.is_infinite());
+        }
+
+        scale(self.mantissa as f32, self.exponent as i32)
+    }
+}
+
+impl<'a> GattValue<'a> for SFloat {
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, ValueError> {
+        let raw = ((self.exponent as u16) << 12) | (self.mantissa as u16 & 0x0FFF);
+        raw.encode(buffer)
+    }
+
+    fn decode(bytes: &'a [u8]) -> Result<Self, ValueError> {
+        let raw = u16::decode(bytes)?;
+
+        // Shift the fields to the top of the word and back to sign-extend them.
+        Ok(SFloat {
+            mantissa: ((raw << 4) as i16) >> 4,
+            exponent: ((raw >> 8) as i8) >> 4,
+        })
+    }
+}
+
+/// The 32-bit IEEE 11073-20601 floating point type (FLOAT), used by the health profiles.
+///
+/// The value is `mantissa * 10^exponent`. The mantissa is a 24-bit and the exponent an 8-bit signed
+/// integer. The special values ([`NAN`](Float::NAN), [`NRES`](Float::NRES),
+/// [`POSITIVE_INFINITY`](Float::POSITIVE_INFINITY), and
+/// [`NEGATIVE_INFINITY`](Float::NEGATIVE_INFINITY)) are reserved mantissas with a zero exponent.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Float {
+    mantissa: i32,
+    exponent: i8,
+}
+
+impl Float {
+    /// Not a number.
+    pub const NAN: Float = Float {
+        mantissa: 0x007F_FFFF,
+        exponent: 0,
+    };
+
+    /// Not at this resolution: the value cannot be represented with the available precision.
+    pub const NRES: Float = Float {
+        mantissa: -0x0080_0000,
+        exponent: 0,
+    };
+
+    /// Positive infinity.
+    pub const POSITIVE_INFINITY: Float = Float {
+        mantissa: 0x007F_FFFE,
+        exponent: 0,
+    };
+
+    /// Negative infinity.
+    pub const NEGATIVE_INFINITY: Float = Float {
+        mantissa: -0x007F_FFFE,
+        exponent: 0,
+    };
+
+    /// Returns the value `mantissa * 10^exponent`.
+    ///
+    /// # Errors
+    ///
+    /// - [`OutOfRange`](ValueError::OutOfRange) if the mantissa does not fit in 24 bits.
+    pub fn new(mantissa: i32, exponent: i8) -> Result<Float, ValueError> {
+        if !(-0x0080_0000..=0x007F_FFFF).contains(&mantissa) {
+            return Err(ValueError::OutOfRange);
+        }
+
+        Ok(Float { mantissa, exponent })
+    }
+
+    /// Returns the mantissa.
+    pub fn mantissa(&self) -> i32 {
+        self.mantissa
+    }
+
+    /// Returns the base-10 exponent.
+    pub fn exponent(&self) -> i8 {
+        self.exponent
+    }
+
+    fn is_special(&self) -> bool {
+        self.exponent == 0 && (self.mantissa >= 0x007F_FFFE || self.mantissa <= -0x007F_FFFE)
+    }
+
+    /// Returns true if the value is [`NAN`](Float::NAN), [`NRES`](Float::NRES), or the reserved
+    /// value between the infinities.
+    pub fn is_nan(&self) -> bool {
+        self.is_special() && !self.is_infinite()
+    }
+
+    /// Returns true if the value is one of the infinities.
+    pub fn is_infinite(&self) -> bool {
+        *self == Float::POSITIVE_INFINITY || *self == Float::NEGATIVE_INFINITY
+    }
+
+    /// Returns the value as an `f32`, which may lose precision. The special values become the
+    /// corresponding `f32` special values; [`NRES`](Float::NRES) becomes NaN.
+    pub fn to_f32(&self) -> f32 {
+        if self.is_special() {
+            return special_to_f32(self.mantissa.signum(), self.is_infinite());
+        }
+
+        scale(self.mantissa as f32, self.exponent as i32)
+    }
+}
+
+impl<'a> GattValue<'a> for Float {
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, ValueError> {
+        let raw = ((self.exponent as u8 as u32) << 24) | (self.mantissa as u32 & 0x00FF_FFFF);
+        raw.encode(buffer)
+    }
+
+    fn decode(bytes: &'a [u8]) -> Result<Self, ValueError> {
+        let raw = u32::decode(bytes)?;
+
+        // Shift the mantissa to the top of the word and back to sign-extend it.
+        Ok(Float {
+            mantissa: ((raw << 8) as i32) >> 8,
+            exponent: (raw >> 24) as i8,
+        })
+    }
+}
+
+// Defines an enum of assigned values with an `Other` variant for the values it does not name, and
+// the conversions from and to the raw value and the display names.
+macro_rules! assigned_values {
+    (
+        $(#[$attr:meta])*
+        pub enum $name:ident: $raw:ty {
+            $($(#[$doc:meta])* $variant:ident = $value:literal, $display:literal;)*
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, Debug, PartialEq)]
+        pub enum $name {
+            $($(#[$doc])* $variant,)*
+            /// A value not named by this enum. Includes the raw value.
+            Other($raw),
+        }
+
+        impl From<$raw> for $name {
+            fn from(value: $raw) -> Self {
+                match value {
+                    $($value => $name::$variant,)*
+                    other => $name::Other(other),
+                }
+            }
+        }
+
+        impl From<$name> for $raw {
+            fn from(value: $name) -> Self {
+                match value {
+                    $($name::$variant => $value,)*
+                    $name::Other(other) => other,
+                }
+            }
+        }
+
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                match *self {
+                    $($name::$variant => f.write_str($display),)*
+                    $name::Other(other) => write!(f, "{:#x}", other),
+                }
+            }
+        }
+    };
+}
+
+assigned_values! {
+    /// Format of a characteristic value, from the [Characteristic Presentation
+    /// Format](PresentationFormat) descriptor.
+    pub enum Format: u8 {
+        /// Unsigned 1-bit integer; 0 is false and 1 is true.
+        Boolean = 0x01, "boolean";
+        /// Unsigned 2-bit integer.
+        UInt2 = 0x02, "2bit";
+        /// Unsigned 4-bit integer.
+        UInt4 = 0x03, "nibble";
+        /// Unsigned 8-bit integer.
+        UInt8 = 0x04, "uint8";
+        /// Unsigned 12-bit integer.
+        UInt12 = 0x05, "uint12";
+        /// Unsigned 16-bit integer.
+        UInt16 = 0x06, "uint16";
+        /// Unsigned 24-bit integer.
+        UInt24 = 0x07, "uint24";
+        /// Unsigned 32-bit integer.
+        UInt32 = 0x08, "uint32";
+        /// Unsigned 48-bit integer.
+        UInt48 = 0x09, "uint48";
+        /// Unsigned 64-bit integer.
+        UInt64 = 0x0A, "uint64";
+        /// Unsigned 128-bit integer.
+        UInt128 = 0x0B, "uint128";
+        /// Signed 8-bit integer.
+        SInt8 = 0x0C, "sint8";
+        /// Signed 12-bit integer.
+        SInt12 = 0x0D, "sint12";
+        /// Signed 16-bit integer.
+        SInt16 = 0x0E, "sint16";
+        /// Signed 24-bit integer.
+        SInt24 = 0x0F, "sint24";
+        /// Signed 32-bit integer.
+        SInt32 = 0x10, "sint32";
+        /// Signed 48-bit integer.
+        SInt48 = 0x11, "sint48";
+        /// Signed 64-bit integer.
+        SInt64 = 0x12, "sint64";
+        /// Signed 128-bit integer.
+        SInt128 = 0x13, "sint128";
+        /// IEEE-754 32-bit floating point.
+        Float32 = 0x14, "float32";
+        /// IEEE-754 64-bit floating point.
+        Float64 = 0x15, "float64";
+        /// IEEE 11073-20601 16-bit floating point; see [`SFloat`].
+        SFloat = 0x16, "SFLOAT";
+        /// IEEE 11073-20601 32-bit floating point; see [`Float`].
+        Float = 0x17, "FLOAT";
+        /// IEEE 20601 format: two unsigned 16-bit integers.
+        DUInt16 = 0x18, "duint16";
+        /// UTF-8 string.
+        Utf8 = 0x19, "utf8s";
+        /// UTF-16 string.
+        Utf16 = 0x1A, "utf16s";
+        /// Opaque structure.
+        Struct = 0x1B, "struct";
+    }
+}
+
+assigned_values! {
+    /// Unit of a characteristic value, from the [Characteristic Presentation
+    /// Format](PresentationFormat) descriptor. Only the most common of the assigned units are
+    /// named.
+    pub enum Unit: u16 {
+        /// The value has no unit.
+        Unitless = 0x2700, "unitless";
+        /// Length in metres.
+        Metre = 0x2701, "metre";
+        /// Mass in kilograms.
+        Kilogram = 0x2702, "kilogram";
+        /// Time in seconds.
+        Second = 0x2703, "second";
+        /// Electric current in amperes.
+        Ampere = 0x2704, "ampere";
+        /// Thermodynamic temperature in kelvin.
+        Kelvin = 0x2705, "kelvin";
+        /// Frequency in hertz.
+        Hertz = 0x2722, "hertz";
+        /// Pressure in pascals.
+        Pascal = 0x2724, "pascal";
+        /// Power in watts.
+        Watt = 0x2726, "watt";
+        /// Electric potential difference in volts.
+        Volt = 0x2728, "volt";
+        /// Celsius temperature in degrees Celsius.
+        DegreeCelsius = 0x272F, "degree Celsius";
+        /// Pressure in millimetres of mercury.
+        MillimetreOfMercury = 0x2781, "millimetre of mercury";
+        /// Thermodynamic temperature in degrees Fahrenheit.
+        DegreeFahrenheit = 0x27AC, "degree Fahrenheit";
+        /// Percentage.
+        Percentage = 0x27AD, "percentage";
+        /// Period in beats per minute.
+        BeatsPerMinute = 0x27AF, "beats per minute";
+    }
+}
+
+/// The value of a Characteristic Presentation Format descriptor (UUID 0x2904), which tells a
+/// generic client how to interpret and display a characteristic value.
+///
+/// ```
+/// use bluenrg::value::{Format, PresentationFormat, Unit};
+///
+/// // sint16, exponent -2, degrees Celsius
+/// let bytes = [0x0E, 0xFE, 0x2F, 0x27, 0x01, 0x00, 0x00];
+/// let format = PresentationFormat::from_bytes(&bytes).unwrap();
+/// assert_eq!(format.format, Format::SInt16);
+/// assert_eq!(format.unit, Unit::DegreeCelsius);
+/// assert_eq!(format.apply(2150), 21.5);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PresentationFormat {
+    /// Format of the characteristic value.
+    pub format: Format,
+
+    /// Base-10 exponent to apply to integer values: the represented value is the raw value times
+    /// `10^exponent`.
+    pub exponent: i8,
+
+    /// Unit of the characteristic value.
+    pub unit: Unit,
+
+    /// Organization that assigned the [description](PresentationFormat::description). 1 is the
+    /// Bluetooth SIG.
+    pub namespace: u8,
+
+    /// Description of the characteristic, as assigned by the
+    /// [namespace](PresentationFormat::namespace). For the Bluetooth SIG, this is usually 0
+    /// ("unknown") or a position such as "first" or "left".
+    pub description: u16,
+}
+
+// Length of the Characteristic Presentation Format descriptor value.
+const PRESENTATION_FORMAT_LEN: usize = 7;
+
+impl PresentationFormat {
+    /// Reads the descriptor value, as returned when the descriptor is read.
+    ///
+    /// # Errors
+    ///
+    /// - [`BadLength`](ValueError::BadLength) if `bytes` is not 7 bytes long.
+    pub fn from_bytes(bytes: &[u8]) -> Result<PresentationFormat, ValueError> {
+        PresentationFormat::decode(bytes)
+    }
+
+    /// Applies the [exponent](PresentationFormat::exponent) to a raw integer value, and returns the
+    /// represented value. For example, a raw value of 2150 with an exponent of -2 is 21.5.
+    pub fn apply(&self, raw: i64) -> f32 {
+        scale(raw as f32, self.exponent as i32)
+    }
+}
+
+impl<'a> GattValue<'a> for PresentationFormat {
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, ValueError> {
+        check_buffer(buffer, PRESENTATION_FORMAT_LEN)?;
+        buffer[0] = self.format.into();
+        buffer[1] = self.exponent as u8;
+        LittleEndian::write_u16(&mut buffer[2..4], self.unit.into());
+        buffer[4] = self.namespace;
+        LittleEndian::write_u16(&mut buffer[5..7], self.description);
+
+        Ok(PRESENTATION_FORMAT_LEN)
+    }
+
+    fn decode(bytes: &'a [u8]) -> Result<Self, ValueError> {
+        check_len(bytes, PRESENTATION_FORMAT_LEN)?;
+
+        Ok(PresentationFormat {
+            format: bytes[0].into(),
+            exponent: bytes[1] as i8,
+            unit: LittleEndian::read_u16(&bytes[2..4]).into(),
+            namespace: bytes[4],
+            description: LittleEndian::read_u16(&bytes[5..7]),
+        })
+    }
+}
+
+impl core::fmt::Display for PresentationFormat {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.format)?;
+        if self.exponent != 0 {
+            write!(f, " x 10^{}", self.exponent)?;
+        }
+        if self.unit != Unit::Unitless {
+            write!(f, " {}", self.unit)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn special_to_f32(sign: i32, infinite: bool) -> f32 {
+    match (infinite, sign) {
+        (true, 1) => f32::INFINITY,
+        (true, _) => f32::NEG_INFINITY,
+        (false, _) => f32::NAN,
+    }
+}
+
+// core does not provide powi, so scale by repeated multiplication.
+fn scale(mut value: f32, exponent: i32) -> f32 {
+    for _ in 0..exponent.abs() {
+        if exponent > 0 {
+            value *= 10.0;
+        } else {
+            value /= 10.0;
+        }
+    }
+
+    value
+}
@@ -0,0 +1,131 @@
+#![cfg(feature = "alloc")]
+
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+
+use bluenrg::event::*;
+use hci::event::VendorEvent;
+
+#[test]
+fn boxed_payloads_shrink_event() {
+    assert!(
+        std::mem::size_of::<BlueNRGEvent>() <= 128,
+        "BlueNRGEvent is {} bytes",
+        std::mem::size_of::<BlueNRGEvent>()
+    );
+}
+
+#[test]
+fn gatt_attribute_modified() {
+    #[cfg(all(feature = "ms", not(feature = "bluenrg-lp")))]
+    let buffer = [
+        0x01, 0x0C, 0x01, 0x02, 0x03, 0x04, 0x02, 0x05, 0x86, 0x07, 0x08,
+    ];
+    #[cfg(not(feature = "ms"))]
+    let buffer = [0x01, 0x0C, 0x01, 0x02, 0x03, 0x04, 0x02, 0x07, 0x08];
+    #[cfg(feature = "bluenrg-lp")]
+    let buffer = [
+        0x01, 0x0C, 0x01, 0x02, 0x03, 0x04, 0x05, 0x86, 0x02, 0x00, 0x07, 0x08,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GattAttributeModified(event)) => {
+            assert_eq!(event.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(event.attr_handle, AttributeHandle(0x0403));
+            assert_eq!(event.data(), [0x07, 0x08]);
+        }
+        other => panic!("Did not get GATT attribute modified: {:?}", other),
+    }
+}
+
+#[test]
+fn att_read_multiple_permit_request() {
+    let buffer = [0x15, 0x0C, 0x01, 0x02, 4, 0x03, 0x04, 0x05, 0x06];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttReadMultiplePermitRequest(event)) => {
+            assert_eq!(
+                event.handles(),
+                [AttributeHandle(0x0403), AttributeHandle(0x0605)]
+            );
+        }
+        other => panic!("Did not get ATT read multiple permit request: {:?}", other),
+    }
+}
+
+#[cfg(feature = "att-client-events")]
+#[test]
+fn att_read_response() {
+    let buffer = [0x07, 0x0C, 0x01, 0x02, 4, 0x01, 0x02, 0x03, 0x04];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttReadResponse(event)) => {
+            assert_eq!(event.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(event.value(), [0x01, 0x02, 0x03, 0x04]);
+        }
+        other => panic!("Did not get ATT read response: {:?}", other),
+    }
+}
+
+#[cfg(feature = "att-client-events")]
+#[test]
+fn att_read_by_type_response() {
+    let buffer = [
+        0x06, 0x0C, 0x01, 0x02, 13, 6, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x11, 0x12, 0x13, 0x14,
+        0x15, 0x16,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttReadByTypeResponse(event)) => {
+            let pairs: Vec<_> = event
+                .handle_value_pair_iter()
+                .map(|pair| (pair.handle, pair.value.to_vec()))
+                .collect();
+            assert_eq!(
+                pairs,
+                [
+                    (AttributeHandle(0x0201), vec![0x03, 0x04, 0x05, 0x06]),
+                    (AttributeHandle(0x1211), vec![0x13, 0x14, 0x15, 0x16]),
+                ]
+            );
+        }
+        other => panic!("Did not get ATT read by type response: {:?}", other),
+    }
+}
+
+#[cfg(feature = "att-client-events")]
+#[test]
+fn att_find_information_response() {
+    let buffer = [
+        0x04, 0x0C, 0x01, 0x02, 9, 1, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttFindInformationResponse(event)) => {
+            match event.handle_uuid_pair_iter() {
+                HandleUuidPairIterator::Format16(iter) => {
+                    let handles: Vec<_> = iter.map(|pair| pair.handle).collect();
+                    assert_eq!(handles, [AttributeHandle(0x0403), AttributeHandle(0x0807)]);
+                }
+                _ => panic!("Did not get 16-bit UUIDs"),
+            }
+        }
+        other => panic!("Did not get ATT find information response: {:?}", other),
+    }
+}
+
+#[cfg(feature = "gap-central-events")]
+#[test]
+fn gap_procedure_complete_name_discovery() {
+    let buffer = [0x07, 0x04, 0x04, 0x00, 0x41, 0x42, 0x43];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GapProcedureComplete(event)) => match event.procedure {
+            GapProcedure::NameDiscovery(len, ref name) => assert_eq!(name[..len], *b"ABC"),
+            other => panic!("Did not get name discovery: {:?}", other),
+        },
+        other => panic!("Did not get GAP procedure complete: {:?}", other),
+    }
+}
+
+#[cfg(feature = "ms")]
+#[test]
+fn crash_report() {
+    let data = FaultData::new(CrashReason::HardFault, &[0xDE, 0xAD]).unwrap();
+    let copy = data.clone();
+    assert_eq!(copy.debug_data(), [0xDE, 0xAD]);
+}
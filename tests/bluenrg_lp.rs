@@ -0,0 +1,171 @@
+#![cfg(feature = "bluenrg-lp")]
+
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+
+use bluenrg::event::*;
+use hci::event::{Error as HciError, VendorEvent};
+
+// These buffers are hand-built to match the BlueNRG-LP event layouts the decoder expects; they were
+// not captured from a device.
+
+#[test]
+fn gap_pairing_complete() {
+    let buffer = [0x01, 0x04, 0x01, 0x02, 0x02, 0x08];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GapPairingComplete(evt)) => {
+            assert_eq!(evt.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(evt.status, GapPairingStatus::Failed);
+            assert_eq!(evt.reason, Some(PairingFailureReason::UnspecifiedReason));
+        }
+        other => panic!("Did not get GAP Pairing complete: {:?}", other),
+    }
+}
+
+#[test]
+fn gap_pairing_complete_failed_missing_reason() {
+    let buffer = [0x01, 0x04, 0x01, 0x02, 0x00];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0401),
+            error: HciError::BadLength(actual, expected),
+        })) => {
+            assert_eq!(actual, 5);
+            assert_eq!(expected, 6);
+        }
+        other => panic!("Did not get bad length: {:?}", other),
+    }
+}
+
+#[test]
+fn gap_device_found_rejected() {
+    let buffer = [
+        0x06, 0x04, 0x00, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 3, 0x01, 0x02, 0x03, 0xD8,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0406),
+            error: HciError::Vendor(BlueNRGError::UnknownEvent(0x0406)),
+        })) => (),
+        other => panic!("Did not get unknown event: {:?}", other),
+    }
+}
+
+#[test]
+fn gatt_attribute_modified() {
+    let buffer = [
+        0x01, 0x0C, 0x01, 0x02, 0x03, 0x04, 0x05, 0x86, 0x02, 0x00, 0x07, 0x08,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GattAttributeModified(event)) => {
+            assert_eq!(event.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(event.attr_handle, AttributeHandle(0x0403));
+            assert_eq!(event.offset, Some(0x0605));
+            assert_eq!(event.continued, Some(true));
+            assert_eq!(event.data(), [0x07, 0x08]);
+        }
+        other => panic!("Did not get GATT Attribute Modified: {:?}", other),
+    }
+}
+
+#[test]
+fn gatt_attribute_modified_failed_bad_data_len() {
+    let buffer = [
+        0x01, 0x0C, 0x01, 0x02, 0x03, 0x04, 0x05, 0x86, 0x03, 0x00, 0x07, 0x08,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C01),
+            error: HciError::BadLength(actual, expected),
+        })) => {
+            assert_eq!(actual, 12);
+            assert_eq!(expected, 13);
+        }
+        other => panic!("Did not get bad length: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "att-client-events")]
+fn gatt_indication_ext() {
+    let buffer = [
+        0x1E, 0x0C, 0x01, 0x02, 0x04, 0x80, 0x05, 0x06, 0x03, 0x00, 0x07, 0x08, 0x09,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GattIndicationExt(event)) => {
+            assert_eq!(event.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(event.attribute_handle, AttributeHandle(0x0605));
+            assert_eq!(event.offset, 4);
+            assert_eq!(event.continued, true);
+            assert_eq!(event.value(), [0x07, 0x08, 0x09]);
+        }
+        other => panic!("Did not get GATT Indication: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "att-client-events")]
+fn gatt_indication_ext_failed_bad_value_len() {
+    let buffer = [
+        0x1E, 0x0C, 0x01, 0x02, 0x04, 0x80, 0x05, 0x06, 0x04, 0x00, 0x07, 0x08, 0x09,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C1E),
+            error: HciError::BadLength(actual, expected),
+        })) => {
+            assert_eq!(actual, 13);
+            assert_eq!(expected, 14);
+        }
+        other => panic!("Did not get bad length: {:?}", other),
+    }
+}
+
+#[test]
+fn att_read_permit_request() {
+    let buffer = [0x14, 0x0C, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttReadPermitRequest(event)) => {
+            assert_eq!(event.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(event.attribute_handle, AttributeHandle(0x0403));
+            assert_eq!(event.offset, 0x0605);
+        }
+        other => panic!("Did not get ATT Read Permit Request: {:?}", other),
+    }
+}
+
+#[test]
+fn att_read_multiple_permit_request() {
+    let buffer = [
+        0x15, 0x0C, 0x01, 0x02, 3, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttReadMultiplePermitRequest(event)) => {
+            assert_eq!(event.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(
+                event.handles(),
+                [
+                    AttributeHandle(0x0403),
+                    AttributeHandle(0x0605),
+                    AttributeHandle(0x0807)
+                ]
+            );
+        }
+        other => panic!("Did not get ATT Read Multiple Permit Request: {:?}", other),
+    }
+}
+
+#[test]
+fn att_read_multiple_permit_request_failed_bad_count() {
+    let buffer = [0x15, 0x0C, 0x01, 0x02, 3, 0x03, 0x04, 0x05, 0x06];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C15),
+            error: HciError::BadLength(actual, expected),
+        })) => {
+            assert_eq!(actual, 9);
+            assert_eq!(expected, 11);
+        }
+        other => panic!("Did not get bad length: {:?}", other),
+    }
+}
@@ -0,0 +1,83 @@
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+#[cfg(feature = "serde")]
+extern crate serde_test;
+
+use bluenrg::bond::*;
+use bluenrg::event::command::ReturnParameters as BNRGParams;
+use bluenrg::event::BlueNRGEvent;
+use hci::event::command::ReturnParameters as HciParams;
+use hci::event::{Event as HciEvent, Packet};
+#[cfg(feature = "serde")]
+use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+type Event = HciEvent<BlueNRGEvent>;
+
+fn two_bond_security_database() -> SecurityDatabase {
+    let buffer = [
+        0x0E, 19, 1, 0xA3, 0xFC, 0, 2, 0, 1, 2, 3, 4, 5, 6, 1, 6, 5, 4, 3, 2, 1,
+    ];
+    match Event::new(Packet(&buffer)) {
+        Ok(HciEvent::CommandComplete(event)) => match event.return_params {
+            HciParams::Vendor(BNRGParams::GapGetBondedDevices(params)) => {
+                SecurityDatabase::from(&params)
+            }
+            other => panic!("Wrong return parameters: {:?}", other),
+        },
+        other => panic!("Did not get command complete event: {:?}", other),
+    }
+}
+
+#[test]
+fn security_database_from_bonded_devices() {
+    let database = two_bond_security_database();
+    assert_eq!(
+        database.bonded_addresses(),
+        [
+            hci::BdAddrType::Public(hci::BdAddr([1, 2, 3, 4, 5, 6])),
+            hci::BdAddrType::Random(hci::BdAddr([6, 5, 4, 3, 2, 1])),
+        ]
+    );
+    assert!(database.contains(&hci::BdAddrType::Random(hci::BdAddr([6, 5, 4, 3, 2, 1]))));
+    assert!(!database.contains(&hci::BdAddrType::Public(hci::BdAddr([6, 5, 4, 3, 2, 1]))));
+}
+
+#[cfg(feature = "serde")]
+fn address_tokens(variant: &'static str, addr: [u8; 6]) -> Vec<Token> {
+    let mut tokens = vec![
+        Token::NewtypeVariant {
+            name: "BondedAddress",
+            variant,
+        },
+        Token::Tuple { len: 6 },
+    ];
+    tokens.extend(addr.iter().map(|&b| Token::U8(b)));
+    tokens.push(Token::TupleEnd);
+    tokens
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn security_database_round_trip() {
+    let mut tokens = vec![Token::Seq { len: Some(2) }];
+    tokens.extend(address_tokens("Public", [1, 2, 3, 4, 5, 6]));
+    tokens.extend(address_tokens("Random", [6, 5, 4, 3, 2, 1]));
+    tokens.push(Token::SeqEnd);
+
+    assert_tokens(&two_bond_security_database(), &tokens);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn security_database_deserialize_too_many_devices() {
+    let mut tokens = vec![Token::Seq { len: Some(36) }];
+    for i in 0..36 {
+        tokens.extend(address_tokens("Public", [i, 0, 0, 0, 0, 0]));
+    }
+    tokens.push(Token::SeqEnd);
+
+    assert_de_tokens_error::<SecurityDatabase>(
+        &tokens,
+        "invalid length 36, expected a list of at most 35 bonded addresses",
+    );
+}
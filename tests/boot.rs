@@ -0,0 +1,127 @@
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+
+use bluenrg::boot::*;
+use bluenrg::event::*;
+use hci::event::VendorEvent;
+
+fn hal_initialized(reason: u8) -> BlueNRGEvent {
+    BlueNRGEvent::new(&[0x01, 0x00, reason]).unwrap()
+}
+
+#[cfg(feature = "ms")]
+fn crash_report() -> BlueNRGEvent {
+    let mut buffer = [0; 40];
+    buffer[0] = 0x03;
+    buffer[2] = 0x02; // hard fault
+    buffer[31] = 0x78; // pc
+    buffer[32] = 0x56;
+    buffer[33] = 0x34;
+    buffer[34] = 0x12;
+    BlueNRGEvent::new(&buffer).unwrap()
+}
+
+#[test]
+fn normal_boot() {
+    let monitor = BootMonitor::new();
+    match monitor.handle_event(&BlueNRGEvent::GapLimitedDiscoverableTimeout) {
+        BootProgress::StillWaiting => (),
+        other => panic!("Boot completed early: {:?}", other),
+    }
+    match monitor.handle_event(&hal_initialized(1)) {
+        BootProgress::Ready => (),
+        other => panic!("Did not get ready: {:?}", other),
+    }
+}
+
+#[test]
+fn updater_boot() {
+    for &(code, expected) in [
+        (2, ResetReason::Updater),
+        (3, ResetReason::UpdaterBadFlag),
+        (4, ResetReason::UpdaterPin),
+    ]
+    .iter()
+    {
+        let monitor = BootMonitor::new();
+        match monitor.handle_event(&hal_initialized(code)) {
+            BootProgress::EnteredUpdater(reason) => assert_eq!(reason, expected),
+            other => panic!("Did not get updater mode: {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn watchdog_reset_boot() {
+    let monitor = BootMonitor::new();
+    match monitor.handle_event(&hal_initialized(5)) {
+        BootProgress::Ready => (),
+        other => panic!("Did not get ready: {:?}", other),
+    }
+}
+
+#[cfg(feature = "ms")]
+#[test]
+fn crash_then_reinit() {
+    let monitor = BootMonitor::new();
+    match monitor.handle_event(&hal_initialized(8)) {
+        BootProgress::StillWaiting => (),
+        other => panic!("Did not wait for crash report: {:?}", other),
+    }
+    match monitor.handle_event(&crash_report()) {
+        BootProgress::Crashed(fault_data) => {
+            assert_eq!(fault_data.reason, CrashReason::HardFault);
+            assert_eq!(fault_data.pc, 0x12345678);
+        }
+        other => panic!("Did not get crash: {:?}", other),
+    }
+    match monitor.handle_event(&hal_initialized(1)) {
+        BootProgress::Ready => (),
+        other => panic!("Did not get ready: {:?}", other),
+    }
+}
+
+#[cfg(not(feature = "ms"))]
+#[test]
+fn crash_then_reinit() {
+    let monitor = BootMonitor::new();
+    match monitor.handle_event(&hal_initialized(8)) {
+        BootProgress::Ready => (),
+        other => panic!("Did not get ready: {:?}", other),
+    }
+}
+
+#[test]
+fn timeout() {
+    let monitor = BootMonitor::with_timeout(100, 50);
+    match monitor.check_timeout(149) {
+        BootProgress::StillWaiting => (),
+        other => panic!("Timed out early: {:?}", other),
+    }
+    match monitor.check_timeout(150) {
+        BootProgress::TimedOut => (),
+        other => panic!("Did not time out: {:?}", other),
+    }
+}
+
+#[test]
+fn timeout_wraps() {
+    let monitor = BootMonitor::with_timeout(u32::max_value() - 10, 50);
+    match monitor.check_timeout(20) {
+        BootProgress::StillWaiting => (),
+        other => panic!("Timed out early: {:?}", other),
+    }
+    match monitor.check_timeout(40) {
+        BootProgress::TimedOut => (),
+        other => panic!("Did not time out: {:?}", other),
+    }
+}
+
+#[test]
+fn no_timeout() {
+    let monitor = BootMonitor::new();
+    match monitor.check_timeout(u32::max_value()) {
+        BootProgress::StillWaiting => (),
+        other => panic!("Timed out: {:?}", other),
+    }
+}
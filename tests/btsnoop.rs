@@ -0,0 +1,192 @@
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+extern crate byteorder;
+
+use bluenrg::event::{BlueNRGError, BlueNRGEvent, ParseError};
+use byteorder::{BigEndian, ByteOrder};
+use hci::event::{Error as HciError, VendorEvent};
+use std::collections::BTreeMap;
+
+// A synthetic capture, built by hand in the btsnoop format rather than recorded from a controller,
+// so every record has the same timestamp. It follows the controller booting, pairing with a peer
+// and then losing the bond, with one vendor event of each type that parses the same way under
+// every feature combination, plus a command and a Command Complete event that the replay should
+// skip.
+const VENDOR_EVENTS: &[u8] = include_bytes!("captures/vendor_events.btsnoop");
+
+const HEADER_LEN: usize = 16;
+const RECORD_HEADER_LEN: usize = 24;
+
+// Datalink types from the btsnoop specification.
+const DATALINK_UNENCAPSULATED: u32 = 1001;
+const DATALINK_H4: u32 = 1002;
+
+const H4_EVENT: u8 = 0x04;
+const FLAG_RECEIVED: u32 = 0x01;
+const FLAG_COMMAND_OR_EVENT: u32 = 0x02;
+
+const VENDOR_EVENT_CODE: u8 = 0xFF;
+
+#[derive(Debug)]
+enum CaptureError {
+    BadHeader,
+    UnsupportedVersion(u32),
+    UnsupportedDatalink(u32),
+    TruncatedRecord(usize),
+}
+
+#[derive(Debug)]
+struct Failure {
+    // Offset of the packet record in the capture file.
+    offset: usize,
+    error: HciError<ParseError>,
+}
+
+#[derive(Debug, Default)]
+struct Report {
+    counts: BTreeMap<u16, usize>,
+    failures: Vec<Failure>,
+}
+
+impl Report {
+    fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+}
+
+// Returns the HCI event packet (starting at the event code) contained in a record, if any.
+fn event_packet(datalink: u32, flags: u32, packet: &[u8]) -> Option<&[u8]> {
+    match datalink {
+        DATALINK_H4 => match packet.split_first() {
+            Some((&H4_EVENT, event)) => Some(event),
+            _ => None,
+        },
+        _ => {
+            let event_flags = FLAG_RECEIVED | FLAG_COMMAND_OR_EVENT;
+            if flags & event_flags == event_flags {
+                Some(packet)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+// Feeds every vendor event in a btsnoop capture to the event parser. Parse failures are collected
+// in the report rather than stopping the replay, so one bad packet does not hide the others.
+fn replay(capture: &[u8]) -> Result<Report, CaptureError> {
+    if capture.len() < HEADER_LEN || &capture[0..8] != b"btsnoop\0" {
+        return Err(CaptureError::BadHeader);
+    }
+    let version = BigEndian::read_u32(&capture[8..]);
+    if version != 1 {
+        return Err(CaptureError::UnsupportedVersion(version));
+    }
+    let datalink = BigEndian::read_u32(&capture[12..]);
+    if datalink != DATALINK_UNENCAPSULATED && datalink != DATALINK_H4 {
+        return Err(CaptureError::UnsupportedDatalink(datalink));
+    }
+
+    let mut report = Report::default();
+    let mut offset = HEADER_LEN;
+    while offset < capture.len() {
+        let record = &capture[offset..];
+        if record.len() < RECORD_HEADER_LEN {
+            return Err(CaptureError::TruncatedRecord(offset));
+        }
+        let included_len = BigEndian::read_u32(&record[4..]) as usize;
+        let flags = BigEndian::read_u32(&record[8..]);
+        if record.len() < RECORD_HEADER_LEN + included_len {
+            return Err(CaptureError::TruncatedRecord(offset));
+        }
+        let packet = &record[RECORD_HEADER_LEN..RECORD_HEADER_LEN + included_len];
+
+        if let Some(event) = event_packet(datalink, flags, packet) {
+            // Strip the event code and parameter length; the vendor event parser expects the
+            // parameters, which begin with the 2-byte vendor event code.
+            if event.len() >= 2 && event[0] == VENDOR_EVENT_CODE {
+                match BlueNRGEvent::new(&event[2..]) {
+                    Ok(event) => *report.counts.entry(event.code()).or_insert(0) += 1,
+                    Err(error) => report.failures.push(Failure { offset, error }),
+                }
+            }
+        }
+
+        offset += RECORD_HEADER_LEN + included_len;
+    }
+
+    Ok(report)
+}
+
+#[test]
+fn checked_in_capture_parses() {
+    let report = replay(VENDOR_EVENTS).unwrap();
+    assert!(
+        report.failures.is_empty(),
+        "Failed to parse: {:?}",
+        report.failures
+    );
+    assert_eq!(report.total(), 9);
+    assert_eq!(report.counts[&0x0001], 1);
+    assert_eq!(report.counts[&0x0402], 2);
+    assert_eq!(report.counts[&0x0405], 1);
+}
+
+#[test]
+fn reports_failures_with_offsets() {
+    // Corrupt the reset reason of the first event, HAL Initialized: the record header is followed
+    // by the H4 packet type, event code, parameter length and 2-byte vendor event code.
+    let mut capture = VENDOR_EVENTS.to_vec();
+    let first = HEADER_LEN;
+    capture[first + RECORD_HEADER_LEN + 5] = 0x7F;
+    let report = replay(&capture[..]).unwrap();
+    assert_eq!(report.total(), 8);
+    assert_eq!(report.failures.len(), 1);
+    assert_eq!(report.failures[0].offset, first);
+    match report.failures[0].error {
+        HciError::Vendor(ParseError {
+            code: Some(0x0001),
+            error: HciError::Vendor(BlueNRGError::UnknownResetReason(0x7F)),
+        }) => (),
+        ref other => panic!("Did not get unknown reset reason: {:?}", other),
+    }
+}
+
+#[test]
+fn rejects_bad_header() {
+    match replay(b"btsnoop\0\x00\x00\x00\x02\x00\x00\x03\xEA") {
+        Err(CaptureError::UnsupportedVersion(2)) => (),
+        other => panic!("Did not reject version: {:?}", other),
+    }
+    match replay(b"btsnoop\0\x00\x00\x00\x01\x00\x00\x03\xEB") {
+        Err(CaptureError::UnsupportedDatalink(1003)) => (),
+        other => panic!("Did not reject datalink: {:?}", other),
+    }
+    match replay(&VENDOR_EVENTS[..VENDOR_EVENTS.len() - 1]) {
+        Err(CaptureError::TruncatedRecord(_)) => (),
+        other => panic!("Did not reject truncated record: {:?}", other),
+    }
+}
+
+// Replays a capture from disk, for checking logs taken from real hardware:
+//
+//     BTSNOOP_CAPTURE=path/to/capture.btsnoop cargo test --test btsnoop -- --nocapture
+#[test]
+fn replay_capture_from_environment() {
+    let path = match std::env::var_os("BTSNOOP_CAPTURE") {
+        Some(path) => path,
+        None => return,
+    };
+    let capture = std::fs::read(&path).unwrap();
+    let report = replay(&capture[..]).unwrap();
+    for (code, count) in &report.counts {
+        println!("0x{:04X}: {}", code, count);
+    }
+    for failure in &report.failures {
+        println!(
+            "offset {}: code {:?}: {:?}",
+            failure.offset, failure.code, failure.error
+        );
+    }
+    assert!(report.failures.is_empty());
+}
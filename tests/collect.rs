@@ -0,0 +1,178 @@
+#![cfg(all(feature = "heapless", feature = "att-client-events"))]
+
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+extern crate heapless;
+
+use bluenrg::event::*;
+use hci::event::VendorEvent;
+
+fn read_by_type_response() -> AttReadByTypeResponse {
+    let buffer = [
+        0x06, 0x0C, 0x01, 0x02, 13, 6, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x11, 0x12, 0x13, 0x14,
+        0x15, 0x16,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttReadByTypeResponse(event)) => event,
+        other => panic!("Did not get ATT read-by-type response: {:?}", other),
+    }
+}
+
+#[test]
+fn read_by_type_exact_fit() {
+    let mut pairs: heapless::Vec<OwnedHandleValuePair<4>, 2> = heapless::Vec::new();
+    {
+        // The copies outlive the event.
+        let event = read_by_type_response();
+        assert_eq!(event.collect_into(&mut pairs), Ok(2));
+    }
+    assert_eq!(pairs[0].handle, AttributeHandle(0x0201));
+    assert_eq!(pairs[0].value[..], [0x03, 0x04, 0x05, 0x06]);
+    assert_eq!(pairs[1].handle, AttributeHandle(0x1211));
+    assert_eq!(pairs[1].value[..], [0x13, 0x14, 0x15, 0x16]);
+}
+
+#[test]
+fn read_by_type_under_fit() {
+    let event = read_by_type_response();
+    let mut pairs: heapless::Vec<OwnedHandleValuePair<8>, 4> = heapless::Vec::new();
+    assert_eq!(event.collect_into(&mut pairs), Ok(2));
+    assert_eq!(pairs.len(), 2);
+    assert_eq!(pairs[1].value[..], [0x13, 0x14, 0x15, 0x16]);
+}
+
+#[test]
+fn read_by_type_overflow() {
+    let event = read_by_type_response();
+    let mut pairs: heapless::Vec<OwnedHandleValuePair<4>, 1> = heapless::Vec::new();
+    assert_eq!(event.collect_into(&mut pairs), Err(CollectError::Full(1)));
+    assert_eq!(pairs.len(), 1);
+    assert_eq!(pairs[0].handle, AttributeHandle(0x0201));
+}
+
+#[test]
+fn read_by_type_value_too_long() {
+    let event = read_by_type_response();
+    let mut pairs: heapless::Vec<OwnedHandleValuePair<3>, 2> = heapless::Vec::new();
+    assert_eq!(
+        event.collect_into(&mut pairs),
+        Err(CollectError::ValueTooLong(0, 4))
+    );
+    assert!(pairs.is_empty());
+}
+
+fn read_by_group_type_response() -> AttReadByGroupTypeResponse {
+    let buffer = [
+        0x0A, 0x0C, 0x01, 0x02, 17, 8, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x11, 0x12,
+        0x13, 0x14, 0x15, 0x16, 0x17, 0x18,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttReadByGroupTypeResponse(event)) => event,
+        other => panic!("Did not get ATT read-by-group-type response: {:?}", other),
+    }
+}
+
+#[test]
+fn read_by_group_type_exact_fit() {
+    let event = read_by_group_type_response();
+    let mut groups: heapless::Vec<OwnedAttributeData<4>, 2> = heapless::Vec::new();
+    assert_eq!(event.collect_into(&mut groups), Ok(2));
+    assert_eq!(groups[0].attribute_handle, AttributeHandle(0x0201));
+    assert_eq!(groups[0].group_end_handle, GroupEndHandle(0x0403));
+    assert_eq!(groups[0].value[..], [0x05, 0x06, 0x07, 0x08]);
+    assert_eq!(groups[1].attribute_handle, AttributeHandle(0x1211));
+    assert_eq!(groups[1].group_end_handle, GroupEndHandle(0x1413));
+    assert_eq!(groups[1].value[..], [0x15, 0x16, 0x17, 0x18]);
+}
+
+#[test]
+fn read_by_group_type_under_fit() {
+    let event = read_by_group_type_response();
+    let mut groups: heapless::Vec<OwnedAttributeData<16>, 8> = heapless::Vec::new();
+    assert_eq!(event.collect_into(&mut groups), Ok(2));
+    assert_eq!(groups.len(), 2);
+}
+
+#[test]
+fn read_by_group_type_overflow() {
+    let event = read_by_group_type_response();
+    let mut groups: heapless::Vec<OwnedAttributeData<4>, 1> = heapless::Vec::new();
+    assert_eq!(event.collect_into(&mut groups), Err(CollectError::Full(1)));
+    assert_eq!(groups.len(), 1);
+}
+
+fn find_information_response() -> AttFindInformationResponse {
+    let buffer = [
+        0x04, 0x0C, 0x01, 0x02, 13, 1, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c,
+        0x0d, 0x0e,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttFindInformationResponse(event)) => event,
+        other => panic!("Did not get ATT find info response: {:?}", other),
+    }
+}
+
+#[test]
+fn find_information_exact_fit() {
+    let event = find_information_response();
+    let mut pairs: heapless::Vec<HandleUuidPair, 3> = heapless::Vec::new();
+    assert_eq!(event.collect_into(&mut pairs), Ok(3));
+    match pairs[2] {
+        HandleUuidPair::Format16(pair) => {
+            assert_eq!(pair.handle, AttributeHandle(0x0c0b));
+            assert_eq!(pair.uuid, Uuid16(0x0e0d));
+        }
+        other => panic!("Did not get 16-bit UUID pair: {:?}", other),
+    }
+}
+
+#[test]
+fn find_information_under_fit() {
+    let event = find_information_response();
+    let mut pairs: heapless::Vec<HandleUuidPair, 8> = heapless::Vec::new();
+    assert_eq!(event.collect_into(&mut pairs), Ok(3));
+    assert_eq!(pairs.len(), 3);
+}
+
+#[test]
+fn find_information_overflow() {
+    let event = find_information_response();
+    let mut pairs: heapless::Vec<HandleUuidPair, 2> = heapless::Vec::new();
+    assert_eq!(event.collect_into(&mut pairs), Err(CollectError::Full(2)));
+    assert_eq!(pairs.len(), 2);
+}
+
+fn find_by_type_value_response() -> AttFindByTypeValueResponse {
+    let buffer = [
+        0x05, 0x0C, 0x01, 0x02, 8, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttFindByTypeValueResponse(event)) => event,
+        other => panic!("Did not get find-by-type-value response: {:?}", other),
+    }
+}
+
+#[test]
+fn find_by_type_value_exact_fit() {
+    let event = find_by_type_value_response();
+    let mut pairs: heapless::Vec<HandleInfoPair, 2> = heapless::Vec::new();
+    assert_eq!(event.collect_into(&mut pairs), Ok(2));
+    assert_eq!(pairs[1].attribute, AttributeHandle(0x0605));
+    assert_eq!(pairs[1].group_end, GroupEndHandle(0x0807));
+}
+
+#[test]
+fn find_by_type_value_under_fit() {
+    let event = find_by_type_value_response();
+    let mut pairs: heapless::Vec<HandleInfoPair, 4> = heapless::Vec::new();
+    assert_eq!(event.collect_into(&mut pairs), Ok(2));
+    assert_eq!(pairs.len(), 2);
+}
+
+#[test]
+fn find_by_type_value_overflow() {
+    let event = find_by_type_value_response();
+    let mut pairs: heapless::Vec<HandleInfoPair, 1> = heapless::Vec::new();
+    assert_eq!(event.collect_into(&mut pairs), Err(CollectError::Full(1)));
+    assert_eq!(pairs[0].attribute, AttributeHandle(0x0201));
+}
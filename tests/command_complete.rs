@@ -4,8 +4,9 @@ extern crate bluetooth_hci as hci;
 use bluenrg::event::command::ReturnParameters as BNRGParams;
 use bluenrg::event::command::*;
 use bluenrg::event::*;
+use bluenrg::hal::{ConfigParameter, Role};
 use hci::event::command::ReturnParameters as HciParams;
-use hci::event::{Error as HciError, Event as HciEvent, Packet};
+use hci::event::{Error as HciError, Event as HciEvent, Packet, VendorReturnParameters};
 use std::time::Duration;
 
 type Event = HciEvent<BlueNRGEvent>;
@@ -43,6 +44,7 @@ status_only! {
     hal_device_standby(0x13, 0xFC, BNRGParams::HalDeviceStandby);
     hal_start_tone(0x15, 0xFC, BNRGParams::HalStartTone);
     hal_stop_tone(0x16, 0xFC, BNRGParams::HalStopTone);
+    hal_write_radio_register(0x30, 0xFC, BNRGParams::HalWriteRadioRegister);
 
     l2cap_connection_parameter_update_response(
         0x82,
@@ -74,6 +76,13 @@ status_only! {
     #[cfg(feature = "ms")]
     gap_start_observation_procedure(0xA2, 0xFC, BNRGParams::GapStartObservationProcedure);
     gap_is_device_bonded(0xA4, 0xFC, BNRGParams::GapIsDeviceBonded);
+    gap_numeric_comparison_value_confirm(
+        0xA5,
+        0xFC,
+        BNRGParams::GapNumericComparisonValueConfirm
+    );
+    #[cfg(feature = "bluenrg-lp")]
+    gap_add_devices_to_resolving_list(0xA9, 0xFC, BNRGParams::GapAddDevicesToResolvingList);
 
     gatt_init(0x01, 0xFD, BNRGParams::GattInit);
     gatt_update_characteristic_value(0x06, 0xFD, BNRGParams::GattUpdateCharacteristicValue);
@@ -94,6 +103,8 @@ status_only! {
         0xFD,
         BNRGParams::GattUpdateLongCharacteristicValue
     );
+    gatt_send_multiple_notifications(0x31, 0xFD, BNRGParams::GattSendMultipleNotifications);
+    gatt_store_db(0x30, 0xFD, BNRGParams::GattStoreDb);
 }
 
 #[test]
@@ -150,6 +161,20 @@ fn hal_write_config_data_nonstandard_status() {
     }
 }
 
+#[test]
+fn gatt_store_db_flash_write_failed() {
+    let buffer = [0x0E, 4, 8, 0x30, 0xFD, 0x4A];
+    match Event::new(Packet(&buffer)) {
+        Ok(HciEvent::CommandComplete(event)) => match event.return_params {
+            HciParams::Vendor(BNRGParams::GattStoreDb(status)) => {
+                assert_eq!(status, hci::Status::Vendor(Status::FlashWriteFailed));
+            }
+            other => panic!("Wrong return parameters: {:?}", other),
+        },
+        other => panic!("Did not get command complete event: {:?}", other),
+    }
+}
+
 #[test]
 fn hal_read_config_data_public_addr() {
     let buffer = [0x0E, 10, 8, 0x0D, 0xFC, 0, 1, 2, 3, 4, 5, 6];
@@ -238,13 +263,62 @@ fn hal_read_config_byte() {
 fn hal_read_config_invalid() {
     let buffer = [0x0E, 7, 8, 0x0D, 0xFC, 0, 0, 1, 2];
     match Event::new(Packet(&buffer)) {
-        Err(HciError::Vendor(BlueNRGError::BadConfigParameterLength(len))) => {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0xFC0D),
+            error: HciError::Vendor(BlueNRGError::BadConfigParameterLength(len)),
+        })) => {
             assert_eq!(len, 3);
         }
         other => panic!("Did not get bad parameter length: {:?}", other),
     }
 }
 
+fn hal_config_data(buffer: &[u8]) -> HalConfigData {
+    match Event::new(Packet(buffer)) {
+        Ok(HciEvent::CommandComplete(event)) => match event.return_params {
+            HciParams::Vendor(BNRGParams::HalReadConfigData(params)) => params,
+            other => panic!("Wrong return parameters: {:?}", other),
+        },
+        other => panic!("Did not get command complete event: {:?}", other),
+    }
+}
+
+#[test]
+fn hal_read_config_data_decode_public_addr() {
+    let params = hal_config_data(&[0x0E, 10, 8, 0x0D, 0xFC, 0, 1, 2, 3, 4, 5, 6]);
+    assert_eq!(
+        params.decode(ConfigParameter::PublicAddress),
+        Ok(ConfigValue::PublicAddress(hci::BdAddr([1, 2, 3, 4, 5, 6])))
+    );
+    assert_eq!(
+        params.decode(ConfigParameter::Role),
+        Err(BlueNRGError::BadConfigParameterLength(6))
+    );
+}
+
+#[test]
+fn hal_read_config_data_decode_flag() {
+    let params = hal_config_data(&[0x0E, 5, 8, 0x0D, 0xFC, 0, 1]);
+    assert_eq!(
+        params.decode(ConfigParameter::LinkLayerOnly),
+        Ok(ConfigValue::LinkLayerOnly(true))
+    );
+    assert_eq!(
+        params.decode(ConfigParameter::Role),
+        Ok(ConfigValue::Role(Role::Peripheral6Kb))
+    );
+
+    let params = hal_config_data(&[0x0E, 5, 8, 0x0D, 0xFC, 0, 5]);
+    assert_eq!(
+        params.decode(ConfigParameter::LinkLayerOnly),
+        Err(BlueNRGError::BadBooleanValue(5))
+    );
+    assert_eq!(
+        params.decode(ConfigParameter::Role),
+        Err(BlueNRGError::BadConfigRole(5))
+    );
+}
+
 #[test]
 fn hal_get_tx_test_packet_count() {
     let buffer = [0x0E, 8, 8, 0x14, 0xFC, 0, 0x1, 0x2, 0x3, 0x4];
@@ -263,6 +337,36 @@ fn hal_get_tx_test_packet_count() {
     }
 }
 
+#[test]
+fn hal_get_tx_test_packet_count_wraps() {
+    let buffer = [0x0E, 8, 8, 0x14, 0xFC, 0, 0xFF, 0xFF, 0xFF, 0xFF];
+    match Event::new(Packet(&buffer)) {
+        Ok(HciEvent::CommandComplete(event)) => match event.return_params {
+            HciParams::Vendor(BNRGParams::HalGetTxTestPacketCount(params)) => {
+                assert_eq!(params.status, hci::Status::Success);
+                assert_eq!(params.packet_count, u32::MAX);
+            }
+            other => panic!("Wrong return parameters: {:?}", other),
+        },
+        other => panic!("Did not get command complete event: {:?}", other),
+    }
+}
+
+#[test]
+fn hal_get_tx_test_packet_count_too_short() {
+    let buffer = [0x0E, 7, 8, 0x14, 0xFC, 0, 0x1, 0x2, 0x3];
+    match Event::new(Packet(&buffer)) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0xFC14),
+            error: HciError::BadLength(actual, expected),
+        })) => {
+            assert_eq!(actual, 4);
+            assert_eq!(expected, 5);
+        }
+        other => panic!("Did not get bad length: {:?}", other),
+    }
+}
+
 #[test]
 fn hal_get_link_status() {
     let buffer = [
@@ -327,7 +431,10 @@ fn hal_get_link_status_invalid_status() {
         0x8, 0x9, 0xA, 0xB, 0xC, 0xD, 0xE, 0xF,
     ];
     match Event::new(Packet(&buffer)) {
-        Err(HciError::Vendor(BlueNRGError::UnknownLinkState(value))) => {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0xFC17),
+            error: HciError::Vendor(BlueNRGError::UnknownLinkState(value)),
+        })) => {
             assert_eq!(value, 8);
         }
         other => panic!("Did not get unknown link state: {:?}", other),
@@ -376,6 +483,118 @@ fn hal_get_anchor_period() {
     }
 }
 
+#[test]
+fn hal_read_radio_register() {
+    let buffer = [0x0E, 5, 8, 0x31, 0xFC, 0, 0x5A];
+    match Event::new(Packet(&buffer)) {
+        Ok(HciEvent::CommandComplete(event)) => {
+            assert_eq!(event.num_hci_command_packets, 8);
+            match event.return_params {
+                HciParams::Vendor(BNRGParams::HalReadRadioRegister(params)) => {
+                    assert_eq!(params.status, hci::Status::Success);
+                    assert_eq!(params.value, 0x5A);
+                }
+                other => panic!("Wrong return parameters: {:?}", other),
+            }
+        }
+        other => panic!("Did not get command complete event: {:?}", other),
+    }
+}
+
+#[test]
+fn hal_read_radio_register_failed() {
+    let buffer = [0x0E, 5, 8, 0x31, 0xFC, 0x12, 0x00];
+    match Event::new(Packet(&buffer)) {
+        Ok(HciEvent::CommandComplete(event)) => match event.return_params {
+            HciParams::Vendor(BNRGParams::HalReadRadioRegister(params)) => {
+                assert_eq!(params.status, hci::Status::InvalidParameters);
+            }
+            other => panic!("Wrong return parameters: {:?}", other),
+        },
+        other => panic!("Did not get command complete event: {:?}", other),
+    }
+}
+
+// LE Read Maximum Data Length is a standard LE command, so its return parameters are decoded
+// directly, starting with the command packet count and opcode.
+#[test]
+fn le_add_device_to_resolving_list() {
+    let bytes = [1, 0x27, 0x20, 0];
+    match BNRGParams::new(&bytes) {
+        Ok(BNRGParams::LeAddDeviceToResolvingList(status)) => {
+            assert_eq!(status, hci::Status::Success);
+        }
+        other => panic!("Wrong return parameters: {:?}", other),
+    }
+}
+
+#[test]
+fn le_remove_device_from_resolving_list() {
+    let bytes = [1, 0x28, 0x20, 0];
+    match BNRGParams::new(&bytes) {
+        Ok(BNRGParams::LeRemoveDeviceFromResolvingList(status)) => {
+            assert_eq!(status, hci::Status::Success);
+        }
+        other => panic!("Wrong return parameters: {:?}", other),
+    }
+}
+
+#[test]
+fn le_clear_resolving_list() {
+    let bytes = [1, 0x29, 0x20, 0];
+    match BNRGParams::new(&bytes) {
+        Ok(BNRGParams::LeClearResolvingList(status)) => {
+            assert_eq!(status, hci::Status::Success);
+        }
+        other => panic!("Wrong return parameters: {:?}", other),
+    }
+}
+
+#[test]
+fn le_clear_resolving_list_too_short() {
+    let bytes = [1, 0x29, 0x20];
+    match BNRGParams::new(&bytes) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x2029),
+            error: HciError::BadLength(actual, expected),
+        })) => {
+            assert_eq!(actual, 0);
+            assert_eq!(expected, 1);
+        }
+        other => panic!("Did not get bad length: {:?}", other),
+    }
+}
+
+#[test]
+fn le_read_maximum_data_length() {
+    let bytes = [8, 0x2F, 0x20, 0, 251, 0, 0x48, 0x08, 251, 0, 0x48, 0x08];
+    match BNRGParams::new(&bytes) {
+        Ok(BNRGParams::LeReadMaximumDataLength(params)) => {
+            assert_eq!(params.status, hci::Status::Success);
+            assert_eq!(params.max_tx_octets, 251);
+            assert_eq!(params.max_tx_time, Duration::from_micros(2120));
+            assert_eq!(params.max_rx_octets, 251);
+            assert_eq!(params.max_rx_time, Duration::from_micros(2120));
+        }
+        other => panic!("Wrong return parameters: {:?}", other),
+    }
+}
+
+#[test]
+fn le_read_maximum_data_length_too_short() {
+    let bytes = [8, 0x2F, 0x20, 0, 251, 0, 0x48, 0x08];
+    match BNRGParams::new(&bytes) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x202F),
+            error: HciError::BadLength(actual, expected),
+        })) => {
+            assert_eq!(actual, 5);
+            assert_eq!(expected, 9);
+        }
+        other => panic!("Did not get bad length: {:?}", other),
+    }
+}
+
 #[test]
 fn gap_init() {
     let buffer = [
@@ -429,7 +648,10 @@ fn gap_get_security_level() {
 fn gap_get_security_level_bad_bool() {
     let buffer = [0x0E, 8, 1, 0x90, 0xFC, 0, 2, 1, 0, 2];
     match Event::new(Packet(&buffer)) {
-        Err(HciError::Vendor(BlueNRGError::BadBooleanValue(value))) => {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0xFC90),
+            error: HciError::Vendor(BlueNRGError::BadBooleanValue(value)),
+        })) => {
             assert_eq!(value, 2);
         }
         other => panic!("Did not get bad boolean: {:?}", other),
@@ -440,7 +662,10 @@ fn gap_get_security_level_bad_bool() {
 fn gap_get_security_level_bad_pass_key_requirement() {
     let buffer = [0x0E, 8, 1, 0x90, 0xFC, 0, 0, 1, 0, 3];
     match Event::new(Packet(&buffer)) {
-        Err(HciError::Vendor(BlueNRGError::BadPassKeyRequirement(value))) => {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0xFC90),
+            error: HciError::Vendor(BlueNRGError::BadPassKeyRequirement(value)),
+        })) => {
             assert_eq!(value, 3);
         }
         other => panic!("Did not get bad pass key requirement: {:?}", other),
@@ -536,7 +761,10 @@ fn gap_get_bonded_addresses_partial() {
         0x0E, 18, 1, 0xA3, 0xFC, 0, 2, 0, 1, 2, 3, 4, 5, 6, 1, 6, 5, 4, 3, 2,
     ];
     match Event::new(Packet(&buffer)) {
-        Err(HciError::Vendor(BlueNRGError::PartialBondedDeviceAddress)) => (),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0xFCA3),
+            error: HciError::Vendor(BlueNRGError::PartialBondedDeviceAddress),
+        })) => (),
         other => panic!("Did not get partial bonded device address: {:?}", other),
     }
 }
@@ -547,7 +775,10 @@ fn gap_get_bonded_addresses_bad_addr_type() {
         0x0E, 19, 1, 0xA3, 0xFC, 0, 2, 2, 1, 2, 3, 4, 5, 6, 1, 6, 5, 4, 3, 2, 1,
     ];
     match Event::new(Packet(&buffer)) {
-        Err(HciError::Vendor(BlueNRGError::BadBdAddrType(2))) => (),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0xFCA3),
+            error: HciError::Vendor(BlueNRGError::BadBdAddrType(2)),
+        })) => (),
         other => panic!("Did not get bad address type: {:?}", other),
     }
 }
@@ -0,0 +1,171 @@
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+extern crate nb;
+
+mod fixture;
+
+use bluenrg::dtm::*;
+use bluenrg::event::BlueNRGEvent;
+use fixture::{Fixture, RecordingSink};
+use hci::event::{Event, Packet};
+
+#[test]
+fn channel_mapping() {
+    assert_eq!(rf_channel(37), Some(0));
+    assert_eq!(rf_channel(0), Some(1));
+    assert_eq!(rf_channel(10), Some(11));
+    assert_eq!(rf_channel(38), Some(12));
+    assert_eq!(rf_channel(11), Some(13));
+    assert_eq!(rf_channel(36), Some(38));
+    assert_eq!(rf_channel(39), Some(39));
+    assert_eq!(rf_channel(40), None);
+    assert_eq!(channel_index(40), None);
+}
+
+#[test]
+fn channel_mapping_round_trips() {
+    for index in 0..=MAX_CHANNEL {
+        let rf = rf_channel(index).unwrap();
+        assert_eq!(channel_index(rf), Some(index));
+    }
+}
+
+#[test]
+fn channel_mapping_frequencies() {
+    // Data channels are spread between the advertising channels at 2402, 2426, and 2480 MHz.
+    let frequency = |index| 2402 + 2 * u32::from(rf_channel(index).unwrap());
+    assert_eq!(frequency(37), 2402);
+    assert_eq!(frequency(0), 2404);
+    assert_eq!(frequency(10), 2424);
+    assert_eq!(frequency(38), 2426);
+    assert_eq!(frequency(11), 2428);
+    assert_eq!(frequency(36), 2478);
+    assert_eq!(frequency(39), 2480);
+}
+
+#[test]
+fn start_rx_test() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| DirectTestMode::new(controller).start_rx_test(38))
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x1D, 0x20, 1, 12]));
+}
+
+#[test]
+fn start_tx_test() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| {
+                DirectTestMode::new(controller).start_tx_test(0, 37, TestPacketPayload::PrbS9)
+            })
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x1E, 0x20, 3, 1, 37, 0]));
+}
+
+#[test]
+fn start_tx_test_invalid_channel() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        let err = fixture
+            .act(|controller| {
+                DirectTestMode::new(controller).start_tx_test(40, 37, TestPacketPayload::PrbS9)
+            })
+            .err()
+            .unwrap();
+        assert_eq!(err, nb::Error::Other(Error::InvalidChannel(40)));
+    }
+    assert!(!sink.wrote_header());
+}
+
+#[test]
+fn start_tx_test_invalid_payload_length() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        let err = fixture
+            .act(|controller| {
+                DirectTestMode::new(controller).start_tx_test(0, 38, TestPacketPayload::PrbS9)
+            })
+            .err()
+            .unwrap();
+        assert_eq!(err, nb::Error::Other(Error::InvalidPayloadLength(38)));
+    }
+    assert!(!sink.wrote_header());
+}
+
+#[test]
+fn stop() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| DirectTestMode::new(controller).stop())
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x1F, 0x20, 0]));
+}
+
+#[test]
+fn carrier_on() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| DirectTestMode::new(controller).carrier_on(11))
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x15, 0xFC, 1, 13]));
+}
+
+#[test]
+fn carrier_on_invalid_channel() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        let err = fixture
+            .act(|controller| DirectTestMode::new(controller).carrier_on(40))
+            .err()
+            .unwrap();
+        assert_eq!(err, nb::Error::Other(Error::InvalidChannel(40)));
+    }
+    assert!(!sink.wrote_header());
+}
+
+#[test]
+fn carrier_off() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| DirectTestMode::new(controller).carrier_off())
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x16, 0xFC, 0]));
+}
+
+#[test]
+fn packet_count_from_test_end() {
+    let buffer = [0x0E, 6, 1, 0x1F, 0x20, 0, 0x34, 0x12];
+    let event = Event::<BlueNRGEvent>::new(Packet(&buffer)).unwrap();
+    assert_eq!(packet_count(&event), Some(0x1234));
+}
+
+#[test]
+fn packet_count_from_other_event() {
+    let buffer = [0x0E, 4, 1, 0x16, 0xFC, 0];
+    let event = Event::<BlueNRGEvent>::new(Packet(&buffer)).unwrap();
+    assert_eq!(packet_count(&event), None);
+}
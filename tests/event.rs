@@ -3,28 +3,141 @@ extern crate bluetooth_hci as hci;
 extern crate byteorder;
 
 use bluenrg::event::*;
+use bluenrg::gap::Appearance;
+use bluenrg::gatt::Uuid;
+#[cfg(feature = "l2cap-events")]
 use byteorder::{ByteOrder, LittleEndian};
 use hci::event::{Error as HciError, VendorEvent};
+use std::convert::TryFrom;
+#[cfg(feature = "l2cap-events")]
 use std::time::Duration;
 
 #[test]
 fn hal_initialized() {
     let buffer = [0x01, 0x00, 0x01];
     match BlueNRGEvent::new(&buffer) {
-        Ok(BlueNRGEvent::HalInitialized(reason)) => assert_eq!(reason, ResetReason::Normal),
+        Ok(event) => {
+            assert_eq!(event.reset_reason(), Some(ResetReason::Normal));
+            match event {
+                BlueNRGEvent::HalInitialized(event) => assert_eq!(event.boot_info(), None),
+                event => panic!("Did not get HalInitialized; got {:?}", event),
+            }
+        }
+        event => panic!("Did not get HalInitialized; got {:?}", event),
+    }
+}
+
+#[test]
+fn hal_initialized_boot_count() {
+    let buffer = [0x01, 0x00, 0x05, 0x2A];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::HalInitialized(event)) => {
+            assert_eq!(event.reason, ResetReason::Watchdog);
+            assert_eq!(
+                event.boot_info(),
+                Some(&BootInfo {
+                    boot_count: 0x2A,
+                    firmware_revision: None,
+                })
+            );
+        }
+        event => panic!("Did not get HalInitialized; got {:?}", event),
+    }
+}
+
+#[test]
+fn hal_initialized_boot_count_and_firmware_revision() {
+    let buffer = [0x01, 0x00, 0x05, 0x2A, 0x13];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::HalInitialized(event)) => {
+            assert_eq!(event.reason, ResetReason::Watchdog);
+            assert_eq!(
+                event.boot_info(),
+                Some(&BootInfo {
+                    boot_count: 0x2A,
+                    firmware_revision: Some(0x13),
+                })
+            );
+        }
+        event => panic!("Did not get HalInitialized; got {:?}", event),
+    }
+}
+
+#[test]
+#[cfg(not(feature = "tolerant-decode"))]
+fn hal_initialized_failed_too_much_boot_info() {
+    let buffer = [0x01, 0x00, 0x01, 0x2A, 0x13, 0x00];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0001),
+            error: HciError::BadLength(actual, expected),
+        })) => {
+            assert_eq!(actual, 6);
+            assert_eq!(expected, 5);
+        }
+        other => panic!("Did not get bad length: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "tolerant-decode")]
+fn hal_initialized_ignores_padding() {
+    let buffer = [0x01, 0x00, 0x01, 0x2A, 0x13, 0x00, 0x00];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::HalInitialized(event)) => assert_eq!(
+            event.boot_info(),
+            Some(&BootInfo {
+                boot_count: 0x2A,
+                firmware_revision: Some(0x13),
+            })
+        ),
         event => panic!("Did not get HalInitialized; got {:?}", event),
     }
 }
 
+#[test]
+fn reset_reason_of_other_event() {
+    let buffer = [0x00, 0x04];
+    assert_eq!(BlueNRGEvent::new(&buffer).unwrap().reset_reason(), None);
+}
+
 #[test]
 fn hal_initialized_failure() {
     let buffer = [0x01, 0x00, 0x00];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::UnknownResetReason(val))) => assert_eq!(val, 0),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0001),
+            error: HciError::Vendor(BlueNRGError::UnknownResetReason(val)),
+        })) => assert_eq!(val, 0),
         other => panic!("Did not get unknown reset reason: {:?}", other),
     }
 }
 
+#[test]
+fn parse_error_unknown_event() {
+    let buffer = [0x99, 0x0C, 0x00];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(e)) => {
+            assert_eq!(e.code, Some(0x0C99));
+            assert_eq!(e.to_string(), "0x0c99: unknown event code 0x0c99");
+        }
+        other => panic!("Did not get unknown event: {:?}", other),
+    }
+}
+
+#[test]
+fn parse_error_no_code() {
+    let buffer = [0x01];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(e)) => {
+            assert_eq!(e.code, None);
+            assert_eq!(e.error, HciError::BadLength(1, 2));
+            assert_eq!(e.to_string(), "bad length 1 (expected 2)");
+        }
+        other => panic!("Did not get bad length: {:?}", other),
+    }
+}
+
 #[test]
 #[cfg(feature = "ms")]
 fn hal_events_lost() {
@@ -75,7 +188,10 @@ fn hal_events_lost_failure() {
         0b00000010, 0b00000000,
     ];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::BadEventFlags(_))) => (),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0002),
+            error: HciError::Vendor(BlueNRGError::BadEventFlags(_)),
+        })) => (),
         other => panic!("Did not get BadEventFlags: {:?}", other),
     }
 }
@@ -88,7 +204,10 @@ fn hal_events_lost_unknown() {
         0b00000000, 0b00000000,
     ];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::UnknownEvent(0x0002))) => (),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0002),
+            error: HciError::Vendor(BlueNRGError::UnknownEvent(0x0002)),
+        })) => (),
         other => panic!("Did not get unknown event: {:?}", other),
     }
 }
@@ -171,7 +290,10 @@ fn hal_crash_info_failed_bad_crash_reason() {
     buffer[2] = 0x03;
     let buffer = buffer;
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::UnknownCrashReason(byte))) => assert_eq!(byte, 0x03),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0003),
+            error: HciError::Vendor(BlueNRGError::UnknownCrashReason(byte)),
+        })) => assert_eq!(byte, 0x03),
         other => panic!("Did not get bad crash type: {:?}", other),
     }
 }
@@ -185,7 +307,10 @@ fn hal_crash_info_failed_bad_debug_data_len() {
     buffer[39] = 1; // Says we have one byte of debug data, but the buffer isn't large enough
     let buffer = buffer;
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::BadLength(actual, expected)) => {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0003),
+            error: HciError::BadLength(actual, expected),
+        })) => {
             assert_eq!(actual, 40);
             assert_eq!(expected, 41);
         }
@@ -193,6 +318,158 @@ fn hal_crash_info_failed_bad_debug_data_len() {
     }
 }
 
+#[test]
+#[cfg(all(feature = "ms", feature = "extended-packets"))]
+fn hal_crash_info_failed_debug_data_too_long() {
+    let mut buffer = [0; 256];
+    buffer[0] = 0x03;
+    buffer[1] = 0x00;
+    buffer[39] = 216; // One byte more than the debug data buffer holds.
+    let buffer = buffer;
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0003),
+            error: HciError::BadLength(actual, expected),
+        })) => {
+            assert_eq!(actual, 256);
+            assert_eq!(expected, 255);
+        }
+        other => panic!("Did not get bad length: {:?}", other),
+    }
+}
+
+#[cfg(feature = "ms")]
+fn fault_data() -> FaultData {
+    let mut buffer = [0; 43];
+    buffer[0] = 0x03; // event code
+    buffer[1] = 0x00;
+    buffer[2] = 0x02; // crash_reason
+    for (i, byte) in buffer[3..39].iter_mut().enumerate() {
+        *byte = i as u8 + 1;
+    }
+    buffer[39] = 3; // debug data len
+    buffer[40] = 0xa0; // debug data
+    buffer[41] = 0xa1;
+    buffer[42] = 0xa2;
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::CrashReport(info)) => info,
+        other => panic!("Did not get crash info: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "ms")]
+fn fault_data_new() {
+    let mut info = FaultData::new(CrashReason::Assertion, &[0xa0, 0xa1]).unwrap();
+    info.pc = 0x0800_1234;
+    assert_eq!(info.reason, CrashReason::Assertion);
+    assert_eq!(info.debug_data(), [0xa0, 0xa1]);
+    assert_eq!(info.serialized_len(), 41);
+
+    let mut bytes = [0; 64];
+    info.serialize(&mut bytes).unwrap();
+    let restored = FaultData::deserialize(&bytes).unwrap();
+    assert_eq!(restored.pc, 0x0800_1234);
+    assert_eq!(restored.debug_data(), [0xa0, 0xa1]);
+}
+
+#[test]
+#[cfg(feature = "ms")]
+fn fault_data_new_debug_data_too_long() {
+    assert!(FaultData::new(CrashReason::HardFault, &[0; 216]).is_none());
+}
+
+#[test]
+#[cfg(feature = "ms")]
+fn fault_data_serialize() {
+    let info = fault_data();
+    let mut bytes = [0xff; 64];
+    assert_eq!(info.serialize(&mut bytes), Ok(42));
+    assert_eq!(info.serialized_len(), 42);
+    assert_eq!(bytes[0], FAULT_DATA_FORMAT_VERSION);
+    assert_eq!(bytes[1], 2);
+    assert_eq!(&bytes[2..6], [0x01, 0x02, 0x03, 0x04]); // sp
+    assert_eq!(&bytes[34..38], [0x21, 0x22, 0x23, 0x24]); // xPSR
+    assert_eq!(bytes[38], 3);
+    assert_eq!(&bytes[39..42], [0xa0, 0xa1, 0xa2]);
+    assert_eq!(bytes[42], 0xff);
+}
+
+#[test]
+#[cfg(feature = "ms")]
+fn fault_data_round_trip() {
+    let info = fault_data();
+    let mut bytes = [0; 64];
+    info.serialize(&mut bytes).unwrap();
+    let restored = FaultData::deserialize(&bytes).unwrap();
+    assert_eq!(restored.reason, CrashReason::HardFault);
+    assert_eq!(restored.sp, info.sp);
+    assert_eq!(restored.r0, info.r0);
+    assert_eq!(restored.r1, info.r1);
+    assert_eq!(restored.r2, info.r2);
+    assert_eq!(restored.r3, info.r3);
+    assert_eq!(restored.r12, info.r12);
+    assert_eq!(restored.lr, info.lr);
+    assert_eq!(restored.pc, info.pc);
+    assert_eq!(restored.xpsr, info.xpsr);
+    assert_eq!(restored.debug_data(), info.debug_data());
+}
+
+#[test]
+#[cfg(feature = "ms")]
+fn fault_data_serialize_buffer_too_small() {
+    let info = fault_data();
+    let mut bytes = [0; 41];
+    assert_eq!(
+        info.serialize(&mut bytes),
+        Err(FaultDataFormatError::BufferTooSmall(42))
+    );
+}
+
+#[test]
+#[cfg(feature = "ms")]
+fn fault_data_deserialize_truncated() {
+    let info = fault_data();
+    let mut bytes = [0; 42];
+    info.serialize(&mut bytes).unwrap();
+    match FaultData::deserialize(&bytes[..41]) {
+        Err(FaultDataFormatError::Truncated(actual, expected)) => {
+            assert_eq!(actual, 41);
+            assert_eq!(expected, 42);
+        }
+        other => panic!("Did not get truncated: {:?}", other),
+    }
+    match FaultData::deserialize(&bytes[..20]) {
+        Err(FaultDataFormatError::Truncated(actual, expected)) => {
+            assert_eq!(actual, 20);
+            assert_eq!(expected, 39);
+        }
+        other => panic!("Did not get truncated: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "ms")]
+fn fault_data_deserialize_erased_storage() {
+    let bytes = [0xff; 64];
+    match FaultData::deserialize(&bytes) {
+        Err(FaultDataFormatError::UnsupportedVersion(0xff)) => (),
+        other => panic!("Did not get unsupported version: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "ms")]
+fn fault_data_deserialize_bad_crash_reason() {
+    let mut bytes = [0; 39];
+    bytes[0] = FAULT_DATA_FORMAT_VERSION;
+    bytes[1] = 7;
+    match FaultData::deserialize(&bytes) {
+        Err(FaultDataFormatError::BadCrashReason(7)) => (),
+        other => panic!("Did not get bad crash reason: {:?}", other),
+    }
+}
+
 #[test]
 #[cfg(not(feature = "ms"))]
 fn hal_crash_info_unknown() {
@@ -200,11 +477,15 @@ fn hal_crash_info_unknown() {
     buffer[0] = 0x03; // event code
     buffer[1] = 0x00;
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::UnknownEvent(0x0003))) => (),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0003),
+            error: HciError::Vendor(BlueNRGError::UnknownEvent(0x0003)),
+        })) => (),
         other => panic!("Did not get unknown event: {:?}", other),
     }
 }
 
+#[cfg(feature = "l2cap-events")]
 fn l2cap_connection_update_response_buffer(
     event_data_len: u8,
     response_code: u8,
@@ -222,8 +503,11 @@ fn l2cap_connection_update_response_buffer(
     buffer
 }
 
+#[cfg(feature = "l2cap-events")]
 const CONNECTION_UPDATE_RESP_EVENT_DATA_LEN: u8 = 6;
+#[cfg(feature = "l2cap-events")]
 const CONNECTION_UPDATE_RESP_L2CAP_LEN: u16 = 2;
+#[cfg(feature = "l2cap-events")]
 fn l2cap_connection_update_response_command_rejected_buffer() -> [u8; 11] {
     l2cap_connection_update_response_buffer(
         CONNECTION_UPDATE_RESP_EVENT_DATA_LEN,
@@ -234,6 +518,7 @@ fn l2cap_connection_update_response_command_rejected_buffer() -> [u8; 11] {
 }
 
 #[test]
+#[cfg(feature = "l2cap-events")]
 fn l2cap_connection_update_response_cmd_rejected() {
     let buffer = l2cap_connection_update_response_command_rejected_buffer();
     match BlueNRGEvent::new(&buffer) {
@@ -251,6 +536,7 @@ fn l2cap_connection_update_response_cmd_rejected() {
 }
 
 #[test]
+#[cfg(feature = "l2cap-events")]
 fn l2cap_connection_update_response_updated_accepted() {
     let buffer = l2cap_connection_update_response_buffer(
         CONNECTION_UPDATE_RESP_EVENT_DATA_LEN,
@@ -268,6 +554,7 @@ fn l2cap_connection_update_response_updated_accepted() {
 }
 
 #[test]
+#[cfg(feature = "l2cap-events")]
 fn l2cap_connection_update_response_updated_param_rejected() {
     let buffer = l2cap_connection_update_response_buffer(
         CONNECTION_UPDATE_RESP_EVENT_DATA_LEN,
@@ -286,6 +573,7 @@ fn l2cap_connection_update_response_updated_param_rejected() {
 }
 
 #[test]
+#[cfg(feature = "l2cap-events")]
 fn l2cap_connection_update_response_failed_code() {
     let buffer = l2cap_connection_update_response_buffer(
         CONNECTION_UPDATE_RESP_EVENT_DATA_LEN,
@@ -294,7 +582,10 @@ fn l2cap_connection_update_response_failed_code() {
         0x0504,
     );
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::BadL2CapConnectionResponseCode(code))) => {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0800),
+            error: HciError::Vendor(BlueNRGError::BadL2CapConnectionResponseCode(code)),
+        })) => {
             assert_eq!(code, 0x02)
         }
         other => panic!("Did not get bad response code: {:?}", other),
@@ -302,6 +593,7 @@ fn l2cap_connection_update_response_failed_code() {
 }
 
 #[test]
+#[cfg(feature = "l2cap-events")]
 fn l2cap_connection_update_response_failed_data_length() {
     let buffer = l2cap_connection_update_response_buffer(
         CONNECTION_UPDATE_RESP_EVENT_DATA_LEN - 1,
@@ -310,7 +602,10 @@ fn l2cap_connection_update_response_failed_data_length() {
         0x0504,
     );
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::BadL2CapDataLength(len, 6))) => {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0800),
+            error: HciError::Vendor(BlueNRGError::BadL2CapDataLength(len, 6)),
+        })) => {
             assert_eq!(len, CONNECTION_UPDATE_RESP_EVENT_DATA_LEN - 1)
         }
         other => panic!("Did not get L2Cap data length code: {:?}", other),
@@ -318,6 +613,7 @@ fn l2cap_connection_update_response_failed_data_length() {
 }
 
 #[test]
+#[cfg(feature = "l2cap-events")]
 fn l2cap_connection_update_response_failed_l2cap_length() {
     let buffer = l2cap_connection_update_response_buffer(
         CONNECTION_UPDATE_RESP_EVENT_DATA_LEN,
@@ -326,7 +622,10 @@ fn l2cap_connection_update_response_failed_l2cap_length() {
         0x0504,
     );
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::BadL2CapLength(len, 2))) => {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0800),
+            error: HciError::Vendor(BlueNRGError::BadL2CapLength(len, 2)),
+        })) => {
             assert_eq!(len, CONNECTION_UPDATE_RESP_L2CAP_LEN + 1)
         }
         other => panic!("Did not get L2CAP length: {:?}", other),
@@ -334,6 +633,7 @@ fn l2cap_connection_update_response_failed_l2cap_length() {
 }
 
 #[test]
+#[cfg(feature = "l2cap-events")]
 fn l2cap_connection_update_response_failed_unknown_result() {
     let buffer = l2cap_connection_update_response_buffer(
         CONNECTION_UPDATE_RESP_EVENT_DATA_LEN,
@@ -342,7 +642,10 @@ fn l2cap_connection_update_response_failed_unknown_result() {
         0x0002,
     );
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::BadL2CapConnectionResponseResult(result))) => {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0800),
+            error: HciError::Vendor(BlueNRGError::BadL2CapConnectionResponseResult(result)),
+        })) => {
             assert_eq!(result, 0x0002)
         }
         other => panic!("Did not get bad result: {:?}", other),
@@ -350,6 +653,7 @@ fn l2cap_connection_update_response_failed_unknown_result() {
 }
 
 #[test]
+#[cfg(feature = "l2cap-events")]
 fn l2cap_connection_update_response_failed_unknown_rejection_reason() {
     let buffer = l2cap_connection_update_response_buffer(
         CONNECTION_UPDATE_RESP_EVENT_DATA_LEN,
@@ -358,7 +662,10 @@ fn l2cap_connection_update_response_failed_unknown_rejection_reason() {
         0x0003,
     );
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::BadL2CapRejectionReason(reason))) => {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0800),
+            error: HciError::Vendor(BlueNRGError::BadL2CapRejectionReason(reason)),
+        })) => {
             assert_eq!(reason, 0x0003)
         }
         other => panic!("Did not get bad rejection reason: {:?}", other),
@@ -366,27 +673,35 @@ fn l2cap_connection_update_response_failed_unknown_rejection_reason() {
 }
 
 #[test]
+#[cfg(feature = "l2cap-events")]
 fn l2cap_procedure_timeout() {
     let buffer = [0x01, 0x08, 0x01, 0x02, 0x00];
     match BlueNRGEvent::new(&buffer) {
-        Ok(BlueNRGEvent::L2CapProcedureTimeout(conn_handle)) => {
-            assert_eq!(conn_handle, ConnectionHandle(0x0201));
+        Ok(BlueNRGEvent::L2CapProcedureTimeout(event)) => {
+            assert_eq!(event.conn_handle, ConnectionHandle(0x0201));
         }
         other => panic!("Did not get L2CAP procedure timeout: {:?}", other),
     }
 }
 
 #[test]
+#[cfg(feature = "l2cap-events")]
 fn l2cap_procedure_timeout_failed() {
     let buffer = [0x01, 0x08, 0x01, 0x02, 0x01];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::BadL2CapDataLength(len, 0))) => assert_eq!(len, 1),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0801),
+            error: HciError::Vendor(BlueNRGError::BadL2CapDataLength(len, 0)),
+        })) => assert_eq!(len, 1),
         other => panic!("Did not get L2Cap data length code: {:?}", other),
     }
 }
 
+#[cfg(feature = "l2cap-events")]
 const L2CAP_CONN_UPDATE_REQ_EVENT_DATA_LEN: u8 = 11;
+#[cfg(feature = "l2cap-events")]
 const L2CAP_CONN_UPDATE_REQ_L2CAP_LEN: u16 = 8;
+#[cfg(feature = "l2cap-events")]
 fn l2cap_connection_update_request_buffer(
     event_data_len: u8,
     l2cap_len: u16,
@@ -410,6 +725,7 @@ fn l2cap_connection_update_request_buffer(
 }
 
 #[test]
+#[cfg(feature = "l2cap-events")]
 fn l2cap_connection_update_request() {
     let buffer = l2cap_connection_update_request_buffer(
         L2CAP_CONN_UPDATE_REQ_EVENT_DATA_LEN,
@@ -438,6 +754,7 @@ fn l2cap_connection_update_request() {
 }
 
 #[test]
+#[cfg(feature = "l2cap-events")]
 fn l2cap_connection_update_request_failed_event_data_len() {
     let buffer = l2cap_connection_update_request_buffer(
         L2CAP_CONN_UPDATE_REQ_EVENT_DATA_LEN - 1,
@@ -448,15 +765,19 @@ fn l2cap_connection_update_request_failed_event_data_len() {
         3200,
     );
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::BadL2CapDataLength(
-            len,
-            L2CAP_CONN_UPDATE_REQ_EVENT_DATA_LEN,
-        ))) => assert_eq!(len, L2CAP_CONN_UPDATE_REQ_EVENT_DATA_LEN - 1),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0802),
+            error: HciError::Vendor(BlueNRGError::BadL2CapDataLength(len, expected)),
+        })) => {
+            assert_eq!(len, L2CAP_CONN_UPDATE_REQ_EVENT_DATA_LEN - 1);
+            assert_eq!(expected, L2CAP_CONN_UPDATE_REQ_EVENT_DATA_LEN);
+        }
         other => panic!("Did not get L2CAP data length code: {:?}", other),
     }
 }
 
 #[test]
+#[cfg(feature = "l2cap-events")]
 fn l2cap_connection_update_request_failed_l2cap_len() {
     let buffer = l2cap_connection_update_request_buffer(
         L2CAP_CONN_UPDATE_REQ_EVENT_DATA_LEN,
@@ -467,10 +788,13 @@ fn l2cap_connection_update_request_failed_l2cap_len() {
         3200,
     );
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::BadL2CapLength(
-            len,
-            L2CAP_CONN_UPDATE_REQ_L2CAP_LEN,
-        ))) => assert_eq!(len, L2CAP_CONN_UPDATE_REQ_L2CAP_LEN - 1),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0802),
+            error: HciError::Vendor(BlueNRGError::BadL2CapLength(len, expected)),
+        })) => {
+            assert_eq!(len, L2CAP_CONN_UPDATE_REQ_L2CAP_LEN - 1);
+            assert_eq!(expected, L2CAP_CONN_UPDATE_REQ_L2CAP_LEN);
+        }
         other => panic!("Did not get L2CAP length: {:?}", other),
     }
 }
@@ -485,22 +809,148 @@ fn gap_limited_discoverable() {
 }
 
 #[test]
+#[cfg(not(feature = "tolerant-decode"))]
+fn gap_limited_discoverable_failed_bad_length() {
+    let buffer = [0x00, 0x04, 0x00];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0400),
+            error: HciError::BadLength(actual, expected),
+        })) => {
+            assert_eq!(actual, 3);
+            assert_eq!(expected, 2);
+        }
+        other => panic!("Did not get bad length: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "tolerant-decode")]
+fn gap_limited_discoverable_with_padding() {
+    let buffer = [0x00, 0x04, 0x00];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GapLimitedDiscoverableTimeout) => (),
+        other => panic!("Did not get GAP Limited discoverable timeout: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(not(feature = "bluenrg-lp"))]
 fn gap_pairing_complete() {
     let buffer = [0x01, 0x04, 0x01, 0x02, 0x00];
     match BlueNRGEvent::new(&buffer) {
         Ok(BlueNRGEvent::GapPairingComplete(evt)) => {
             assert_eq!(evt.conn_handle, ConnectionHandle(0x0201));
             assert_eq!(evt.status, GapPairingStatus::Success);
+            assert_eq!(evt.reason, None);
+        }
+        other => panic!("Did not get GAP Pairing complete: {:?}", other),
+    }
+}
+
+#[test]
+fn gap_pairing_complete_with_reason() {
+    let buffer = [0x01, 0x04, 0x01, 0x02, 0x02, 0x01];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GapPairingComplete(evt)) => {
+            assert_eq!(evt.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(evt.status, GapPairingStatus::Failed);
+            assert_eq!(evt.reason, Some(PairingFailureReason::PasskeyEntryFailed));
+        }
+        other => panic!("Did not get GAP Pairing complete: {:?}", other),
+    }
+}
+
+#[test]
+fn gap_pairing_complete_with_dhkey_check_failed() {
+    let buffer = [0x01, 0x04, 0x01, 0x02, 0x02, 0x0B];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GapPairingComplete(evt)) => {
+            assert_eq!(evt.status, GapPairingStatus::Failed);
+            assert_eq!(evt.reason, Some(PairingFailureReason::DhKeyCheckFailed));
+        }
+        other => panic!("Did not get GAP Pairing complete: {:?}", other),
+    }
+}
+
+#[test]
+fn gap_pairing_complete_with_no_reason() {
+    let buffer = [0x01, 0x04, 0x01, 0x02, 0x00, 0x00];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GapPairingComplete(evt)) => {
+            assert_eq!(evt.status, GapPairingStatus::Success);
+            assert_eq!(evt.reason, None);
+        }
+        other => panic!("Did not get GAP Pairing complete: {:?}", other),
+    }
+}
+
+#[test]
+fn gap_pairing_complete_failed_bad_reason() {
+    let buffer = [0x01, 0x04, 0x01, 0x02, 0x02, 0x0F];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0401),
+            error: HciError::Vendor(BlueNRGError::BadPairingFailureReason(value)),
+        })) => {
+            assert_eq!(value, 0x0F)
+        }
+        other => panic!("Did not get bad pairing failure reason: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(not(any(feature = "bluenrg-lp", feature = "tolerant-decode")))]
+fn gap_pairing_complete_failed_bad_length() {
+    let buffer = [0x01, 0x04, 0x01, 0x02, 0x02, 0x01, 0x00];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0401),
+            error: HciError::BadLength(actual, expected),
+        })) => {
+            assert_eq!(actual, 7);
+            assert_eq!(expected, 5);
+        }
+        other => panic!("Did not get bad length: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "tolerant-decode")]
+fn gap_pairing_complete_with_padding() {
+    let buffer = [0x01, 0x04, 0x01, 0x02, 0x02, 0x01, 0x00, 0x00];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GapPairingComplete(evt)) => {
+            assert_eq!(evt.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(evt.status, GapPairingStatus::Failed);
+            assert_eq!(evt.reason, Some(PairingFailureReason::PasskeyEntryFailed));
+        }
+        other => panic!("Did not get GAP Pairing complete: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "tolerant-decode")]
+fn gap_pairing_complete_with_no_reason_and_padding() {
+    let buffer = [0x01, 0x04, 0x01, 0x02, 0x00, 0x00, 0xFF];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GapPairingComplete(evt)) => {
+            assert_eq!(evt.status, GapPairingStatus::Success);
+            assert_eq!(evt.reason, None);
         }
         other => panic!("Did not get GAP Pairing complete: {:?}", other),
     }
 }
 
 #[test]
+#[cfg(not(feature = "bluenrg-lp"))]
 fn gap_pairing_complete_failed() {
     let buffer = [0x01, 0x04, 0x01, 0x02, 0x03];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::BadGapPairingStatus(value))) => assert_eq!(value, 3),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0401),
+            error: HciError::Vendor(BlueNRGError::BadGapPairingStatus(value)),
+        })) => assert_eq!(value, 3),
         other => panic!("Did not get bad pairing status: {:?}", other),
     }
 }
@@ -509,8 +959,8 @@ fn gap_pairing_complete_failed() {
 fn gap_pass_key_request() {
     let buffer = [0x02, 0x04, 0x01, 0x02];
     match BlueNRGEvent::new(&buffer) {
-        Ok(BlueNRGEvent::GapPassKeyRequest(conn_handle)) => {
-            assert_eq!(conn_handle, ConnectionHandle(0x0201))
+        Ok(BlueNRGEvent::GapPassKeyRequest(event)) => {
+            assert_eq!(event.conn_handle, ConnectionHandle(0x0201))
         }
         other => panic!("Did not get GAP pass key request: {:?}", other),
     }
@@ -520,8 +970,8 @@ fn gap_pass_key_request() {
 fn gap_authorization_request() {
     let buffer = [0x03, 0x04, 0x01, 0x02];
     match BlueNRGEvent::new(&buffer) {
-        Ok(BlueNRGEvent::GapAuthorizationRequest(conn_handle)) => {
-            assert_eq!(conn_handle, ConnectionHandle(0x0201))
+        Ok(BlueNRGEvent::GapAuthorizationRequest(event)) => {
+            assert_eq!(event.conn_handle, ConnectionHandle(0x0201))
         }
         other => panic!("Did not get GAP authorization request: {:?}", other),
     }
@@ -546,6 +996,50 @@ fn gap_bond_lost() {
 }
 
 #[test]
+#[cfg(not(feature = "tolerant-decode"))]
+fn gap_bond_lost_failed_bad_length() {
+    let buffer = [0x05, 0x04, 0x01, 0x02];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0405),
+            error: HciError::BadLength(actual, expected),
+        })) => {
+            assert_eq!(actual, 4);
+            assert_eq!(expected, 2);
+        }
+        other => panic!("Did not get bad length: {:?}", other),
+    }
+}
+
+#[test]
+fn gap_numeric_comparison_value() {
+    let buffer = [0x09, 0x04, 0x01, 0x02, 0x3F, 0x42, 0x0F, 0x00];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GapNumericComparisonValue(event)) => {
+            assert_eq!(event.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(event.numeric_value, 999_999);
+        }
+        other => panic!("Did not get GAP numeric comparison value: {:?}", other),
+    }
+}
+
+#[test]
+fn gap_numeric_comparison_value_failed_bad_length() {
+    let buffer = [0x09, 0x04, 0x01, 0x02, 0x3F, 0x42, 0x0F];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0409),
+            error: HciError::BadLength(actual, expected),
+        })) => {
+            assert_eq!(actual, 7);
+            assert_eq!(expected, 8);
+        }
+        other => panic!("Did not get bad length: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(all(feature = "gap-central-events", not(feature = "bluenrg-lp")))]
 fn gap_device_found() {
     let buffer = [
         0x06, 0x04, 0x00, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 3, 0x01, 0x02, 0x03, 0x04,
@@ -562,12 +1056,184 @@ fn gap_device_found() {
 }
 
 #[test]
+fn gap_device_found_new() {
+    let event = GapDeviceFound::new(
+        GapDeviceFoundEvent::ScanResponse,
+        BdAddrType::Random(BdAddr([1, 2, 3, 4, 5, 6])),
+        &[2, 0x01, 0x06],
+        None,
+    )
+    .unwrap();
+    assert_eq!(event.event, GapDeviceFoundEvent::ScanResponse);
+    assert_eq!(event.data(), [2, 0x01, 0x06]);
+    assert_eq!(event.ad_structures().count(), 1);
+    assert_eq!(event.rssi, None);
+}
+
+#[test]
+#[cfg(not(feature = "extended-advertising"))]
+fn gap_device_found_new_data_too_long() {
+    assert!(GapDeviceFound::new(
+        GapDeviceFoundEvent::Advertisement,
+        BdAddrType::Public(BdAddr([1, 2, 3, 4, 5, 6])),
+        &[0; 32],
+        Some(-40),
+    )
+    .is_none());
+}
+
+#[test]
+fn gap_device_found_manufacturer_data() {
+    let event = GapDeviceFound::new(
+        GapDeviceFoundEvent::Advertisement,
+        BdAddrType::Public(BdAddr([1, 2, 3, 4, 5, 6])),
+        &[2, 0x01, 0x06, 6, 0xFF, 0x4C, 0x00, 0x02, 0x15, 0x01],
+        Some(-40),
+    )
+    .unwrap();
+    assert_eq!(
+        event.manufacturer_data(),
+        Some((0x004C, &[0x02, 0x15, 0x01][..]))
+    );
+}
+
+#[test]
+fn gap_device_found_no_manufacturer_data() {
+    let event = GapDeviceFound::new(
+        GapDeviceFoundEvent::Advertisement,
+        BdAddrType::Public(BdAddr([1, 2, 3, 4, 5, 6])),
+        &[2, 0x01, 0x06, 3, 0x09, b'h', b'i'],
+        Some(-40),
+    )
+    .unwrap();
+    assert_eq!(event.manufacturer_data(), None);
+}
+
+#[test]
+fn gap_device_found_appearance() {
+    let event = GapDeviceFound::new(
+        GapDeviceFoundEvent::Advertisement,
+        BdAddrType::Public(BdAddr([1, 2, 3, 4, 5, 6])),
+        &[2, 0x01, 0x06, 3, 0x19, 0x41, 0x03],
+        Some(-40),
+    )
+    .unwrap();
+    assert_eq!(event.appearance(), Some(Appearance::HeartRateBelt));
+}
+
+#[test]
+fn gap_device_found_complete_local_name() {
+    let event = GapDeviceFound::new(
+        GapDeviceFoundEvent::Advertisement,
+        BdAddrType::Public(BdAddr([1, 2, 3, 4, 5, 6])),
+        &[3, 0x08, b'd', b'e', 5, 0x09, b'd', b'e', b'v', b'1'],
+        Some(-40),
+    )
+    .unwrap();
+    assert_eq!(event.local_name(), Some("dev1"));
+}
+
+#[test]
+fn gap_device_found_shortened_local_name() {
+    let event = GapDeviceFound::new(
+        GapDeviceFoundEvent::Advertisement,
+        BdAddrType::Public(BdAddr([1, 2, 3, 4, 5, 6])),
+        &[2, 0x01, 0x06, 3, 0x08, b'd', b'e'],
+        Some(-40),
+    )
+    .unwrap();
+    assert_eq!(event.local_name(), Some("de"));
+}
+
+#[test]
+fn gap_device_found_local_name_invalid_utf8() {
+    let event = GapDeviceFound::new(
+        GapDeviceFoundEvent::Advertisement,
+        BdAddrType::Public(BdAddr([1, 2, 3, 4, 5, 6])),
+        &[3, 0x09, 0xC3, 0x28],
+        Some(-40),
+    )
+    .unwrap();
+    assert_eq!(event.local_name(), None);
+}
+
+#[test]
+fn gap_device_found_no_local_name() {
+    let event = GapDeviceFound::new(
+        GapDeviceFoundEvent::Advertisement,
+        BdAddrType::Public(BdAddr([1, 2, 3, 4, 5, 6])),
+        &[2, 0x01, 0x06],
+        Some(-40),
+    )
+    .unwrap();
+    assert_eq!(event.local_name(), None);
+}
+
+#[test]
+fn gap_device_found_service_uuids() {
+    let data = [
+        2, 0x01, 0x06, // Flags
+        5, 0x03, 0x0F, 0x18, 0x0A, 0x18, // Complete list of 16-bit UUIDs
+        17, 0x07, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C,
+        0x0D, 0x0E, 0x0F, // Complete list of 128-bit UUIDs
+    ];
+    let event = GapDeviceFound::new(
+        GapDeviceFoundEvent::Advertisement,
+        BdAddrType::Public(BdAddr([1, 2, 3, 4, 5, 6])),
+        &data,
+        Some(-40),
+    )
+    .unwrap();
+    let mut uuids = event.service_uuids();
+    assert_eq!(uuids.next(), Some(Uuid::Uuid16(0x180F)));
+    assert_eq!(uuids.next(), Some(Uuid::Uuid16(0x180A)));
+    assert_eq!(
+        uuids.next(),
+        Some(Uuid::Uuid128([
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D,
+            0x0E, 0x0F
+        ]))
+    );
+    assert_eq!(uuids.next(), None);
+}
+
+#[test]
+fn gap_device_found_service_uuids_32_bit() {
+    let event = GapDeviceFound::new(
+        GapDeviceFoundEvent::Advertisement,
+        BdAddrType::Public(BdAddr([1, 2, 3, 4, 5, 6])),
+        &[5, 0x04, 0x0F, 0x18, 0x00, 0x00],
+        Some(-40),
+    )
+    .unwrap();
+    let uuids: Vec<Uuid> = event.service_uuids().collect();
+    assert_eq!(uuids.len(), 1);
+    assert!(uuids[0].matches(&Uuid::Uuid16(0x180F)));
+}
+
+#[test]
+fn gap_device_found_manufacturer_data_too_short() {
+    let event = GapDeviceFound::new(
+        GapDeviceFoundEvent::Advertisement,
+        BdAddrType::Public(BdAddr([1, 2, 3, 4, 5, 6])),
+        &[2, 0xFF, 0x4C],
+        Some(-40),
+    )
+    .unwrap();
+    assert_eq!(event.manufacturer_data(), None);
+}
+
+#[test]
+#[cfg(all(feature = "gap-central-events", not(feature = "bluenrg-lp")))]
 fn gap_device_found_failure_bad_event() {
     let buffer = [
         0x06, 0x04, 0x05, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 3, 0x01, 0x02, 0x03, 0x04,
     ];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::BadGapDeviceFoundEvent(code))) => {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0406),
+            error: HciError::Vendor(BlueNRGError::BadGapDeviceFoundEvent(code)),
+        })) => {
             assert_eq!(code, 0x05);
         }
         other => panic!("Did not get bad GAP device found event: {:?}", other),
@@ -575,12 +1241,16 @@ fn gap_device_found_failure_bad_event() {
 }
 
 #[test]
+#[cfg(all(feature = "gap-central-events", not(feature = "bluenrg-lp")))]
 fn gap_device_found_failure_bad_bdaddr_type() {
     let buffer = [
         0x06, 0x04, 0x04, 0x02, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 3, 0x01, 0x02, 0x03, 0x04,
     ];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::BadGapBdAddrType(bdaddr_type))) => {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0406),
+            error: HciError::Vendor(BlueNRGError::BadGapBdAddrType(bdaddr_type)),
+        })) => {
             assert_eq!(bdaddr_type, 0x02);
         }
         other => panic!("Did not get bad GAP device found event: {:?}", other),
@@ -588,12 +1258,16 @@ fn gap_device_found_failure_bad_bdaddr_type() {
 }
 
 #[test]
+#[cfg(all(feature = "gap-central-events", not(feature = "bluenrg-lp")))]
 fn gap_device_found_failure_bad_data_length() {
     let buffer = [
         0x06, 0x04, 0x04, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 4, 0x01, 0x02, 0x03, 0x04,
     ];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::BadLength(actual, expected)) => {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0406),
+            error: HciError::BadLength(actual, expected),
+        })) => {
             assert_eq!(actual, buffer.len());
             assert_eq!(expected, buffer.len() + 1);
         }
@@ -602,6 +1276,7 @@ fn gap_device_found_failure_bad_data_length() {
 }
 
 #[test]
+#[cfg(all(feature = "gap-central-events", not(feature = "bluenrg-lp")))]
 fn gap_device_found_failure_bad_rssi() {
     let buffer = [
         0x06, 0x04, 0x00, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 3, 0x01, 0x02, 0x03, 0x7F,
@@ -618,26 +1293,196 @@ fn gap_device_found_failure_bad_rssi() {
 }
 
 #[test]
+#[cfg(all(feature = "gap-central-events", not(feature = "bluenrg-lp")))]
+fn gap_device_found_legacy_max_data() {
+    let mut buffer = [0; 43];
+    buffer[0] = 0x06;
+    buffer[1] = 0x04;
+    buffer[4..10].copy_from_slice(&[0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+    buffer[10] = 31;
+    // Flags, then manufacturer-specific data filling the rest of the 31 bytes.
+    buffer[11..14].copy_from_slice(&[2, 0x01, 0x06]);
+    buffer[14] = 27;
+    buffer[15] = 0xFF;
+    for (i, byte) in buffer[16..42].iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+    buffer[42] = 0xD8;
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GapDeviceFound(event)) => {
+            assert_eq!(event.data(), &buffer[11..42]);
+            assert_eq!(event.rssi, Some(-40));
+
+            let mut ad_structures = event.ad_structures();
+            assert_eq!(
+                ad_structures.next(),
+                Some(AdStructure {
+                    ad_type: 0x01,
+                    data: &[0x06],
+                })
+            );
+            assert_eq!(
+                ad_structures.next(),
+                Some(AdStructure {
+                    ad_type: 0xFF,
+                    data: &buffer[16..42],
+                })
+            );
+            assert_eq!(ad_structures.next(), None);
+        }
+        other => panic!("Did not get GAP Device found: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(all(feature = "gap-central-events", not(feature = "bluenrg-lp")))]
+fn gap_device_found_ad_structures_stop_at_padding() {
+    let buffer = [
+        0x06, 0x04, 0x00, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 6, 2, 0x01, 0x06, 0, 0, 0, 0xD8,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GapDeviceFound(event)) => {
+            let mut ad_structures = event.ad_structures();
+            assert_eq!(
+                ad_structures.next(),
+                Some(AdStructure {
+                    ad_type: 0x01,
+                    data: &[0x06],
+                })
+            );
+            assert_eq!(ad_structures.next(), None);
+        }
+        other => panic!("Did not get GAP Device found: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(all(feature = "gap-central-events", not(feature = "bluenrg-lp")))]
+fn gap_device_found_ad_structures_truncated() {
+    let buffer = [
+        0x06, 0x04, 0x00, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 5, 2, 0x01, 0x06, 3, 0x09, 0xD8,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GapDeviceFound(event)) => {
+            let mut ad_structures = event.ad_structures();
+            assert_eq!(
+                ad_structures.next(),
+                Some(AdStructure {
+                    ad_type: 0x01,
+                    data: &[0x06],
+                })
+            );
+            assert_eq!(ad_structures.next(), None);
+        }
+        other => panic!("Did not get GAP Device found: {:?}", other),
+    }
+}
+
+#[cfg(all(feature = "gap-central-events", not(feature = "bluenrg-lp")))]
+fn extended_device_found_buffer() -> [u8; 112] {
+    let mut buffer = [0; 112];
+    buffer[0] = 0x06;
+    buffer[1] = 0x04;
+    buffer[4..10].copy_from_slice(&[0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+    buffer[10] = 100;
+    // Four 25-byte AD structures of manufacturer-specific data.
+    for structure in 0..4 {
+        let start = 11 + 25 * structure;
+        buffer[start] = 24;
+        buffer[start + 1] = 0xFF;
+        for byte in buffer[start + 2..start + 25].iter_mut() {
+            *byte = structure as u8;
+        }
+    }
+    buffer[111] = 0xD8;
+    buffer
+}
+
+#[test]
+#[cfg(all(
+    feature = "gap-central-events",
+    feature = "extended-advertising",
+    not(feature = "bluenrg-lp")
+))]
+fn gap_device_found_extended_advertising() {
+    let buffer = extended_device_found_buffer();
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GapDeviceFound(event)) => {
+            assert_eq!(event.data(), &buffer[11..111]);
+            assert_eq!(event.rssi, Some(-40));
+            assert_eq!(event.ad_structures().count(), 4);
+            for (i, ad_structure) in event.ad_structures().enumerate() {
+                assert_eq!(ad_structure.ad_type, 0xFF);
+                assert_eq!(ad_structure.data, &[i as u8; 23][..]);
+            }
+        }
+        other => panic!("Did not get GAP Device found: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "gap-central-events",
+    not(feature = "extended-advertising"),
+    not(feature = "bluenrg-lp")
+))]
+fn gap_device_found_failure_extended_advertising() {
+    let buffer = extended_device_found_buffer();
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0406),
+            error: HciError::BadLength(actual, expected),
+        })) => {
+            assert_eq!(actual, 112);
+            assert_eq!(expected, 43);
+        }
+        other => panic!("Did not get bad GAP device found length: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(all(feature = "gap-central-events", not(feature = "bluenrg-lp")))]
+fn gap_device_found_device_key() {
+    let advertisement = [
+        0x06, 0x04, 0x00, 0x01, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 3, 0x01, 0x02, 0x03, 0xD8,
+    ];
+    let scan_response = [
+        0x06, 0x04, 0x04, 0x01, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 1, 0x09, 0xC4,
+    ];
+    let other_type = [
+        0x06, 0x04, 0x00, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0, 0xD8,
+    ];
+    let key = |buffer: &[u8]| match BlueNRGEvent::new(buffer) {
+        Ok(BlueNRGEvent::GapDeviceFound(event)) => event.device_key(),
+        other => panic!("Did not get GAP Device found: {:?}", other),
+    };
+    assert_eq!(key(&advertisement), (1, [1, 2, 3, 4, 5, 6]));
+    assert_eq!(key(&advertisement), key(&scan_response));
+    assert_eq!(key(&other_type), (0, [1, 2, 3, 4, 5, 6]));
+}
+
+#[test]
+#[cfg(feature = "gap-central-events")]
 fn gap_procedure_complete() {
     let buffer = [0x07, 0x04, 0x01, 0x00];
     match BlueNRGEvent::new(&buffer) {
         Ok(BlueNRGEvent::GapProcedureComplete(evt)) => {
             assert_eq!(evt.procedure, GapProcedure::LimitedDiscovery);
             assert_eq!(evt.status, GapProcedureStatus::Success);
+            assert_eq!(evt.reconnection_address(), None);
         }
         other => panic!("Did not get GAP Procedure Complete: {:?}", other),
     }
 }
 
 #[test]
+#[cfg(feature = "gap-central-events")]
 fn gap_procedure_complete_name_discovery() {
     let buffer = [0x07, 0x04, 0x04, 0x00, 0x41, 0x42, 0x43];
     match BlueNRGEvent::new(&buffer) {
         Ok(BlueNRGEvent::GapProcedureComplete(evt)) => {
-            let mut name = NameBuffer([0; MAX_NAME_LEN]);
-            name.0[0] = 0x41;
-            name.0[1] = 0x42;
-            name.0[2] = 0x43;
+            let mut name = NameBuffer::new([0; MAX_NAME_LEN]);
+            name[..3].copy_from_slice(b"ABC");
             let name = name;
             assert_eq!(evt.procedure, GapProcedure::NameDiscovery(3, name));
             assert_eq!(evt.status, GapProcedureStatus::Success);
@@ -647,6 +1492,27 @@ fn gap_procedure_complete_name_discovery() {
 }
 
 #[test]
+#[cfg(feature = "gap-central-events")]
+fn gap_procedure_complete_name_discovery_failed_name_too_long() {
+    let mut buffer = [0; 253];
+    buffer[0] = 0x07;
+    buffer[1] = 0x04;
+    buffer[2] = 0x04;
+    let buffer = buffer;
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0407),
+            error: HciError::BadLength(actual, expected),
+        })) => {
+            assert_eq!(actual, 253);
+            assert_eq!(expected, 4 + MAX_NAME_LEN);
+        }
+        other => panic!("Did not get bad length: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "gap-central-events")]
 fn gap_procedure_complete_general_connection_establishment() {
     let buffer = [0x07, 0x04, 0x10, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06];
     match BlueNRGEvent::new(&buffer) {
@@ -656,36 +1522,131 @@ fn gap_procedure_complete_general_connection_establishment() {
                 GapProcedure::GeneralConnectionEstablishment(BdAddr([1, 2, 3, 4, 5, 6]))
             );
             assert_eq!(evt.status, GapProcedureStatus::Success);
+            assert_eq!(
+                evt.reconnection_address(),
+                Some(&BdAddr([1, 2, 3, 4, 5, 6]))
+            );
+        }
+        other => panic!("Did not get GAP Procedure Complete: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "gap-central-events")]
+fn gap_procedure_complete_multiple_procedures() {
+    let buffer = [0x07, 0x04, 0x41, 0x00];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GapProcedureComplete(evt)) => {
+            assert_eq!(
+                evt.procedure,
+                GapProcedure::Multiple(
+                    GapProcedureFlags::LIMITED_DISCOVERY
+                        | GapProcedureFlags::DIRECT_CONNECTION_ESTABLISHMENT
+                )
+            );
+            assert_eq!(evt.status, GapProcedureStatus::Success);
+            assert_eq!(evt.reconnection_address(), None);
         }
         other => panic!("Did not get GAP Procedure Complete: {:?}", other),
     }
 }
 
 #[test]
+#[cfg(feature = "gap-central-events")]
 fn gap_procedure_complete_failed_bad_procedure() {
-    let buffer = [0x07, 0x04, 0x03, 0x00];
+    let buffer = [0x07, 0x04, 0x00, 0x00];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::BadGapProcedure(code))) => assert_eq!(code, 0x03),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0407),
+            error: HciError::Vendor(BlueNRGError::BadGapProcedure(code)),
+        })) => assert_eq!(code, 0x00),
         other => panic!("Did not get bad GAP Procedure code: {:?}", other),
     }
 }
 
 #[test]
-fn gap_procedure_complete_failed_bad_status() {
-    let buffer = [0x07, 0x04, 0x02, 0x01];
+#[cfg(feature = "gap-central-events")]
+fn gap_procedure_complete_multiple_with_observation() {
+    let buffer = [0x07, 0x04, 0x82, 0x00];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::BadGapProcedureStatus(code))) => assert_eq!(code, 0x01),
-        other => panic!("Did not get bad GAP Procedure status: {:?}", other),
+        Ok(BlueNRGEvent::GapProcedureComplete(evt)) => assert_eq!(
+            evt.procedure,
+            GapProcedure::Multiple(
+                GapProcedureFlags::GENERAL_DISCOVERY | GapProcedureFlags::OBSERVATION
+            )
+        ),
+        other => panic!("Did not get GAP Procedure Complete: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "gap-central-events")]
+fn gap_procedure_complete_multiple_with_observation_and_limited_discovery() {
+    let buffer = [0x07, 0x04, 0x81, 0x00];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GapProcedureComplete(evt)) => assert_eq!(
+            evt.procedure,
+            GapProcedure::Multiple(
+                GapProcedureFlags::LIMITED_DISCOVERY | GapProcedureFlags::OBSERVATION
+            )
+        ),
+        other => panic!("Did not get GAP Procedure Complete: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "gap-central-events")]
+fn gap_procedure_complete_failed_observation_alone() {
+    let buffer = [0x07, 0x04, 0x80, 0x00];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0407),
+            error: HciError::Vendor(BlueNRGError::BadGapProcedure(code)),
+        })) => assert_eq!(code, 0x80),
+        other => panic!("Did not get bad GAP Procedure code: {:?}", other),
     }
 }
 
 #[test]
+#[cfg(feature = "gap-central-events")]
+fn gap_procedure_complete_other_status() {
+    let buffer = [0x07, 0x04, 0x02, 0x1F];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GapProcedureComplete(evt)) => {
+            assert_eq!(evt.status, GapProcedureStatus::Other(0x1F));
+            assert_eq!(evt.status.raw(), 0x1F);
+            assert_eq!(
+                evt.status.strict(),
+                Err(BlueNRGError::BadGapProcedureStatus(0x1F))
+            );
+        }
+        other => panic!("Did not get GAP Procedure Complete: {:?}", other),
+    }
+}
+
+#[test]
+fn gap_procedure_status_strict() {
+    assert_eq!(
+        GapProcedureStatus::from_raw(0x05).strict(),
+        Ok(GapProcedureStatus::AuthFailure)
+    );
+    assert_eq!(
+        GapProcedureStatus::try_from(0x01),
+        Err(BlueNRGError::BadGapProcedureStatus(0x01))
+    );
+}
+
+#[test]
+#[cfg(all(feature = "gap-central-events", not(feature = "tolerant-decode")))]
 fn gap_procedure_complete_failed_general_connection_establishment_length() {
     let buffer = [
         0x07, 0x04, 0x10, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
     ];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::BadLength(11, 10)) => (),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0407),
+            error: HciError::BadLength(11, 10),
+        })) => (),
         other => panic!("Did not get bad length: {:?}", other),
     }
 }
@@ -695,14 +1656,14 @@ fn gap_procedure_complete_failed_general_connection_establishment_length() {
 fn gap_addr_not_resolved() {
     let buffer = [0x08, 0x04, 0x01, 0x02];
     match BlueNRGEvent::new(&buffer) {
-        Ok(BlueNRGEvent::GapAddressNotResolved(conn_handle)) => {
-            assert_eq!(conn_handle, ConnectionHandle(0x0201))
+        Ok(BlueNRGEvent::GapAddressNotResolved(event)) => {
+            assert_eq!(event.conn_handle, ConnectionHandle(0x0201))
         }
         other => panic!("Did not get Address not Resolved event: {:?}", other),
     }
 }
 
-#[cfg(not(feature = "ms"))]
+#[cfg(all(not(feature = "ms"), feature = "gap-central-events"))]
 #[test]
 fn gap_addr_not_resolved() {
     let buffer = [0x08, 0x04, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06];
@@ -714,7 +1675,7 @@ fn gap_addr_not_resolved() {
     }
 }
 
-#[cfg(feature = "ms")]
+#[cfg(all(feature = "ms", not(feature = "bluenrg-lp")))]
 #[test]
 fn gatt_attribute_modified() {
     let buffer = [
@@ -724,22 +1685,25 @@ fn gatt_attribute_modified() {
         Ok(BlueNRGEvent::GattAttributeModified(event)) => {
             assert_eq!(event.conn_handle, ConnectionHandle(0x0201));
             assert_eq!(event.attr_handle, AttributeHandle(0x0403));
-            assert_eq!(event.offset, 0x0605);
-            assert_eq!(event.continued, true);
+            assert_eq!(event.offset, Some(0x0605));
+            assert_eq!(event.continued, Some(true));
             assert_eq!(event.data(), [0x07, 0x08]);
         }
         other => panic!("Did not get Gatt attribute modified: {:?}", other),
     }
 }
 
-#[cfg(feature = "ms")]
+#[cfg(all(feature = "ms", not(feature = "bluenrg-lp")))]
 #[test]
 fn gatt_attribute_modified_failed_bad_data_len() {
     let buffer = [
         0x01, 0x0C, 0x01, 0x02, 0x03, 0x04, 0x03, 0x05, 0x06, 0x07, 0x08,
     ];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::BadLength(actual, expected)) => {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C01),
+            error: HciError::BadLength(actual, expected),
+        })) => {
             assert_eq!(actual, buffer.len());
             assert_eq!(expected, buffer.len() + 1);
         }
@@ -755,18 +1719,90 @@ fn gatt_attribute_modified() {
         Ok(BlueNRGEvent::GattAttributeModified(event)) => {
             assert_eq!(event.conn_handle, ConnectionHandle(0x0201));
             assert_eq!(event.attr_handle, AttributeHandle(0x0403));
+            assert_eq!(event.offset, None);
+            assert_eq!(event.continued, None);
             assert_eq!(event.data(), [0x07, 0x08]);
         }
         other => panic!("Did not get Gatt attribute modified: {:?}", other),
     }
 }
 
+#[cfg(all(feature = "ms", not(feature = "bluenrg-lp")))]
+#[test]
+fn gatt_attribute_modified_cccd_write() {
+    let buffer = [
+        0x01, 0x0C, 0x01, 0x02, 0x03, 0x04, 0x02, 0x00, 0x00, 0x02, 0x00,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GattAttributeModified(event)) => {
+            let cccd = event.as_cccd_write().unwrap();
+            assert!(!cccd.notifications_enabled());
+            assert!(cccd.indications_enabled());
+        }
+        other => panic!("Did not get Gatt attribute modified: {:?}", other),
+    }
+
+    // A write at a nonzero offset is not a complete CCCD value.
+    let buffer = [
+        0x01, 0x0C, 0x01, 0x02, 0x03, 0x04, 0x02, 0x01, 0x00, 0x02, 0x00,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GattAttributeModified(event)) => assert_eq!(event.as_cccd_write(), None),
+        other => panic!("Did not get Gatt attribute modified: {:?}", other),
+    }
+}
+
+#[cfg(not(feature = "ms"))]
+#[test]
+fn gatt_attribute_modified_cccd_write() {
+    let buffer = [0x01, 0x0C, 0x01, 0x02, 0x03, 0x04, 0x02, 0x01, 0x00];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GattAttributeModified(event)) => {
+            let cccd = event.as_cccd_write().unwrap();
+            assert!(cccd.notifications_enabled());
+            assert!(!cccd.indications_enabled());
+        }
+        other => panic!("Did not get Gatt attribute modified: {:?}", other),
+    }
+}
+
+#[test]
+fn cccd_value() {
+    let disabled = CccdValue::from_bytes(&[0x00, 0x00]).unwrap();
+    assert!(!disabled.notifications_enabled());
+    assert!(!disabled.indications_enabled());
+
+    let notify = CccdValue::from_bytes(&[0x01, 0x00]).unwrap();
+    assert!(notify.notifications_enabled());
+    assert!(!notify.indications_enabled());
+
+    let indicate = CccdValue::from_bytes(&[0x02, 0x00]).unwrap();
+    assert!(!indicate.notifications_enabled());
+    assert!(indicate.indications_enabled());
+
+    let both = CccdValue::from_bytes(&[0x03, 0x00]).unwrap();
+    assert!(both.notifications_enabled());
+    assert!(both.indications_enabled());
+}
+
+#[test]
+fn cccd_value_failed_bad_length() {
+    assert_eq!(CccdValue::from_bytes(&[0x01]), Err(BadCccdLength(1)));
+    assert_eq!(
+        CccdValue::from_bytes(&[0x01, 0x00, 0x00]),
+        Err(BadCccdLength(3))
+    );
+}
+
 #[cfg(not(feature = "ms"))]
 #[test]
 fn gatt_attribute_modified_failed_bad_data_len() {
     let buffer = [0x01, 0x0C, 0x01, 0x02, 0x03, 0x04, 0x03, 0x07, 0x08];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::BadLength(actual, expected)) => {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C01),
+            error: HciError::BadLength(actual, expected),
+        })) => {
             assert_eq!(actual, buffer.len());
             assert_eq!(expected, buffer.len() + 1);
         }
@@ -774,18 +1810,55 @@ fn gatt_attribute_modified_failed_bad_data_len() {
     }
 }
 
+#[cfg(all(
+    feature = "ms",
+    not(feature = "bluenrg-lp"),
+    feature = "att-client-events"
+))]
+#[test]
+fn attribute_events() {
+    fn summary<T: AttributeEvent>(event: &T) -> (ConnectionHandle, AttributeHandle, Vec<u8>) {
+        (
+            event.conn_handle(),
+            event.attribute_handle(),
+            event.value().to_vec(),
+        )
+    }
+
+    let modified = [
+        0x01, 0x0C, 0x01, 0x02, 0x03, 0x04, 0x02, 0x00, 0x00, 0x07, 0x08,
+    ];
+    let notification = [0x0F, 0x0C, 0x01, 0x02, 4, 0x03, 0x04, 0x07, 0x08];
+    let expected = (
+        ConnectionHandle(0x0201),
+        AttributeHandle(0x0403),
+        vec![0x07, 0x08],
+    );
+    for buffer in [&modified[..], &notification[..]].iter() {
+        match BlueNRGEvent::new(buffer) {
+            Ok(BlueNRGEvent::GattAttributeModified(event)) => {
+                assert_eq!(summary(&event), expected)
+            }
+            Ok(BlueNRGEvent::GattNotification(event)) => assert_eq!(summary(&event), expected),
+            other => panic!("Did not get an attribute event: {:?}", other),
+        }
+    }
+}
+
 #[test]
+#[cfg(feature = "att-client-events")]
 fn gatt_procedure_timeout() {
     let buffer = [0x02, 0x0C, 0x01, 0x02];
     match BlueNRGEvent::new(&buffer) {
-        Ok(BlueNRGEvent::GattProcedureTimeout(conn_handle)) => {
-            assert_eq!(conn_handle, ConnectionHandle(0x0201));
+        Ok(BlueNRGEvent::GattProcedureTimeout(event)) => {
+            assert_eq!(event.conn_handle, ConnectionHandle(0x0201));
         }
         other => panic!("Did not get GATT procedure timeout: {:?}", other),
     }
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn att_exchange_mtu_response() {
     let buffer = [0x03, 0x0C, 0x01, 0x02, 0x01, 0x03, 0x04];
     match BlueNRGEvent::new(&buffer) {
@@ -798,6 +1871,7 @@ fn att_exchange_mtu_response() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn att_find_information_response_16bit_uuids() {
     let buffer = [
         0x04, 0x0C, 0x01, 0x02, 13, 1, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c,
@@ -806,6 +1880,7 @@ fn att_find_information_response_16bit_uuids() {
     match BlueNRGEvent::new(&buffer) {
         Ok(BlueNRGEvent::AttFindInformationResponse(event)) => {
             assert_eq!(event.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(event.len(), 3);
             if let HandleUuidPairIterator::Format16(mut iter) = event.handle_uuid_pair_iter() {
                 let actual = iter.next().unwrap();
                 assert_eq!(actual.handle, AttributeHandle(0x0403));
@@ -832,6 +1907,59 @@ fn att_find_information_response_16bit_uuids() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
+fn att_find_information_response_for_loop() {
+    let buffer = [
+        0x04, 0x0C, 0x01, 0x02, 9, 1, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttFindInformationResponse(event)) => {
+            let mut pairs = Vec::new();
+            for (handle, uuid) in &event {
+                pairs.push((handle, uuid));
+            }
+            assert_eq!(
+                pairs,
+                [
+                    (AttributeHandle(0x0403), Uuid::Uuid16(0x0605)),
+                    (AttributeHandle(0x0807), Uuid::Uuid16(0x0a09)),
+                ]
+            );
+        }
+        other => panic!("Did not get ATT find info response: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "att-client-events")]
+fn att_find_information_response_for_loop_128bit_uuids() {
+    let buffer = [
+        0x04, 0x0C, 0x01, 0x02, 19, 2, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c,
+        0x0d, 0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13, 0x14,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttFindInformationResponse(event)) => {
+            let mut pairs = Vec::new();
+            for (handle, uuid) in &event {
+                pairs.push((handle, uuid));
+            }
+            assert_eq!(
+                pairs,
+                [(
+                    AttributeHandle(0x0403),
+                    Uuid::Uuid128([
+                        0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10,
+                        0x11, 0x12, 0x13, 0x14,
+                    ])
+                )]
+            );
+        }
+        other => panic!("Did not get ATT find info response: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "att-client-events")]
 fn att_find_information_response_128bit_uuids() {
     let buffer = [
         0x04, 0x0C, 0x01, 0x02, 37, 2, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c,
@@ -875,21 +2003,29 @@ fn att_find_information_response_128bit_uuids() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn att_find_information_response_failed_format() {
     let buffer = [0x04, 0x0C, 0x01, 0x02, 1, 3];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::BadAttFindInformationResponseFormat(3))) => (),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C04),
+            error: HciError::Vendor(BlueNRGError::BadAttFindInformationResponseFormat(3)),
+        })) => (),
         other => panic!("Did not get bad ATT Find info response format: {:?}", other),
     }
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn att_find_information_response_failed_partial_uuid() {
     let buffer = [
         0x04, 0x0C, 0x01, 0x02, 11, 1, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c,
     ];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::AttFindInformationResponsePartialPair16)) => (),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C04),
+            error: HciError::Vendor(BlueNRGError::AttFindInformationResponsePartialPair16),
+        })) => (),
         other => panic!(
             "Did not get bad ATT Find info response partial pair: {:?}",
             other
@@ -898,6 +2034,7 @@ fn att_find_information_response_failed_partial_uuid() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn att_find_by_type_value_response() {
     let buffer = [
         0x05, 0x0C, 0x01, 0x02, 8, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
@@ -906,6 +2043,8 @@ fn att_find_by_type_value_response() {
         Ok(BlueNRGEvent::AttFindByTypeValueResponse(event)) => {
             assert_eq!(event.conn_handle, ConnectionHandle(0x0201));
 
+            assert_eq!(event.len(), 2);
+            assert!(!event.is_empty());
             assert_eq!(event.handle_pairs_iter().count(), 2);
             for (actual, expected) in event.handle_pairs_iter().zip(&[
                 HandleInfoPair {
@@ -926,12 +2065,31 @@ fn att_find_by_type_value_response() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
+fn att_find_by_type_value_response_no_matches() {
+    let buffer = [0x05, 0x0C, 0x01, 0x02, 0];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttFindByTypeValueResponse(event)) => {
+            assert_eq!(event.conn_handle, ConnectionHandle(0x0201));
+            assert!(event.is_empty());
+            assert_eq!(event.len(), 0);
+            assert_eq!(event.handle_pairs_iter().count(), 0);
+        }
+        other => panic!("Did not get find-by-type-value response: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "att-client-events")]
 fn att_find_by_type_value_response_failed_partial_pair() {
     let buffer = [
         0x05, 0x0C, 0x01, 0x02, 7, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
     ];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::AttFindByTypeValuePartial)) => (),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C05),
+            error: HciError::Vendor(BlueNRGError::AttFindByTypeValuePartial),
+        })) => (),
         other => panic!(
             "Did not get find-by-type-value response failure: {:?}",
             other
@@ -940,6 +2098,7 @@ fn att_find_by_type_value_response_failed_partial_pair() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn att_read_by_type_response() {
     let buffer = [
         0x06, 0x0C, 0x01, 0x02, 13, 6, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x11, 0x12, 0x13, 0x14,
@@ -968,18 +2127,101 @@ fn att_read_by_type_response() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
+fn att_read_by_type_response_for_loop() {
+    let buffer = [
+        0x06, 0x0C, 0x01, 0x02, 13, 6, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x11, 0x12, 0x13, 0x14,
+        0x15, 0x16,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttReadByTypeResponse(event)) => {
+            let mut handles = Vec::new();
+            for pair in &event {
+                handles.push(pair.handle);
+            }
+            assert_eq!(handles, [AttributeHandle(0x0201), AttributeHandle(0x1211)]);
+        }
+        other => panic!("Did not get read-by-type response: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "att-client-events")]
+fn att_read_by_type_response_pair_count() {
+    let buffer = [
+        0x06, 0x0C, 0x01, 0x02, 13, 4, 0x01, 0x02, 0x03, 0x04, 0x11, 0x12, 0x13, 0x14, 0x21, 0x22,
+        0x23, 0x24,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttReadByTypeResponse(event)) => {
+            assert_eq!(event.pair_count(), 3);
+            assert_eq!(event.pair_count(), event.handle_value_pair_iter().count());
+        }
+        other => panic!("Did not get read-by-type response: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "att-client-events")]
+fn att_read_by_type_response_fills_event() {
+    // One handle-value pair that fills the largest event.
+    let mut buffer = vec![0x06, 0x0C, 0x01, 0x02, 250, 249];
+    buffer.extend((0..249).map(|i| i as u8));
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttReadByTypeResponse(event)) => {
+            let pair = event.handle_value_pair_iter().next().unwrap();
+            assert_eq!(pair.handle, AttributeHandle(0x0100));
+            assert_eq!(pair.value.len(), 247);
+            assert_eq!(pair.value[246], 248);
+        }
+        other => panic!("Did not get read-by-type response: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "att-client-events")]
 fn att_read_by_type_response_failed_partial_pair() {
     let buffer = [
         0x06, 0x0C, 0x01, 0x02, 12, 6, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x11, 0x12, 0x13, 0x14,
         0x15,
     ];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::AttReadByTypeResponsePartial)) => (),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C06),
+            error: HciError::Vendor(BlueNRGError::AttReadByTypeResponsePartial),
+        })) => (),
         other => panic!("Did not get partial read-by-type response: {:?}", other),
     }
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
+fn att_read_by_type_response_failed_short_pair_len() {
+    let buffer = [0x06, 0x0C, 0x01, 0x02, 3, 1, 0x01, 0x02];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C06),
+            error: HciError::Vendor(BlueNRGError::BadAttReadByTypeResponsePairLength(1)),
+        })) => (),
+        other => panic!("Did not get bad read-by-type pair length: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "att-client-events")]
+fn att_read_by_type_response_failed_zero_pair_len() {
+    let buffer = [0x06, 0x0C, 0x01, 0x02, 1, 0];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C06),
+            error: HciError::Vendor(BlueNRGError::BadAttReadByTypeResponsePairLength(0)),
+        })) => (),
+        other => panic!("Did not get bad read-by-type pair length: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "att-client-events")]
 fn att_read_response() {
     let buffer = [0x07, 0x0C, 0x01, 0x02, 4, 0x01, 0x02, 0x03, 0x04];
     match BlueNRGEvent::new(&buffer) {
@@ -992,6 +2234,23 @@ fn att_read_response() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
+fn att_read_response_truncated() {
+    let mut buffer = [0; 27];
+    buffer[0..5].copy_from_slice(&[0x07, 0x0C, 0x01, 0x02, 22]);
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttReadResponse(event)) => {
+            assert_eq!(event.value().len(), 22);
+            assert!(event.was_truncated(23));
+            assert!(!event.was_truncated(24));
+            assert!(!event.was_truncated(247));
+        }
+        other => panic!("Did not get ATT read response: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "att-client-events")]
 fn att_read_response_empty() {
     let buffer = [0x07, 0x0C, 0x01, 0x02, 0];
     match BlueNRGEvent::new(&buffer) {
@@ -1004,10 +2263,14 @@ fn att_read_response_empty() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn att_read_response_failed() {
     let buffer = [0x07, 0x0C, 0x01, 0x02, 3, 0x01, 0x02, 0x03, 0x04];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::BadLength(actual, expected)) => {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C07),
+            error: HciError::BadLength(actual, expected),
+        })) => {
             assert_eq!(actual, buffer.len());
             assert_eq!(expected, buffer.len() - 1);
         }
@@ -1016,6 +2279,7 @@ fn att_read_response_failed() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn att_read_blob_response() {
     let buffer = [0x08, 0x0C, 0x01, 0x02, 4, 0x01, 0x02, 0x03, 0x04];
     match BlueNRGEvent::new(&buffer) {
@@ -1028,6 +2292,7 @@ fn att_read_blob_response() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn att_read_blob_response_empty() {
     let buffer = [0x08, 0x0C, 0x01, 0x02, 0];
     match BlueNRGEvent::new(&buffer) {
@@ -1040,10 +2305,14 @@ fn att_read_blob_response_empty() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn att_read_blob_response_failed() {
     let buffer = [0x08, 0x0C, 0x01, 0x02, 2, 0x01];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::BadLength(actual, expected)) => {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C08),
+            error: HciError::BadLength(actual, expected),
+        })) => {
             assert_eq!(actual, buffer.len());
             assert_eq!(expected, buffer.len() + 1);
         }
@@ -1052,6 +2321,7 @@ fn att_read_blob_response_failed() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn att_read_multiple_response() {
     let buffer = [0x09, 0x0C, 0x01, 0x02, 4, 0x01, 0x02, 0x03, 0x04];
     match BlueNRGEvent::new(&buffer) {
@@ -1064,6 +2334,7 @@ fn att_read_multiple_response() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn att_read_multiple_response_empty() {
     let buffer = [0x09, 0x0C, 0x01, 0x02, 0];
     match BlueNRGEvent::new(&buffer) {
@@ -1076,10 +2347,14 @@ fn att_read_multiple_response_empty() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn att_read_multiple_response_failed() {
     let buffer = [0x09, 0x0C, 0x01, 0x02, 2, 0x01];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::BadLength(actual, expected)) => {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C09),
+            error: HciError::BadLength(actual, expected),
+        })) => {
             assert_eq!(actual, buffer.len());
             assert_eq!(expected, buffer.len() + 1);
         }
@@ -1088,6 +2363,7 @@ fn att_read_multiple_response_failed() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn att_read_by_group_type_response() {
     let buffer = [
         0x0A, 0x0C, 0x01, 0x02, 17, 8, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x11, 0x12,
@@ -1096,6 +2372,7 @@ fn att_read_by_group_type_response() {
     match BlueNRGEvent::new(&buffer) {
         Ok(BlueNRGEvent::AttReadByGroupTypeResponse(event)) => {
             assert_eq!(event.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(event.len(), 2);
 
             let mut iter = event.attribute_data_iter();
             let actual = iter.next().unwrap();
@@ -1118,13 +2395,17 @@ fn att_read_by_group_type_response() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn att_read_by_group_type_response_failed() {
     let buffer = [
         0x0A, 0x0C, 0x01, 0x02, 16, 8, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x11, 0x12,
         0x13, 0x14, 0x15, 0x16, 0x17,
     ];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::AttReadByGroupTypeResponsePartial)) => (),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C0A),
+            error: HciError::Vendor(BlueNRGError::AttReadByGroupTypeResponsePartial),
+        })) => (),
         other => panic!(
             "Did not get partial Read by Group Type Response: {:?}",
             other
@@ -1133,6 +2414,39 @@ fn att_read_by_group_type_response_failed() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
+fn att_read_by_group_type_response_failed_zero_group_len() {
+    let buffer = [0x0A, 0x0C, 0x01, 0x02, 1, 0];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C0A),
+            error: HciError::Vendor(BlueNRGError::BadAttReadByGroupTypeResponseGroupLength(0)),
+        })) => (),
+        other => panic!(
+            "Did not get bad read-by-group-type group length: {:?}",
+            other
+        ),
+    }
+}
+
+#[test]
+#[cfg(feature = "att-client-events")]
+fn att_read_by_group_type_response_failed_short_group_len() {
+    let buffer = [0x0A, 0x0C, 0x01, 0x02, 4, 3, 0x01, 0x02, 0x03];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C0A),
+            error: HciError::Vendor(BlueNRGError::BadAttReadByGroupTypeResponseGroupLength(3)),
+        })) => (),
+        other => panic!(
+            "Did not get bad read-by-group-type group length: {:?}",
+            other
+        ),
+    }
+}
+
+#[test]
+#[cfg(feature = "att-client-events")]
 fn att_prepare_write_response() {
     let buffer = [
         0x0C, 0x0C, 0x01, 0x02, 8, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a,
@@ -1149,6 +2463,7 @@ fn att_prepare_write_response() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn att_prepare_write_response_empty() {
     let buffer = [0x0C, 0x0C, 0x01, 0x02, 4, 0x03, 0x04, 0x05, 0x06];
     match BlueNRGEvent::new(&buffer) {
@@ -1163,17 +2478,19 @@ fn att_prepare_write_response_empty() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn att_execute_write_response() {
     let buffer = [0x0D, 0x0C, 0x01, 0x02, 0];
     match BlueNRGEvent::new(&buffer) {
-        Ok(BlueNRGEvent::AttExecuteWriteResponse(conn_handle)) => {
-            assert_eq!(conn_handle, ConnectionHandle(0x0201));
+        Ok(BlueNRGEvent::AttExecuteWriteResponse(event)) => {
+            assert_eq!(event.conn_handle, ConnectionHandle(0x0201));
         }
         other => panic!("Did not get ATT Execute Write Response: {:?}", other),
     }
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn gatt_indication() {
     let buffer = [
         0x0E, 0x0C, 0x01, 0x02, 6, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
@@ -1189,6 +2506,7 @@ fn gatt_indication() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn gatt_indication_empty() {
     let buffer = [0x0E, 0x0C, 0x01, 0x02, 2, 0x03, 0x04];
     match BlueNRGEvent::new(&buffer) {
@@ -1202,6 +2520,7 @@ fn gatt_indication_empty() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn gatt_notification() {
     let buffer = [
         0x0F, 0x0C, 0x01, 0x02, 6, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
@@ -1217,6 +2536,7 @@ fn gatt_notification() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn gatt_notification_empty() {
     let buffer = [0x0F, 0x0C, 0x01, 0x02, 2, 0x03, 0x04];
     match BlueNRGEvent::new(&buffer) {
@@ -1230,6 +2550,7 @@ fn gatt_notification_empty() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn gatt_procedure_complete_success() {
     let buffer = [0x10, 0x0C, 0x01, 0x02, 1, 0];
     match BlueNRGEvent::new(&buffer) {
@@ -1242,6 +2563,7 @@ fn gatt_procedure_complete_success() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn gatt_procedure_complete_failed() {
     let buffer = [0x10, 0x0C, 0x01, 0x02, 1, 0x41];
     match BlueNRGEvent::new(&buffer) {
@@ -1254,17 +2576,36 @@ fn gatt_procedure_complete_failed() {
 }
 
 #[test]
-fn gatt_procedure_complete_error_unknown_code() {
-    let buffer = [0x10, 0x0C, 0x01, 0x02, 1, 0x40];
+#[cfg(feature = "att-client-events")]
+fn gatt_procedure_complete_other_status() {
+    let buffer = [0x10, 0x0C, 0x01, 0x02, 1, 0x3E];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::BadGattProcedureStatus(code))) => {
-            assert_eq!(code, 0x40);
+        Ok(BlueNRGEvent::GattProcedureComplete(event)) => {
+            assert_eq!(event.status, GattProcedureStatus::Other(0x3E));
+            assert_eq!(event.status.raw(), 0x3E);
+            assert_eq!(
+                event.status.strict(),
+                Err(BlueNRGError::BadGattProcedureStatus(0x3E))
+            );
         }
-        other => panic!("Did not get Bad GATT Procedure Status: {:?}", other),
+        other => panic!("Did not get GATT Procedure Complete: {:?}", other),
     }
 }
 
 #[test]
+fn gatt_procedure_status_strict() {
+    assert_eq!(
+        GattProcedureStatus::from_raw(0x41).strict(),
+        Ok(GattProcedureStatus::Failed)
+    );
+    assert_eq!(
+        GattProcedureStatus::try_from(0x40),
+        Err(BlueNRGError::BadGattProcedureStatus(0x40))
+    );
+}
+
+#[test]
+#[cfg(feature = "att-client-events")]
 fn att_error_response() {
     let buffer = [0x11, 0x0C, 0x01, 0x02, 4, 0x03, 0x04, 0x05, 0x07];
     match BlueNRGEvent::new(&buffer) {
@@ -1279,10 +2620,14 @@ fn att_error_response() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn att_error_response_failed_bad_request_opcode() {
     let buffer = [0x11, 0x0C, 0x01, 0x02, 4, 0x48, 0x04, 0x05, 0x07];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::BadAttRequestOpcode(code))) => {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C11),
+            error: HciError::Vendor(BlueNRGError::BadAttRequestOpcode(code)),
+        })) => {
             assert_eq!(code, 0x48);
         }
         other => panic!("Did not get bad ATT request opcode: {:?}", other),
@@ -1290,10 +2635,14 @@ fn att_error_response_failed_bad_request_opcode() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn att_error_response_failed_bad_error_code() {
     let buffer = [0x11, 0x0C, 0x01, 0x02, 4, 0x03, 0x04, 0x05, 0x12];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::BadAttError(code))) => {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C11),
+            error: HciError::Vendor(BlueNRGError::BadAttError(code)),
+        })) => {
             assert_eq!(code, 0x12);
         }
         other => panic!("Did not get bad ATT error code: {:?}", other),
@@ -1301,6 +2650,7 @@ fn att_error_response_failed_bad_error_code() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn gatt_discover_or_read_characteristic_by_uuid_response() {
     let buffer = [
         0x12, 0x0C, 0x01, 0x02, 6, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
@@ -1316,6 +2666,7 @@ fn gatt_discover_or_read_characteristic_by_uuid_response() {
 }
 
 #[test]
+#[cfg(feature = "att-client-events")]
 fn gatt_discover_or_read_characteristic_by_uuid_response_empty() {
     let buffer = [0x12, 0x0C, 0x01, 0x02, 2, 0x03, 0x04];
     match BlueNRGEvent::new(&buffer) {
@@ -1356,6 +2707,7 @@ fn att_write_permit_request_empty() {
     }
 }
 
+#[cfg(not(feature = "bluenrg-lp"))]
 #[test]
 fn att_read_permit_request() {
     let buffer = [0x14, 0x0C, 0x01, 0x02, 0x03, 0x04, 2, 0x05, 0x06];
@@ -1369,6 +2721,23 @@ fn att_read_permit_request() {
     }
 }
 
+#[cfg(not(feature = "bluenrg-lp"))]
+#[test]
+fn att_read_permit_request_bad_data_len() {
+    let buffer = [0x14, 0x0C, 0x01, 0x02, 0x03, 0x04, 3, 0x05, 0x06];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C14),
+            error: HciError::BadLength(actual, expected),
+        })) => {
+            assert_eq!(actual, 10);
+            assert_eq!(expected, 9);
+        }
+        other => panic!("Did not get bad length: {:?}", other),
+    }
+}
+
+#[cfg(not(feature = "bluenrg-lp"))]
 #[test]
 fn att_read_multiple_permit_request() {
     let buffer = [0x15, 0x0C, 0x01, 0x02, 4, 0x03, 0x04, 0x05, 0x06];
@@ -1384,11 +2753,37 @@ fn att_read_multiple_permit_request() {
     }
 }
 
+#[cfg(not(feature = "bluenrg-lp"))]
+#[test]
+fn att_read_multiple_permit_request_three_handles() {
+    let buffer = [
+        0x15, 0x0C, 0x01, 0x02, 6, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttReadMultiplePermitRequest(event)) => {
+            assert_eq!(event.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(
+                event.handles(),
+                [
+                    AttributeHandle(0x0403),
+                    AttributeHandle(0x0605),
+                    AttributeHandle(0x0807)
+                ]
+            );
+        }
+        other => panic!("Did not get ATT Read Multiple Permit Request: {:?}", other),
+    }
+}
+
+#[cfg(not(feature = "bluenrg-lp"))]
 #[test]
 fn att_read_multiple_permit_request_failed() {
     let buffer = [0x15, 0x0C, 0x01, 0x02, 3, 0x03, 0x04, 0x05];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::AttReadMultiplePermitRequestPartial)) => (),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C15),
+            error: HciError::Vendor(BlueNRGError::AttReadMultiplePermitRequestPartial),
+        })) => (),
         other => panic!(
             "Did not get partial ATT Read Multiple Permit Request: {:?}",
             other
@@ -1409,12 +2804,31 @@ fn gatt_tx_pool_available() {
     }
 }
 
+#[cfg(all(feature = "ms", not(feature = "tolerant-decode")))]
+#[test]
+fn gatt_tx_pool_available_failed_bad_length() {
+    let buffer = [0x16, 0x0C, 0x01, 0x02, 0x03];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C16),
+            error: HciError::BadLength(actual, expected),
+        })) => {
+            assert_eq!(actual, 5);
+            assert_eq!(expected, 6);
+        }
+        other => panic!("Did not get bad length: {:?}", other),
+    }
+}
+
 #[cfg(not(feature = "ms"))]
 #[test]
 fn gatt_tx_pool_available_unknown() {
     let buffer = [0x16, 0x0C, 0x01, 0x02, 0x03, 0x04];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::UnknownEvent(0x0C16))) => (),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C16),
+            error: HciError::Vendor(BlueNRGError::UnknownEvent(0x0C16)),
+        })) => (),
         other => panic!("Did not get unknown event: {:?}", other),
     }
 }
@@ -1431,12 +2845,32 @@ fn gatt_server_confirmation() {
     }
 }
 
+#[cfg(feature = "ms")]
+#[test]
+fn gatt_server_confirmation_failed_bad_length() {
+    for buffer in &[&[0x17, 0x0C, 0x01][..], &[0x17, 0x0C, 0x01, 0x02, 0x03][..]] {
+        match BlueNRGEvent::new(buffer) {
+            Err(HciError::Vendor(ParseError {
+                code: Some(0x0C17),
+                error: HciError::BadLength(actual, expected),
+            })) => {
+                assert_eq!(actual, buffer.len());
+                assert_eq!(expected, 4);
+            }
+            other => panic!("Did not get bad length: {:?}", other),
+        }
+    }
+}
+
 #[cfg(not(feature = "ms"))]
 #[test]
 fn gatt_server_confirmation_unknown() {
     let buffer = [0x17, 0x0C, 0x01, 0x02];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::UnknownEvent(0x0C17))) => (),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C17),
+            error: HciError::Vendor(BlueNRGError::UnknownEvent(0x0C17)),
+        })) => (),
         other => panic!("Did not get unknown event: {:?}", other),
     }
 }
@@ -1480,7 +2914,260 @@ fn att_prepare_write_permit_request_unknown() {
         0x18, 0x0C, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 4, 0x07, 0x08, 0x09, 0x0a,
     ];
     match BlueNRGEvent::new(&buffer) {
-        Err(HciError::Vendor(BlueNRGError::UnknownEvent(0x0C18))) => (),
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C18),
+            error: HciError::Vendor(BlueNRGError::UnknownEvent(0x0C18)),
+        })) => (),
+        other => panic!("Did not get unknown event: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(any(not(feature = "gap-central-events"), feature = "bluenrg-lp"))]
+fn gap_device_found_compiled_out() {
+    let buffer = [
+        0x06, 0x04, 0x04, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x00, 0x01,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0406),
+            error: HciError::Vendor(BlueNRGError::UnknownEvent(0x0406)),
+        })) => (),
+        other => panic!("Did not get unknown event: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(not(feature = "bluenrg-lp"))]
+fn gatt_indication_ext_unknown() {
+    let buffer = [
+        0x1E, 0x0C, 0x01, 0x02, 0x04, 0x80, 0x05, 0x06, 0x03, 0x00, 0x07, 0x08, 0x09,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C1E),
+            error: HciError::Vendor(BlueNRGError::UnknownEvent(0x0C1E)),
+        })) => (),
+        other => panic!("Did not get unknown event: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(not(feature = "l2cap-events"))]
+fn l2cap_procedure_timeout_compiled_out() {
+    let buffer = [0x01, 0x08, 0x01, 0x02, 0x00];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0801),
+            error: HciError::Vendor(BlueNRGError::UnknownEvent(0x0801)),
+        })) => (),
+        other => panic!("Did not get unknown event: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(not(feature = "att-client-events"))]
+fn gatt_notification_compiled_out() {
+    let buffer = [0x0F, 0x0C, 0x01, 0x02, 0x04, 0x03, 0x04, 0x05, 0x06];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C0F),
+            error: HciError::Vendor(BlueNRGError::UnknownEvent(0x0C0F)),
+        })) => (),
         other => panic!("Did not get unknown event: {:?}", other),
     }
 }
+
+#[test]
+fn parse_agrees_with_vendor_event() {
+    let buffer = [0x02, 0x04, 0x01, 0x02];
+    match (parse(&buffer), BlueNRGEvent::new(&buffer)) {
+        (
+            Ok(BlueNRGEvent::GapPassKeyRequest(parsed)),
+            Ok(BlueNRGEvent::GapPassKeyRequest(created)),
+        ) => assert_eq!(parsed, created),
+        other => panic!("Did not get matching pass key requests: {:?}", other),
+    }
+}
+
+#[test]
+fn parse_agrees_with_vendor_event_failure() {
+    let buffer = [0xFF, 0xFF];
+    match (parse(&buffer), BlueNRGEvent::new(&buffer)) {
+        (Err(HciError::Vendor(parsed)), Err(HciError::Vendor(created))) => {
+            assert_eq!(parsed, created);
+            assert_eq!(parsed.code, Some(0xFFFF));
+            assert_eq!(
+                parsed.error,
+                HciError::Vendor(BlueNRGError::UnknownEvent(0xFFFF))
+            );
+        }
+        other => panic!("Did not get matching errors: {:?}", other),
+    }
+}
+
+#[cfg(feature = "att-client-events")]
+fn long_notification_buffer() -> [u8; 300] {
+    let mut buffer = [0; 300];
+    buffer[0] = 0x0F; // event code
+    buffer[1] = 0x0C;
+    buffer[2] = 0x01; // connection handle
+    buffer[3] = 0x02;
+    buffer[4] = (300 - 5) as u8; // low byte of the data length
+    buffer[5] = 0x03; // attribute handle
+    buffer[6] = 0x04;
+    for (i, byte) in buffer[7..].iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+    buffer
+}
+
+#[test]
+#[cfg(all(feature = "att-client-events", feature = "extended-packets"))]
+fn gatt_notification_extended() {
+    let buffer = long_notification_buffer();
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GattNotification(event)) => {
+            assert_eq!(event.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(event.attribute_handle, AttributeHandle(0x0403));
+            assert_eq!(event.value().len(), 293);
+            assert_eq!(event.value(), &buffer[7..]);
+        }
+        other => panic!("Did not get GATT notification: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(all(feature = "att-client-events", not(feature = "extended-packets")))]
+fn gatt_notification_too_long() {
+    let buffer = long_notification_buffer();
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C0F),
+            error: HciError::BadLength(300, 255),
+        })) => (),
+        other => panic!("Did not get bad length: {:?}", other),
+    }
+}
+
+#[test]
+fn att_error_round_trips_every_byte() {
+    let mut known = 0;
+    for value in 0..=255u8 {
+        match AttError::try_from(value) {
+            Ok(error) => {
+                assert_eq!(error as u8, value);
+                known += 1;
+            }
+            Err(unknown) => assert_eq!(unknown, value),
+        }
+    }
+
+    // 0x01-0x11, 0x80-0x9F, and 0xFC-0xFF
+    assert_eq!(known, 17 + 32 + 4);
+}
+
+#[test]
+#[cfg(feature = "att-client-events")]
+fn gatt_multiple_notification() {
+    let buffer = [
+        0x1A, 0x0C, 0x01, 0x02, 11, 0x03, 0x04, 2, 0, 0x05, 0x06, 0x07, 0x08, 1, 0, 0x09,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GattMultipleNotification(event)) => {
+            assert_eq!(event.conn_handle, ConnectionHandle(0x0201));
+
+            let mut iter = event.handle_value_pair_iter();
+            let actual = iter.next().unwrap();
+            assert_eq!(actual.handle, AttributeHandle(0x0403));
+            assert_eq!(actual.value, [0x05, 0x06]);
+
+            let actual = iter.next().unwrap();
+            assert_eq!(actual.handle, AttributeHandle(0x0807));
+            assert_eq!(actual.value, [0x09]);
+
+            assert!(iter.next().is_none());
+        }
+        other => panic!("Did not get GATT multiple notification: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "att-client-events")]
+fn gatt_multiple_notification_failed_partial_pair() {
+    let buffer = [
+        0x1A, 0x0C, 0x01, 0x02, 10, 0x03, 0x04, 2, 0, 0x05, 0x06, 0x07, 0x08, 1, 0,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0x0C1A),
+            error: HciError::Vendor(BlueNRGError::GattMultipleNotificationPartial),
+        })) => (),
+        other => panic!("Did not get partial multiple notification: {:?}", other),
+    }
+}
+
+#[test]
+fn event_codes_match_events() {
+    let codes: Vec<u16> = event_codes().collect();
+    assert!(codes.windows(2).all(|pair| pair[0] < pair[1]));
+
+    for code in 0..=0xFFFF_u16 {
+        match BlueNRGEvent::new(&code.to_le_bytes()) {
+            Err(HciError::Vendor(ParseError {
+                error: HciError::Vendor(BlueNRGError::UnknownEvent(unknown)),
+                ..
+            })) => {
+                assert_eq!(unknown, code);
+                assert!(
+                    !codes.contains(&code),
+                    "{:#06x} is listed but not decoded",
+                    code
+                );
+            }
+            Ok(event) => {
+                assert!(codes.contains(&code));
+                assert_eq!(event.code(), code);
+            }
+            Err(_) => assert!(codes.contains(&code)),
+        }
+    }
+}
+
+// The event codes of the BlueNRG-MS ACI with every event family enabled, listed by hand so that
+// an entry missing from or mistyped in the decoder table is caught.
+#[cfg(all(
+    feature = "ms",
+    feature = "gap-central-events",
+    feature = "att-client-events",
+    feature = "l2cap-events",
+    not(feature = "bluenrg-lp")
+))]
+#[test]
+fn event_codes_ms() {
+    assert_eq!(
+        event_codes().collect::<Vec<_>>(),
+        [
+            0x0001, 0x0002, 0x0003, 0x0400, 0x0401, 0x0402, 0x0403, 0x0404, 0x0405, 0x0406, 0x0407,
+            0x0408, 0x0409, 0x0800, 0x0801, 0x0802, 0x0C01, 0x0C02, 0x0C03, 0x0C04, 0x0C05, 0x0C06,
+            0x0C07, 0x0C08, 0x0C09, 0x0C0A, 0x0C0C, 0x0C0D, 0x0C0E, 0x0C0F, 0x0C10, 0x0C11, 0x0C12,
+            0x0C13, 0x0C14, 0x0C15, 0x0C16, 0x0C17, 0x0C18, 0x0C1A,
+        ]
+    );
+}
+
+#[test]
+fn event_categories() {
+    let event = BlueNRGEvent::new(&[0x02, 0x04, 0x01, 0x02]).unwrap();
+    assert_eq!(event.category(), EventCategory::Request);
+
+    let event = BlueNRGEvent::new(&[0x01, 0x00, 0x01]).unwrap();
+    assert_eq!(event.category(), EventCategory::Status);
+}
+
+#[cfg(feature = "att-client-events")]
+#[test]
+fn notification_category() {
+    let event = BlueNRGEvent::new(&[0x0F, 0x0C, 0x01, 0x02, 3, 0x03, 0x04, 0x05]).unwrap();
+    assert_eq!(event.category(), EventCategory::Notification);
+}
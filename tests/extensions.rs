@@ -1,7 +1,8 @@
 extern crate bluenrg;
+extern crate bluetooth_hci as hci;
 extern crate core;
 
-use bluenrg::{HardwareError, InvalidHardwareError};
+use bluenrg::{BdAddrTypeExt, HardwareError, InvalidHardwareError, RandomAddressKind};
 use core::convert::TryInto;
 
 macro_rules! assert_eq_hw_error {
@@ -29,3 +30,42 @@ fn hardware_error_failed() {
         other => panic!("Did not get invalid hardware error: {:?}", other),
     }
 }
+
+#[test]
+fn random_address_kind() {
+    // Addresses are little-endian, so the sub-type is in the top bits of the last byte.
+    let non_resolvable = hci::BdAddrType::Random(hci::BdAddr([0x01, 0x02, 0x03, 0x04, 0x05, 0x16]));
+    assert_eq!(
+        non_resolvable.random_kind(),
+        Some(RandomAddressKind::NonResolvablePrivate)
+    );
+    assert!(non_resolvable.is_non_resolvable_private());
+
+    let resolvable = hci::BdAddrType::Random(hci::BdAddr([0x01, 0x02, 0x03, 0x04, 0x05, 0x4A]));
+    assert_eq!(
+        resolvable.random_kind(),
+        Some(RandomAddressKind::ResolvablePrivate)
+    );
+    assert!(resolvable.is_resolvable_private());
+    assert!(!resolvable.is_static());
+
+    let static_addr = hci::BdAddrType::Random(hci::BdAddr([0x01, 0x02, 0x03, 0x04, 0x05, 0xC6]));
+    assert_eq!(static_addr.random_kind(), Some(RandomAddressKind::Static));
+    assert!(static_addr.is_static());
+    assert!(!static_addr.is_resolvable_private());
+}
+
+#[test]
+fn random_address_kind_reserved() {
+    let reserved = hci::BdAddrType::Random(hci::BdAddr([0x01, 0x02, 0x03, 0x04, 0x05, 0x86]));
+    assert_eq!(reserved.random_kind(), None);
+    assert!(!reserved.is_static());
+}
+
+#[test]
+fn public_address_has_no_random_kind() {
+    // The same bytes as a static random address, but public addresses have no sub-type.
+    let public = hci::BdAddrType::Public(hci::BdAddr([0x01, 0x02, 0x03, 0x04, 0x05, 0xC6]));
+    assert_eq!(public.random_kind(), None);
+    assert!(!public.is_static());
+}
@@ -0,0 +1,130 @@
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+extern crate embedded_hal as hal;
+extern crate nb;
+
+mod fixture;
+
+use bluenrg::event::BlueNRGEvent;
+use bluenrg::firmware::*;
+use fixture::{Fixture, RecordingSink};
+
+fn info(major: u8, minor: u8, patch: u8) -> FirmwareInfo {
+    FirmwareInfo {
+        hw_version: 0x31,
+        major,
+        minor,
+        patch,
+        revision: 0,
+    }
+}
+
+#[test]
+fn known_versions() {
+    // Version, stack, and whether the firmware supports extended attribute events, the data
+    // length extension, and the tx pool available event.
+    let table = [
+        ((6, 4, 0), Stack::BlueNrg, [false, false, false]),
+        ((7, 1, 0xB), Stack::BlueNrgMs, [false, false, true]),
+        ((7, 2, 0xC), Stack::BlueNrgMs, [false, false, true]),
+        ((2, 1, 0), Stack::V2, [false, true, true]),
+        ((3, 2, 0), Stack::V3, [true, true, true]),
+        ((9, 0, 0), Stack::Unknown(9), [false, false, false]),
+    ];
+
+    for &((major, minor, patch), stack, capabilities) in table.iter() {
+        let info = info(major, minor, patch);
+        assert_eq!(info.stack(), stack);
+        assert_eq!(
+            [
+                info.supports_extended_attribute_events(),
+                info.supports_data_length_extension(),
+                info.has_tx_pool_available_event(),
+            ],
+            capabilities
+        );
+    }
+}
+
+#[test]
+fn decoding() {
+    assert_eq!(info(6, 4, 0).decoding(), Some(Decoding::BlueNrg));
+    assert_eq!(info(7, 2, 0xC).decoding(), Some(Decoding::Ms));
+    assert_eq!(info(2, 1, 0).decoding(), Some(Decoding::Lp));
+    assert_eq!(info(3, 2, 0).decoding(), Some(Decoding::Lp));
+    assert_eq!(info(9, 0, 0).decoding(), None);
+}
+
+#[test]
+fn built_decoding() {
+    if cfg!(feature = "bluenrg-lp") {
+        assert_eq!(Decoding::BUILT, Decoding::Lp);
+    } else if cfg!(feature = "ms") {
+        assert_eq!(Decoding::BUILT, Decoding::Ms);
+    } else {
+        assert_eq!(Decoding::BUILT, Decoding::BlueNrg);
+    }
+}
+
+fn command_complete(bytes: &[u8]) -> hci::event::Event<BlueNRGEvent> {
+    hci::event::Event::new(hci::event::Packet(bytes)).unwrap()
+}
+
+#[test]
+fn detect() {
+    let mut sink = RecordingSink::new();
+    let mut detection = {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| FirmwareDetection::detect(controller))
+            .unwrap()
+    };
+    assert!(sink.wrote(&[1, 0x01, 0x10, 0]));
+
+    // Local version information: hardware 0x31, stack 7.2.c
+    let version = command_complete(&[
+        0x0E, 12, 1, 0x01, 0x10, 0, 0x07, 0x07, 0x31, 0x07, 0x30, 0x00, 0x2C, 0x00,
+    ]);
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        let result = fixture.act(|controller| detection.poll(controller, &version));
+        assert_eq!(result, Ok(None));
+    }
+    assert!(sink.wrote(&[1, 0x00, 0xFC, 0]));
+
+    let revision = command_complete(&[0x0E, 6, 1, 0x00, 0xFC, 0, 0x2C, 0x07]);
+    let mut sink = RecordingSink::new();
+    let mut fixture = Fixture::new(&mut sink);
+    let result = fixture.act(|controller| detection.poll(controller, &revision));
+    assert_eq!(
+        result,
+        Ok(Some(FirmwareInfo {
+            hw_version: 0x31,
+            major: 7,
+            minor: 2,
+            patch: 0xC,
+            revision: 0x072C,
+        }))
+    );
+}
+
+#[test]
+fn detect_command_failed() {
+    let mut sink = RecordingSink::new();
+    let mut fixture = Fixture::new(&mut sink);
+    let mut detection = fixture
+        .act(|controller| FirmwareDetection::detect(controller))
+        .unwrap();
+
+    let version = command_complete(&[
+        0x0E, 12, 1, 0x01, 0x10, 0x41, 0x07, 0x07, 0x31, 0x07, 0x30, 0x00, 0x2C, 0x00,
+    ]);
+    let result = fixture.act(|controller| detection.poll(controller, &version));
+    assert_eq!(
+        result,
+        Err(nb::Error::Other(Error::CommandFailed(hci::Status::Vendor(
+            bluenrg::event::Status::Failed
+        ))))
+    );
+}
@@ -113,8 +113,10 @@ fn set_limited_discoverable_bad_adv_interval() {
         assert_eq!(
             err,
             nb::Error::Other(Error::BadAdvertisingInterval(
-                Duration::from_millis(1280),
-                Duration::from_millis(1279)
+                AdvertisingIntervalError::Inverted(
+                    AdvertisingInterval::from_duration(Duration::from_millis(1280)).unwrap(),
+                    AdvertisingInterval::from_duration(Duration::from_millis(1279)).unwrap()
+                )
             ))
         );
     }
@@ -246,8 +248,47 @@ fn set_discoverable_bad_adv_interval() {
         assert_eq!(
             err,
             nb::Error::Other(Error::BadAdvertisingInterval(
-                Duration::from_millis(1280),
-                Duration::from_millis(1279)
+                AdvertisingIntervalError::Inverted(
+                    AdvertisingInterval::from_duration(Duration::from_millis(1280)).unwrap(),
+                    AdvertisingInterval::from_duration(Duration::from_millis(1279)).unwrap()
+                )
+            ))
+        );
+    }
+
+    assert!(!sink.wrote_header());
+}
+
+#[cfg(not(feature = "bluenrg-lp"))]
+#[test]
+fn set_discoverable_adv_interval_too_short_for_type() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        let err = fixture
+            .act(|controller| {
+                controller.set_discoverable(&DiscoverableParameters {
+                    advertising_type: AdvertisingType::NonConnectableUndirected,
+                    advertising_interval: Some((
+                        Duration::from_millis(50),
+                        Duration::from_millis(200),
+                    )),
+                    address_type: OwnAddressType::Public,
+                    filter_policy: AdvertisingFilterPolicy::AllowConnectionAndScan,
+                    local_name: Some(LocalName::Shortened(b"testdev")),
+                    advertising_data: &[0x01, 0x02, 0x03, 0x04],
+                    conn_interval: (Some(Duration::from_millis(5000)), None),
+                })
+            })
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            nb::Error::Other(Error::BadAdvertisingInterval(
+                AdvertisingIntervalError::TooShortForType(
+                    AdvertisingType::NonConnectableUndirected,
+                    AdvertisingInterval::from_duration(Duration::from_millis(50)).unwrap()
+                )
             ))
         );
     }
@@ -292,6 +333,110 @@ fn set_discoverable_bad_conn_interval() {
     assert!(!sink.wrote_header());
 }
 
+// Complete list of 128-bit service UUIDs, with one UUID: 18 bytes.
+const SERVICE_UUID_LIST: [u8; 18] = [
+    17, 0x07, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D,
+    0x0E, 0x0F,
+];
+
+fn discoverable_with_uuids<'a, 'b>(
+    local_name: &'a [u8],
+    conn_interval: (Option<Duration>, Option<Duration>),
+) -> DiscoverableParameters<'a, 'b> {
+    DiscoverableParameters {
+        advertising_type: AdvertisingType::ConnectableUndirected,
+        advertising_interval: None,
+        address_type: OwnAddressType::Public,
+        filter_policy: AdvertisingFilterPolicy::AllowConnectionAndScan,
+        local_name: Some(LocalName::Complete(local_name)),
+        advertising_data: &SERVICE_UUID_LIST,
+        conn_interval,
+    }
+}
+
+#[test]
+fn set_discoverable_advertising_data_too_long() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        // 3 bytes of flags, 2 bytes of AD structure header and 14 bytes of name, and 18 bytes of
+        // service UUIDs.
+        let err = fixture
+            .act(|controller| {
+                controller
+                    .set_discoverable(&discoverable_with_uuids(b"BlueNRG sensor", (None, None)))
+            })
+            .err()
+            .unwrap();
+        assert_eq!(err, nb::Error::Other(Error::BadAdvertisingDataLength(37)));
+    }
+
+    assert!(!sink.wrote_header());
+}
+
+#[test]
+fn set_discoverable_advertising_data_fills_packet() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        // 3 bytes of flags, 2 bytes of AD structure header and 8 bytes of name, and 18 bytes of
+        // service UUIDs: exactly 31 bytes.
+        fixture
+            .act(|controller| {
+                controller.set_discoverable(&discoverable_with_uuids(b"BlueNRG!", (None, None)))
+            })
+            .unwrap();
+    }
+
+    assert!(sink.wrote_header());
+}
+
+#[test]
+fn set_discoverable_advertising_data_one_byte_too_long() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        let err = fixture
+            .act(|controller| {
+                controller.set_discoverable(&discoverable_with_uuids(b"BlueNRG!!", (None, None)))
+            })
+            .err()
+            .unwrap();
+        assert_eq!(err, nb::Error::Other(Error::BadAdvertisingDataLength(32)));
+    }
+
+    assert!(!sink.wrote_header());
+}
+
+#[test]
+fn set_discoverable_advertising_data_with_conn_interval() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        // The Slave Connection Interval Range takes 6 more bytes, so only a 2-byte name fits.
+        fixture
+            .act(|controller| {
+                controller.set_discoverable(&discoverable_with_uuids(
+                    b"BN",
+                    (Some(Duration::from_millis(50)), None),
+                ))
+            })
+            .unwrap();
+        let err = fixture
+            .act(|controller| {
+                controller.set_discoverable(&discoverable_with_uuids(
+                    b"BNR",
+                    (None, Some(Duration::from_millis(100))),
+                ))
+            })
+            .err()
+            .unwrap();
+        assert_eq!(err, nb::Error::Other(Error::BadAdvertisingDataLength(32)));
+    }
+
+    assert!(sink.wrote_header());
+}
+
 #[cfg(not(feature = "ms"))]
 #[test]
 fn set_direct_connectable() {
@@ -368,10 +513,23 @@ fn set_direct_connectable_bad_adv_interval() {
     let mut sink = RecordingSink::new();
     {
         let mut fixture = Fixture::new(&mut sink);
-        for (min, max) in [
-            (Duration::from_millis(19), Duration::from_millis(50)),
-            (Duration::from_millis(20), Duration::from_millis(10241)),
-            (Duration::from_millis(500), Duration::from_millis(499)),
+        let interval = |ms| AdvertisingInterval::from_duration(Duration::from_millis(ms)).unwrap();
+        for (min, max, expected) in [
+            (
+                Duration::from_millis(19),
+                Duration::from_millis(50),
+                AdvertisingIntervalError::OutOfRange(Duration::from_millis(19)),
+            ),
+            (
+                Duration::from_millis(20),
+                Duration::from_millis(10241),
+                AdvertisingIntervalError::OutOfRange(Duration::from_millis(10241)),
+            ),
+            (
+                Duration::from_millis(500),
+                Duration::from_millis(499),
+                AdvertisingIntervalError::Inverted(interval(500), interval(499)),
+            ),
         ]
         .into_iter()
         {
@@ -388,7 +546,7 @@ fn set_direct_connectable_bad_adv_interval() {
                 .unwrap();
             assert_eq!(
                 err,
-                nb::Error::Other(Error::BadAdvertisingInterval(*min, *max))
+                nb::Error::Other(Error::BadAdvertisingInterval(*expected))
             );
         }
     }
@@ -692,6 +850,24 @@ fn set_undirected_connectable() {
     assert!(sink.wrote(&[1, 0x8C, 0xFC, 2, 0x00, 0x02]));
 }
 
+#[test]
+fn set_undirected_connectable_white_list() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| {
+                controller.set_undirected_connectable(
+                    AdvertisingFilterPolicy::WhiteListConnectionAndScan,
+                    AddressType::Public,
+                )
+            })
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x8C, 0xFC, 2, 0x03, 0x00]));
+}
+
 #[test]
 fn set_undirected_connectable_bad_advertising_filter_policy() {
     let mut sink = RecordingSink::new();
@@ -716,6 +892,30 @@ fn set_undirected_connectable_bad_advertising_filter_policy() {
     assert!(!sink.wrote_header());
 }
 
+#[test]
+fn set_undirected_connectable_bad_advertising_filter_policy_scan() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        let err = fixture
+            .act(|controller| {
+                controller.set_undirected_connectable(
+                    AdvertisingFilterPolicy::WhiteListScanAllowConnection,
+                    AddressType::Public,
+                )
+            })
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            nb::Error::Other(Error::BadAdvertisingFilterPolicy(
+                AdvertisingFilterPolicy::WhiteListScanAllowConnection
+            ))
+        );
+    }
+    assert!(!sink.wrote_header());
+}
+
 #[test]
 fn peripheral_security_request() {
     let mut sink = RecordingSink::new();
@@ -805,6 +1005,19 @@ fn set_event_mask() {
     assert!(sink.wrote(&[1, 0x91, 0xFC, 2, 0x03, 0x00]));
 }
 
+#[test]
+fn set_event_mask_all_events() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| controller.set_gap_event_mask(EventFlags::all()))
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x91, 0xFC, 2, 0x3F, 0x00]));
+}
+
 #[test]
 fn configure_white_list() {
     let mut sink = RecordingSink::new();
@@ -1208,6 +1421,36 @@ fn start_general_connection_establishment() {
     assert!(sink.wrote(&[1, 0x9A, 0xFC, 6, 0x04, 0x00, 0x04, 0x00, 0x01, 0x1]));
 }
 
+// Every scanning command takes a ScanWindow, which can only be built with an interval and window
+// in 0x0004..=0x4000 (2.5 ms to 10.24 s) and a window no longer than the interval.
+#[test]
+fn scan_window_interval_bounds() {
+    assert!(ScanWindow::start_every(Duration::from_micros(1875)).is_err());
+    assert!(ScanWindow::start_every(Duration::from_micros(2500)).is_ok());
+    assert!(ScanWindow::start_every(Duration::from_millis(10240)).is_ok());
+    assert!(ScanWindow::start_every(Duration::from_micros(10_240_625)).is_err());
+}
+
+#[test]
+fn scan_window_window_bounds() {
+    let every = |interval| ScanWindow::start_every(interval).unwrap();
+    assert!(every(Duration::from_millis(10))
+        .open_for(Duration::from_micros(1875))
+        .is_err());
+    assert!(every(Duration::from_millis(10))
+        .open_for(Duration::from_micros(2500))
+        .is_ok());
+    assert!(every(Duration::from_millis(10))
+        .open_for(Duration::from_millis(10))
+        .is_ok());
+    assert!(every(Duration::from_millis(10))
+        .open_for(Duration::from_micros(10_625))
+        .is_err());
+    assert!(every(Duration::from_millis(10240))
+        .open_for(Duration::from_millis(10240))
+        .is_ok());
+}
+
 #[test]
 fn start_selective_connection_establishment() {
     let mut sink = RecordingSink::new();
@@ -1244,6 +1487,41 @@ fn start_selective_connection_establishment() {
     ]));
 }
 
+#[test]
+fn start_selective_connection_establishment_full_white_list() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| {
+                controller.start_selective_connection_establishment(
+                    &SelectiveConnectionEstablishmentParameters {
+                        scan_type: hci::host::ScanType::Passive,
+                        scan_window: ScanWindow::start_every(Duration::from_micros(2500))
+                            .unwrap()
+                            .open_for(Duration::from_micros(2500))
+                            .unwrap(),
+                        own_address_type: hci::host::OwnAddressType::Public,
+                        filter_duplicates: false,
+                        white_list: &[hci::host::PeerAddrType::RandomDeviceAddress(hci::BdAddr([
+                            1, 2, 3, 4, 5, 6,
+                        ])); 35],
+                    },
+                )
+            })
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+
+    let mut expected = vec![
+        1, 0x9B, 0xFC, 253, 0x00, 0x04, 0x00, 0x04, 0x00, 0x00, 0x00, 35,
+    ];
+    for _ in 0..35 {
+        expected.extend_from_slice(&[0x01, 1, 2, 3, 4, 5, 6]);
+    }
+    assert!(sink.wrote(&expected));
+}
+
 #[test]
 fn start_selective_connection_establishment_white_list_too_long() {
     let mut sink = RecordingSink::new();
@@ -1311,6 +1589,44 @@ fn create_connection() {
     ]));
 }
 
+#[test]
+fn create_connection_to_random_static_peer() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| {
+                controller.create_connection(&ConnectionParameters {
+                    scan_window: ScanWindow::start_every(Duration::from_millis(60))
+                        .unwrap()
+                        .open_for(Duration::from_millis(30))
+                        .unwrap(),
+                    peer_address: hci::host::PeerAddrType::RandomDeviceAddress(hci::BdAddr([
+                        0x11, 0x22, 0x33, 0x44, 0x55, 0xC6,
+                    ])),
+                    own_address_type: hci::host::OwnAddressType::Public,
+                    conn_interval: ConnectionIntervalBuilder::new()
+                        .with_range(Duration::from_millis(30), Duration::from_millis(50))
+                        .with_latency(0)
+                        .with_supervision_timeout(Duration::from_secs(4))
+                        .build()
+                        .unwrap(),
+                    expected_connection_length: ExpectedConnectionLength::new(
+                        Duration::from_millis(0),
+                        Duration::from_millis(10),
+                    )
+                    .unwrap(),
+                })
+            })
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[
+        1, 0x9C, 0xFC, 24, 0x60, 0x00, 0x30, 0x00, 0x01, 0x11, 0x22, 0x33, 0x44, 0x55, 0xC6, 0x00,
+        0x18, 0x00, 0x28, 0x00, 0x00, 0x00, 0x90, 0x01, 0x00, 0x00, 0x10, 0x00
+    ]));
+}
+
 #[test]
 fn terminate_procedure() {
     let mut sink = RecordingSink::new();
@@ -1658,3 +1974,360 @@ fn is_device_bonded() {
     assert!(sink.wrote_header());
     assert!(sink.wrote(&[1, 0xA4, 0xFC, 7, 0x00, 1, 2, 3, 4, 5, 6]));
 }
+
+#[cfg(feature = "bluenrg-lp")]
+#[test]
+fn add_devices_to_resolving_list_clear() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| controller.add_devices_to_resolving_list(true, &[]))
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0xA9, 0xFC, 2, 0, 1]));
+}
+
+#[cfg(feature = "bluenrg-lp")]
+#[test]
+fn add_devices_to_resolving_list() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| {
+                controller.add_devices_to_resolving_list(
+                    false,
+                    &[
+                        hci::host::PeerAddrType::PublicDeviceAddress(hci::BdAddr([
+                            1, 2, 3, 4, 5, 6,
+                        ])),
+                        hci::host::PeerAddrType::RandomDeviceAddress(hci::BdAddr([
+                            7, 8, 9, 10, 11, 0xC0,
+                        ])),
+                    ],
+                )
+            })
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(
+        sink.wrote(&[1, 0xA9, 0xFC, 16, 2, 0x00, 1, 2, 3, 4, 5, 6, 0x01, 7, 8, 9, 10, 11, 0xC0, 0])
+    );
+}
+
+#[cfg(feature = "bluenrg-lp")]
+#[test]
+fn add_devices_to_resolving_list_too_long() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        let identities =
+            [hci::host::PeerAddrType::PublicDeviceAddress(hci::BdAddr([1, 2, 3, 4, 5, 6])); 37];
+        let err = fixture
+            .act(|controller| controller.add_devices_to_resolving_list(false, &identities))
+            .err()
+            .unwrap();
+        assert_eq!(err, nb::Error::Other(Error::ResolvingListTooLong(37)));
+    }
+    assert!(!sink.wrote_header());
+}
+
+#[cfg(feature = "periodic-advertising")]
+#[test]
+fn set_periodic_advertising() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| {
+                controller.set_periodic_advertising(&PeriodicAdvertisingParameters {
+                    advertising_handle: 1,
+                    interval: (Duration::from_micros(7500), Duration::from_millis(100)),
+                    properties: PeriodicAdvertisingProperties::INCLUDE_TX_POWER,
+                })
+            })
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x3E, 0x20, 7, 1, 6, 0, 80, 0, 0x40, 0]));
+}
+
+#[cfg(feature = "periodic-advertising")]
+#[test]
+fn set_periodic_advertising_bad_interval() {
+    for &(min, max) in [
+        (Duration::from_millis(5), Duration::from_millis(100)),
+        (Duration::from_millis(100), Duration::from_millis(50)),
+        (Duration::from_millis(100), Duration::from_secs(82)),
+    ]
+    .iter()
+    {
+        let mut sink = RecordingSink::new();
+        {
+            let mut fixture = Fixture::new(&mut sink);
+            let err = fixture
+                .act(|controller| {
+                    controller.set_periodic_advertising(&PeriodicAdvertisingParameters {
+                        advertising_handle: 1,
+                        interval: (min, max),
+                        properties: PeriodicAdvertisingProperties::empty(),
+                    })
+                })
+                .err()
+                .unwrap();
+            assert_eq!(
+                err,
+                nb::Error::Other(Error::BadPeriodicAdvertisingInterval(min, max))
+            );
+        }
+        assert!(!sink.wrote_header());
+    }
+}
+
+#[cfg(feature = "periodic-advertising")]
+#[test]
+fn periodic_advertising_enable() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| controller.periodic_advertising_enable(1, true))
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x40, 0x20, 2, 1, 1]));
+}
+
+#[cfg(feature = "periodic-advertising")]
+#[test]
+fn periodic_advertising_disable() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| controller.periodic_advertising_enable(1, false))
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x40, 0x20, 2, 0, 1]));
+}
+
+#[test]
+fn numeric_comparison_value_confirm() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| {
+                controller.numeric_comparison_value_confirm(hci::ConnectionHandle(0x0201), true)
+            })
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0xA5, 0xFC, 3, 0x01, 0x02, 0x01]));
+}
+
+#[test]
+fn numeric_comparison_value_reject() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| {
+                controller.numeric_comparison_value_confirm(hci::ConnectionHandle(0x0201), false)
+            })
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0xA5, 0xFC, 3, 0x01, 0x02, 0x00]));
+}
+
+#[test]
+fn appearance_round_trip() {
+    let table = [
+        (Appearance::Unknown, 0),
+        (Appearance::GenericPhone, 64),
+        (Appearance::GenericWatch, 192),
+        (Appearance::SportsWatch, 193),
+        (Appearance::GenericThermometer, 768),
+        (Appearance::GenericHeartRateSensor, 832),
+        (Appearance::HeartRateBelt, 833),
+        (Appearance::Keyboard, 961),
+        (Appearance::CyclingSpeedAndCadenceSensor, 1157),
+        (Appearance::WristWornPulseOximeter, 3138),
+        (Appearance::LocationAndNavigationPod, 5188),
+    ];
+    for &(appearance, value) in table.iter() {
+        assert_eq!(u16::from(appearance), value);
+        assert_eq!(Appearance::from(value), appearance);
+    }
+}
+
+#[test]
+fn appearance_other() {
+    assert_eq!(Appearance::from(0x1234), Appearance::Other(0x1234));
+    assert_eq!(u16::from(Appearance::Other(0x1234)), 0x1234);
+}
+
+#[test]
+fn appearance_to_bytes() {
+    assert_eq!(Appearance::HeartRateBelt.to_bytes(), [0x41, 0x03]);
+}
+
+#[test]
+fn appearance_display() {
+    assert_eq!(
+        format!("{}", Appearance::HeartRateBelt),
+        "Heart Rate Sensor: Heart Rate Belt"
+    );
+    assert_eq!(
+        format!("{}", Appearance::Other(0x1234)),
+        "Appearance 0x1234"
+    );
+}
+
+#[test]
+fn advertising_interval_bounds() {
+    assert_eq!(AdvertisingInterval::from_units(0x001F), None);
+    assert_eq!(
+        AdvertisingInterval::from_units(0x0020),
+        Some(AdvertisingInterval::MIN)
+    );
+    assert_eq!(
+        AdvertisingInterval::from_units(0x4000),
+        Some(AdvertisingInterval::MAX)
+    );
+    assert_eq!(AdvertisingInterval::from_units(0x4001), None);
+
+    assert_eq!(
+        AdvertisingInterval::MIN.duration(),
+        Duration::from_millis(20)
+    );
+    assert_eq!(
+        AdvertisingInterval::MAX.duration(),
+        Duration::from_millis(10240)
+    );
+}
+
+#[test]
+fn advertising_interval_from_duration() {
+    assert_eq!(
+        AdvertisingInterval::from_duration(Duration::from_micros(19_999)),
+        None
+    );
+    assert_eq!(
+        AdvertisingInterval::from_duration(Duration::from_millis(20)),
+        Some(AdvertisingInterval::MIN)
+    );
+    assert_eq!(
+        AdvertisingInterval::from_duration(Duration::from_millis(10240)),
+        Some(AdvertisingInterval::MAX)
+    );
+    assert_eq!(
+        AdvertisingInterval::from_duration(Duration::from_micros(10_240_001)),
+        None
+    );
+
+    // Rounded down to whole units.
+    let interval = AdvertisingInterval::from_duration(Duration::from_micros(100_600)).unwrap();
+    assert_eq!(interval.units(), 160);
+    assert_eq!(interval.duration(), Duration::from_millis(100));
+}
+
+#[test]
+fn advertising_interval_range() {
+    let range = AdvertisingIntervalRange::from_durations(
+        Duration::from_millis(100),
+        Duration::from_millis(200),
+    )
+    .unwrap();
+    assert_eq!(range.min().units(), 0x00A0);
+    assert_eq!(range.max().units(), 0x0140);
+
+    let mut bytes = [0; AdvertisingIntervalRange::LENGTH];
+    range.copy_into_slice(&mut bytes);
+    assert_eq!(bytes, [0xA0, 0x00, 0x40, 0x01]);
+}
+
+#[test]
+fn advertising_interval_range_errors() {
+    assert_eq!(
+        AdvertisingIntervalRange::from_durations(
+            Duration::from_millis(19),
+            Duration::from_millis(50)
+        ),
+        Err(AdvertisingIntervalError::OutOfRange(Duration::from_millis(
+            19
+        )))
+    );
+    assert_eq!(
+        AdvertisingIntervalRange::from_durations(
+            Duration::from_millis(20),
+            Duration::from_millis(10241)
+        ),
+        Err(AdvertisingIntervalError::OutOfRange(Duration::from_millis(
+            10241
+        )))
+    );
+    assert_eq!(
+        AdvertisingIntervalRange::new(AdvertisingInterval::MAX, AdvertisingInterval::MIN),
+        Err(AdvertisingIntervalError::Inverted(
+            AdvertisingInterval::MAX,
+            AdvertisingInterval::MIN
+        ))
+    );
+}
+
+#[test]
+fn advertising_interval_range_minimum_for_type() {
+    let range = AdvertisingIntervalRange::from_durations(
+        Duration::from_millis(20),
+        Duration::from_millis(200),
+    )
+    .unwrap();
+    assert_eq!(
+        range.validate_for(AdvertisingType::ConnectableUndirected),
+        Ok(())
+    );
+
+    #[cfg(not(feature = "bluenrg-lp"))]
+    {
+        assert_eq!(
+            range.validate_for(AdvertisingType::ScannableUndirected),
+            Err(AdvertisingIntervalError::TooShortForType(
+                AdvertisingType::ScannableUndirected,
+                AdvertisingInterval::MIN
+            ))
+        );
+        assert_eq!(
+            range.validate_for(AdvertisingType::NonConnectableUndirected),
+            Err(AdvertisingIntervalError::TooShortForType(
+                AdvertisingType::NonConnectableUndirected,
+                AdvertisingInterval::MIN
+            ))
+        );
+    }
+    #[cfg(feature = "bluenrg-lp")]
+    {
+        assert_eq!(
+            range.validate_for(AdvertisingType::ScannableUndirected),
+            Ok(())
+        );
+        assert_eq!(
+            range.validate_for(AdvertisingType::NonConnectableUndirected),
+            Ok(())
+        );
+    }
+
+    let range = AdvertisingIntervalRange::from_durations(
+        Duration::from_millis(100),
+        Duration::from_millis(200),
+    )
+    .unwrap();
+    assert_eq!(
+        range.validate_for(AdvertisingType::ScannableUndirected),
+        Ok(())
+    );
+}
@@ -5,6 +5,7 @@ extern crate nb;
 mod fixture;
 
 use bluenrg::gatt::*;
+use bluenrg::uuids;
 use fixture::{Fixture, RecordingSink};
 
 #[test]
@@ -147,6 +148,39 @@ fn add_characteristic_16() {
     ]));
 }
 
+#[test]
+fn add_characteristic_event_mask() {
+    for &(mask, byte) in &[
+        (CharacteristicEvent::empty(), 0x00),
+        (CharacteristicEvent::ATTRIBUTE_WRITE, 0x01),
+        (CharacteristicEvent::CONFIRM_WRITE, 0x02),
+        (CharacteristicEvent::CONFIRM_READ, 0x04),
+    ] {
+        let mut sink = RecordingSink::new();
+        {
+            let mut fixture = Fixture::new(&mut sink);
+            fixture
+                .act(|controller| {
+                    controller.add_characteristic(&AddCharacteristicParameters {
+                        service_handle: ServiceHandle(0x0201),
+                        characteristic_uuid: Uuid::Uuid16(0x0403),
+                        characteristic_value_len: 0x0605,
+                        characteristic_properties: CharacteristicProperty::WRITE,
+                        security_permissions: CharacteristicPermission::empty(),
+                        gatt_event_mask: mask,
+                        encryption_key_size: EncryptionKeySize::with_value(16).unwrap(),
+                        is_variable: false,
+                        fw_version_before_v72: false,
+                    })
+                })
+                .unwrap();
+        }
+        assert!(sink.wrote(&[
+            1, 0x04, 0xFD, 12, 0x01, 0x02, 0x01, 0x03, 0x04, 0x05, 0x06, 0x08, 0x00, byte, 16, 0
+        ]));
+    }
+}
+
 #[test]
 fn add_characteristic_128() {
     let mut sink = RecordingSink::new();
@@ -419,6 +453,54 @@ fn update_characteristic_value() {
     assert!(sink.wrote(&[1, 0x06, 0xFD, 11, 0x01, 0x02, 0x03, 0x04, 0, 5, 1, 2, 3, 4, 5]));
 }
 
+#[test]
+fn update_characteristic_value_serialize_into() {
+    let params = UpdateCharacteristicValueParameters {
+        service_handle: ServiceHandle(0x0201),
+        characteristic_handle: CharacteristicHandle(0x0403),
+        offset: 0,
+        value: &[1, 2, 3, 4, 5],
+    };
+    let mut bytes = [0; 11];
+    assert_eq!(params.serialized_len(), 11);
+    assert_eq!(params.serialize_into::<()>(&mut bytes), Ok(11));
+
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| controller.update_characteristic_value(&params))
+            .unwrap();
+    }
+    let mut expected = vec![1, 0x06, 0xFD, 11];
+    expected.extend_from_slice(&bytes);
+    assert!(sink.wrote(&expected));
+}
+
+#[test]
+fn update_characteristic_value_serialize_into_too_small() {
+    let params = UpdateCharacteristicValueParameters {
+        service_handle: ServiceHandle(0x0201),
+        characteristic_handle: CharacteristicHandle(0x0403),
+        offset: 0,
+        value: &[1, 2, 3, 4, 5],
+    };
+    let mut bytes = [0xFF; 10];
+    assert_eq!(
+        params.serialize_into::<()>(&mut bytes),
+        Err(Error::BufferTooSmall(11))
+    );
+    assert_eq!(bytes, [0xFF; 10]);
+    assert_eq!(
+        UpdateCharacteristicValueParameters {
+            value: &[0; 250],
+            ..params
+        }
+        .serialize_into::<()>(&mut [0; 256]),
+        Err(Error::ValueBufferTooLong)
+    );
+}
+
 #[test]
 fn delete_characteristic() {
     let mut sink = RecordingSink::new();
@@ -1552,3 +1634,90 @@ fn update_long_characteristic_value_too_long() {
     }
     assert!(!sink.wrote_header());
 }
+
+#[test]
+fn send_multiple_notifications() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| {
+                controller.send_multiple_notifications(&MultipleNotificationsParameters {
+                    conn_handle: hci::ConnectionHandle(0x0201),
+                    mtu: 23,
+                    notifications: &[
+                        (CharacteristicHandle(0x0403), &[1, 2, 3]),
+                        (CharacteristicHandle(0x0605), &[4]),
+                    ],
+                })
+            })
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(
+        sink.wrote(&[1, 0x31, 0xFD, 15, 0x1, 0x2, 2, 0x3, 0x4, 3, 0, 1, 2, 3, 0x5, 0x6, 1, 0, 4,])
+    );
+}
+
+#[test]
+fn send_multiple_notifications_exceeds_mtu() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        let err = fixture
+            .act(|controller| {
+                controller.send_multiple_notifications(&MultipleNotificationsParameters {
+                    conn_handle: hci::ConnectionHandle(0x0201),
+                    mtu: 23,
+                    notifications: &[
+                        (CharacteristicHandle(0x0403), &[0; 10]),
+                        (CharacteristicHandle(0x0605), &[0; 5]),
+                    ],
+                })
+            })
+            .err()
+            .unwrap();
+        assert_eq!(err, nb::Error::Other(Error::NotificationsExceedMtu(23)));
+    }
+    assert!(!sink.wrote_header());
+}
+
+#[test]
+fn store_db() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture.act(|controller| controller.store_db()).unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x30, 0xFD, 0]));
+}
+
+#[test]
+fn uuid_matches() {
+    // Battery Service: 0000180F-0000-1000-8000-00805F9B34FB
+    let uuid16 = uuids::service::BATTERY;
+    let uuid128 = Uuid::Uuid128([
+        0xFB, 0x34, 0x9B, 0x5F, 0x80, 0x00, 0x00, 0x80, 0x00, 0x10, 0x00, 0x00, 0x0F, 0x18, 0x00,
+        0x00,
+    ]);
+    assert!(uuid16.matches(&uuid128));
+    assert!(uuid128.matches(&uuid16));
+    assert!(uuid16.matches(&uuid16));
+    assert!(uuid128.matches(&uuid128));
+    assert_ne!(uuid16, uuid128);
+}
+
+#[test]
+fn uuid_does_not_match() {
+    let uuid16 = uuids::service::BATTERY;
+    assert!(!uuid16.matches(&uuids::service::DEVICE_INFORMATION));
+
+    // Same 16 bits, but not based on the Bluetooth Base UUID.
+    let vendor_uuid128 = Uuid::Uuid128([
+        0x1B, 0xC5, 0xD5, 0xA5, 0x02, 0x00, 0xA6, 0x87, 0xE5, 0x11, 0x36, 0x39, 0x0F, 0x18, 0x00,
+        0x00,
+    ]);
+    assert!(!uuid16.matches(&vendor_uuid128));
+    assert!(!vendor_uuid128.matches(&uuid16));
+}
@@ -5,6 +5,7 @@ extern crate nb;
 mod fixture;
 
 use bluenrg::hal::*;
+use bluenrg::keys::IdentityResolvingKey;
 use fixture::{Fixture, RecordingSink};
 
 #[test]
@@ -209,3 +210,257 @@ fn get_anchor_period() {
     assert!(sink.wrote_header());
     assert!(sink.wrote(&[1, 0x19, 0xFC, 0]));
 }
+
+#[test]
+fn read_radio_register() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| controller.read_radio_register(0x2C))
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x31, 0xFC, 1, 0x2C]));
+}
+
+#[test]
+fn write_radio_register() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| controller.write_radio_register(0x2C, 0x5A))
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x30, 0xFC, 2, 0x2C, 0x5A]));
+}
+
+#[test]
+fn le_set_data_length() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| {
+                controller.le_set_data_length(hci::ConnectionHandle(0x0201), 251, 0x0848)
+            })
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x22, 0x20, 6, 0x01, 0x02, 251, 0x00, 0x48, 0x08]));
+}
+
+#[test]
+fn le_set_data_length_bad_tx_octets() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        for &tx_octets in [26, 252].iter() {
+            let err = fixture
+                .act(|controller| {
+                    controller.le_set_data_length(hci::ConnectionHandle(0x0201), tx_octets, 0x0148)
+                })
+                .err()
+                .unwrap();
+            assert_eq!(
+                err,
+                nb::Error::Other(DataLengthError::BadTxOctets(tx_octets))
+            );
+        }
+    }
+    assert!(!sink.wrote_header());
+}
+
+#[test]
+fn le_set_data_length_tx_time_limits() {
+    for &(tx_time, encoded) in [(MIN_TX_TIME, [0x48, 0x01]), (MAX_TX_TIME, [0x90, 0x42])].iter() {
+        let mut sink = RecordingSink::new();
+        {
+            let mut fixture = Fixture::new(&mut sink);
+            fixture
+                .act(|controller| {
+                    controller.le_set_data_length(hci::ConnectionHandle(0x0201), 27, tx_time)
+                })
+                .unwrap();
+        }
+        assert!(sink.wrote_header());
+        assert!(sink.wrote(&[1, 0x22, 0x20, 6, 0x01, 0x02, 27, 0x00, encoded[0], encoded[1]]));
+    }
+}
+
+#[test]
+fn le_set_data_length_coded_phy_tx_time() {
+    // 27 octets on the LE Coded PHY with S=8 coding take 2704 us, more than any 1M PHY packet.
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| {
+                controller.le_set_data_length(hci::ConnectionHandle(0x0201), 27, 2704)
+            })
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x22, 0x20, 6, 0x01, 0x02, 27, 0x00, 0x90, 0x0A]));
+}
+
+#[test]
+fn le_set_data_length_bad_tx_time() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        for &tx_time in [327, 17041].iter() {
+            let err = fixture
+                .act(|controller| {
+                    controller.le_set_data_length(hci::ConnectionHandle(0x0201), 27, tx_time)
+                })
+                .err()
+                .unwrap();
+            assert_eq!(err, nb::Error::Other(DataLengthError::BadTxTime(tx_time)));
+        }
+    }
+    assert!(!sink.wrote_header());
+}
+
+#[test]
+fn le_read_maximum_data_length() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| controller.le_read_maximum_data_length())
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x2F, 0x20, 0]));
+}
+
+#[test]
+fn le_set_phy_2m() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| {
+                controller.le_set_phy(
+                    hci::ConnectionHandle(0x0201),
+                    AllPhys::empty(),
+                    PhyMask::LE_2M,
+                    PhyMask::LE_2M,
+                    CodingPreference::NoPreference,
+                )
+            })
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x32, 0x20, 7, 0x01, 0x02, 0x00, 0x02, 0x02, 0x00, 0x00]));
+}
+
+#[test]
+fn le_set_phy_coded() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| {
+                controller.le_set_phy(
+                    hci::ConnectionHandle(0x0201),
+                    AllPhys::NO_RX_PREFERENCE,
+                    PhyMask::LE_1M | PhyMask::LE_CODED,
+                    PhyMask::empty(),
+                    CodingPreference::S8,
+                )
+            })
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x32, 0x20, 7, 0x01, 0x02, 0x02, 0x05, 0x00, 0x02, 0x00]));
+}
+
+#[test]
+fn le_add_device_to_resolving_list() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| {
+                controller.le_add_device_to_resolving_list(&ResolvingListEntry {
+                    peer_identity_address: hci::host::PeerAddrType::PublicDeviceAddress(
+                        hci::BdAddr([1, 2, 3, 4, 5, 6]),
+                    ),
+                    peer_irk: IdentityResolvingKey::new([
+                        0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB,
+                        0xCC, 0xDD, 0xEE, 0xFF,
+                    ]),
+                    local_irk: IdentityResolvingKey::new([0x5A; 16]),
+                })
+            })
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[
+        1, 0x27, 0x20, 39, 0x00, 1, 2, 3, 4, 5, 6, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77,
+        0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF, 0x5A, 0x5A, 0x5A, 0x5A, 0x5A, 0x5A, 0x5A,
+        0x5A, 0x5A, 0x5A, 0x5A, 0x5A, 0x5A, 0x5A, 0x5A, 0x5A,
+    ]));
+}
+
+#[test]
+fn le_remove_device_from_resolving_list() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| {
+                controller.le_remove_device_from_resolving_list(
+                    hci::host::PeerAddrType::RandomDeviceAddress(hci::BdAddr([
+                        7, 8, 9, 10, 11, 0xC0,
+                    ])),
+                )
+            })
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x28, 0x20, 7, 0x01, 7, 8, 9, 10, 11, 0xC0]));
+}
+
+#[test]
+fn le_clear_resolving_list() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| controller.le_clear_resolving_list())
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x29, 0x20, 0]));
+}
+
+#[test]
+fn set_default_event_mask() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| hci::host::Hci::set_event_mask(controller, DEFAULT_EVENT_MASK))
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x01, 0x0C, 8, 0x90, 0x88, 0x00, 0x02, 0x00, 0x80, 0x00, 0x20]));
+}
+
+#[test]
+fn set_default_le_event_mask() {
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| hci::host::Hci::le_set_event_mask(controller, DEFAULT_LE_EVENT_MASK))
+            .unwrap();
+    }
+    assert!(sink.wrote_header());
+    assert!(sink.wrote(&[1, 0x01, 0x20, 8, 0x5F, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]));
+}
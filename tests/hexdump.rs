@@ -0,0 +1,30 @@
+extern crate bluenrg;
+
+use bluenrg::hexdump::hex_dump;
+
+#[test]
+#[cfg(feature = "heapless")]
+fn dump_into_heapless_string() {
+    let mut dump: heapless::String<256> = heapless::String::new();
+    hex_dump(b"0123456789:;<=>?@A\x00\xFF", &mut dump).unwrap();
+    assert_eq!(
+        dump.as_str(),
+        "00000000  30 31 32 33 34 35 36 37  38 39 3a 3b 3c 3d 3e 3f  |0123456789:;<=>?|\n\
+         00000010  40 41 00 ff                                       |@A..|\n\
+         00000014\n"
+    );
+}
+
+#[test]
+fn empty_buffer() {
+    let mut dump = String::new();
+    hex_dump(&[], &mut dump).unwrap();
+    assert_eq!(dump, "00000000\n");
+}
+
+#[test]
+#[cfg(feature = "heapless")]
+fn sink_full() {
+    let mut dump: heapless::String<16> = heapless::String::new();
+    assert!(hex_dump(&[1, 2, 3], &mut dump).is_err());
+}
@@ -0,0 +1,44 @@
+extern crate bluenrg;
+
+use bluenrg::keys::*;
+
+const KEY: [u8; 16] = [
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+];
+
+#[test]
+fn debug_redacts_key() {
+    assert_eq!(
+        format!("{:?}", LongTermKey::new(KEY)),
+        "LongTermKey(fingerprint 3aea)"
+    );
+    assert_eq!(
+        format!("{:?}", IdentityResolvingKey::new(KEY)),
+        "IdentityResolvingKey(fingerprint 3aea)"
+    );
+    assert_eq!(
+        format!("{:?}", Csrk::new([0; 16])),
+        "Csrk(fingerprint 706c)"
+    );
+}
+
+#[test]
+fn as_bytes() {
+    assert_eq!(LongTermKey::new(KEY).as_bytes(), &KEY);
+}
+
+#[test]
+fn compare_keys() {
+    assert_eq!(LongTermKey::new(KEY), LongTermKey::new(KEY));
+
+    let mut first_differs = KEY;
+    first_differs[0] ^= 0x80;
+    assert_ne!(LongTermKey::new(KEY), LongTermKey::new(first_differs));
+
+    let mut last_differs = KEY;
+    last_differs[15] ^= 0x01;
+    assert_ne!(
+        IdentityResolvingKey::new(KEY),
+        IdentityResolvingKey::new(last_differs)
+    );
+}
@@ -68,3 +68,163 @@ fn connection_parameter_update_response() {
         );
     );
 }
+
+#[cfg(feature = "l2cap-events")]
+mod policy {
+    use bluenrg::event::L2CapConnectionUpdateRequest;
+    use bluenrg::l2cap::*;
+    use hci::types::{ConnectionIntervalBuilder, ExpectedConnectionLength};
+    use std::time::Duration;
+
+    fn policy(clamp: bool) -> L2CapParameterPolicy {
+        L2CapParameterPolicy {
+            interval: (Duration::from_millis(15), Duration::from_millis(50)),
+            conn_latency: (0, 4),
+            supervision_timeout: (Duration::from_millis(100), Duration::from_secs(6)),
+            expected_connection_length: ExpectedConnectionLength::new(
+                Duration::from_millis(0),
+                Duration::from_millis(10),
+            )
+            .unwrap(),
+            clamp,
+        }
+    }
+
+    fn request(
+        interval: (u64, u64),
+        conn_latency: u16,
+        supervision_timeout: Duration,
+    ) -> L2CapConnectionUpdateRequest {
+        L2CapConnectionUpdateRequest {
+            conn_handle: hci::ConnectionHandle(0x0201),
+            identifier: 0xA7,
+            conn_interval: ConnectionIntervalBuilder::new()
+                .with_range(
+                    Duration::from_micros(interval.0),
+                    Duration::from_micros(interval.1),
+                )
+                .with_latency(conn_latency)
+                .with_supervision_timeout(supervision_timeout)
+                .build()
+                .unwrap(),
+        }
+    }
+
+    #[test]
+    fn accept() {
+        let (decision, response) =
+            policy(false).respond(&request((20_000, 40_000), 2, Duration::from_secs(4)));
+        assert_eq!(decision, ParameterDecision::Accepted);
+        assert!(response.accepted);
+        assert_eq!(response.conn_handle, hci::ConnectionHandle(0x0201));
+        assert_eq!(response.identifier, 0xA7);
+        assert_eq!(
+            response.conn_interval.interval(),
+            (Duration::from_millis(20), Duration::from_millis(40))
+        );
+        assert_eq!(response.conn_interval.conn_latency(), 2);
+        assert_eq!(
+            response.conn_interval.supervision_timeout(),
+            Duration::from_secs(4)
+        );
+    }
+
+    #[test]
+    fn reject() {
+        let (decision, response) =
+            policy(false).respond(&request((7_500, 100_000), 2, Duration::from_secs(4)));
+        assert_eq!(decision, ParameterDecision::Rejected);
+        assert!(!response.accepted);
+        assert_eq!(response.conn_handle, hci::ConnectionHandle(0x0201));
+        assert_eq!(response.identifier, 0xA7);
+        assert_eq!(
+            response.conn_interval.interval(),
+            (Duration::from_micros(7_500), Duration::from_millis(100))
+        );
+    }
+
+    #[test]
+    fn clamp() {
+        let (decision, response) =
+            policy(true).respond(&request((7_500, 100_000), 10, Duration::from_secs(10)));
+        assert_eq!(decision, ParameterDecision::Clamped);
+        assert!(response.accepted);
+        assert_eq!(response.identifier, 0xA7);
+        assert_eq!(
+            response.conn_interval.interval(),
+            (Duration::from_millis(15), Duration::from_millis(50))
+        );
+        assert_eq!(response.conn_interval.conn_latency(), 4);
+        assert_eq!(
+            response.conn_interval.supervision_timeout(),
+            Duration::from_secs(6)
+        );
+    }
+
+    #[test]
+    fn clamp_rejects_inconsistent_parameters() {
+        // With a latency of 4, clamping the interval to 15 ms needs a supervision timeout over
+        // 150 ms.
+        let (decision, response) =
+            policy(true).respond(&request((7_500, 10_000), 4, Duration::from_millis(120)));
+        assert_eq!(decision, ParameterDecision::Rejected);
+        assert!(!response.accepted);
+        assert_eq!(response.identifier, 0xA7);
+        assert_eq!(
+            response.conn_interval.interval(),
+            (Duration::from_micros(7_500), Duration::from_millis(10))
+        );
+    }
+}
+
+#[cfg(feature = "l2cap-events")]
+mod request {
+    use super::*;
+    use bluenrg::event::{BlueNRGEvent, L2CapConnectionUpdateRequest};
+    use hci::event::VendorEvent;
+
+    fn decoded_request() -> L2CapConnectionUpdateRequest {
+        let buffer = [
+            0x02, 0x08, 0x01, 0x02, 11, 0xA7, 8, 0, 0x18, 0x00, 0xF0, 0x00, 0x0A, 0x00, 0x95, 0x02,
+        ];
+        match BlueNRGEvent::new(&buffer) {
+            Ok(BlueNRGEvent::L2CapConnectionUpdateRequest(request)) => request,
+            other => panic!("Did not get L2CAP connection update request: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn accept() {
+        let response = decoded_request().accept(
+            ExpectedConnectionLength::new(Duration::from_millis(500), Duration::from_millis(1250))
+                .unwrap(),
+        );
+        let mut sink = RecordingSink::new();
+        {
+            let mut fixture = Fixture::new(&mut sink);
+            fixture
+                .act(|controller| controller.connection_parameter_update_response(&response))
+                .unwrap();
+        }
+        assert!(sink.wrote(&[
+            1, 0x82, 0xFD, 16, 0x01, 0x02, 0x18, 0x00, 0xF0, 0x00, 0x0A, 0x00, 0x95, 0x02, 0x20,
+            0x03, 0xD0, 0x07, 0xA7, 0x01
+        ]));
+    }
+
+    #[test]
+    fn reject() {
+        let response = decoded_request().reject();
+        let mut sink = RecordingSink::new();
+        {
+            let mut fixture = Fixture::new(&mut sink);
+            fixture
+                .act(|controller| controller.connection_parameter_update_response(&response))
+                .unwrap();
+        }
+        assert!(sink.wrote(&[
+            1, 0x82, 0xFD, 16, 0x01, 0x02, 0x18, 0x00, 0xF0, 0x00, 0x0A, 0x00, 0x95, 0x02, 0x00,
+            0x00, 0x00, 0x00, 0xA7, 0x00
+        ]));
+    }
+}
@@ -0,0 +1,51 @@
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+
+use bluenrg::event::link::*;
+use std::time::Duration;
+
+type Event = hci::event::Event<bluenrg::event::BlueNRGEvent>;
+
+#[test]
+fn current_connection_parameters_from_connection_complete() {
+    let buffer = [
+        0x3E, 19, 0x01, 0x00, 0x01, 0x02, 0x00, 0x00, 1, 2, 3, 4, 5, 6, 0x18, 0x00, 0x04, 0x00,
+        0xF4, 0x01, 0x00,
+    ];
+    let event = Event::new(hci::event::Packet(&buffer)).unwrap();
+    assert_eq!(
+        CurrentConnectionParameters::from_event(&event),
+        Some(CurrentConnectionParameters {
+            conn_handle: hci::ConnectionHandle(0x0201),
+            interval: Duration::from_millis(30),
+            conn_latency: 4,
+            supervision_timeout: Duration::from_secs(5),
+        })
+    );
+}
+
+#[test]
+fn current_connection_parameters_from_connection_update_complete() {
+    let buffer = [
+        0x3E, 10, 0x03, 0x00, 0x01, 0x02, 0x06, 0x00, 0x00, 0x00, 0x48, 0x00,
+    ];
+    let event = Event::new(hci::event::Packet(&buffer)).unwrap();
+    assert_eq!(
+        CurrentConnectionParameters::from_event(&event),
+        Some(CurrentConnectionParameters {
+            conn_handle: hci::ConnectionHandle(0x0201),
+            interval: Duration::from_micros(7500),
+            conn_latency: 0,
+            supervision_timeout: Duration::from_millis(720),
+        })
+    );
+}
+
+#[test]
+fn current_connection_parameters_failed_update() {
+    let buffer = [
+        0x3E, 10, 0x03, 0x3B, 0x01, 0x02, 0x06, 0x00, 0x00, 0x00, 0x48, 0x00,
+    ];
+    let event = Event::new(hci::event::Packet(&buffer)).unwrap();
+    assert_eq!(CurrentConnectionParameters::from_event(&event), None);
+}
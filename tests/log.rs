@@ -0,0 +1,53 @@
+#![cfg(feature = "log")]
+
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+extern crate log;
+
+use bluenrg::event::*;
+use hci::event::{Error as HciError, VendorEvent};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingLogger {
+    warnings: AtomicUsize,
+}
+
+impl log::Log for CountingLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        if record.level() == log::Level::Warn {
+            self.warnings.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CountingLogger = CountingLogger {
+    warnings: AtomicUsize::new(0),
+};
+
+// The logger is global, so both paths are checked from a single test to keep the counts
+// independent of test ordering.
+#[test]
+fn parse_failure_logs_one_warning() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let buffer = [0x01, 0x00, 0x01];
+    assert!(BlueNRGEvent::new(&buffer).is_ok());
+    assert_eq!(LOGGER.warnings.load(Ordering::SeqCst), 0);
+
+    let buffer = [0xFF, 0xFF];
+    match BlueNRGEvent::new(&buffer) {
+        Err(HciError::Vendor(ParseError {
+            code: Some(0xFFFF),
+            error: HciError::Vendor(BlueNRGError::UnknownEvent(0xFFFF)),
+        })) => (),
+        other => panic!("Did not get unknown event: {:?}", other),
+    }
+    assert_eq!(LOGGER.warnings.load(Ordering::SeqCst), 1);
+}
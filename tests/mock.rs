@@ -0,0 +1,155 @@
+#![cfg(feature = "mock")]
+
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+
+use bluenrg::event::command::ReturnParameters as BNRGParams;
+use bluenrg::event::*;
+use bluenrg::gap::{Commands as GapCommands, DiscoverableParameters, LocalName};
+use bluenrg::hal::Commands as HalCommands;
+use bluenrg::mock::MockController;
+use bluenrg::{AdvertisingFilterPolicy, AdvertisingType, OwnAddressType};
+use hci::event::command::ReturnParameters as HciParams;
+use hci::event::{Event as HciEvent, VendorReturnParameters};
+use hci::host::uart::{Hci as HciUart, Packet};
+use std::time::Duration;
+
+fn discoverable_parameters<'a, 'b>() -> DiscoverableParameters<'a, 'b> {
+    DiscoverableParameters {
+        advertising_type: AdvertisingType::ConnectableUndirected,
+        advertising_interval: Some((Duration::from_millis(1280), Duration::from_millis(2560))),
+        address_type: OwnAddressType::Public,
+        filter_policy: AdvertisingFilterPolicy::AllowConnectionAndScan,
+        local_name: Some(LocalName::Shortened(b"testdev")),
+        advertising_data: &[],
+        conn_interval: (None, None),
+    }
+}
+
+#[test]
+fn records_commands() {
+    let mut rx_buffer = [0; 64];
+    let mut mock = MockController::new(&mut rx_buffer);
+    mock.act(|controller| controller.set_nondiscoverable())
+        .unwrap();
+
+    assert_eq!(mock.commands().len(), 1);
+    assert_eq!(mock.commands()[0].opcode, hci::Opcode(0xFC81));
+    assert!(mock.commands()[0].params.is_empty());
+}
+
+#[test]
+fn delivers_command_complete() {
+    let mut rx_buffer = [0; 64];
+    let mut mock = MockController::new(&mut rx_buffer);
+    mock.expect(|controller| controller.set_discoverable(&discoverable_parameters()))
+        .then_command_complete(&[0x00]);
+
+    mock.act(|controller| controller.set_discoverable(&discoverable_parameters()))
+        .unwrap();
+    match mock.act(|controller| controller.read()) {
+        Ok(Packet::Event(HciEvent::CommandComplete(event))) => match event.return_params {
+            HciParams::Vendor(BNRGParams::GapSetDiscoverable(status)) => {
+                assert_eq!(status, hci::Status::Success);
+            }
+            other => panic!("Wrong return parameters: {:?}", other),
+        },
+        other => panic!("Did not get command complete: {:?}", other),
+    }
+
+    mock.assert_satisfied();
+}
+
+#[test]
+#[cfg(all(feature = "gap-central-events", not(feature = "bluenrg-lp")))]
+fn delivers_vendor_event_after_command() {
+    let mut rx_buffer = [0; 64];
+    let mut mock = MockController::new(&mut rx_buffer);
+    mock.expect(|controller| controller.set_discoverable(&discoverable_parameters()))
+        .then_command_complete(&[0x00])
+        .then_vendor_event(
+            0x0406,
+            &[
+                0x00, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 3, 0x01, 0x02, 0x03, 0x04,
+            ],
+        );
+
+    // Nothing is delivered until the application issues the expected command.
+    assert!(mock.act(|controller| controller.read()).is_err());
+
+    mock.act(|controller| controller.set_discoverable(&discoverable_parameters()))
+        .unwrap();
+    match mock.act(|controller| controller.read()) {
+        Ok(Packet::Event(HciEvent::CommandComplete(_))) => (),
+        other => panic!("Did not get command complete: {:?}", other),
+    }
+    match mock.act(|controller| controller.read()) {
+        Ok(Packet::Event(HciEvent::Vendor(BlueNRGEvent::GapDeviceFound(event)))) => {
+            assert_eq!(event.bdaddr, BdAddrType::Public(BdAddr([1, 2, 3, 4, 5, 6])));
+            assert_eq!(event.data(), [1, 2, 3]);
+        }
+        other => panic!("Did not get GAP Device Found: {:?}", other),
+    }
+
+    mock.assert_satisfied();
+}
+
+#[test]
+#[cfg(feature = "ms")]
+fn delivers_tx_pool_available() {
+    let mut rx_buffer = [0; 64];
+    let mut mock = MockController::new(&mut rx_buffer);
+    mock.queue_vendor_event(0x0C16, &[0x01, 0x02, 0x03, 0x00]);
+
+    match mock.act(|controller| controller.read()) {
+        Ok(Packet::Event(HciEvent::Vendor(BlueNRGEvent::GattTxPoolAvailable(event)))) => {
+            assert_eq!(event.conn_handle, hci::ConnectionHandle(0x0201));
+            assert_eq!(event.available_buffers, 3);
+        }
+        other => panic!("Did not get GATT TX Pool Available: {:?}", other),
+    }
+
+    mock.assert_satisfied();
+}
+
+#[test]
+fn decodes_le_resolving_list_command_complete() {
+    let mut rx_buffer = [0; 64];
+    let mut mock = MockController::new(&mut rx_buffer);
+    mock.expect(|controller| controller.le_clear_resolving_list());
+
+    mock.act(|controller| controller.le_clear_resolving_list())
+        .unwrap();
+    mock.assert_satisfied();
+
+    // The Command Complete event the controller returns for the recorded command.
+    let opcode = mock.commands()[0].opcode;
+    let return_params = [1, opcode.0 as u8, (opcode.0 >> 8) as u8, 0x00];
+    match BNRGParams::new(&return_params) {
+        Ok(BNRGParams::LeClearResolvingList(status)) => {
+            assert_eq!(status, hci::Status::Success);
+        }
+        other => panic!("Wrong return parameters: {:?}", other),
+    }
+}
+
+#[test]
+#[should_panic(expected = "Command does not match expectation")]
+fn unexpected_command_panics() {
+    let mut rx_buffer = [0; 64];
+    let mut mock = MockController::new(&mut rx_buffer);
+    mock.expect(|controller| controller.set_nondiscoverable());
+
+    mock.act(|controller| controller.set_discoverable(&discoverable_parameters()))
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Expected command was not issued")]
+fn missing_command_panics() {
+    let mut rx_buffer = [0; 64];
+    let mut mock = MockController::new(&mut rx_buffer);
+    mock.expect(|controller| controller.set_nondiscoverable());
+
+    mock.assert_satisfied();
+}
@@ -0,0 +1,275 @@
+#![cfg(feature = "std")]
+
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+
+use bluenrg::event::owned::*;
+use bluenrg::event::*;
+use bluenrg::gatt::Uuid;
+use hci::event::VendorEvent;
+
+#[test]
+fn gap_device_found() {
+    let event = GapDeviceFound::new(
+        GapDeviceFoundEvent::Advertisement,
+        BdAddrType::Public(BdAddr([1, 2, 3, 4, 5, 6])),
+        &[2, 0x01, 0x06, 3, 0xFF, 0x30, 0x00],
+        Some(-60),
+    )
+    .unwrap();
+    let owned = OwnedGapDeviceFound::from(&event);
+    assert_eq!(owned.event, GapDeviceFoundEvent::Advertisement);
+    assert_eq!(owned.bdaddr, BdAddrType::Public(BdAddr([1, 2, 3, 4, 5, 6])));
+    assert_eq!(owned.data, event.data());
+    assert_eq!(owned.rssi, Some(-60));
+}
+
+#[cfg(all(feature = "ms", not(feature = "bluenrg-lp")))]
+#[test]
+fn gatt_attribute_modified() {
+    let buffer = [
+        0x01, 0x0C, 0x01, 0x02, 0x03, 0x04, 0x02, 0x05, 0x86, 0x07, 0x08,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GattAttributeModified(event)) => {
+            let owned = OwnedGattAttributeModified::from(&event);
+            assert_eq!(owned.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(owned.attr_handle, AttributeHandle(0x0403));
+            assert_eq!(owned.offset, Some(0x0605));
+            assert_eq!(owned.continued, Some(true));
+            assert_eq!(owned.data, [0x07, 0x08]);
+        }
+        other => panic!("Did not get GATT attribute modified: {:?}", other),
+    }
+}
+
+#[cfg(feature = "att-client-events")]
+#[test]
+fn att_read_response() {
+    let buffer = [0x07, 0x0C, 0x01, 0x02, 4, 0x01, 0x02, 0x03, 0x04];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttReadResponse(event)) => {
+            let owned = OwnedAttReadResponse::from(&event);
+            assert_eq!(owned.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(owned.value, [0x01, 0x02, 0x03, 0x04]);
+        }
+        other => panic!("Did not get ATT read response: {:?}", other),
+    }
+}
+
+#[cfg(feature = "att-client-events")]
+#[test]
+fn att_read_response_empty() {
+    let buffer = [0x07, 0x0C, 0x01, 0x02, 0];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttReadResponse(event)) => {
+            assert!(OwnedAttReadResponse::from(&event).value.is_empty());
+        }
+        other => panic!("Did not get ATT read response: {:?}", other),
+    }
+}
+
+#[cfg(feature = "att-client-events")]
+#[test]
+fn gatt_notification() {
+    let buffer = [
+        0x0F, 0x0C, 0x01, 0x02, 6, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GattNotification(event)) => {
+            let owned = OwnedAttributeValue::from(&event);
+            assert_eq!(owned.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(owned.attribute_handle, AttributeHandle(0x0403));
+            assert_eq!(owned.value, [0x05, 0x06, 0x07, 0x08]);
+        }
+        other => panic!("Did not get GATT notification: {:?}", other),
+    }
+}
+
+#[cfg(feature = "gap-central-events")]
+#[test]
+fn gap_procedure_complete_name_discovery() {
+    let buffer = [0x07, 0x04, 0x04, 0x00, 0x41, 0x42, 0x43];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GapProcedureComplete(event)) => {
+            let owned = OwnedGapProcedureComplete::from(&event);
+            assert_eq!(
+                owned.procedure,
+                OwnedGapProcedure::NameDiscovery(b"ABC".to_vec())
+            );
+            assert_eq!(owned.status, GapProcedureStatus::Success);
+        }
+        other => panic!("Did not get GAP procedure complete: {:?}", other),
+    }
+}
+
+#[cfg(feature = "att-client-events")]
+#[test]
+fn att_find_information_response() {
+    let buffer = [
+        0x04, 0x0C, 0x01, 0x02, 9, 1, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttFindInformationResponse(event)) => {
+            let owned = OwnedAttFindInformationResponse::from(&event);
+            assert_eq!(owned.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(
+                owned.handle_uuid_pairs,
+                [
+                    (AttributeHandle(0x0403), Uuid::Uuid16(0x0605)),
+                    (AttributeHandle(0x0807), Uuid::Uuid16(0x0a09)),
+                ]
+            );
+        }
+        other => panic!("Did not get ATT find information response: {:?}", other),
+    }
+}
+
+#[cfg(feature = "att-client-events")]
+#[test]
+fn att_find_by_type_value_response() {
+    let buffer = [
+        0x05, 0x0C, 0x01, 0x02, 8, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttFindByTypeValueResponse(event)) => {
+            let owned = OwnedAttFindByTypeValueResponse::from(&event);
+            assert_eq!(owned.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(
+                owned.handles,
+                [
+                    HandleInfoPair {
+                        attribute: AttributeHandle(0x0201),
+                        group_end: GroupEndHandle(0x0403),
+                    },
+                    HandleInfoPair {
+                        attribute: AttributeHandle(0x0605),
+                        group_end: GroupEndHandle(0x0807),
+                    },
+                ]
+            );
+        }
+        other => panic!("Did not get ATT find by type value response: {:?}", other),
+    }
+}
+
+#[cfg(feature = "att-client-events")]
+#[test]
+fn att_read_by_type_response() {
+    let buffer = [
+        0x06, 0x0C, 0x01, 0x02, 9, 4, 0x01, 0x02, 0x03, 0x04, 0x11, 0x12, 0x13, 0x14,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttReadByTypeResponse(event)) => {
+            let owned = OwnedAttReadByTypeResponse::from(&event);
+            assert_eq!(owned.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(
+                owned.handle_value_pairs,
+                [
+                    OwnedValuePair {
+                        handle: AttributeHandle(0x0201),
+                        value: vec![0x03, 0x04],
+                    },
+                    OwnedValuePair {
+                        handle: AttributeHandle(0x1211),
+                        value: vec![0x13, 0x14],
+                    },
+                ]
+            );
+        }
+        other => panic!("Did not get ATT read by type response: {:?}", other),
+    }
+}
+
+#[cfg(feature = "att-client-events")]
+#[test]
+fn att_read_by_group_type_response() {
+    let buffer = [
+        0x0A, 0x0C, 0x01, 0x02, 17, 8, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x11, 0x12,
+        0x13, 0x14, 0x15, 0x16, 0x17, 0x18,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttReadByGroupTypeResponse(event)) => {
+            let owned = OwnedAttReadByGroupTypeResponse::from(&event);
+            assert_eq!(owned.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(
+                owned.attribute_data,
+                [
+                    OwnedAttributeGroup {
+                        attribute_handle: AttributeHandle(0x0201),
+                        group_end_handle: GroupEndHandle(0x0403),
+                        value: vec![0x05, 0x06, 0x07, 0x08],
+                    },
+                    OwnedAttributeGroup {
+                        attribute_handle: AttributeHandle(0x1211),
+                        group_end_handle: GroupEndHandle(0x1413),
+                        value: vec![0x15, 0x16, 0x17, 0x18],
+                    },
+                ]
+            );
+        }
+        other => panic!("Did not get ATT read by group type response: {:?}", other),
+    }
+}
+
+#[cfg(feature = "att-client-events")]
+#[test]
+fn gatt_multiple_notification() {
+    let buffer = [
+        0x1A, 0x0C, 0x01, 0x02, 11, 0x03, 0x04, 2, 0, 0x05, 0x06, 0x07, 0x08, 1, 0, 0x09,
+    ];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GattMultipleNotification(event)) => {
+            let owned = OwnedGattMultipleNotification::from(&event);
+            assert_eq!(owned.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(
+                owned.handle_value_pairs,
+                [
+                    OwnedValuePair {
+                        handle: AttributeHandle(0x0403),
+                        value: vec![0x05, 0x06],
+                    },
+                    OwnedValuePair {
+                        handle: AttributeHandle(0x0807),
+                        value: vec![0x09],
+                    },
+                ]
+            );
+        }
+        other => panic!("Did not get GATT multiple notification: {:?}", other),
+    }
+}
+
+#[test]
+fn att_read_multiple_permit_request() {
+    let buffer = [0x15, 0x0C, 0x01, 0x02, 4, 0x03, 0x04, 0x05, 0x06];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttReadMultiplePermitRequest(event)) => {
+            let owned = OwnedAttReadMultiplePermitRequest::from(&event);
+            assert_eq!(owned.conn_handle, ConnectionHandle(0x0201));
+            assert_eq!(
+                owned.handles,
+                [AttributeHandle(0x0403), AttributeHandle(0x0605)]
+            );
+        }
+        other => panic!("Did not get ATT read multiple permit request: {:?}", other),
+    }
+}
+
+#[cfg(feature = "ms")]
+#[test]
+fn fault_data() {
+    let data = FaultData::new(CrashReason::HardFault, &[0xDE, 0xAD, 0xBE, 0xEF]).unwrap();
+    let owned = OwnedFaultData::from(&data);
+    assert_eq!(owned.reason, CrashReason::HardFault);
+    assert_eq!(owned.debug_data, [0xDE, 0xAD, 0xBE, 0xEF]);
+}
+
+#[test]
+fn errors_implement_std_error() {
+    let error: &dyn std::error::Error = &BlueNRGError::BadPhy(4);
+    assert_eq!(error.to_string(), "unknown PHY 0x04");
+
+    let error: &dyn std::error::Error = &bluenrg::hal::Error::<()>::InvalidChannel(40);
+    assert_eq!(error.to_string(), "channel 40 is greater than 39");
+}
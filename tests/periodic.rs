@@ -0,0 +1,172 @@
+#![cfg(feature = "periodic-advertising")]
+
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+
+use bluenrg::event::periodic::*;
+use bluenrg::event::BlueNRGError;
+use hci::event::Error as HciError;
+use hci::{BdAddr, BdAddrType};
+use std::time::Duration;
+
+#[test]
+fn sync_established() {
+    let buffer = [
+        0x0E, 0x00, 0x01, 0x02, 0x03, 0x01, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x02, 0x50, 0x00,
+        0x05,
+    ];
+    match PeriodicAdvertisingEvent::new(&buffer) {
+        Ok(PeriodicAdvertisingEvent::SyncEstablished(event)) => {
+            assert_eq!(event.status, hci::Status::Success);
+            assert_eq!(event.sync_handle, SyncHandle(0x0201));
+            assert_eq!(event.advertising_sid, 3);
+            assert_eq!(
+                event.advertiser_address,
+                BdAddrType::Random(BdAddr([1, 2, 3, 4, 5, 6]))
+            );
+            assert!(!event.advertiser_address_resolved);
+            assert_eq!(event.advertiser_phy, Phy::Le2M);
+            assert_eq!(
+                event.periodic_advertising_interval,
+                Duration::from_millis(100)
+            );
+            assert_eq!(event.advertiser_clock_accuracy, 5);
+        }
+        other => panic!("Did not get sync established: {:?}", other),
+    }
+}
+
+#[test]
+fn sync_established_resolved_public_identity_address() {
+    let buffer = [
+        0x0E, 0x00, 0x01, 0x02, 0x03, 0x02, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x02, 0x50, 0x00,
+        0x05,
+    ];
+    match PeriodicAdvertisingEvent::new(&buffer) {
+        Ok(PeriodicAdvertisingEvent::SyncEstablished(event)) => {
+            assert_eq!(
+                event.advertiser_address,
+                BdAddrType::Public(BdAddr([1, 2, 3, 4, 5, 6]))
+            );
+            assert!(event.advertiser_address_resolved);
+        }
+        other => panic!("Did not get sync established: {:?}", other),
+    }
+}
+
+#[test]
+fn sync_established_resolved_random_identity_address() {
+    let buffer = [
+        0x0E, 0x00, 0x01, 0x02, 0x03, 0x03, 0x01, 0x02, 0x03, 0x04, 0x05, 0xC6, 0x02, 0x50, 0x00,
+        0x05,
+    ];
+    match PeriodicAdvertisingEvent::new(&buffer) {
+        Ok(PeriodicAdvertisingEvent::SyncEstablished(event)) => {
+            assert_eq!(
+                event.advertiser_address,
+                BdAddrType::Random(BdAddr([1, 2, 3, 4, 5, 0xC6]))
+            );
+            assert!(event.advertiser_address_resolved);
+        }
+        other => panic!("Did not get sync established: {:?}", other),
+    }
+}
+
+#[test]
+fn sync_established_failed_bad_address_type() {
+    let buffer = [
+        0x0E, 0x00, 0x01, 0x02, 0x03, 0x04, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x02, 0x50, 0x00,
+        0x05,
+    ];
+    match PeriodicAdvertisingEvent::new(&buffer) {
+        Err(HciError::Vendor(BlueNRGError::BadBdAddrType(value))) => assert_eq!(value, 4),
+        other => panic!("Did not get bad address type: {:?}", other),
+    }
+}
+
+#[test]
+fn sync_established_failed_bad_phy() {
+    let buffer = [
+        0x0E, 0x00, 0x01, 0x02, 0x03, 0x01, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x04, 0x50, 0x00,
+        0x05,
+    ];
+    match PeriodicAdvertisingEvent::new(&buffer) {
+        Err(HciError::Vendor(BlueNRGError::BadPhy(value))) => assert_eq!(value, 4),
+        other => panic!("Did not get bad PHY: {:?}", other),
+    }
+}
+
+#[test]
+fn sync_established_failed_bad_length() {
+    let buffer = [
+        0x0E, 0x00, 0x01, 0x02, 0x03, 0x01, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x02, 0x50, 0x00,
+    ];
+    match PeriodicAdvertisingEvent::new(&buffer) {
+        Err(HciError::BadLength(actual, expected)) => {
+            assert_eq!(actual, 15);
+            assert_eq!(expected, 16);
+        }
+        other => panic!("Did not get bad length: {:?}", other),
+    }
+}
+
+#[test]
+fn report() {
+    let buffer = [
+        0x0F, 0x01, 0x02, 0xFC, 0xC4, 0xFF, 0x01, 3, 0x01, 0x02, 0x03,
+    ];
+    match PeriodicAdvertisingEvent::new(&buffer) {
+        Ok(PeriodicAdvertisingEvent::Report(event)) => {
+            assert_eq!(event.sync_handle, SyncHandle(0x0201));
+            assert_eq!(event.tx_power, Some(-4));
+            assert_eq!(event.rssi, Some(-60));
+            assert_eq!(event.data_status, DataStatus::Incomplete);
+            assert_eq!(event.data(), [1, 2, 3]);
+        }
+        other => panic!("Did not get report: {:?}", other),
+    }
+}
+
+#[test]
+fn report_largest() {
+    let mut buffer = [0; 255];
+    buffer[..8].copy_from_slice(&[0x0F, 0x01, 0x02, 0x7F, 0x7F, 0xFF, 0x00, 247]);
+    match PeriodicAdvertisingEvent::new(&buffer) {
+        Ok(PeriodicAdvertisingEvent::Report(event)) => assert_eq!(event.data().len(), 247),
+        other => panic!("Did not get report: {:?}", other),
+    }
+}
+
+#[test]
+fn report_unavailable_tx_power_and_rssi() {
+    let buffer = [0x0F, 0x01, 0x02, 0x7F, 0x7F, 0xFF, 0x00, 0];
+    match PeriodicAdvertisingEvent::new(&buffer) {
+        Ok(PeriodicAdvertisingEvent::Report(event)) => {
+            assert_eq!(event.tx_power, None);
+            assert_eq!(event.rssi, None);
+            assert_eq!(event.data_status, DataStatus::Complete);
+            assert!(event.data().is_empty());
+        }
+        other => panic!("Did not get report: {:?}", other),
+    }
+}
+
+#[test]
+fn report_failed_bad_data_status() {
+    let buffer = [0x0F, 0x01, 0x02, 0x7F, 0x7F, 0xFF, 0x03, 0];
+    match PeriodicAdvertisingEvent::new(&buffer) {
+        Err(HciError::Vendor(BlueNRGError::BadPeriodicAdvertisingDataStatus(value))) => {
+            assert_eq!(value, 3)
+        }
+        other => panic!("Did not get bad data status: {:?}", other),
+    }
+}
+
+#[test]
+fn unknown_subevent() {
+    let buffer = [0x0D, 0x00];
+    match PeriodicAdvertisingEvent::new(&buffer) {
+        Err(HciError::Vendor(BlueNRGError::UnknownEvent(value))) => assert_eq!(value, 0x0D),
+        other => panic!("Did not get unknown event: {:?}", other),
+    }
+}
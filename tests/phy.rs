@@ -0,0 +1,55 @@
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+
+use bluenrg::event::phy::*;
+use bluenrg::event::BlueNRGError;
+use bluenrg::hal::PhyMask;
+use hci::event::Error as HciError;
+
+#[test]
+fn phy_update_complete_2m() {
+    let buffer = [0x0C, 0x00, 0x01, 0x02, 0x02, 0x02];
+    match LePhyUpdateComplete::new(&buffer) {
+        Ok(event) => {
+            assert_eq!(event.status, hci::Status::Success);
+            assert_eq!(event.conn_handle, hci::ConnectionHandle(0x0201));
+            assert_eq!(event.tx_phy, Some(Phy::Le2M));
+            assert_eq!(event.rx_phy, Some(Phy::Le2M));
+            assert_eq!(PhyMask::from(event.tx_phy.unwrap()), PhyMask::LE_2M);
+        }
+        other => panic!("Did not get PHY update complete: {:?}", other),
+    }
+}
+
+#[test]
+fn phy_update_complete_failed_bad_phy() {
+    let buffer = [0x0C, 0x00, 0x01, 0x02, 0x01, 0x04];
+    match LePhyUpdateComplete::new(&buffer) {
+        Err(HciError::Vendor(BlueNRGError::BadPhy(value))) => assert_eq!(value, 4),
+        other => panic!("Did not get bad PHY: {:?}", other),
+    }
+}
+
+#[test]
+fn phy_update_complete_unsuccessful() {
+    // The PHY fields are not checked when the procedure fails.
+    let buffer = [0x0C, 0x1A, 0x01, 0x02, 0x00, 0x00];
+    match LePhyUpdateComplete::new(&buffer) {
+        Ok(event) => {
+            assert_eq!(event.status, hci::Status::UnsupportedRemoteFeature);
+            assert_eq!(event.conn_handle, hci::ConnectionHandle(0x0201));
+            assert_eq!(event.tx_phy, None);
+            assert_eq!(event.rx_phy, None);
+        }
+        other => panic!("Did not get PHY update complete: {:?}", other),
+    }
+}
+
+#[test]
+fn phy_update_complete_failed_other_subevent() {
+    let buffer = [0x0E, 0x00, 0x01, 0x02, 0x01, 0x01];
+    match LePhyUpdateComplete::new(&buffer) {
+        Err(HciError::Vendor(BlueNRGError::UnknownEvent(code))) => assert_eq!(code, 0x0E),
+        other => panic!("Did not get unknown event: {:?}", other),
+    }
+}
@@ -0,0 +1,347 @@
+#![cfg(feature = "att-client-events")]
+
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+extern crate embedded_hal as hal;
+extern crate nb;
+
+mod fixture;
+
+use bluenrg::event::*;
+use bluenrg::gatt::{CharacteristicHandle, Commands as GattCommands, RangeError};
+use bluenrg::procedure::*;
+use fixture::{Fixture, RecordingSink};
+use hci::event::VendorEvent;
+
+fn procedure_complete(conn_handle: u16, status: u8) -> BlueNRGEvent {
+    BlueNRGEvent::new(&[
+        0x10,
+        0x0C,
+        conn_handle as u8,
+        (conn_handle >> 8) as u8,
+        1,
+        status,
+    ])
+    .unwrap()
+}
+
+#[test]
+fn read_then_complete() {
+    let conn_handle = hci::ConnectionHandle(0x0201);
+    let mut procedures = PendingProcedures::new();
+
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| {
+                controller.read_characteristic_value(conn_handle, CharacteristicHandle(0x0403))
+            })
+            .unwrap();
+    }
+    procedures
+        .start(conn_handle, GattProcedure::ReadCharacteristicValue)
+        .unwrap();
+    assert_eq!(
+        procedures.pending(conn_handle),
+        Some(GattProcedure::ReadCharacteristicValue)
+    );
+
+    assert_eq!(
+        procedures.handle_event(&procedure_complete(0x0201, 0x00)),
+        Some(CompletedProcedure {
+            conn_handle,
+            procedure: GattProcedure::ReadCharacteristicValue,
+            outcome: ProcedureOutcome::Success,
+        })
+    );
+    assert_eq!(procedures.pending(conn_handle), None);
+}
+
+#[test]
+fn completion_on_other_connection() {
+    let mut procedures = PendingProcedures::new();
+    procedures
+        .start(
+            hci::ConnectionHandle(0x0201),
+            GattProcedure::DiscoverAllPrimaryServices,
+        )
+        .unwrap();
+
+    assert_eq!(
+        procedures.handle_event(&procedure_complete(0x0202, 0x41)),
+        None
+    );
+    assert_eq!(
+        procedures.pending(hci::ConnectionHandle(0x0201)),
+        Some(GattProcedure::DiscoverAllPrimaryServices)
+    );
+}
+
+#[test]
+fn timeout_ends_procedure() {
+    let mut procedures = PendingProcedures::new();
+    procedures
+        .start(
+            hci::ConnectionHandle(0x0201),
+            GattProcedure::WriteCharacteristicValue,
+        )
+        .unwrap();
+
+    let timeout = BlueNRGEvent::new(&[0x02, 0x0C, 0x01, 0x02]).unwrap();
+    match procedures.handle_event(&timeout) {
+        Some(completed) => {
+            assert_eq!(completed.procedure, GattProcedure::WriteCharacteristicValue);
+            assert_eq!(completed.outcome, ProcedureOutcome::Timeout);
+        }
+        other => panic!("Did not get completed procedure: {:?}", other),
+    }
+}
+
+#[test]
+fn one_procedure_per_connection() {
+    let mut procedures = PendingProcedures::new();
+    procedures
+        .start(hci::ConnectionHandle(0x0201), GattProcedure::ReadByType)
+        .unwrap();
+    assert_eq!(
+        procedures.start(
+            hci::ConnectionHandle(0x0201),
+            GattProcedure::ReadByGroupType
+        ),
+        Err(PendingProcedureError::Busy(GattProcedure::ReadByType))
+    );
+}
+
+#[test]
+fn custom_procedure_context() {
+    let mut procedures = PendingProcedures::new();
+    procedures
+        .start(hci::ConnectionHandle(0x0201), CharacteristicHandle(0x0403))
+        .unwrap();
+
+    match procedures.handle_event(&procedure_complete(0x0201, 0x00)) {
+        Some(completed) => assert_eq!(completed.procedure, CharacteristicHandle(0x0403)),
+        other => panic!("Did not get completed procedure: {:?}", other),
+    }
+}
+
+fn find_information_response(conn_handle: u16, pairs: &[u8]) -> BlueNRGEvent {
+    let mut buffer = vec![
+        0x04,
+        0x0C,
+        conn_handle as u8,
+        (conn_handle >> 8) as u8,
+        1 + pairs.len() as u8,
+        1,
+    ];
+    buffer.extend_from_slice(pairs);
+    BlueNRGEvent::new(&buffer).unwrap()
+}
+
+#[test]
+fn cccd_discovery_finds_cccd() {
+    let conn_handle = hci::ConnectionHandle(0x0201);
+    let mut discovery = CccdDiscovery::new(
+        conn_handle,
+        CharacteristicHandle(0x0010),
+        CharacteristicHandle(0x0013),
+    )
+    .unwrap();
+
+    let mut sink = RecordingSink::new();
+    {
+        let mut fixture = Fixture::new(&mut sink);
+        fixture
+            .act(|controller| discovery.start(controller))
+            .unwrap();
+    }
+    assert!(sink.wrote(&[1, 0x17, 0xFD, 6, 0x01, 0x02, 0x11, 0x00, 0x13, 0x00]));
+
+    // User description, then the CCCD, then a presentation format descriptor.
+    let response = find_information_response(
+        0x0201,
+        &[
+            0x11, 0x00, 0x01, 0x29, 0x12, 0x00, 0x02, 0x29, 0x13, 0x00, 0x04, 0x29,
+        ],
+    );
+    match response {
+        BlueNRGEvent::AttFindInformationResponse(ref event) => {
+            assert_eq!(event.cccd_handle(), Some(AttributeHandle(0x0012)))
+        }
+        ref other => panic!("Did not get find information response: {:?}", other),
+    }
+    assert_eq!(discovery.handle_event(&response), None);
+    assert_eq!(discovery.cccd_handle(), Some(AttributeHandle(0x0012)));
+
+    // Responses and completions for other connections are ignored.
+    assert_eq!(
+        discovery.handle_event(&procedure_complete(0x0301, 0x00)),
+        None
+    );
+    assert_eq!(
+        discovery.handle_event(&procedure_complete(0x0201, 0x00)),
+        Some(ProcedureOutcome::Success)
+    );
+    assert_eq!(discovery.cccd_handle(), Some(AttributeHandle(0x0012)));
+}
+
+#[test]
+fn cccd_discovery_without_cccd() {
+    let conn_handle = hci::ConnectionHandle(0x0201);
+    let mut discovery = CccdDiscovery::new(
+        conn_handle,
+        CharacteristicHandle(0x0010),
+        CharacteristicHandle(0x0011),
+    )
+    .unwrap();
+    assert_eq!(
+        discovery.handle_event(&find_information_response(
+            0x0201,
+            &[0x11, 0x00, 0x01, 0x29]
+        )),
+        None
+    );
+    assert_eq!(
+        discovery.handle_event(&procedure_complete(0x0201, 0x00)),
+        Some(ProcedureOutcome::Success)
+    );
+    assert_eq!(discovery.cccd_handle(), None);
+}
+
+#[test]
+fn cccd_discovery_needs_room_for_descriptors() {
+    let conn_handle = hci::ConnectionHandle(0x0201);
+    assert_eq!(
+        CccdDiscovery::new(
+            conn_handle,
+            CharacteristicHandle(0x0010),
+            CharacteristicHandle(0x0010)
+        )
+        .err(),
+        Some(RangeError::Inverted)
+    );
+    assert_eq!(
+        CccdDiscovery::new(
+            conn_handle,
+            CharacteristicHandle(0xFFFF),
+            CharacteristicHandle(0xFFFF)
+        )
+        .err(),
+        Some(RangeError::Inverted)
+    );
+}
+
+fn att_error_response(conn_handle: u16) -> BlueNRGEvent {
+    BlueNRGEvent::new(&[
+        0x11,
+        0x0C,
+        conn_handle as u8,
+        (conn_handle >> 8) as u8,
+        4,
+        0x0A,
+        0x03,
+        0x00,
+        0x02,
+    ])
+    .unwrap()
+}
+
+fn read_response(conn_handle: u16) -> BlueNRGEvent {
+    BlueNRGEvent::new(&[
+        0x07,
+        0x0C,
+        conn_handle as u8,
+        (conn_handle >> 8) as u8,
+        2,
+        0x01,
+        0x02,
+    ])
+    .unwrap()
+}
+
+#[test]
+fn tracker_success() {
+    let mut tracker = ProcedureTracker::new(hci::ConnectionHandle(0x0201));
+    assert_eq!(tracker.handle_event(&read_response(0x0201)), None);
+    assert_eq!(
+        tracker.handle_event(&procedure_complete(0x0201, 0x00)),
+        Some(ProcedureOutcome::Success)
+    );
+}
+
+#[test]
+fn tracker_att_error() {
+    let mut tracker = ProcedureTracker::new(hci::ConnectionHandle(0x0201));
+    assert_eq!(tracker.handle_event(&att_error_response(0x0201)), None);
+    match tracker.handle_event(&procedure_complete(0x0201, 0x41)) {
+        Some(ProcedureOutcome::AttError(response)) => {
+            assert_eq!(response.conn_handle, hci::ConnectionHandle(0x0201));
+            assert_eq!(response.request, AttRequest::ReadRequest);
+            assert_eq!(response.attribute_handle, AttributeHandle(0x0003));
+            assert_eq!(response.error, AttError::ReadNotPermitted);
+        }
+        other => panic!("Did not get ATT error outcome: {:?}", other),
+    }
+
+    // The error is not reported again for the next procedure.
+    assert_eq!(
+        tracker.handle_event(&procedure_complete(0x0201, 0x00)),
+        Some(ProcedureOutcome::Success)
+    );
+}
+
+#[test]
+fn tracker_failed() {
+    let mut tracker = ProcedureTracker::new(hci::ConnectionHandle(0x0201));
+    assert_eq!(
+        tracker.handle_event(&procedure_complete(0x0201, 0x41)),
+        Some(ProcedureOutcome::Failed(GattProcedureStatus::Failed))
+    );
+}
+
+#[test]
+fn tracker_timeout() {
+    let mut tracker = ProcedureTracker::new(hci::ConnectionHandle(0x0201));
+    assert_eq!(tracker.handle_event(&att_error_response(0x0201)), None);
+    assert_eq!(
+        tracker.handle_event(&BlueNRGEvent::new(&[0x02, 0x0C, 0x01, 0x02]).unwrap()),
+        Some(ProcedureOutcome::Timeout)
+    );
+}
+
+#[test]
+fn tracker_ignores_other_connections() {
+    let mut tracker = ProcedureTracker::new(hci::ConnectionHandle(0x0201));
+    assert_eq!(tracker.handle_event(&att_error_response(0x0202)), None);
+    assert_eq!(
+        tracker.handle_event(&procedure_complete(0x0202, 0x00)),
+        None
+    );
+    assert_eq!(
+        tracker.handle_event(&procedure_complete(0x0201, 0x00)),
+        Some(ProcedureOutcome::Success)
+    );
+}
+
+#[test]
+fn pending_procedure_att_error() {
+    let mut procedures = PendingProcedures::new();
+    procedures
+        .start(
+            hci::ConnectionHandle(0x0201),
+            GattProcedure::ReadCharacteristicValue,
+        )
+        .unwrap();
+
+    assert_eq!(procedures.handle_event(&att_error_response(0x0201)), None);
+    match procedures.handle_event(&procedure_complete(0x0201, 0x41)) {
+        Some(CompletedProcedure {
+            procedure: GattProcedure::ReadCharacteristicValue,
+            outcome: ProcedureOutcome::AttError(response),
+            ..
+        }) => assert_eq!(response.error, AttError::ReadNotPermitted),
+        other => panic!("Did not get ATT error outcome: {:?}", other),
+    }
+    assert_eq!(procedures.pending(hci::ConnectionHandle(0x0201)), None);
+}
@@ -0,0 +1,195 @@
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+
+use bluenrg::event::BlueNRGEvent;
+use bluenrg::rebond::*;
+use hci::event::{Event, Packet};
+
+const CONN_HANDLE: hci::ConnectionHandle = hci::ConnectionHandle(0x0201);
+const PEER_ADDRESS: hci::BdAddrType = hci::BdAddrType::Public(hci::BdAddr([1, 2, 3, 4, 5, 6]));
+
+fn event(bytes: &[u8]) -> Event<BlueNRGEvent> {
+    Event::new(Packet(bytes)).unwrap()
+}
+
+fn connection_complete() -> Event<BlueNRGEvent> {
+    event(&[
+        0x3E, 19, 0x01, 0x00, 0x01, 0x02, 0x01, 0x00, 1, 2, 3, 4, 5, 6, 0x18, 0x00, 0x00, 0x00,
+        0xF4, 0x01, 0x00,
+    ])
+}
+
+fn connection_complete_from(conn_handle: u8, addr_type: u8, addr: [u8; 6]) -> Event<BlueNRGEvent> {
+    let mut bytes = [
+        0x3E, 19, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0, 0, 0, 0, 0, 0, 0x18, 0x00, 0x00, 0x00,
+        0xF4, 0x01, 0x00,
+    ];
+    bytes[4] = conn_handle;
+    bytes[7] = addr_type;
+    bytes[8..14].copy_from_slice(&addr);
+    event(&bytes)
+}
+
+fn bond_lost() -> Event<BlueNRGEvent> {
+    event(&[0xFF, 2, 0x05, 0x04])
+}
+
+fn pairing_complete() -> Event<BlueNRGEvent> {
+    event(&[0xFF, 6, 0x01, 0x04, 0x01, 0x02, 0x00, 0x00])
+}
+
+fn disconnection_complete() -> Event<BlueNRGEvent> {
+    event(&[0x05, 4, 0x00, 0x01, 0x02, 0x13])
+}
+
+fn lose_bond<F>(policy: &mut RebondPolicy<F>) -> Option<RebondAction>
+where
+    F: FnMut(&Peer) -> bool,
+{
+    assert_eq!(policy.handle_event(&connection_complete()), None);
+    policy.handle_event(&bond_lost())
+}
+
+#[test]
+fn allow() {
+    let mut policy = RebondPolicy::allow();
+    assert_eq!(
+        lose_bond(&mut policy),
+        Some(RebondAction::AllowRebond(CONN_HANDLE))
+    );
+    assert_eq!(policy.rebonding(), Some(CONN_HANDLE));
+}
+
+#[test]
+fn reject() {
+    let mut policy = RebondPolicy::reject();
+    assert_eq!(
+        lose_bond(&mut policy),
+        Some(RebondAction::Terminate(
+            CONN_HANDLE,
+            hci::Status::AuthFailure
+        ))
+    );
+    assert_eq!(policy.rebonding(), None);
+}
+
+#[test]
+fn allow_if() {
+    let mut user_present = false;
+    let mut peers = Vec::new();
+    {
+        let mut policy = RebondPolicy::allow_if(|peer: &Peer| {
+            peers.push(*peer);
+            user_present
+        });
+        assert_eq!(
+            lose_bond(&mut policy),
+            Some(RebondAction::Terminate(
+                CONN_HANDLE,
+                hci::Status::AuthFailure
+            ))
+        );
+    }
+
+    user_present = true;
+    let mut policy = RebondPolicy::allow_if(|_: &Peer| user_present);
+    assert_eq!(
+        lose_bond(&mut policy),
+        Some(RebondAction::AllowRebond(CONN_HANDLE))
+    );
+    assert_eq!(
+        peers,
+        [Peer {
+            conn_handle: CONN_HANDLE,
+            address: PEER_ADDRESS,
+        }]
+    );
+}
+
+#[test]
+fn allow_list() {
+    let bonded = [PEER_ADDRESS];
+    let mut policy = RebondPolicy::allow().with_allow_list(&bonded);
+    assert_eq!(
+        lose_bond(&mut policy),
+        Some(RebondAction::AllowRebond(CONN_HANDLE))
+    );
+
+    let bonded = [hci::BdAddrType::Random(hci::BdAddr([1, 2, 3, 4, 5, 6]))];
+    let mut policy = RebondPolicy::allow().with_allow_list(&bonded);
+    assert_eq!(
+        lose_bond(&mut policy),
+        Some(RebondAction::Terminate(
+            CONN_HANDLE,
+            hci::Status::AuthFailure
+        ))
+    );
+}
+
+#[test]
+fn allow_list_does_not_resolve_private_addresses() {
+    // The bonded peer connects with a resolvable private address instead of its identity address.
+    let bonded = [PEER_ADDRESS];
+    let mut policy = RebondPolicy::allow().with_allow_list(&bonded);
+    assert_eq!(
+        policy.handle_event(&connection_complete_from(0x03, 0x01, [1, 2, 3, 4, 5, 0x45])),
+        None
+    );
+    assert_eq!(
+        policy.handle_event(&bond_lost()),
+        Some(RebondAction::Terminate(
+            hci::ConnectionHandle(0x0003),
+            hci::Status::AuthFailure
+        ))
+    );
+}
+
+#[test]
+fn allow_list_checks_most_recent_connection() {
+    let bonded = [PEER_ADDRESS];
+    let mut policy = RebondPolicy::allow().with_allow_list(&bonded);
+    assert_eq!(policy.handle_event(&connection_complete()), None);
+    assert_eq!(
+        policy.handle_event(&connection_complete_from(0x03, 0x00, [6, 5, 4, 3, 2, 1])),
+        None
+    );
+    assert_eq!(
+        policy.handle_event(&bond_lost()),
+        Some(RebondAction::Terminate(
+            hci::ConnectionHandle(0x0003),
+            hci::Status::AuthFailure
+        ))
+    );
+
+    // Once the most recent connection closes, the earlier one is not tracked anymore.
+    assert_eq!(
+        policy.handle_event(&event(&[0x05, 4, 0x00, 0x03, 0x00, 0x13])),
+        None
+    );
+    assert_eq!(policy.handle_event(&bond_lost()), None);
+}
+
+#[test]
+fn pairing_complete_ends_rebond() {
+    let mut policy = RebondPolicy::allow();
+    lose_bond(&mut policy).unwrap();
+    assert_eq!(policy.handle_event(&pairing_complete()), None);
+    assert_eq!(policy.rebonding(), None);
+
+    // The connection is still open, so a later bond lost event applies to it again.
+    assert_eq!(
+        policy.handle_event(&bond_lost()),
+        Some(RebondAction::AllowRebond(CONN_HANDLE))
+    );
+}
+
+#[test]
+fn bond_lost_without_connection() {
+    let mut policy = RebondPolicy::allow();
+    assert_eq!(policy.handle_event(&bond_lost()), None);
+
+    lose_bond(&mut policy).unwrap();
+    assert_eq!(policy.handle_event(&disconnection_complete()), None);
+    assert_eq!(policy.rebonding(), None);
+    assert_eq!(policy.handle_event(&bond_lost()), None);
+}
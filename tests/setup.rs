@@ -0,0 +1,219 @@
+#![cfg(all(feature = "mock", feature = "ms"))]
+
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+extern crate nb;
+
+use bluenrg::event::ResetReason;
+use bluenrg::gap::{
+    Appearance, AuthenticationRequirements, Commands as GapCommands, DiscoverableParameters,
+    LocalName, OutOfBandAuthentication, Pin, Role,
+};
+use bluenrg::gatt::{
+    CharacteristicHandle, Commands as GattCommands, ServiceHandle,
+    UpdateCharacteristicValueParameters,
+};
+use bluenrg::hal::{Commands as HalCommands, ConfigData, PowerLevel};
+use bluenrg::mock::MockController;
+use bluenrg::setup::{BlueNrgSetup, SetupError, SetupParameters, SetupProgress, SetupStep};
+use bluenrg::{AdvertisingFilterPolicy, AdvertisingType, OwnAddressType};
+use hci::host::uart::{Hci as HciUart, Packet};
+use hci::BdAddr;
+use std::time::Duration;
+
+const ADDRESS: BdAddr = BdAddr([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+const NAME: &[u8] = b"testdev";
+
+fn authentication() -> AuthenticationRequirements {
+    AuthenticationRequirements {
+        mitm_protection_required: true,
+        out_of_band_auth: OutOfBandAuthentication::Disabled,
+        encryption_key_size_range: (7, 16),
+        fixed_pin: Pin::Fixed(123_456),
+        bonding_required: true,
+    }
+}
+
+fn discoverable<'a, 'b>() -> DiscoverableParameters<'a, 'b> {
+    DiscoverableParameters {
+        advertising_type: AdvertisingType::ConnectableUndirected,
+        advertising_interval: Some((Duration::from_millis(100), Duration::from_millis(200))),
+        address_type: OwnAddressType::Public,
+        filter_policy: AdvertisingFilterPolicy::AllowConnectionAndScan,
+        local_name: Some(LocalName::Complete(NAME)),
+        advertising_data: &[],
+        conn_interval: (None, None),
+    }
+}
+
+fn parameters<'a, 'b>() -> SetupParameters<'a, 'b> {
+    SetupParameters {
+        address: ADDRESS,
+        name: NAME,
+        appearance: Appearance::GenericWatch,
+        role: Role::PERIPHERAL,
+        privacy_enabled: false,
+        tx_power_level: PowerLevel::DbmNeg2_1,
+        authentication: authentication(),
+        discoverable: discoverable(),
+    }
+}
+
+// Feeds the setup every event the mock has ready, and returns the last progress reported.
+fn run(
+    mock: &mut MockController,
+    setup: &mut BlueNrgSetup,
+) -> nb::Result<SetupProgress, SetupError<()>> {
+    let mut progress = Ok(SetupProgress::WaitingForBoot);
+    while let Ok(Packet::Event(event)) = mock.act(|controller| controller.read()) {
+        progress = mock.act(|controller| setup.poll(controller, &event));
+        if progress.is_err() {
+            break;
+        }
+    }
+
+    progress
+}
+
+#[test]
+fn runs_sequence_in_order() {
+    let mut rx_buffer = [0; 64];
+    let mut mock = MockController::new(&mut rx_buffer);
+    mock.expect(|controller| {
+        controller.write_config_data(&ConfigData::public_address(ADDRESS).build())
+    })
+    .then_command_complete(&[0x00]);
+    mock.expect(|controller| controller.set_tx_power_level(PowerLevel::DbmNeg2_1))
+        .then_command_complete(&[0x00]);
+    mock.expect(|controller| controller.init_gatt())
+        .then_command_complete(&[0x00]);
+    mock.expect(|controller| controller.init_gap(Role::PERIPHERAL, false, 7))
+        .then_command_complete(&[0x00, 0x01, 0x00, 0x02, 0x00, 0x04, 0x00]);
+    mock.expect(|controller| {
+        controller.update_characteristic_value(&UpdateCharacteristicValueParameters {
+            service_handle: ServiceHandle(0x0001),
+            characteristic_handle: CharacteristicHandle(0x0002),
+            offset: 0,
+            value: NAME,
+        })
+    })
+    .then_command_complete(&[0x00]);
+    mock.expect(|controller| {
+        controller.update_characteristic_value(&UpdateCharacteristicValueParameters {
+            service_handle: ServiceHandle(0x0001),
+            characteristic_handle: CharacteristicHandle(0x0004),
+            offset: 0,
+            value: &[0xC0, 0x00],
+        })
+    })
+    .then_command_complete(&[0x00]);
+    mock.expect(|controller| controller.set_authentication_requirement(&authentication()))
+        .then_command_complete(&[0x00]);
+    mock.expect(|controller| controller.set_discoverable(&discoverable()))
+        .then_command_complete(&[0x00]);
+
+    let mut setup = BlueNrgSetup::new(parameters());
+    mock.queue_vendor_event(0x0001, &[0x01]);
+    match run(&mut mock, &mut setup) {
+        Ok(SetupProgress::Complete(gap)) => {
+            assert_eq!(gap.service_handle, ServiceHandle(0x0001));
+            assert_eq!(gap.dev_name_handle, CharacteristicHandle(0x0002));
+            assert_eq!(gap.appearance_handle, CharacteristicHandle(0x0004));
+        }
+        other => panic!("Did not complete setup: {:?}", other),
+    }
+
+    mock.assert_satisfied();
+}
+
+#[test]
+fn waits_for_boot() {
+    let mut rx_buffer = [0; 64];
+    let mut mock = MockController::new(&mut rx_buffer);
+    let mut setup = BlueNrgSetup::new(parameters());
+
+    // GAP Pairing Complete
+    mock.queue_vendor_event(0x0401, &[0x01, 0x02, 0x00]);
+    match run(&mut mock, &mut setup) {
+        Ok(SetupProgress::WaitingForBoot) => (),
+        other => panic!("Did not wait for boot: {:?}", other),
+    }
+    assert!(mock.commands().is_empty());
+}
+
+#[test]
+fn reports_progress_after_each_step() {
+    let mut rx_buffer = [0; 64];
+    let mut mock = MockController::new(&mut rx_buffer);
+    mock.expect(|controller| {
+        controller.write_config_data(&ConfigData::public_address(ADDRESS).build())
+    })
+    .then_command_complete(&[0x00]);
+    mock.expect(|controller| controller.set_tx_power_level(PowerLevel::DbmNeg2_1));
+
+    let mut setup = BlueNrgSetup::new(parameters());
+    mock.queue_vendor_event(0x0001, &[0x01]);
+    match run(&mut mock, &mut setup) {
+        Ok(SetupProgress::Started(SetupStep::SetTxPowerLevel)) => (),
+        other => panic!("Did not start setting the TX power level: {:?}", other),
+    }
+    assert!(setup.gap_handles().is_none());
+
+    mock.assert_satisfied();
+}
+
+#[test]
+fn reports_failed_step() {
+    let mut rx_buffer = [0; 64];
+    let mut mock = MockController::new(&mut rx_buffer);
+    mock.expect(|controller| {
+        controller.write_config_data(&ConfigData::public_address(ADDRESS).build())
+    })
+    .then_command_complete(&[0x12]);
+
+    let mut setup = BlueNrgSetup::new(parameters());
+    mock.queue_vendor_event(0x0001, &[0x01]);
+    match run(&mut mock, &mut setup) {
+        Err(nb::Error::Other(SetupError::CommandFailed(step, status))) => {
+            assert_eq!(step, SetupStep::WriteConfigData);
+            assert_eq!(status, hci::Status::InvalidParameters);
+        }
+        other => panic!("Did not get failed step: {:?}", other),
+    }
+
+    mock.assert_satisfied();
+}
+
+#[test]
+fn reports_updater_mode() {
+    let mut rx_buffer = [0; 64];
+    let mut mock = MockController::new(&mut rx_buffer);
+    let mut setup = BlueNrgSetup::new(parameters());
+
+    mock.queue_vendor_event(0x0001, &[0x02]);
+    match run(&mut mock, &mut setup) {
+        Err(nb::Error::Other(SetupError::EnteredUpdater(reason))) => {
+            assert_eq!(reason, ResetReason::Updater)
+        }
+        other => panic!("Did not get updater mode: {:?}", other),
+    }
+    assert!(mock.commands().is_empty());
+}
+
+#[test]
+fn rejects_long_name() {
+    let mut rx_buffer = [0; 64];
+    let mut mock = MockController::new(&mut rx_buffer);
+    let name = [b'a'; 256];
+    let mut setup = BlueNrgSetup::new(SetupParameters {
+        name: &name,
+        ..parameters()
+    });
+
+    mock.queue_vendor_event(0x0001, &[0x01]);
+    match run(&mut mock, &mut setup) {
+        Err(nb::Error::Other(SetupError::NameTooLong(len))) => assert_eq!(len, 256),
+        other => panic!("Did not reject long name: {:?}", other),
+    }
+    assert!(mock.commands().is_empty());
+}
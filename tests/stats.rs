@@ -0,0 +1,168 @@
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+
+use bluenrg::event::*;
+use bluenrg::stats::*;
+
+fn record_all(stats: &mut EventStats, packets: &[&[u8]]) {
+    for packet in packets {
+        stats.record(&parse(packet));
+    }
+}
+
+#[test]
+fn counts_mixed_sequence() {
+    let mut stats = EventStats::new();
+    record_all(
+        &mut stats,
+        &[
+            &[0x01, 0x00, 0x01],       // HAL initialized
+            &[0x02, 0x04, 0x01, 0x02], // pass key request
+            &[0x02, 0x04, 0x01],       // pass key request, too short
+            &[0x99, 0x0C],             // unknown
+            &[0x01, 0x00, 0x7F],       // HAL initialized, bad reset reason
+            &[0x02, 0x04, 0x03, 0x04], // pass key request
+            &[0x05, 0x04],             // bond lost
+        ],
+    );
+
+    assert_eq!(stats.events(), 4);
+    assert_eq!(stats.count(0x0001), 1);
+    assert_eq!(stats.count(0x0402), 2);
+    assert_eq!(stats.count(0x0405), 1);
+    assert_eq!(stats.count(0x0403), 0);
+    assert_eq!(stats.counts(), &[(0x0001, 1), (0x0402, 2), (0x0405, 1)]);
+
+    assert_eq!(stats.errors(), 3);
+    assert_eq!(stats.error_count(ErrorKind::BadLength), 1);
+    assert_eq!(stats.error_count(ErrorKind::UnknownEvent), 1);
+    assert_eq!(stats.error_count(ErrorKind::Vendor), 1);
+    assert_eq!(stats.error_count(ErrorKind::Other), 0);
+    assert_eq!(
+        stats.vendor_error_count(&BlueNRGError::UnknownResetReason(0)),
+        1
+    );
+    assert_eq!(
+        stats.vendor_errors(),
+        &[(BlueNRGError::UnknownResetReason(0x7F), 1)]
+    );
+    assert_eq!(stats.recent_unknown_events().collect::<Vec<_>>(), [0x0C99]);
+}
+
+#[test]
+fn counts_vendor_errors_by_variant() {
+    let mut stats = EventStats::new();
+    record_all(
+        &mut stats,
+        &[
+            &[0x01, 0x00, 0x7F],                   // HAL initialized, bad reset reason
+            &[0x01, 0x04, 0x01, 0x02, 0x07, 0x00], // pairing complete, bad status
+            &[0x01, 0x00, 0x7E],                   // HAL initialized, bad reset reason
+        ],
+    );
+
+    assert_eq!(stats.error_count(ErrorKind::Vendor), 3);
+    assert_eq!(
+        stats.vendor_error_count(&BlueNRGError::UnknownResetReason(0)),
+        2
+    );
+    assert_eq!(
+        stats.vendor_error_count(&BlueNRGError::BadGapPairingStatus(0)),
+        1
+    );
+    assert_eq!(stats.vendor_error_count(&BlueNRGError::BadPhy(0)), 0);
+    assert_eq!(
+        stats.vendor_errors(),
+        &[
+            (BlueNRGError::UnknownResetReason(0x7E), 2),
+            (BlueNRGError::BadGapPairingStatus(0x07), 1)
+        ]
+    );
+}
+
+#[test]
+fn keeps_most_recent_unknown_events() {
+    let mut stats = EventStats::new();
+    record_all(
+        &mut stats,
+        &[
+            &[0x90, 0x0C],
+            &[0x91, 0x0C],
+            &[0x92, 0x0C],
+            &[0x93, 0x0C],
+            &[0x94, 0x0C],
+            &[0x95, 0x0C],
+        ],
+    );
+    assert_eq!(stats.error_count(ErrorKind::UnknownEvent), 6);
+    assert_eq!(
+        stats.recent_unknown_events().collect::<Vec<_>>(),
+        [0x0C92, 0x0C93, 0x0C94, 0x0C95]
+    );
+}
+
+#[test]
+#[cfg(feature = "ms")]
+fn accumulates_lost_event_flags() {
+    let mut stats = EventStats::new();
+    record_all(
+        &mut stats,
+        &[
+            &[0x02, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+            &[0x02, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        ],
+    );
+    assert_eq!(stats.lost_events(), 2);
+    assert_eq!(
+        stats.lost_event_flags(),
+        EventFlags::DISCONNECTION_COMPLETE | EventFlags::READ_REMOTE_VERSION_COMPLETE
+    );
+}
+
+#[test]
+fn reset_clears_counters() {
+    let mut stats = EventStats::new();
+    record_all(&mut stats, &[&[0x05, 0x04], &[0x99, 0x0C]]);
+    stats.reset();
+    assert_eq!(stats.events(), 0);
+    assert_eq!(stats.errors(), 0);
+    assert!(stats.counts().is_empty());
+    assert_eq!(stats.recent_unknown_events().count(), 0);
+}
+
+#[test]
+#[cfg(not(feature = "ms"))]
+fn display() {
+    let mut stats = EventStats::new();
+    record_all(
+        &mut stats,
+        &[
+            &[0x01, 0x00, 0x01],
+            &[0x02, 0x04, 0x01, 0x02],
+            &[0x99, 0x0C],
+        ],
+    );
+    assert_eq!(
+        format!("{}", stats),
+        "events 2 [0x0001:1 0x0402:1] errors 1 (length 0 unknown 1 vendor 0 [] other 0) \
+         recent [0x0c99]"
+    );
+}
+
+#[test]
+#[cfg(feature = "ms")]
+fn display() {
+    let mut stats = EventStats::new();
+    record_all(
+        &mut stats,
+        &[
+            &[0x02, 0x04, 0x01, 0x02],
+            &[0x02, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        ],
+    );
+    assert_eq!(
+        format!("{}", stats),
+        "events 2 [0x0402:1 0x0002:1] errors 0 (length 0 unknown 0 vendor 0 [] other 0) \
+         lost 1 (0x1) recent []"
+    );
+}
@@ -0,0 +1,109 @@
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+
+use bluenrg::event::stream::*;
+use bluenrg::event::*;
+use hci::event::Error as HciError;
+
+#[test]
+fn parse_all_events() {
+    let buffer = [
+        0xFF, 3, 0x01, 0x00, 0x01, // HAL Initialized, normal reset
+        0xFF, 3, 0x01, 0x00, 0x02, // HAL Initialized, updater
+    ];
+    let mut stream = parse_all(&buffer);
+    match stream.next() {
+        Some(Ok(BlueNRGEvent::HalInitialized(event))) => {
+            assert_eq!(event.reason, ResetReason::Normal)
+        }
+        other => panic!("Did not get HalInitialized: {:?}", other),
+    }
+    assert_eq!(stream.offset(), 5);
+    match stream.next() {
+        Some(Ok(BlueNRGEvent::HalInitialized(event))) => {
+            assert_eq!(event.reason, ResetReason::Updater)
+        }
+        other => panic!("Did not get HalInitialized: {:?}", other),
+    }
+    assert!(stream.next().is_none());
+}
+
+#[test]
+fn parse_all_continues_after_corrupted_event() {
+    let buffer = [
+        0xFF, 3, 0x01, 0x00, 0x01, // HAL Initialized, normal reset
+        0xFF, 3, 0x01, 0x00, 0x55, // HAL Initialized, corrupted reset reason
+        0xFF, 3, 0x01, 0x00, 0x02, // HAL Initialized, updater
+    ];
+    let results: Vec<_> = parse_all(&buffer).collect();
+    assert_eq!(results.len(), 3);
+    match results[0] {
+        Ok(BlueNRGEvent::HalInitialized(HalInitialized {
+            reason: ResetReason::Normal,
+            ..
+        })) => (),
+        ref other => panic!("Did not get HalInitialized: {:?}", other),
+    }
+    match results[1] {
+        Err(EventStreamError::BadEvent(
+            5,
+            HciError::Vendor(ParseError {
+                code: Some(0x0001),
+                error: HciError::Vendor(BlueNRGError::UnknownResetReason(0x55)),
+            }),
+        )) => (),
+        ref other => panic!("Did not get bad event: {:?}", other),
+    }
+    match results[2] {
+        Ok(BlueNRGEvent::HalInitialized(HalInitialized {
+            reason: ResetReason::Updater,
+            ..
+        })) => (),
+        ref other => panic!("Did not get HalInitialized: {:?}", other),
+    }
+}
+
+#[test]
+fn parse_all_resynchronizes_after_bad_event_code() {
+    let buffer = [
+        0xFF, 3, 0x01, 0x00, 0x01, // HAL Initialized, normal reset
+        0x12, 0x34, // Garbage
+        0xFF, 3, 0x01, 0x00, 0x02, // HAL Initialized, updater
+    ];
+    let mut stream = parse_all(&buffer);
+    assert!(stream.next().unwrap().is_ok());
+    match stream.next() {
+        Some(Err(err)) => {
+            assert_eq!(err.offset(), 5);
+            match err {
+                EventStreamError::BadEventCode(5, 0x12) => (),
+                other => panic!("Did not get bad event code: {:?}", other),
+            }
+        }
+        other => panic!("Did not get an error: {:?}", other),
+    }
+    assert_eq!(stream.offset(), 7);
+    match stream.next() {
+        Some(Ok(BlueNRGEvent::HalInitialized(HalInitialized {
+            reason: ResetReason::Updater,
+            ..
+        }))) => (),
+        other => panic!("Did not get HalInitialized: {:?}", other),
+    }
+    assert!(stream.next().is_none());
+}
+
+#[test]
+fn parse_all_stops_at_truncated_packet() {
+    let buffer = [
+        0xFF, 3, 0x01, 0x00, 0x01, // HAL Initialized, normal reset
+        0xFF, 3, 0x01, 0x00, // Truncated
+    ];
+    let mut stream = parse_all(&buffer);
+    assert!(stream.next().unwrap().is_ok());
+    match stream.next() {
+        Some(Err(EventStreamError::Truncated(5))) => (),
+        other => panic!("Did not get truncated: {:?}", other),
+    }
+    assert!(stream.next().is_none());
+}
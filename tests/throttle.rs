@@ -0,0 +1,315 @@
+#![cfg(feature = "ms")]
+
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+extern crate nb;
+
+use bluenrg::event::*;
+use bluenrg::throttle::*;
+use hci::event::{Event as HciEvent, Packet};
+use std::time::Duration;
+
+type Event = HciEvent<BlueNRGEvent>;
+
+const CONN_HANDLE: hci::ConnectionHandle = hci::ConnectionHandle(0x0201);
+
+fn update_complete(status: u8) -> Event {
+    Event::new(Packet(&[0x0E, 4, 1, 0x06, 0xFD, status])).unwrap()
+}
+
+fn tx_pool_available(conn_handle: u16, available_buffers: u16) -> Event {
+    Event::new(Packet(&[
+        0xFF,
+        6,
+        0x16,
+        0x0C,
+        conn_handle as u8,
+        (conn_handle >> 8) as u8,
+        available_buffers as u8,
+        (available_buffers >> 8) as u8,
+    ]))
+    .unwrap()
+}
+
+fn send_to(sent: &mut Vec<u8>) -> impl FnMut(&u8) -> nb::Result<(), ()> + '_ {
+    move |value| {
+        sent.push(*value);
+        Ok(())
+    }
+}
+
+#[test]
+fn sends_when_idle() {
+    let mut buffer = [0; 4];
+    let mut throttle = NotificationThrottle::new(CONN_HANDLE, &mut buffer);
+    let mut sent = Vec::new();
+
+    assert_eq!(
+        throttle.notify(1, send_to(&mut sent)),
+        Ok(NotificationOutcome::Sent)
+    );
+    assert_eq!(throttle.handle_event(&update_complete(0x00)), None);
+    assert_eq!(
+        throttle.notify(2, send_to(&mut sent)),
+        Ok(NotificationOutcome::Sent)
+    );
+    assert_eq!(throttle.handle_event(&update_complete(0x00)), None);
+
+    assert_eq!(sent, [1, 2]);
+    assert_eq!(throttle.pending(), 0);
+    assert!(!throttle.is_blocked());
+}
+
+#[test]
+fn resumes_in_order_after_exhaustion() {
+    let mut buffer = [0; 4];
+    let mut throttle = NotificationThrottle::new(CONN_HANDLE, &mut buffer);
+    let mut sent = Vec::new();
+
+    assert_eq!(
+        throttle.notify(1, send_to(&mut sent)),
+        Ok(NotificationOutcome::Sent)
+    );
+    assert_eq!(
+        throttle.notify(2, send_to(&mut sent)),
+        Ok(NotificationOutcome::Queued)
+    );
+
+    // The controller is out of TX buffers, so the first notification was not sent.
+    assert_eq!(throttle.handle_event(&update_complete(0x64)), None);
+    assert!(throttle.is_blocked());
+    assert_eq!(throttle.send_next(send_to(&mut sent)), Ok(false));
+    assert_eq!(
+        throttle.notify(3, send_to(&mut sent)),
+        Ok(NotificationOutcome::Queued)
+    );
+    assert_eq!(throttle.pending(), 3);
+
+    assert_eq!(
+        throttle.handle_event(&tx_pool_available(0x0201, 2)),
+        Some(Resume { retries: 2 })
+    );
+    assert!(!throttle.is_blocked());
+
+    for _ in 0..3 {
+        assert_eq!(throttle.send_next(send_to(&mut sent)), Ok(true));
+        assert_eq!(throttle.send_next(send_to(&mut sent)), Ok(false));
+        assert_eq!(throttle.handle_event(&update_complete(0x00)), None);
+    }
+
+    assert_eq!(sent, [1, 1, 2, 3]);
+    assert_eq!(throttle.pending(), 0);
+}
+
+fn send_chunk_to(sent: &mut Vec<Vec<u8>>) -> impl FnMut(&&[u8]) -> nb::Result<(), ()> + '_ {
+    move |chunk| {
+        sent.push(chunk.to_vec());
+        Ok(())
+    }
+}
+
+#[test]
+fn paces_chunks_from_iterator() {
+    let value = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+    let mut chunks = value.chunks(3);
+    let mut buffer: [&[u8]; 2] = [&[]; 2];
+    let mut throttle = NotificationThrottle::new(CONN_HANDLE, &mut buffer);
+    let mut sent = Vec::new();
+
+    assert_eq!(
+        throttle.notify_from(&mut chunks, send_chunk_to(&mut sent)),
+        Ok(2)
+    );
+    assert_eq!(sent, [[0, 1, 2]]);
+
+    // The pool is exhausted, so nothing more is taken or sent until it has room.
+    assert_eq!(throttle.handle_event(&update_complete(0x64)), None);
+    assert_eq!(
+        throttle.notify_from(&mut chunks, send_chunk_to(&mut sent)),
+        Ok(0)
+    );
+    assert_eq!(sent.len(), 1);
+    assert_eq!(
+        throttle.handle_event(&tx_pool_available(0x0201, 4)),
+        Some(Resume { retries: 2 })
+    );
+
+    for &queued in &[0, 1, 1, 0] {
+        assert_eq!(
+            throttle.notify_from(&mut chunks, send_chunk_to(&mut sent)),
+            Ok(queued)
+        );
+        assert_eq!(throttle.handle_event(&update_complete(0x00)), None);
+    }
+
+    assert_eq!(
+        sent,
+        [
+            vec![0, 1, 2],
+            vec![0, 1, 2],
+            vec![3, 4, 5],
+            vec![6, 7, 8],
+            vec![9]
+        ]
+    );
+    assert_eq!(throttle.pending(), 0);
+}
+
+#[test]
+fn ignores_pool_event_for_other_connection() {
+    let mut buffer = [0; 2];
+    let mut throttle = NotificationThrottle::new(CONN_HANDLE, &mut buffer);
+    let mut sent = Vec::new();
+
+    throttle.notify(1, send_to(&mut sent)).unwrap();
+    throttle.handle_event(&update_complete(0x64));
+
+    assert_eq!(throttle.handle_event(&tx_pool_available(0x0303, 4)), None);
+    assert!(throttle.is_blocked());
+    assert_eq!(throttle.send_next(send_to(&mut sent)), Ok(false));
+    assert_eq!(sent, [1]);
+}
+
+#[test]
+fn drops_when_buffer_full() {
+    let mut buffer = [0; 2];
+    let mut throttle = NotificationThrottle::new(CONN_HANDLE, &mut buffer);
+    let mut sent = Vec::new();
+
+    assert_eq!(
+        throttle.notify(1, send_to(&mut sent)),
+        Ok(NotificationOutcome::Sent)
+    );
+    assert_eq!(
+        throttle.notify(2, send_to(&mut sent)),
+        Ok(NotificationOutcome::Queued)
+    );
+    assert_eq!(
+        throttle.notify(3, send_to(&mut sent)),
+        Ok(NotificationOutcome::Dropped)
+    );
+    assert_eq!(throttle.pending(), 2);
+}
+
+#[test]
+fn send_error_does_not_queue() {
+    let mut buffer = [0; 2];
+    let mut throttle = NotificationThrottle::new(CONN_HANDLE, &mut buffer);
+
+    assert_eq!(
+        throttle.notify(1, |_: &u8| Err(nb::Error::WouldBlock::<()>)),
+        Err(nb::Error::WouldBlock)
+    );
+    assert_eq!(throttle.pending(), 0);
+}
+
+fn server_confirmation(conn_handle: u16) -> BlueNRGEvent {
+    BlueNRGEvent::new(&[0x17, 0x0C, conn_handle as u8, (conn_handle >> 8) as u8]).unwrap()
+}
+
+#[test]
+fn indication_confirmed() {
+    let mut sender = IndicationSender::new();
+    let mut sent = 0;
+
+    sender
+        .try_send(CONN_HANDLE, || -> nb::Result<(), ()> {
+            sent += 1;
+            Ok(())
+        })
+        .unwrap();
+    assert!(sender.is_pending(CONN_HANDLE));
+
+    assert_eq!(sender.handle_event(&server_confirmation(0x0303)), None);
+    assert_eq!(
+        sender.handle_event(&server_confirmation(0x0201)),
+        Some(CompletedIndication {
+            conn_handle: CONN_HANDLE,
+            end: IndicationEnd::Confirmed,
+        })
+    );
+    assert!(!sender.is_pending(CONN_HANDLE));
+
+    sender
+        .try_send(CONN_HANDLE, || -> nb::Result<(), ()> {
+            sent += 1;
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(sent, 2);
+}
+
+#[test]
+fn indication_refused_while_pending() {
+    let mut sender = IndicationSender::new();
+    sender
+        .try_send(CONN_HANDLE, || -> nb::Result<(), ()> { Ok(()) })
+        .unwrap();
+
+    let mut sent = false;
+    assert_eq!(
+        sender.try_send(CONN_HANDLE, || -> nb::Result<(), ()> {
+            sent = true;
+            Ok(())
+        }),
+        Err(nb::Error::Other(IndicationError::Busy))
+    );
+    assert!(!sent);
+
+    // Other connections are not affected.
+    sender
+        .try_send(hci::ConnectionHandle(0x0303), || -> nb::Result<(), ()> {
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn indication_send_error_is_not_recorded() {
+    let mut sender = IndicationSender::new();
+    assert_eq!(
+        sender.try_send(CONN_HANDLE, || Err(nb::Error::Other(5))),
+        Err(nb::Error::Other(IndicationError::Comm(5)))
+    );
+    assert!(!sender.is_pending(CONN_HANDLE));
+}
+
+#[test]
+fn indication_times_out_on_tick() {
+    let mut sender = IndicationSender::new();
+    sender
+        .try_send(CONN_HANDLE, || -> nb::Result<(), ()> { Ok(()) })
+        .unwrap();
+
+    assert_eq!(sender.tick(Duration::from_secs(20)), None);
+    assert_eq!(
+        sender.tick(Duration::from_secs(10)),
+        Some(CompletedIndication {
+            conn_handle: CONN_HANDLE,
+            end: IndicationEnd::TimedOut,
+        })
+    );
+    assert!(!sender.is_pending(CONN_HANDLE));
+
+    // A late confirmation is ignored.
+    assert_eq!(sender.handle_event(&server_confirmation(0x0201)), None);
+}
+
+#[test]
+#[cfg(feature = "att-client-events")]
+fn indication_times_out_on_event() {
+    let mut sender = IndicationSender::new();
+    sender
+        .try_send(CONN_HANDLE, || -> nb::Result<(), ()> { Ok(()) })
+        .unwrap();
+
+    let timeout = BlueNRGEvent::new(&[0x02, 0x0C, 0x01, 0x02]).unwrap();
+    assert_eq!(
+        sender.handle_event(&timeout),
+        Some(CompletedIndication {
+            conn_handle: CONN_HANDLE,
+            end: IndicationEnd::TimedOut,
+        })
+    );
+    assert_eq!(sender.tick(Duration::from_secs(60)), None);
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "ufmt")]
+
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+extern crate ufmt;
+
+use bluenrg::event::*;
+use hci::event::VendorEvent;
+use ufmt::{uWrite, uwrite};
+
+struct FixedBuffer {
+    buf: [u8; 128],
+    len: usize,
+}
+
+impl FixedBuffer {
+    fn new() -> FixedBuffer {
+        FixedBuffer {
+            buf: [0; 128],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.buf[..self.len]).unwrap()
+    }
+}
+
+impl uWrite for FixedBuffer {
+    type Error = ();
+
+    fn write_str(&mut self, s: &str) -> Result<(), ()> {
+        let end = self.len + s.len();
+        if end > self.buf.len() {
+            return Err(());
+        }
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+fn render<T: ufmt::uDisplay>(value: &T) -> FixedBuffer {
+    let mut out = FixedBuffer::new();
+    uwrite!(&mut out, "{}", value).unwrap();
+    out
+}
+
+#[test]
+#[cfg(all(feature = "gap-central-events", not(feature = "bluenrg-lp")))]
+fn gap_device_found() {
+    let buffer = [
+        0x06, 0x04, 0x00, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 3, 0x01, 0x02, 0x03, 0xD8,
+    ];
+    let event = BlueNRGEvent::new(&buffer).unwrap();
+    assert_eq!(
+        render(&event).as_str(),
+        "GapDeviceFound public 06:05:04:03:02:01 rssi -40 data 3"
+    );
+}
+
+#[cfg(all(feature = "ms", not(feature = "bluenrg-lp")))]
+#[test]
+fn gatt_attribute_modified() {
+    let buffer = [
+        0x01, 0x0C, 0x01, 0x02, 0x03, 0x04, 0x02, 0x05, 0x86, 0x07, 0x08,
+    ];
+    let event = BlueNRGEvent::new(&buffer).unwrap();
+    assert_eq!(
+        render(&event).as_str(),
+        "GattAttributeModified conn 0x0201 attr 0x0403 offset 1541 continued data 2"
+    );
+}
+
+#[cfg(not(feature = "ms"))]
+#[test]
+fn gatt_attribute_modified() {
+    let buffer = [0x01, 0x0C, 0x01, 0x02, 0x03, 0x04, 0x02, 0x07, 0x08];
+    let event = BlueNRGEvent::new(&buffer).unwrap();
+    assert_eq!(
+        render(&event).as_str(),
+        "GattAttributeModified conn 0x0201 attr 0x0403 data 2"
+    );
+}
+
+#[test]
+#[cfg(feature = "att-client-events")]
+fn att_error_response() {
+    let buffer = [0x11, 0x0C, 0x01, 0x02, 4, 0x03, 0x04, 0x05, 0x07];
+    let event = BlueNRGEvent::new(&buffer).unwrap();
+    assert_eq!(
+        render(&event).as_str(),
+        "AttErrorResponse conn 0x0201 attr 0x0504 request 0x03 error 0x07"
+    );
+}
+
+#[test]
+fn other_event() {
+    let buffer = [0x02, 0x04, 0x01, 0x02];
+    let event = BlueNRGEvent::new(&buffer).unwrap();
+    assert_eq!(render(&event).as_str(), "GapPassKeyRequest conn 0x0201");
+}
+
+#[test]
+fn error() {
+    let buffer = [0xFF, 0xFF];
+    match BlueNRGEvent::new(&buffer) {
+        Err(hci::event::Error::Vendor(ParseError {
+            error: hci::event::Error::Vendor(e),
+            ..
+        })) => {
+            assert_eq!(render(&e).as_str(), "UnknownEvent 65535")
+        }
+        other => panic!("Did not get vendor error: {:?}", other),
+    }
+}
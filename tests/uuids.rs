@@ -0,0 +1,112 @@
+extern crate bluenrg;
+
+use bluenrg::event::{AttributeHandle, HandleUuid16Pair, Uuid16};
+use bluenrg::gatt::{KnownDescriptor, Uuid};
+use bluenrg::uuids::*;
+
+#[test]
+fn assigned_numbers() {
+    // A sample of values from the Bluetooth Assigned Numbers document.
+    assert_eq!(declaration::PRIMARY_SERVICE, Uuid::Uuid16(0x2800));
+    assert_eq!(declaration::SECONDARY_SERVICE, Uuid::Uuid16(0x2801));
+    assert_eq!(declaration::INCLUDE, Uuid::Uuid16(0x2802));
+    assert_eq!(declaration::CHARACTERISTIC, Uuid::Uuid16(0x2803));
+    assert_eq!(
+        descriptor::CHARACTERISTIC_USER_DESCRIPTION,
+        Uuid::Uuid16(0x2901)
+    );
+    assert_eq!(
+        descriptor::CLIENT_CHARACTERISTIC_CONFIGURATION,
+        Uuid::Uuid16(0x2902)
+    );
+    assert_eq!(service::DEVICE_INFORMATION, Uuid::Uuid16(0x180A));
+    assert_eq!(service::BATTERY, Uuid::Uuid16(0x180F));
+    assert_eq!(characteristic::BATTERY_LEVEL, Uuid::Uuid16(0x2A19));
+    assert_eq!(
+        characteristic::MANUFACTURER_NAME_STRING,
+        Uuid::Uuid16(0x2A29)
+    );
+}
+
+#[test]
+fn descriptors_match_known_descriptors() {
+    assert_eq!(
+        descriptor::CLIENT_CHARACTERISTIC_CONFIGURATION,
+        Uuid::from(KnownDescriptor::ClientConfiguration)
+    );
+    assert_eq!(
+        descriptor::CHARACTERISTIC_PRESENTATION_FORMAT,
+        Uuid::from(KnownDescriptor::CharacteristicPresentationFormat)
+    );
+}
+
+#[test]
+fn uuid128_expansion() {
+    // Battery Level: 00002A19-0000-1000-8000-00805F9B34FB
+    assert_eq!(
+        characteristic::BATTERY_LEVEL.to_uuid128(),
+        [
+            0xFB, 0x34, 0x9B, 0x5F, 0x80, 0x00, 0x00, 0x80, 0x00, 0x10, 0x00, 0x00, 0x19, 0x2A,
+            0x00, 0x00,
+        ]
+    );
+}
+
+#[test]
+#[cfg(feature = "uuid-names")]
+fn names() {
+    assert_eq!(name(0x1800), Some("Generic Access"));
+    assert_eq!(name(0x180F), Some("Battery"));
+    assert_eq!(name(0x2902), Some("Client Characteristic Configuration"));
+    assert_eq!(name(0x2A19), Some("Battery Level"));
+    assert_eq!(name(0x2B3A), Some("Server Supported Features"));
+    assert_eq!(Uuid16(0x2A29).name(), Some("Manufacturer Name String"));
+}
+
+#[test]
+#[cfg(feature = "uuid-names")]
+fn assigned_names_are_sorted() {
+    // `name` relies on the table being sorted for its binary search.
+    let names = assigned_names();
+    assert!(names.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    for &(uuid, uuid_name) in names {
+        assert_eq!(name(uuid), Some(uuid_name));
+    }
+}
+
+#[test]
+#[cfg(feature = "uuid-names")]
+fn names_unassigned() {
+    assert_eq!(name(0x0000), None);
+    assert_eq!(name(0x180B), None);
+    assert_eq!(name(0xFFFF), None);
+}
+
+#[test]
+#[cfg(feature = "uuid-names")]
+fn display_with_names() {
+    assert_eq!(Uuid16(0x2A19).to_string(), "Battery Level (0x2A19)");
+    assert_eq!(Uuid16(0x180B).to_string(), "0x180B");
+    assert_eq!(
+        HandleUuid16Pair {
+            handle: AttributeHandle(0x0003),
+            uuid: Uuid16(0x2A19),
+        }
+        .to_string(),
+        "0x0003: Battery Level (0x2A19)"
+    );
+}
+
+#[test]
+#[cfg(not(feature = "uuid-names"))]
+fn display_without_names() {
+    assert_eq!(Uuid16(0x2A19).to_string(), "0x2A19");
+    assert_eq!(
+        HandleUuid16Pair {
+            handle: AttributeHandle(0x0003),
+            uuid: Uuid16(0x2A19),
+        }
+        .to_string(),
+        "0x0003: 0x2A19"
+    );
+}
@@ -0,0 +1,222 @@
+extern crate bluenrg;
+
+use bluenrg::event::BlueNRGEvent;
+use bluenrg::value::*;
+
+fn round_trip<'a, T>(value: T, bytes: &[u8], buffer: &'a mut [u8])
+where
+    T: GattValue<'a> + core::fmt::Debug + PartialEq,
+{
+    let len = value.encode(buffer).unwrap();
+    assert_eq!(&buffer[..len], bytes);
+    assert_eq!(T::decode(&buffer[..len]), Ok(value));
+}
+
+#[test]
+fn integers() {
+    round_trip(0xA5_u8, &[0xA5], &mut [0; 8]);
+    round_trip(-2_i8, &[0xFE], &mut [0; 8]);
+    round_trip(0x1234_u16, &[0x34, 0x12], &mut [0; 8]);
+    round_trip(-2_i16, &[0xFE, 0xFF], &mut [0; 8]);
+    round_trip(0x1234_5678_u32, &[0x78, 0x56, 0x34, 0x12], &mut [0; 8]);
+    round_trip(-2_i32, &[0xFE, 0xFF, 0xFF, 0xFF], &mut [0; 8]);
+    round_trip(
+        0x0102_0304_0506_0708_u64,
+        &[0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01],
+        &mut [0; 8],
+    );
+    round_trip(
+        -2_i64,
+        &[0xFE, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+        &mut [0; 8],
+    );
+}
+
+#[test]
+fn booleans() {
+    round_trip(true, &[1], &mut [0; 1]);
+    round_trip(false, &[0], &mut [0; 1]);
+    assert_eq!(bool::decode(&[2]), Err(ValueError::OutOfRange));
+}
+
+#[test]
+fn integer_bad_length() {
+    assert_eq!(u16::decode(&[1, 2, 3]), Err(ValueError::BadLength(3, 2)));
+    assert_eq!(u32::decode(&[1, 2]), Err(ValueError::BadLength(2, 4)));
+}
+
+#[test]
+fn buffer_too_small() {
+    assert_eq!(
+        0x1234_5678_u32.encode(&mut [0; 3]),
+        Err(ValueError::BufferTooSmall(3, 4))
+    );
+    assert_eq!(
+        "hello".encode(&mut [0; 4]),
+        Err(ValueError::BufferTooSmall(4, 5))
+    );
+}
+
+#[test]
+fn strings() {
+    round_trip("BlueNRG", b"BlueNRG", &mut [0; 8]);
+    round_trip("", b"", &mut [0; 8]);
+    assert_eq!(<&str>::decode(&[0xC3, 0x28]), Err(ValueError::BadUtf8));
+}
+
+#[test]
+#[cfg(feature = "heapless")]
+fn bounded_strings() {
+    let string: heapless::String<8> = heapless::String::from("BlueNRG");
+    round_trip(string, b"BlueNRG", &mut [0; 8]);
+    assert_eq!(
+        heapless::String::<4>::decode(b"BlueNRG"),
+        Err(ValueError::OutOfRange)
+    );
+}
+
+#[test]
+fn sfloat() {
+    round_trip(SFloat::new(368, -1).unwrap(), &[0x70, 0xF1], &mut [0; 2]);
+    round_trip(SFloat::new(-1, 2).unwrap(), &[0xFF, 0x2F], &mut [0; 2]);
+    round_trip(SFloat::new(-2048, -8).unwrap(), &[0x00, 0x88], &mut [0; 2]);
+    assert_eq!(SFloat::new(368, -1).unwrap().to_f32(), 36.8);
+    assert_eq!(SFloat::new(-1, 2).unwrap().to_f32(), -100.0);
+}
+
+#[test]
+fn sfloat_out_of_range() {
+    assert_eq!(SFloat::new(2048, 0), Err(ValueError::OutOfRange));
+    assert_eq!(SFloat::new(-2049, 0), Err(ValueError::OutOfRange));
+    assert_eq!(SFloat::new(0, 8), Err(ValueError::OutOfRange));
+    assert_eq!(SFloat::new(0, -9), Err(ValueError::OutOfRange));
+}
+
+#[test]
+fn sfloat_special_values() {
+    round_trip(SFloat::NAN, &[0xFF, 0x07], &mut [0; 2]);
+    round_trip(SFloat::NRES, &[0x00, 0x08], &mut [0; 2]);
+    round_trip(SFloat::POSITIVE_INFINITY, &[0xFE, 0x07], &mut [0; 2]);
+    round_trip(SFloat::NEGATIVE_INFINITY, &[0x02, 0x08], &mut [0; 2]);
+
+    assert!(SFloat::NAN.is_nan());
+    assert!(SFloat::NRES.is_nan());
+    assert!(SFloat::decode(&[0x01, 0x08]).unwrap().is_nan());
+    assert!(SFloat::POSITIVE_INFINITY.is_infinite());
+    assert!(!SFloat::POSITIVE_INFINITY.is_nan());
+    assert!(SFloat::NAN.to_f32().is_nan());
+    assert_eq!(SFloat::POSITIVE_INFINITY.to_f32(), f32::INFINITY);
+    assert_eq!(SFloat::NEGATIVE_INFINITY.to_f32(), f32::NEG_INFINITY);
+
+    // The reserved mantissas are ordinary values with a non-zero exponent.
+    let value = SFloat::new(0x07FF, 1).unwrap();
+    assert!(!value.is_nan());
+    assert_eq!(value.to_f32(), 20470.0);
+}
+
+#[test]
+fn float() {
+    round_trip(
+        Float::new(36_875, -3).unwrap(),
+        &[0x0B, 0x90, 0x00, 0xFD],
+        &mut [0; 4],
+    );
+    round_trip(
+        Float::new(-1, 2).unwrap(),
+        &[0xFF, 0xFF, 0xFF, 0x02],
+        &mut [0; 4],
+    );
+    assert_eq!(Float::new(-1, 2).unwrap().to_f32(), -100.0);
+    assert_eq!(Float::new(0x0080_0000, 0), Err(ValueError::OutOfRange));
+}
+
+#[test]
+fn float_special_values() {
+    round_trip(Float::NAN, &[0xFF, 0xFF, 0x7F, 0x00], &mut [0; 4]);
+    round_trip(Float::NRES, &[0x00, 0x00, 0x80, 0x00], &mut [0; 4]);
+    round_trip(
+        Float::POSITIVE_INFINITY,
+        &[0xFE, 0xFF, 0x7F, 0x00],
+        &mut [0; 4],
+    );
+    round_trip(
+        Float::NEGATIVE_INFINITY,
+        &[0x02, 0x00, 0x80, 0x00],
+        &mut [0; 4],
+    );
+
+    assert!(Float::NAN.to_f32().is_nan());
+    assert!(Float::NRES.to_f32().is_nan());
+    assert_eq!(Float::POSITIVE_INFINITY.to_f32(), f32::INFINITY);
+    assert_eq!(Float::NEGATIVE_INFINITY.to_f32(), f32::NEG_INFINITY);
+}
+
+#[test]
+#[cfg(feature = "att-client-events")]
+fn decode_notification() {
+    let buffer = [0x0F, 0x0C, 0x01, 0x02, 4, 0x03, 0x04, 0x70, 0xF1];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::GattNotification(event)) => {
+            assert_eq!(event.decode(), Ok(SFloat::new(368, -1).unwrap()));
+            assert_eq!(event.decode::<u16>(), Ok(0xF170));
+            assert_eq!(event.decode::<u8>(), Err(ValueError::BadLength(2, 1)));
+        }
+        other => panic!("Did not get GATT notification: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(feature = "att-client-events")]
+fn decode_read_response() {
+    let buffer = [0x07, 0x0C, 0x01, 0x02, 3, b'a', b'b', b'c'];
+    match BlueNRGEvent::new(&buffer) {
+        Ok(BlueNRGEvent::AttReadResponse(event)) => assert_eq!(event.decode(), Ok("abc")),
+        other => panic!("Did not get ATT read response: {:?}", other),
+    }
+}
+
+#[test]
+fn presentation_format() {
+    let bytes = [0x0E, 0xFE, 0x2F, 0x27, 0x01, 0x00, 0x00];
+    let format = PresentationFormat::from_bytes(&bytes).unwrap();
+    assert_eq!(format.format, Format::SInt16);
+    assert_eq!(format.exponent, -2);
+    assert_eq!(format.unit, Unit::DegreeCelsius);
+    assert_eq!(format.namespace, 1);
+    assert_eq!(format.description, 0);
+    assert_eq!(format.apply(-1025), -10.25);
+    assert_eq!(format.to_string(), "sint16 x 10^-2 degree Celsius");
+
+    let mut buffer = [0; 7];
+    assert_eq!(format.encode(&mut buffer), Ok(7));
+    assert_eq!(buffer, bytes);
+}
+
+#[test]
+fn presentation_format_unknown_values() {
+    let bytes = [0x1C, 0x03, 0xFF, 0x27, 0x01, 0x01, 0x01];
+    let format = PresentationFormat::from_bytes(&bytes).unwrap();
+    assert_eq!(format.format, Format::Other(0x1C));
+    assert_eq!(format.unit, Unit::Other(0x27FF));
+    assert_eq!(format.description, 0x0101);
+    assert_eq!(format.apply(2), 2000.0);
+    assert_eq!(format.to_string(), "0x1c x 10^3 0x27ff");
+}
+
+#[test]
+fn presentation_format_unitless() {
+    let format = PresentationFormat::from_bytes(&[0x04, 0x00, 0x00, 0x27, 0x01, 0x00, 0x00]);
+    assert_eq!(format.unwrap().to_string(), "uint8");
+}
+
+#[test]
+fn presentation_format_bad_length() {
+    assert_eq!(
+        PresentationFormat::from_bytes(&[0x0E, 0xFE, 0x2F, 0x27, 0x01, 0x00]),
+        Err(ValueError::BadLength(6, 7))
+    );
+    assert_eq!(
+        PresentationFormat::from_bytes(&[]),
+        Err(ValueError::BadLength(0, 7))
+    );
+}
@@ -0,0 +1,33 @@
+extern crate bluenrg;
+extern crate bluetooth_hci as hci;
+
+use bluenrg::event::command::ReturnParameters as BNRGParams;
+use bluenrg::event::{BlueNRGEvent, Status};
+use bluenrg::BlueNRGTypes;
+use hci::event::command::ReturnParameters as HciParams;
+use hci::event::{Event, Packet};
+
+type VendorEvent = <BlueNRGTypes as hci::Vendor>::Event;
+type VendorStatus = <BlueNRGTypes as hci::Vendor>::Status;
+
+#[test]
+fn vendor_types() {
+    fn same_type<T>(_: Option<T>, _: Option<T>) {}
+
+    same_type::<BlueNRGEvent>(None, None::<VendorEvent>);
+    same_type::<Status>(None, None::<VendorStatus>);
+}
+
+#[test]
+fn read_event_with_vendor_types() {
+    let buffer = [0x0E, 4, 8, 0x16, 0xFC, 0x41];
+    match Event::<VendorEvent>::new(Packet(&buffer)) {
+        Ok(Event::CommandComplete(event)) => match event.return_params {
+            HciParams::Vendor(BNRGParams::HalStopTone(status)) => {
+                assert_eq!(status, hci::Status::<VendorStatus>::Vendor(Status::Failed));
+            }
+            other => panic!("Wrong return parameters: {:?}", other),
+        },
+        other => panic!("Did not get command complete event: {:?}", other),
+    }
+}